@@ -0,0 +1,75 @@
+//! Compare memory-mapped and buffered regex search on a 512MB file
+//!
+//! Run with `cargo bench --bench mmap_vs_buffered`. The file is mostly zeros with a
+//! match every 64MB, so the timings measure scanning rather than output.
+
+use hxgrep::progress::ProgressIndicator;
+use hxgrep::{Config, FileProcessor, RegexProcessor, SearchOptions};
+use std::fs::{self, File};
+use std::io::{self, Seek, SeekFrom, Write};
+use std::time::{Duration, Instant};
+
+const FILE_SIZE: u64 = 512 * 1024 * 1024;
+const MATCH_INTERVAL: u64 = 64 * 1024 * 1024;
+const RUNS: usize = 3;
+
+fn create_test_file() -> std::io::Result<std::path::PathBuf> {
+    let path = std::env::temp_dir().join(format!("hxgrep_bench_mmap_{}.bin", std::process::id()));
+    let mut file = File::create(&path)?;
+
+    // Write real zero blocks so both backends read from the page cache, not from holes
+    let block = vec![0u8; 4 * 1024 * 1024];
+    for _ in 0..FILE_SIZE / block.len() as u64 {
+        file.write_all(&block)?;
+    }
+    for offset in (MATCH_INTERVAL / 2..FILE_SIZE).step_by(MATCH_INTERVAL as usize) {
+        file.seek(SeekFrom::Start(offset))?;
+        file.write_all(b"\xCA\xFE\xBA\xBE")?;
+    }
+    file.sync_all()?;
+    Ok(path)
+}
+
+/// Best wall-clock time of several searches
+fn time_search(path: &std::path::Path, config: &Config) -> Duration {
+    let regex = RegexProcessor::compile_pattern("\\xCA\\xFE\\xBA\\xBE").unwrap();
+    (0..RUNS)
+        .map(|_| {
+            let mut processor = FileProcessor::new(config.clone());
+            let mut file = File::open(path).unwrap();
+            let mut progress = ProgressIndicator::disabled();
+            let start = Instant::now();
+            processor
+                .grep_file(&mut file, &mut io::sink(), &regex, &SearchOptions::new(), &mut progress)
+                .unwrap();
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+fn main() {
+    let path = create_test_file().expect("failed to create benchmark file");
+
+    let buffered = Config {
+        use_mmap: false,
+        mmap_threshold: u64::MAX,
+        ..Config::default()
+    };
+    let mapped = Config {
+        use_mmap: true,
+        ..Config::default()
+    };
+
+    let buffered_time = time_search(&path, &buffered);
+    let mapped_time = time_search(&path, &mapped);
+    fs::remove_file(&path).ok();
+
+    let throughput = |elapsed: Duration| FILE_SIZE as f64 / (1024.0 * 1024.0) / elapsed.as_secs_f64();
+    eprintln!("buffered: {:>8.1?} ({:.0} MB/s)", buffered_time, throughput(buffered_time));
+    eprintln!("mmap:     {:>8.1?} ({:.0} MB/s)", mapped_time, throughput(mapped_time));
+    eprintln!(
+        "speedup:  {:.2}x",
+        buffered_time.as_secs_f64() / mapped_time.as_secs_f64()
+    );
+}
@@ -0,0 +1,39 @@
+//! Build script: generates the C header for the `ffi` feature
+//!
+//! The header is written to `OUT_DIR`; the checked-in `include/hxgrep.h` is only
+//! rewritten when `HXGREP_UPDATE_HEADER=1` is set, so builds never touch the source tree.
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    #[cfg(feature = "ffi")]
+    generate_ffi_header();
+}
+
+#[cfg(feature = "ffi")]
+fn generate_ffi_header() {
+    use std::path::PathBuf;
+
+    let crate_dir = std::env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is set by cargo");
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR").expect("OUT_DIR is set by cargo"));
+    println!("cargo:rerun-if-changed=src/ffi.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-env-changed=HXGREP_UPDATE_HEADER");
+
+    // cbindgen.toml in the crate directory selects C output and the include guard
+    let generated = out_dir.join("hxgrep.h");
+    cbindgen::generate(&crate_dir)
+        .expect("Unable to generate the C header for src/ffi.rs")
+        .write_to_file(&generated);
+
+    let checked_in = PathBuf::from(&crate_dir).join("include").join("hxgrep.h");
+    let header = std::fs::read(&generated).expect("Unable to read the generated C header");
+    if std::env::var_os("HXGREP_UPDATE_HEADER").is_some_and(|value| value == "1") {
+        std::fs::write(&checked_in, &header).expect("Unable to update include/hxgrep.h");
+    } else if std::fs::read(&checked_in).ok().as_deref() != Some(header.as_slice()) {
+        println!(
+            "cargo:warning=include/hxgrep.h is out of date with src/ffi.rs; \
+             regenerate it with HXGREP_UPDATE_HEADER=1 cargo build --features ffi"
+        );
+    }
+}
@@ -0,0 +1,42 @@
+//! Carve JPEG images out of a disk image or memory dump
+//!
+//! Usage: `cargo run --example carve_jpegs -- <FILE> <OUT_DIR>`
+//!
+//! Every run of bytes from a JPEG start-of-image marker to the next end-of-image marker
+//! is written to `<OUT_DIR>/<FILE>_offset_<HEX>.bin` through the processor's extraction
+//! directory, the same files `hxgrep -x` writes.
+
+use hxgrep::extract;
+use hxgrep::{Config, FileProcessor, RegexProcessor, SearchOptions};
+use std::env;
+use std::fs::File;
+use std::path::Path;
+use std::process;
+
+/// SOI marker followed by a segment marker, up to the nearest EOI marker
+const JPEG_PATTERN: &str = "(?s-u)\\xFF\\xD8\\xFF.*?\\xFF\\xD9";
+
+fn main() -> hxgrep::Result<()> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let [path, out_dir] = args.as_slice() else {
+        eprintln!("usage: carve_jpegs <FILE> <OUT_DIR>");
+        process::exit(2);
+    };
+    let out_dir = Path::new(out_dir);
+
+    let regex = RegexProcessor::compile_pattern(JPEG_PATTERN)?;
+    let mut processor = FileProcessor::new(Config::default());
+    processor.set_source_name(path);
+    processor.set_extract_dir(Some(out_dir.to_path_buf()));
+
+    // Width 0 keeps whole matches, even when they run past a read buffer
+    let mut file = File::open(path)?;
+    let carved = processor.find_matches(&mut file, &regex, &SearchOptions::new().width(0))?;
+
+    for jpeg in &carved {
+        let written = extract::extraction_path(out_dir, path, jpeg.offset);
+        println!("{:X}h: {} bytes -> {}", jpeg.offset, jpeg.len, written.display());
+    }
+    println!("{} JPEG images carved", carved.len());
+    Ok(())
+}
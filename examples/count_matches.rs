@@ -0,0 +1,43 @@
+//! Count the matches of a pattern in a file with the lazy search iterator
+//!
+//! Usage: `cargo run --example count_matches -- <FILE> <PATTERN>`
+//!
+//! The pattern uses the same `\xHH` syntax as `hxgrep -e`. Matches are pulled one at a
+//! time, so only the buffer being scanned is held in memory.
+
+use hxgrep::{Config, FileProcessor, RegexProcessor, SearchOptions};
+use std::env;
+use std::fs::File;
+use std::process;
+
+/// Number of match offsets listed after the count
+const LISTED_OFFSETS: usize = 5;
+
+fn main() -> hxgrep::Result<()> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let [path, pattern] = args.as_slice() else {
+        eprintln!("usage: count_matches <FILE> <PATTERN>");
+        process::exit(2);
+    };
+
+    let regex = RegexProcessor::compile_pattern(pattern)?;
+    let mut file = File::open(path)?;
+    let mut processor = FileProcessor::new(Config::default());
+    processor.set_source_name(path);
+
+    let mut count = 0;
+    let mut offsets = Vec::new();
+    for found in processor.search(&mut file, &regex, &SearchOptions::new().width(0)) {
+        let found = found?;
+        if offsets.len() < LISTED_OFFSETS {
+            offsets.push(found.offset);
+        }
+        count += 1;
+    }
+
+    println!("{}: {} matches", path, count);
+    for offset in offsets {
+        println!("  {:X}h", offset);
+    }
+    Ok(())
+}
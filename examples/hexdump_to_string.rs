@@ -0,0 +1,42 @@
+//! Render a hex dump into a `String` through the writer-based formatter
+//!
+//! Usage: `cargo run --example hexdump_to_string [-- <FILE>]`
+//!
+//! Without a file a built-in sample is dumped. The processor writes to any
+//! `std::io::Write`, so the dump is collected in memory instead of going to stdout.
+
+use hxgrep::cli::ColorChoice;
+use hxgrep::color_context;
+use hxgrep::progress::ProgressIndicator;
+use hxgrep::{Config, DumpOptions, FileProcessor};
+use std::env;
+use std::fs;
+use std::io::Cursor;
+
+const SAMPLE: &[u8] = b"MZ\x90\x00\x03\x00\x00\x00\x04\x00\x00\x00\xFF\xFF\x00\x00This program";
+
+/// Hex dump `data` with `options`, returning the lines as text
+fn hexdump(data: &[u8], options: &DumpOptions) -> hxgrep::Result<String> {
+    let mut processor = FileProcessor::new(Config::default());
+    let mut progress = ProgressIndicator::disabled();
+    let mut out = Vec::new();
+    processor.dump_reader(&mut Cursor::new(data), &mut out, options, data.len() as u64, &mut progress)?;
+    Ok(String::from_utf8_lossy(&out).into_owned())
+}
+
+fn main() -> hxgrep::Result<()> {
+    // The text is kept rather than printed to a terminal, so leave out color escapes
+    color_context::set_color_choice(ColorChoice::Never);
+
+    let data = match env::args().nth(1) {
+        Some(path) => fs::read(path)?,
+        None => SAMPLE.to_vec(),
+    };
+
+    let options = DumpOptions::new().width(8).group_size(2);
+    let dump = hexdump(&data, &options)?;
+
+    print!("{}", dump);
+    println!("({} lines, {} characters)", dump.lines().count(), dump.len());
+    Ok(())
+}
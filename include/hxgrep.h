@@ -0,0 +1,61 @@
+#ifndef HXGREP_H
+#define HXGREP_H
+
+/* Generated by cbindgen from src/ffi.rs with `HXGREP_UPDATE_HEADER=1 cargo build --features ffi`; do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Compiled patterns, created by `hxgrep_compile` and released with `hxgrep_free`
+typedef struct HxgrepHandle HxgrepHandle;
+
+// Called for each match with its absolute offset, length, the index of the pattern that
+// matched (-1 if none could be determined) and the caller's `user_data`
+//
+// Return `false` to stop the scan.
+typedef bool (*HxgrepMatchCallback)(uint64_t offset,
+                                    size_t length,
+                                    int32_t pattern_index,
+                                    void *user_data);
+
+// Compile patterns written with the CLI's `-e` syntax, one per line
+//
+// Blank lines and lines starting with `#` are skipped like in a `--pattern-file`, and
+// pattern indexes count the remaining lines from 0. Returns NULL if `patterns` is NULL,
+// not UTF-8, empty or contains an invalid pattern.
+//
+// # Safety
+//
+// `patterns` must be NULL or point to a NUL-terminated string.
+HxgrepHandle *hxgrep_compile(const char *patterns);
+
+// Scan `len` bytes at `data`, reporting each match through `callback`
+//
+// `base_offset` is added to the reported offsets, so a caller scanning a larger input
+// in pieces gets absolute offsets; offsets past `u64::MAX` saturate instead of wrapping.
+// Matches are not extended past the end of the buffer;
+// overlapping consecutive buffers is up to the caller. Returns the number of matches
+// reported, or -1 if `handle`, `callback` or a non-empty `data` is NULL.
+//
+// # Safety
+//
+// `handle` must come from `hxgrep_compile` and not be freed yet, and `data` must be
+// valid for reads of `len` bytes.
+int64_t hxgrep_scan_buffer(const HxgrepHandle *handle,
+                           const uint8_t *data,
+                           size_t len,
+                           uint64_t base_offset,
+                           HxgrepMatchCallback callback,
+                           void *user_data);
+
+// Release a handle returned by `hxgrep_compile`
+//
+// # Safety
+//
+// `handle` must be NULL or come from `hxgrep_compile`, and must not be used afterwards.
+void hxgrep_free(HxgrepHandle *handle);
+
+#endif  /* HXGREP_H */
@@ -0,0 +1,196 @@
+//! Byte-to-character mappings for the text column of hex output
+//!
+//! By default only printable ASCII is shown. `--charset ebcdic` decodes mainframe data with
+//! the built-in code page 037 table, and `--charset file:<path>` loads a custom table.
+//! Bytes mapped to control characters render as `.`.
+
+use crate::error::{BingrepError, Result};
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+static CHARSET: OnceLock<Charset> = OnceLock::new();
+
+/// Character rendered for bytes without a printable mapping
+const UNPRINTABLE: char = '.';
+
+/// EBCDIC code page 037 (US/Canada)
+const EBCDIC_CP037: [char; 256] = [
+    '\u{00}', '\u{01}', '\u{02}', '\u{03}', '\u{9c}', '\u{09}', '\u{86}', '\u{7f}',
+    '\u{97}', '\u{8d}', '\u{8e}', '\u{0b}', '\u{0c}', '\u{0d}', '\u{0e}', '\u{0f}',
+    '\u{10}', '\u{11}', '\u{12}', '\u{13}', '\u{9d}', '\u{85}', '\u{08}', '\u{87}',
+    '\u{18}', '\u{19}', '\u{92}', '\u{8f}', '\u{1c}', '\u{1d}', '\u{1e}', '\u{1f}',
+    '\u{80}', '\u{81}', '\u{82}', '\u{83}', '\u{84}', '\u{0a}', '\u{17}', '\u{1b}',
+    '\u{88}', '\u{89}', '\u{8a}', '\u{8b}', '\u{8c}', '\u{05}', '\u{06}', '\u{07}',
+    '\u{90}', '\u{91}', '\u{16}', '\u{93}', '\u{94}', '\u{95}', '\u{96}', '\u{04}',
+    '\u{98}', '\u{99}', '\u{9a}', '\u{9b}', '\u{14}', '\u{15}', '\u{9e}', '\u{1a}',
+    ' ', '\u{a0}', 'â', 'ä', 'à', 'á', 'ã', 'å',
+    'ç', 'ñ', '¢', '.', '<', '(', '+', '|',
+    '&', 'é', 'ê', 'ë', 'è', 'í', 'î', 'ï',
+    'ì', 'ß', '!', '$', '*', ')', ';', '¬',
+    '-', '/', 'Â', 'Ä', 'À', 'Á', 'Ã', 'Å',
+    'Ç', 'Ñ', '¦', ',', '%', '_', '>', '?',
+    'ø', 'É', 'Ê', 'Ë', 'È', 'Í', 'Î', 'Ï',
+    'Ì', '`', ':', '#', '@', '\'', '=', '"',
+    'Ø', 'a', 'b', 'c', 'd', 'e', 'f', 'g',
+    'h', 'i', '«', '»', 'ð', 'ý', 'þ', '±',
+    '°', 'j', 'k', 'l', 'm', 'n', 'o', 'p',
+    'q', 'r', 'ª', 'º', 'æ', '¸', 'Æ', '¤',
+    'µ', '~', 's', 't', 'u', 'v', 'w', 'x',
+    'y', 'z', '¡', '¿', 'Ð', 'Ý', 'Þ', '®',
+    '^', '£', '¥', '·', '©', '§', '¶', '¼',
+    '½', '¾', '[', ']', '¯', '¨', '´', '×',
+    '{', 'A', 'B', 'C', 'D', 'E', 'F', 'G',
+    'H', 'I', '\u{ad}', 'ô', 'ö', 'ò', 'ó', 'õ',
+    '}', 'J', 'K', 'L', 'M', 'N', 'O', 'P',
+    'Q', 'R', '¹', 'û', 'ü', 'ù', 'ú', 'ÿ',
+    '\\', '÷', 'S', 'T', 'U', 'V', 'W', 'X',
+    'Y', 'Z', '²', 'Ô', 'Ö', 'Ò', 'Ó', 'Õ',
+    '0', '1', '2', '3', '4', '5', '6', '7',
+    '8', '9', '³', 'Û', 'Ü', 'Ù', 'Ú', '\u{9f}',
+];
+
+/// A 256-entry mapping of byte values to display characters
+#[derive(Debug, Clone, PartialEq)]
+pub struct Charset {
+    table: [char; 256],
+}
+
+impl Charset {
+    /// Printable ASCII (`0x20`-`0x7E`); every other byte renders as `.`
+    pub fn ascii() -> Self {
+        let mut table = [UNPRINTABLE; 256];
+        for byte in 0x20..=0x7Eu8 {
+            table[byte as usize] = byte as char;
+        }
+        Self { table }
+    }
+
+    /// EBCDIC code page 037
+    pub fn ebcdic() -> Self {
+        Self { table: EBCDIC_CP037 }
+    }
+
+    /// Load a charset file with one line per byte value, in order from `0x00` to `0xFF`
+    ///
+    /// Each line holds the display character for its byte; an empty line marks the byte
+    /// as unprintable.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)?;
+        // Split on every newline so an empty last line still counts as a byte
+        let content = content.strip_suffix('\n').unwrap_or(&content);
+        let lines: Vec<&str> = content
+            .split('\n')
+            .map(|line| line.strip_suffix('\r').unwrap_or(line))
+            .collect();
+
+        if lines.len() != 256 {
+            return Err(BingrepError::InvalidPattern(format!(
+                "Charset file {} must have 256 lines, found {}",
+                path.display(),
+                lines.len()
+            )));
+        }
+
+        let mut table = [UNPRINTABLE; 256];
+        for (byte, line) in lines.iter().enumerate() {
+            let mut chars = line.chars();
+            match (chars.next(), chars.next()) {
+                (None, _) => {}
+                (Some(c), None) => table[byte] = c,
+                (Some(_), Some(_)) => {
+                    return Err(BingrepError::InvalidPattern(format!(
+                        "Charset file {} line {} (byte {:02X}h) must hold a single character",
+                        path.display(),
+                        byte + 1,
+                        byte
+                    )));
+                }
+            }
+        }
+
+        Ok(Self { table })
+    }
+
+    /// Parse a `--charset` value: `ascii`, `ebcdic` or `file:<path>`
+    pub fn parse(spec: &str) -> Result<Self> {
+        match spec {
+            "ascii" => Ok(Self::ascii()),
+            "ebcdic" | "cp037" => Ok(Self::ebcdic()),
+            _ => match spec.strip_prefix("file:") {
+                Some(path) => Self::from_file(path),
+                None => Err(BingrepError::InvalidPattern(format!(
+                    "Unknown charset '{}' (expected ascii, ebcdic or file:<path>)",
+                    spec
+                ))),
+            },
+        }
+    }
+
+    /// Display character for a byte, `.` if it has no printable mapping
+    pub fn display_char(&self, byte: u8) -> char {
+        match self.table[byte as usize] {
+            c if c.is_control() => UNPRINTABLE,
+            c => c,
+        }
+    }
+
+    /// Render bytes as display text, one character per byte
+    pub fn decode(&self, bytes: &[u8]) -> String {
+        bytes.iter().map(|&byte| self.display_char(byte)).collect()
+    }
+}
+
+/// Set the global charset used for the text column and structured `ascii_data`
+pub fn set_charset(charset: Charset) {
+    CHARSET.set(charset).ok();
+}
+
+/// Get the charset selected with `--charset`, if any
+pub fn get_charset() -> Option<&'static Charset> {
+    CHARSET.get()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ebcdic_decode() {
+        let charset = Charset::ebcdic();
+        assert_eq!(charset.decode(&[0xC8, 0xC5, 0xD3, 0xD3, 0xD6]), "HELLO");
+        assert_eq!(charset.decode(&[0xF1, 0xF2, 0x40, 0x81]), "12 a");
+        // Control characters render as dots
+        assert_eq!(charset.decode(&[0x00, 0x25]), "..");
+    }
+
+    #[test]
+    fn test_ascii_decode() {
+        assert_eq!(Charset::ascii().decode(b"Hi\x00\xff~"), "Hi..~");
+    }
+
+    #[test]
+    fn test_parse_charset_spec() {
+        assert_eq!(Charset::parse("ascii").unwrap(), Charset::ascii());
+        assert_eq!(Charset::parse("ebcdic").unwrap(), Charset::ebcdic());
+        assert!(Charset::parse("latin9").is_err());
+        assert!(Charset::parse("file:/nonexistent/charset.txt").is_err());
+    }
+
+    #[test]
+    fn test_charset_from_file() {
+        let path = std::env::temp_dir().join(format!("hxgrep_charset_{}.txt", std::process::id()));
+
+        // Map every byte to 'x' except 0x41, which is unprintable
+        let lines: Vec<&str> = (0..256).map(|byte| if byte == 0x41 { "" } else { "x" }).collect();
+        fs::write(&path, lines.join("\n")).unwrap();
+        let charset = Charset::from_file(&path).unwrap();
+        assert_eq!(charset.decode(&[0x00, 0x41]), "x.");
+
+        fs::write(&path, "x\n").unwrap();
+        assert!(Charset::from_file(&path).is_err());
+
+        fs::remove_file(&path).ok();
+    }
+}
@@ -1,9 +1,30 @@
-use clap::{Parser, ValueEnum};
+use clap::parser::ValueSource;
+use clap::{ArgGroup, ArgMatches, Parser, ValueEnum};
+use colored::Color;
+use crate::collapse::MatchCollapser;
+use crate::config::CliDefaults;
+use crate::entropy::EntropyFilter;
+use crate::error::Result;
+use crate::options::{DumpOptions, SearchOptions};
+use crate::output::HexCase;
+use crate::progress::ProgressIndicator;
+use crate::regex_processor::{IntegerType, MatchAlignment, PatternSet};
+use std::path::PathBuf;
+
+/// `-n` values at least this large without `-s` probably meant a start offset
+const LARGE_LIMIT_HINT: usize = 65536;
+/// `-s` values up to this on a large file without `-e` probably meant a line count
+const SMALL_POSITION_HINT: u64 = 1000;
+/// Files at least this large make a full dump from a tiny `-s` suspicious
+const LARGE_FILE_HINT: u64 = 256 * 1024 * 1024;
 
 #[derive(Parser)]
 #[command(name = "hxgrep")]
 #[command(about = "바이너리 파일 정규표현식 검색 도구")]
 #[command(version = "0.1.0")]
+#[command(group(ArgGroup::new("expression").multiple(true).args(["patterns", "pattern_file"])))]
+#[command(group(ArgGroup::new("file_set").args(["multi_file", "recursive", "files_from"])))]
+#[command(group(ArgGroup::new("null_output").multiple(true).args(["files_from", "byte_offset"])))]
 #[command(long_about = "바이너리 파일을 정규표현식으로 검색하는 도구입니다.
 
 이 프로그램의 정규표현식은 Rust regex 라이브러리의 문법을 따릅니다.
@@ -18,15 +39,126 @@ pub struct Cli {
     /// 입력 파일 경로 또는 glob 패턴 (예: "*.bin", "data/**/*.txt")
     pub file_path: Option<String>,
 
-    /// 정규표현식 패턴 (예: -e "\x00\x00\x00\x01\x67")
+    /// 추가 입력 파일 (--multi와 함께 쓰면 셸이 펼친 glob처럼 목록 순서대로 검색)
+    #[arg(value_name = "MORE_PATHS")]
+    pub extra_paths: Vec<String>,
+
+    /// 정규표현식 패턴, 여러 번 지정하면 OR 검색 (예: -e "\x00\x00\x00\x01\x67" -e "\x00\x00\x00\x01\x68")
     #[arg(short = 'e', long = "regex")]
+    pub patterns: Vec<String>,
+
+    /// 패턴 파일에서 정규표현식을 한 줄에 하나씩 읽어 OR 검색 (빈 줄과 #으로 시작하는 줄은 무시, -e와 함께 사용 가능)
+    #[arg(long = "pattern-file", value_name = "PATH")]
+    pub pattern_file: Option<PathBuf>,
+
+    /// 컴파일된 정규표현식의 최대 크기(바이트, 기본값: 10485760). 초과하면 종료 코드 3으로 종료 (예: (?:\x00{1,100000}){1,100})
+    #[arg(long = "regex-size-limit", value_name = "BYTES")]
+    pub regex_size_limit: Option<usize>,
+
+    /// Search expression combined from `-e` and `--pattern-file` patterns or built from a literal search option
+    #[arg(skip)]
     pub expression: Option<String>,
 
+    /// 한 줄 너비(-w) 안에 이 개수보다 많은 매치가 몰리면 한 줄로 축약해 "(+K collapsed)" 표시 (-n은 축약된 매치도 셈)
+    #[arg(long = "max-matches-per-offset", value_name = "N", default_value = "8")]
+    pub max_matches_per_window: usize,
+
+    /// 밀집된 매치 축약 비활성화 (모든 매치를 한 줄씩 출력)
+    #[arg(long = "no-collapse")]
+    pub no_collapse: bool,
+
+    /// 매치된 줄마다 매치한 패턴의 번호 표시 (-e 순서 다음 패턴 파일 순서, 1부터, 예: "| #2")
+    #[arg(long = "with-pattern", requires = "expression", conflicts_with_all = ["file_set", "invert_match"])]
+    pub with_pattern: bool,
+
+    /// 정규표현식 대신 리터럴 문자열로 검색 (예: -F "MZ", 메타문자 이스케이프 불필요)
+    #[arg(short = 'F', long = "string", conflicts_with = "expression")]
+    pub string: Option<String>,
+
+    /// 리터럴 문자열 인코딩 (utf8, utf16le, utf16be)
+    #[arg(long = "string-encoding", default_value = "utf8", requires = "string")]
+    pub string_encoding: StringEncoding,
+
+    /// YARA 형식 헥사 패턴으로 검색 (예: --hex-pattern "4D 5A ?? ?? 50 45", ??: 임의 바이트, 4?/?D: 니블 와일드카드, [4-6]: 4~6바이트 점프)
+    #[arg(long = "hex-pattern", conflicts_with_all = ["expression", "string", "integer"])]
+    pub hex_pattern: Option<String>,
+
+    /// 비트 마스크로 검색: 마스크의 비트만 비교 (예: --mask 80:f0 은 0x80-0x8F와 매치, 여러 바이트 가능)
+    #[arg(long = "mask", value_name = "VALUE:MASK", conflicts_with_all = ["expression", "string", "hex_pattern", "integer"])]
+    pub mask: Option<String>,
+
+    /// 16비트 부호 없는 정수 값으로 검색 (10진수 또는 0x 16진수)
+    #[arg(long = "u16", group = "integer", conflicts_with_all = ["expression", "string"])]
+    pub u16_value: Option<String>,
+
+    /// 32비트 부호 없는 정수 값으로 검색 (예: --u32 0x00401000)
+    #[arg(long = "u32", group = "integer", conflicts_with_all = ["expression", "string"])]
+    pub u32_value: Option<String>,
+
+    /// 64비트 부호 없는 정수 값으로 검색 (10진수 또는 0x 16진수)
+    #[arg(long = "u64", group = "integer", conflicts_with_all = ["expression", "string"])]
+    pub u64_value: Option<String>,
+
+    /// 32비트 부호 있는 정수 값으로 검색 (예: --i32 -1)
+    #[arg(long = "i32", group = "integer", conflicts_with_all = ["expression", "string"], allow_hyphen_values = true)]
+    pub i32_value: Option<String>,
+
+    /// 16비트 리틀 엔디안 정수 값으로 검색 (--u16 VALUE --endian le 와 같음)
+    #[arg(long = "uint16-le", value_name = "VALUE", group = "integer", conflicts_with_all = ["expression", "string", "endian"])]
+    pub uint16_le: Option<String>,
+
+    /// 16비트 빅 엔디안 정수 값으로 검색 (--u16 VALUE --endian be 와 같음)
+    #[arg(long = "uint16-be", value_name = "VALUE", group = "integer", conflicts_with_all = ["expression", "string", "endian"])]
+    pub uint16_be: Option<String>,
+
+    /// 32비트 리틀 엔디안 정수 값으로 검색 (예: --uint32-le 0xDEADBEEF)
+    #[arg(long = "uint32-le", value_name = "VALUE", group = "integer", conflicts_with_all = ["expression", "string", "endian"])]
+    pub uint32_le: Option<String>,
+
+    /// 32비트 빅 엔디안 정수 값으로 검색 (예: --uint32-be 0xCAFEBABE)
+    #[arg(long = "uint32-be", value_name = "VALUE", group = "integer", conflicts_with_all = ["expression", "string", "endian"])]
+    pub uint32_be: Option<String>,
+
+    /// 64비트 리틀 엔디안 정수 값으로 검색 (--u64 VALUE --endian le 와 같음)
+    #[arg(long = "uint64-le", value_name = "VALUE", group = "integer", conflicts_with_all = ["expression", "string", "endian"])]
+    pub uint64_le: Option<String>,
+
+    /// 64비트 빅 엔디안 정수 값으로 검색 (--u64 VALUE --endian be 와 같음)
+    #[arg(long = "uint64-be", value_name = "VALUE", group = "integer", conflicts_with_all = ["expression", "string", "endian"])]
+    pub uint64_be: Option<String>,
+
+    /// 정수 검색 시 바이트 순서 (le, be)
+    #[arg(long = "endian", default_value = "le", requires = "integer")]
+    pub endian: Endian,
+
+    /// 패턴과 매치되지 않는 영역만 헥사 덤프 (매치 영역의 여집합, -w/-n 적용)
+    #[arg(
+        short = 'v',
+        long = "invert-match",
+        conflicts_with_all = ["file_set", "quiet", "interactive", "extract_dir", "skip_holes", "msgpack", "aligned", "show_entropy"]
+    )]
+    pub invert_match: bool,
+
+    /// 출력 가능한 ASCII 문자열(0x20-0x7E) 추출 (Unix strings와 유사, --no-offset 사용 시 strings와 같은 출력)
+    #[arg(
+        long = "strings",
+        conflicts_with_all = ["expression", "string", "hex_pattern", "mask", "integer", "invert_match", "file_set", "quiet", "interactive", "extract_dir", "show_entropy", "aligned", "skip_holes"]
+    )]
+    pub strings_mode: bool,
+
+    /// --strings 모드에서 출력할 최소 문자열 길이
+    #[arg(long = "strings-min", default_value = "4", requires = "strings_mode")]
+    pub strings_min_len: usize,
+
     /// 한 줄에 표시할 바이트 개수 (기본값: 16)
     #[arg(short = 'w', long = "width", default_value = "16")]
     pub line_width: usize,
 
-    /// 출력할 라인 수 (0: 무제한)
+    /// 고정 폭 대신 매치된 바이트만 정확히 출력 (-w 무시)
+    #[arg(long = "match-only")]
+    pub match_only: bool,
+
+    /// 출력할 라인 수 (0: 무제한, 멀티파일 모드에서는 파일마다 적용)
     #[arg(short = 'n', long = "line", default_value = "0")]
     pub limit: usize,
 
@@ -34,41 +166,286 @@ pub struct Cli {
     #[arg(short = 's', long = "position", default_value = "0")]
     pub position: u64,
 
+    /// 검색할 바이트 수 (시작 위치부터, --end와 함께 사용 불가)
+    #[arg(long = "length", conflicts_with = "end")]
+    pub length: Option<u64>,
+
+    /// 검색 종료 위치 (바이트 단위, 이 위치 이후에 시작하는 매치는 출력하지 않음)
+    #[arg(long = "end")]
+    pub end: Option<u64>,
+
+    /// 디스크 이미지의 파티션 테이블(MBR/GPT)을 출력하고 종료 (번호는 --partition에 사용)
+    #[arg(long = "list-partitions", conflicts_with_all = ["file_set", "partition", "quiet", "interactive", "watch"])]
+    pub list_partitions: bool,
+
+    /// 디스크 이미지에서 N번 파티션만 검색 (-s/--end/--length는 파티션 시작 기준, 출력 오프셋은 이미지 기준)
+    #[arg(long = "partition", value_name = "N", conflicts_with_all = ["file_set", "watch"])]
+    pub partition: Option<usize>,
+
+    /// 매치 출력 시작 오프셋을 N바이트 경계로 정렬 (2의 거듭제곱, 1~65536)
+    #[arg(long = "align")]
+    pub align: Option<usize>,
+
     /// 바이트 문자열 분리 기호
     #[arg(short = 't', long = "separator", default_value = " ")]
     pub separator: String,
 
-    /// 오프셋 출력 안함
-    #[arg(long = "no-offset")]
-    pub no_offset: bool,
+    /// N바이트씩 붙여서 묶음 단위로 분리 기호 출력 (xxd -g와 같음, 예: 4 → DEADBEEF CAFEBABE)
+    #[arg(long = "group-size", default_value = "1")]
+    pub group_size: usize,
+
+    /// 헥사 바이트를 소문자로 출력 (예: ab cd)
+    #[arg(long = "lowercase")]
+    pub lowercase: bool,
+
+    /// 오프셋 출력 안함 (이전 이름 --hideoffset도 사용 가능)
+    #[arg(long = "no-offset", alias = "hideoffset")]
+    pub hide_offset: bool,
 
     /// 병렬 처리 사용 (큰 파일에서 성능 향상)
     #[arg(short = 'p', long = "parallel")]
     pub parallel: bool,
 
+    /// 정규식 검색 시 일반 파일을 항상 메모리 매핑으로 검색 (256MB 이상 파일은 지정하지 않아도 적용)
+    #[arg(long = "mmap", conflicts_with = "parallel")]
+    pub mmap: bool,
+
     /// 청크 크기 (병렬 처리 시, 바이트 단위, 기본값: 16MB)
     #[arg(long = "chunk-size", default_value = "16777216")]
     pub chunk_size: usize,
 
+    /// 병렬 검색 후 청크 수, 청크 평균 검색 시간, 읽기 대기/작업자 유휴 시간, 병렬 속도 향상 추정치를 stderr에 출력
+    #[arg(long = "stats", requires = "parallel")]
+    pub stats: bool,
+
     /// 다중 파일 모드 (glob 패턴 또는 여러 파일 처리)
     #[arg(short = 'm', long = "multi")]
     pub multi_file: bool,
 
-    /// 전체 파일에 대한 전역 제한 (0: 무제한)
+    /// 디렉터리를 재귀적으로 탐색해 모든 일반 파일 검색 (디렉터리 심볼릭 링크는 --follow를 지정할 때만 따라감)
+    #[arg(short = 'r', long = "recursive", conflicts_with = "quiet")]
+    pub recursive: bool,
+
+    /// 검색할 파일 목록을 파일 또는 stdin(-)에서 읽음 (한 줄에 하나, 없는 경로는 경고 후 건너뜀, 예: find . -name '*.bin' | hxgrep --files-from - -e ...)
+    #[arg(long = "files-from", value_name = "PATH", conflicts_with_all = ["file_path", "quiet"])]
+    pub files_from: Option<String>,
+
+    /// --files-from 목록과 -b 출력을 줄바꿈 대신 NUL 문자로 구분 (find -print0, xargs -0과 함께 사용, -Z도 같음)
+    #[arg(short = '0', long = "null", short_alias = 'Z', requires = "null_output")]
+    pub null_separated: bool,
+
+    /// 각 매치의 시작 오프셋만 10진수로 한 줄에 하나씩 출력 (헥사 출력 생략, -s 값으로 그대로 사용 가능)
+    #[arg(short = 'b', long = "byte-offset", conflicts_with_all = ["invert_match", "strings_mode", "msgpack", "quiet", "interactive"])]
+    pub byte_offset: bool,
+
+    /// 매치가 있는 파일 경로만 출력 (단일 파일이면 매치가 있을 때 그 경로, stdin이면 "(standard input)")
+    #[arg(short = 'l', long = "files-with-matches", conflicts_with_all = ["files_without_match", "interactive"])]
+    pub files_with_matches: bool,
+
+    /// 매치가 없는 파일 경로만 출력 (단일 파일이면 매치가 없을 때 그 경로)
+    #[arg(short = 'L', long = "files-without-match", conflicts_with = "interactive")]
+    pub files_without_match: bool,
+
+    /// 다중 파일 또는 재귀 모드에서 glob 패턴과 일치하는 파일 제외 (경로 또는 파일 이름, 여러 번 지정 가능, 예: --exclude "*.log")
+    #[arg(long = "exclude", value_name = "GLOB", requires = "file_set")]
+    pub exclude: Vec<String>,
+
+    /// 다중 파일 또는 재귀 모드에서 glob 패턴과 일치하는 파일만 검색 (여러 번 지정 가능, --exclude가 우선)
+    #[arg(long = "include", value_name = "GLOB", requires = "file_set")]
+    pub include: Vec<String>,
+
+    /// 재귀 모드에서 디렉터리 심볼릭 링크를 따라감 (링크 루프는 경고 후 건너뜀)
+    #[arg(long = "follow", requires = "recursive")]
+    pub follow: bool,
+
+    /// 재귀 모드에서 탐색할 최대 디렉터리 깊이 (1: 지정한 디렉터리 바로 아래 파일만)
+    #[arg(long = "max-depth", value_name = "N", requires = "recursive")]
+    pub max_depth: Option<usize>,
+
+    /// 재귀 모드에서 .gitignore/.ignore 파일에 따라 무시되는 파일 제외
+    #[arg(long = "respect-gitignore", requires = "recursive")]
+    pub respect_gitignore: bool,
+
+    /// 다중 파일 또는 재귀 모드에서 이 크기(바이트)보다 큰 파일 건너뛰기 (기본값: 100GB)
+    #[arg(long = "max-filesize", value_name = "BYTES", requires = "file_set")]
+    pub max_filesize: Option<u64>,
+
+    /// 다중 파일 또는 재귀 모드에서 검색 중 오류가 난 파일이 하나라도 있으면 실패로 종료 (기본값: 경고 후 계속하고 모든 파일이 실패할 때만 실패)
+    #[arg(long = "strict", requires = "file_set")]
+    pub strict: bool,
+
+    /// 다중 파일 또는 재귀 모드에서 파일 처리 순서 (name: 경로(기본), size: 크기, modified: 수정 시각, none: 찾은 순서)
+    #[arg(long = "sort-files", value_enum, value_name = "ORDER", requires = "file_set")]
+    pub sort_files: Option<SortOrder>,
+
+    /// --sort-files 순서를 뒤집음 (큰 파일, 최근 파일부터)
+    #[arg(long = "sort-descending", requires = "file_set")]
+    pub sort_descending: bool,
+
+    /// 다중 파일 또는 재귀 모드에서 동시에 검색할 파일 수 (기본값: 1, 출력 순서와 내용은 1개일 때와 같음, --global-limit과 함께 쓰면 순차 검색)
+    #[arg(short = 'j', long = "jobs", value_name = "N", requires = "file_set")]
+    pub jobs: Option<usize>,
+
+    /// 멀티파일 모드에서 모든 파일을 합친 매치/줄 수 제한 (0: 무제한, 도달한 파일 안에서 바로 멈춤)
     #[arg(long = "global-limit", default_value = "0")]
     pub global_limit: usize,
 
-    /// 출력 형식 (hex, json, csv, plain)
+    /// 출력 형식 (hex, json, csv, plain, ndjson, msgpack, xxd, od)
     #[arg(short = 'f', long = "format", default_value = "hex")]
     pub output_format: String,
 
-    /// 진행률 표시 (대용량 파일 처리 시)
-    #[arg(long = "progress")]
-    pub show_progress: bool,
+    /// 출력을 stdout 대신 파일에 씀 (셸 리다이렉트 없이 진행률은 stderr에 그대로 표시, 디렉터리가 없으면 오류, --color auto에서는 색상 없음)
+    #[arg(short = 'o', long = "output", value_name = "PATH", conflicts_with_all = ["quiet", "interactive"])]
+    pub output: Option<PathBuf>,
+
+    /// MessagePack 형식으로 출력 (`-f msgpack`의 단축 옵션, 바이너리 출력이므로 파일로 리다이렉트 권장)
+    #[arg(long = "msgpack", conflicts_with = "output_format")]
+    pub msgpack: bool,
+
+    /// 구조화 출력 레코드의 JSON Schema를 출력하고 종료 (matches: 매치, hexdump: 헥사 덤프 줄, summary: 멀티파일 skipped/summary)
+    #[arg(long = "schema", value_enum)]
+    pub schema: Option<SchemaKind>,
+
+    /// xxd 기본 형식과 동일하게 출력 (`-f xxd`의 단축 옵션, 16바이트 고정 폭, 소문자 16진수, ASCII 열)
+    #[arg(long = "xxd-compat", conflicts_with_all = ["output_format", "msgpack"])]
+    pub xxd_compat: bool,
+
+    /// `od -tx1 -v` 형식과 동일하게 출력 (`-f od`의 단축 옵션, 16바이트 고정 폭, --no-offset 시 `od -An`)
+    #[arg(long = "od-compat", conflicts_with_all = ["output_format", "msgpack", "xxd_compat"])]
+    pub od_compat: bool,
+
+    /// od 형식의 오프셋 진법 (o: 8진수, d: 10진수, x: 16진수, od의 -A 옵션과 동일)
+    #[arg(long = "od-radix", value_enum, default_value = "o")]
+    pub od_radix: OdRadix,
+
+    /// 파일 끝에 추가되는 데이터를 계속 감시하며 새 매치 출력 (Ctrl-C로 종료하면 요약 출력)
+    #[arg(
+        long = "watch",
+        requires = "expression",
+        conflicts_with_all = ["file_set", "parallel", "invert_match", "strings_mode", "quiet", "interactive", "msgpack", "end", "length", "skip_holes"]
+    )]
+    pub watch: bool,
+
+    /// --watch 모드에서 파일 크기를 확인하는 간격 (밀리초)
+    #[arg(long = "poll-ms", default_value = "250", requires = "watch")]
+    pub poll_interval_ms: u64,
 
-    /// 색상 출력 설정 (always, never, auto)
+    /// -s/-n 인자를 바꿔 쓴 것으로 보일 때 stderr에 출력하는 힌트 끄기
+    #[arg(long = "no-hints")]
+    pub no_hints: bool,
+
+    /// 경고(건너뛴 파일, 검색 중 잘린 파일 등)가 하나라도 있으면 끝에 목록을 출력하고 종료 코드 2로 종료
+    #[arg(long = "fail-on-warning")]
+    pub fail_on_warning: bool,
+
+    /// 진행률 표시 (대용량 파일 처리 시, 기본은 stderr가 터미널일 때만, --progress=always는 리다이렉트된 stderr에도 표시)
+    #[arg(
+        long = "progress",
+        value_name = "WHEN",
+        value_enum,
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "auto"
+    )]
+    pub progress: Option<ProgressWhen>,
+
+    /// 출력 없이 매치 여부만 종료 코드로 반환 (0: 매치, 1: 매치 없음, 2: 오류)
+    #[arg(short = 'q', long = "quiet")]
+    pub quiet: bool,
+
+    /// 절대 파일 오프셋이 N의 배수인 매치만 출력 (예: 512, 4096)
+    #[arg(long = "aligned", conflicts_with = "file_set")]
+    pub aligned: Option<u64>,
+
+    /// --aligned와 함께 오프셋 % N == K 인 매치만 출력 (예: MBR 시그니처는 --aligned 512 --aligned-offset 510)
+    #[arg(long = "aligned-offset", default_value = "0", requires = "aligned")]
+    pub aligned_offset: u64,
+
+    /// 각 매치의 전체 바이트를 <디렉터리>/<원본파일명>_offset_<HEX>.bin 파일로 추출 (manifest.jsonl에 기록, 다시 실행하면 같은 내용은 건너뛰고 바뀐 내용은 _v2 등 새 이름으로 저장)
+    #[arg(short = 'x', long = "extract-dir")]
+    pub extract_dir: Option<PathBuf>,
+
+    /// 각 출력 라인 끝에 섀넌 엔트로피(바이트당 비트, 0.00~8.00) 표시
+    #[arg(long = "entropy")]
+    pub show_entropy: bool,
+
+    /// 엔트로피가 이 값 이상인 라인만 출력
+    #[arg(long = "entropy-threshold-min", requires = "show_entropy")]
+    pub entropy_min: Option<f64>,
+
+    /// 엔트로피가 이 값 이하인 라인만 출력
+    #[arg(long = "entropy-threshold-max", requires = "show_entropy")]
+    pub entropy_max: Option<f64>,
+
+    /// 희소 파일의 빈 영역(hole)을 건너뜀 (SEEK_DATA/SEEK_HOLE 지원 시)
+    #[arg(long = "skip-holes")]
+    pub skip_holes: bool,
+
+    /// gzip으로 압축된 파일을 풀면서 검색 (오프셋은 압축을 푼 데이터 기준, 매치마다 압축 파일 안의 위치를 버퍼 단위의 범위로 함께 표시)
+    #[arg(
+        short = 'z',
+        long = "decompress",
+        conflicts_with_all = ["file_set", "parallel", "mmap", "skip_holes", "watch", "interactive", "partition", "list_partitions"]
+    )]
+    pub decompress: bool,
+
+    /// 헥사 덤프에서 모든 바이트가 0인 줄을 생략하고 연속된 줄마다 `*` 한 줄로 표시 (hexdump와 같음)
+    #[arg(long = "skip-zeros")]
+    pub skip_zeros: bool,
+
+    /// 매치 출력과 -x 추출에서 끝에 연속된 이 바이트를 잘라냄 (예: FF, 0x00; 잘린 바이트 수를 `| +N*FF`로 표시, 전부 같은 바이트면 첫 바이트만 남김)
+    #[arg(long = "trim-trailing", value_name = "BYTE", value_parser = parse_byte)]
+    pub trim_trailing: Option<u8>,
+
+    /// 대화형 패턴 테스트 모드: 파일을 한 번 로드한 뒤 stdin에서 패턴을 한 줄씩 읽어 검색 (:width N, :limit N, :quit)
+    #[arg(long = "interactive", conflicts_with_all = ["file_set", "quiet"])]
+    pub interactive: bool,
+
+    /// 대화형 모드에서 로드할 최대 크기 (MB 단위)
+    #[arg(long = "interactive-mb", default_value = "256", requires = "interactive")]
+    pub interactive_mb: u64,
+
+    /// 텍스트 열과 구조화 출력의 ascii_data에 사용할 문자 집합 (ascii, ebcdic, file:<경로>)
+    /// 파일은 바이트 0x00-0xFF 순서로 한 줄에 한 문자 (빈 줄은 출력 불가 문자 '.')
+    #[arg(long = "charset", value_name = "CHARSET")]
+    pub charset: Option<String>,
+
+    /// 색상 출력 설정 (always, never, auto; never는 stdout과 stderr 모두에 적용, auto는 NO_COLOR, CLICOLOR_FORCE, TERM=dumb를 이 순서로 따름)
     #[arg(long = "color", default_value = "auto")]
     pub color: ColorChoice,
+
+    /// 매치된 바이트의 색상 (예: red, yellow, bright_magenta; 기본값 red)
+    #[arg(long = "highlight-color", value_name = "NAME", value_parser = parse_color)]
+    pub highlight_color: Option<Color>,
+
+    /// 매치되지 않은 바이트를 종류별로 색칠 (0x00 회색, 출력 가능 ASCII 초록, 제어 문자 노랑, 0xFF 빨강, 그 외 파랑)
+    #[arg(long = "color-bytes")]
+    pub color_bytes: bool,
+
+    /// 설정 파일 (TOML) 경로: Config 값과 옵션 기본값(default_width, default_separator, default_limit, default_format) 지정, 명령줄 옵션이 우선
+    #[arg(long = "config", value_name = "PATH")]
+    pub config_file: Option<PathBuf>,
+}
+
+/// Parse a byte value written as two hex digits, with an optional `0x` or `\x` prefix
+fn parse_byte(value: &str) -> std::result::Result<u8, String> {
+    let digits = value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+        .or_else(|| value.strip_prefix("\\x"))
+        .unwrap_or(value);
+    match digits.len() {
+        1 | 2 => u8::from_str_radix(digits, 16).map_err(|_| format!("invalid byte value: {}", value)),
+        _ => Err(format!("expected one byte as hex digits (e.g. FF), got: {}", value)),
+    }
+}
+
+/// Parse a color name such as `red` or `bright_magenta`
+fn parse_color(value: &str) -> std::result::Result<Color, String> {
+    value
+        .replace(['_', '-'], " ")
+        .parse()
+        .map_err(|_| format!("unknown color: {} (e.g. red, yellow, bright_blue)", value))
 }
 
 #[derive(Debug, Clone, ValueEnum)]
@@ -80,3 +457,245 @@ pub enum ColorChoice {
     /// 터미널일 때만 색상 출력
     Auto,
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ProgressWhen {
+    /// stderr가 터미널일 때만 진행률 표시
+    Auto,
+    /// stderr가 파일이나 파이프로 리다이렉트되어도 진행률 표시 (로그 수집용)
+    Always,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum SortOrder {
+    /// 경로의 바이트 순서 (로케일과 무관하게 항상 같은 순서)
+    #[default]
+    Name,
+    /// 파일 크기 순서 (같으면 경로 순서)
+    Size,
+    /// 수정 시각 순서 (같으면 경로 순서)
+    Modified,
+    /// 정렬하지 않고 glob 또는 디렉터리 탐색이 찾은 순서
+    None,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SchemaKind {
+    /// 매치 레코드 (-f json 배열 원소, -f ndjson 한 줄)
+    Matches,
+    /// 헥사 덤프 줄 레코드
+    Hexdump,
+    /// 멀티파일 검색의 skipped/summary 레코드와 JSON 보고서
+    Summary,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum StringEncoding {
+    /// UTF-8 (ASCII 포함)
+    Utf8,
+    /// UTF-16 리틀 엔디안 (Windows 와이드 문자열)
+    Utf16le,
+    /// UTF-16 빅 엔디안
+    Utf16be,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OdRadix {
+    /// 16진수 오프셋 (od -Ax)
+    X,
+    /// 8진수 오프셋 (od -Ao, 기본값)
+    O,
+    /// 10진수 오프셋 (od -Ad)
+    D,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Endian {
+    /// 리틀 엔디안
+    Le,
+    /// 빅 엔디안
+    Be,
+}
+
+impl Cli {
+    /// Apply option defaults from a config file to the options not given on the command line
+    pub fn apply_config_defaults(&mut self, defaults: &CliDefaults, matches: &ArgMatches) {
+        let from_command_line = |id: &str| matches.value_source(id) == Some(ValueSource::CommandLine);
+
+        if let Some(width) = defaults.width.filter(|_| !from_command_line("line_width")) {
+            self.line_width = width;
+        }
+        if let Some(separator) = defaults.separator.as_ref().filter(|_| !from_command_line("separator")) {
+            self.separator = separator.clone();
+        }
+        if let Some(limit) = defaults.limit.filter(|_| !from_command_line("limit")) {
+            self.limit = limit;
+        }
+        // --xxd-compat and --od-compat conflict with -f, so they also override its default
+        if let Some(format) = defaults.format.as_ref().filter(|_| {
+            !from_command_line("output_format") && !self.xxd_compat && !self.od_compat && !self.msgpack
+        }) {
+            self.output_format = format.clone();
+        }
+    }
+
+    /// Whether output should match `xxd`, from `--xxd-compat` or `-f xxd`
+    pub fn xxd_output(&self) -> bool {
+        self.xxd_compat || self.output_format.eq_ignore_ascii_case("xxd")
+    }
+
+    /// Whether output should match `od -tx1 -v`, from `--od-compat` or `-f od`
+    pub fn od_output(&self) -> bool {
+        self.od_compat || self.output_format.eq_ignore_ascii_case("od")
+    }
+
+    /// Absolute offset at which reading stops, from `--end` or `--length`
+    pub fn end_offset(&self) -> Option<u64> {
+        match (self.end, self.length) {
+            (Some(end), _) => Some(end),
+            (None, Some(length)) => Some(self.position.saturating_add(length)),
+            (None, None) => None,
+        }
+    }
+
+    /// Whether part of the input may be left unread: `--skip-holes`, `-s`, `--end`/`--length`
+    /// or `--partition`, which sets the start and end
+    pub fn limits_scan(&self) -> bool {
+        self.skip_holes || self.position > 0 || self.end_offset().is_some()
+    }
+
+    /// Whether progress is drawn, from `--progress` on a terminal stderr or `--progress=always`
+    pub fn show_progress(&self) -> bool {
+        match self.progress {
+            Some(ProgressWhen::Always) => true,
+            Some(ProgressWhen::Auto) => ProgressIndicator::should_show_progress(),
+            None => false,
+        }
+    }
+
+    /// Letter case of the hex digits selected with `--lowercase`
+    pub fn hex_case(&self) -> HexCase {
+        if self.lowercase {
+            HexCase::Lower
+        } else {
+            HexCase::Upper
+        }
+    }
+
+    /// Search settings from the display, limit and parallel options
+    ///
+    /// With `--match-only` and a pattern the width is 0, so exactly the matched bytes are displayed.
+    pub fn search_options(&self) -> SearchOptions {
+        let width = if self.match_only && self.expression.is_some() {
+            0
+        } else {
+            self.line_width
+        };
+        SearchOptions::new()
+            .width(width)
+            .limit(self.limit)
+            .separator(&self.separator)
+            .group_size(self.group_size)
+            .hex_case(self.hex_case())
+            .show_offset(!self.hide_offset)
+            .skip_zeros(self.skip_zeros)
+            .align(self.align)
+            .trim_trailing(self.trim_trailing)
+            .parallel(self.parallel)
+            .chunk_size(self.chunk_size)
+            .global_limit(self.global_limit)
+            .offsets_only(self.byte_offset)
+            .null_terminated(self.byte_offset && self.null_separated)
+    }
+
+    /// Hex dump settings from the display, limit and chunk size options
+    pub fn dump_options(&self) -> DumpOptions {
+        DumpOptions::new()
+            .width(self.line_width)
+            .limit(self.limit)
+            .separator(&self.separator)
+            .group_size(self.group_size)
+            .hex_case(self.hex_case())
+            .show_offset(!self.hide_offset)
+            .skip_zeros(self.skip_zeros)
+            .chunk_size(self.chunk_size)
+    }
+
+    /// Integer value to search for, with its type and byte order
+    ///
+    /// `--u16`, `--u32`, `--u64` and `--i32` use `--endian`; the typed options such as
+    /// `--uint32-le` carry their own byte order.
+    pub fn integer_search(&self) -> Option<(&str, IntegerType, Endian)> {
+        [
+            (&self.u16_value, IntegerType::U16, self.endian),
+            (&self.u32_value, IntegerType::U32, self.endian),
+            (&self.u64_value, IntegerType::U64, self.endian),
+            (&self.i32_value, IntegerType::I32, self.endian),
+            (&self.uint16_le, IntegerType::U16, Endian::Le),
+            (&self.uint16_be, IntegerType::U16, Endian::Be),
+            (&self.uint32_le, IntegerType::U32, Endian::Le),
+            (&self.uint32_be, IntegerType::U32, Endian::Be),
+            (&self.uint64_le, IntegerType::U64, Endian::Le),
+            (&self.uint64_be, IntegerType::U64, Endian::Be),
+        ]
+        .into_iter()
+        .find_map(|(value, integer_type, endian)| value.as_deref().map(|value| (value, integer_type, endian)))
+    }
+
+    /// Entropy display settings from `--entropy` and its thresholds
+    pub fn entropy_filter(&self) -> Option<EntropyFilter> {
+        self.show_entropy
+            .then(|| EntropyFilter::new(self.entropy_min, self.entropy_max))
+    }
+
+    /// Filter collapsing dense match runs, unless `--no-collapse` was given
+    ///
+    /// The window is the line width, so more than `--max-matches-per-offset` matches
+    /// starting within one line's worth of bytes are reported as a single line.
+    pub fn match_collapser(&self) -> Option<MatchCollapser> {
+        (!self.no_collapse).then(|| MatchCollapser::new(self.line_width, self.max_matches_per_window))
+    }
+
+    /// Per-pattern matchers for `--with-pattern`
+    pub fn pattern_set(&self) -> Result<Option<PatternSet>> {
+        if !self.with_pattern {
+            return Ok(None);
+        }
+        PatternSet::new(&self.patterns).map(Some)
+    }
+
+    /// Match offset constraint from `--aligned` and `--aligned-offset`
+    pub fn match_alignment(&self) -> Option<MatchAlignment> {
+        self.aligned
+            .map(|boundary| MatchAlignment::new(boundary, self.aligned_offset))
+    }
+
+    /// Hints for a likely mix-up of `-s` (start offset) and `-n` (line limit)
+    ///
+    /// A huge `-n` without `-s` usually meant a start offset, and a tiny `-s` dumping
+    /// a huge file without `-e` or `-n` usually meant a line count.
+    pub fn argument_hints(&self, file_size: u64) -> Vec<String> {
+        let mut hints = Vec::new();
+
+        if self.limit >= LARGE_LIMIT_HINT && self.position == 0 {
+            hints.push(format!(
+                "-n {} prints up to {} lines from the start of the file; to start at byte offset {} use -s {}",
+                self.limit, self.limit, self.limit, self.limit
+            ));
+        }
+
+        if self.position > 0
+            && self.position <= SMALL_POSITION_HINT
+            && file_size >= LARGE_FILE_HINT
+            && self.expression.is_none()
+            && self.limit == 0
+        {
+            hints.push(format!(
+                "-s {} dumps the whole {} byte file from byte offset {}; to print only {} lines use -n {}",
+                self.position, file_size, self.position, self.position, self.position
+            ));
+        }
+
+        hints
+    }
+}
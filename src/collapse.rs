@@ -0,0 +1,130 @@
+//! Collapsing of match floods from degenerate patterns
+//!
+//! Patterns such as `(\x00|\x00\x00)` match at nearly every offset of a zero run, so even
+//! non-overlapping results can produce one line per byte. `MatchCollapser` is a streaming
+//! filter over formatted match lines in offset order: once more than the allowed number
+//! of matches fall within one window, the dense run is reported as its first line with a
+//! `(+K collapsed)` suffix. Only the matches of the current window are held back.
+
+use std::collections::VecDeque;
+
+/// Default number of matches allowed within one window before collapsing
+pub const DEFAULT_MAX_MATCHES_PER_WINDOW: usize = 8;
+
+/// Streaming filter that collapses dense runs of matches into a single line
+pub struct MatchCollapser {
+    window: u64,
+    max_matches: usize,
+    pending: VecDeque<(u64, String)>,
+    collapsed: Option<CollapsedRun>,
+}
+
+/// A dense run being collapsed into its first line
+struct CollapsedRun {
+    line: String,
+    last_offset: u64,
+    hidden: usize,
+}
+
+impl MatchCollapser {
+    /// Collapse runs with more than `max_matches` matches within `window` bytes
+    pub fn new(window: usize, max_matches: usize) -> Self {
+        Self {
+            window: window.max(1) as u64,
+            max_matches: max_matches.max(1),
+            pending: VecDeque::new(),
+            collapsed: None,
+        }
+    }
+
+    /// Add the next match in offset order, returning the lines that are ready to print
+    ///
+    /// A collapsed run continues as long as each match starts within one window of the
+    /// previous one.
+    pub fn push(&mut self, offset: u64, line: String) -> Vec<String> {
+        let mut ready = Vec::new();
+
+        if let Some(run) = self.collapsed.as_mut() {
+            if offset < run.last_offset.saturating_add(self.window) {
+                run.last_offset = offset;
+                run.hidden += 1;
+                return ready;
+            }
+            ready.extend(self.finish());
+        }
+
+        // Lines that can no longer share a window with this match are final
+        while let Some(&(first, _)) = self.pending.front() {
+            if first.saturating_add(self.window) > offset {
+                break;
+            }
+            ready.extend(self.pending.pop_front().map(|(_, line)| line));
+        }
+
+        self.pending.push_back((offset, line));
+        if self.pending.len() > self.max_matches {
+            let hidden = self.pending.len() - 1;
+            let (_, line) = self.pending.pop_front().unwrap_or_default();
+            self.pending.clear();
+            self.collapsed = Some(CollapsedRun {
+                line,
+                last_offset: offset,
+                hidden,
+            });
+        }
+
+        ready
+    }
+
+    /// Return every line still held back, e.g. at the end of the input or the match limit
+    pub fn finish(&mut self) -> Vec<String> {
+        let mut ready = Vec::new();
+        if let Some(run) = self.collapsed.take() {
+            ready.push(format!("{} (+{} collapsed)", run.line, run.hidden));
+        }
+        ready.extend(self.pending.drain(..).map(|(_, line)| line));
+        ready
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collapse(offsets: &[u64], window: usize, max_matches: usize) -> Vec<String> {
+        let mut collapser = MatchCollapser::new(window, max_matches);
+        let mut lines = Vec::new();
+        for &offset in offsets {
+            lines.extend(collapser.push(offset, format!("{:X}h", offset)));
+        }
+        lines.extend(collapser.finish());
+        lines
+    }
+
+    #[test]
+    fn test_sparse_matches_pass_through() {
+        assert_eq!(collapse(&[0, 10, 20, 40], 16, 2), vec!["0h", "Ah", "14h", "28h"]);
+    }
+
+    #[test]
+    fn test_dense_run_collapses_into_one_line() {
+        // A match at every offset of a 4KB run, followed by an isolated match
+        let mut offsets: Vec<u64> = (0..4096).collect();
+        offsets.push(8192);
+        assert_eq!(
+            collapse(&offsets, 16, 8),
+            vec!["0h (+4095 collapsed)".to_string(), "2000h".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_lines_before_dense_window_are_kept() {
+        // 0 is a window apart from the burst starting at 20
+        assert_eq!(
+            collapse(&[0, 20, 21, 22, 23], 16, 3),
+            vec!["0h", "14h (+3 collapsed)"]
+        );
+        // At the limit the window is not collapsed
+        assert_eq!(collapse(&[0, 1, 2], 16, 3), vec!["0h", "1h", "2h"]);
+    }
+}
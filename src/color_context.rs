@@ -1,16 +1,221 @@
 //! Global color context for managing color output settings
 
 use crate::cli::ColorChoice;
+use colored::Color;
+use std::ffi::OsStr;
+use std::io::{self, IsTerminal};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::OnceLock;
 
 static COLOR_CONTEXT: OnceLock<ColorChoice> = OnceLock::new();
+static COLOR_THEME: OnceLock<ColorTheme> = OnceLock::new();
+static OUTPUT_TO_FILE: AtomicBool = AtomicBool::new(false);
+
+/// Theme used when none has been set: bold red matches, other bytes uncolored
+static DEFAULT_THEME: ColorTheme = ColorTheme {
+    highlight: Color::Red,
+    byte_categories: false,
+};
+
+/// Colors applied to the hex bytes when output is colored
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColorTheme {
+    /// Color of matched bytes (`--highlight-color`), always drawn bold
+    pub highlight: Color,
+    /// Whether the other bytes are colored by category (`--color-bytes`)
+    pub byte_categories: bool,
+}
+
+impl Default for ColorTheme {
+    fn default() -> Self {
+        DEFAULT_THEME
+    }
+}
+
+/// Output stream whose coloring is being decided
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorStream {
+    Stdout,
+    Stderr,
+}
 
 /// Set the global color choice
+///
+/// Every colored write is gated by [`use_color`], so the `colored` crate is told to
+/// always emit escapes unless colors are off entirely; left alone it would decide by
+/// stdout alone, even for text written to stderr.
+/// On Windows, virtual terminal processing is enabled first so the console interprets
+/// ANSI escapes instead of printing them raw.
 pub fn set_color_choice(color: ColorChoice) {
+    let color = match color {
+        ColorChoice::Never => ColorChoice::Never,
+        requested => resolve_color_choice(requested, console::enable_virtual_terminal()),
+    };
+
+    colored::control::set_override(!matches!(color, ColorChoice::Never));
     COLOR_CONTEXT.set(color).ok();
 }
 
+/// Send the data output to a `-o/--output` file instead of stdout
+///
+/// Under `auto` the output is then treated like redirected stdout and left uncolored,
+/// whatever stdout itself is connected to.
+pub fn set_output_to_file(to_file: bool) {
+    OUTPUT_TO_FILE.store(to_file, Ordering::SeqCst);
+}
+
+/// Whether text written to `stream` should be colored under the global color choice
+pub fn use_color(stream: ColorStream) -> bool {
+    color_enabled(get_color_choice(), stream)
+}
+
+/// Whether text written to `stream` should be colored under `color_choice`
+///
+/// `always` and `never` are final. Under `auto`, a non-empty `NO_COLOR` turns colors
+/// off, then a `CLICOLOR_FORCE` other than empty or `0` turns them on even when the
+/// stream is redirected; otherwise only a terminal whose `TERM` is not `dumb` is colored.
+pub fn color_enabled(color_choice: &ColorChoice, stream: ColorStream) -> bool {
+    match color_choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => {
+            let is_terminal = match stream {
+                ColorStream::Stdout => !OUTPUT_TO_FILE.load(Ordering::SeqCst) && io::stdout().is_terminal(),
+                ColorStream::Stderr => io::stderr().is_terminal(),
+            };
+            auto_color(
+                std::env::var_os("NO_COLOR").as_deref(),
+                std::env::var_os("CLICOLOR_FORCE").as_deref(),
+                std::env::var_os("TERM").as_deref(),
+                is_terminal,
+            )
+        }
+    }
+}
+
+/// Decide `auto` coloring from the environment and the stream's terminal check
+fn auto_color(
+    no_color: Option<&OsStr>,
+    clicolor_force: Option<&OsStr>,
+    term: Option<&OsStr>,
+    is_terminal: bool,
+) -> bool {
+    if no_color.is_some_and(|value| !value.is_empty()) {
+        return false;
+    }
+    if clicolor_force.is_some_and(|value| !value.is_empty() && value != "0") {
+        return true;
+    }
+    if term.is_some_and(|term| term == "dumb") {
+        return false;
+    }
+    is_terminal
+}
+
 /// Get the current color choice (defaults to Auto if not set)
 pub fn get_color_choice() -> &'static ColorChoice {
     COLOR_CONTEXT.get().unwrap_or(&ColorChoice::Auto)
-}
\ No newline at end of file
+}
+
+/// Set the global color theme
+pub fn set_color_theme(theme: ColorTheme) {
+    COLOR_THEME.set(theme).ok();
+}
+
+/// Get the current color theme (defaults to [`ColorTheme::default`] if not set)
+pub fn color_theme() -> &'static ColorTheme {
+    COLOR_THEME.get().unwrap_or(&DEFAULT_THEME)
+}
+
+/// Decide the effective color choice once the console has been prepared
+///
+/// If the console cannot interpret ANSI escapes, `auto` falls back to `never` so output
+/// stays clean. An explicit `always` is kept, since the output may be going to a pager
+/// or file that understands the escapes.
+fn resolve_color_choice(requested: ColorChoice, terminal_supports_ansi: bool) -> ColorChoice {
+    match requested {
+        ColorChoice::Auto if !terminal_supports_ansi => ColorChoice::Never,
+        requested => requested,
+    }
+}
+
+/// Console setup for legacy Windows consoles (conhost)
+#[cfg(windows)]
+mod console {
+    /// Enable virtual terminal processing, returning whether ANSI escapes are supported
+    pub fn enable_virtual_terminal() -> bool {
+        colored::control::set_virtual_terminal(true).is_ok()
+    }
+}
+
+/// Terminals on other platforms interpret ANSI escapes natively
+#[cfg(not(windows))]
+mod console {
+    pub fn enable_virtual_terminal() -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_falls_back_when_enable_failed() {
+        assert!(matches!(
+            resolve_color_choice(ColorChoice::Auto, false),
+            ColorChoice::Never
+        ));
+        assert!(matches!(
+            resolve_color_choice(ColorChoice::Always, false),
+            ColorChoice::Always
+        ));
+    }
+
+    #[test]
+    fn test_auto_color_environment() {
+        assert!(auto_color(None, None, Some(OsStr::new("xterm-256color")), true));
+        assert!(!auto_color(None, None, None, false));
+        assert!(!auto_color(Some(OsStr::new("1")), None, None, true));
+        assert!(!auto_color(None, None, Some(OsStr::new("dumb")), true));
+        // An empty NO_COLOR does not disable colors
+        assert!(auto_color(Some(OsStr::new("")), None, None, true));
+    }
+
+    #[test]
+    fn test_clicolor_force() {
+        let force = Some(OsStr::new("1"));
+        // Forces colors on a redirected stream and a dumb terminal
+        assert!(auto_color(None, force, None, false));
+        assert!(auto_color(None, force, Some(OsStr::new("dumb")), false));
+        // NO_COLOR takes precedence
+        assert!(!auto_color(Some(OsStr::new("1")), force, None, true));
+        // Empty or 0 does not force
+        assert!(!auto_color(None, Some(OsStr::new("0")), None, false));
+        assert!(!auto_color(None, Some(OsStr::new("")), None, false));
+    }
+
+    #[test]
+    fn test_explicit_choice_ignores_stream() {
+        for stream in [ColorStream::Stdout, ColorStream::Stderr] {
+            assert!(color_enabled(&ColorChoice::Always, stream));
+            assert!(!color_enabled(&ColorChoice::Never, stream));
+        }
+    }
+
+    #[test]
+    fn test_resolve_keeps_choice_when_enabled() {
+        assert!(matches!(
+            resolve_color_choice(ColorChoice::Auto, true),
+            ColorChoice::Auto
+        ));
+        assert!(matches!(
+            resolve_color_choice(ColorChoice::Always, true),
+            ColorChoice::Always
+        ));
+        assert!(matches!(
+            resolve_color_choice(ColorChoice::Never, true),
+            ColorChoice::Never
+        ));
+    }
+}
@@ -0,0 +1,167 @@
+//! Transparent gzip decompression with compressed offset tracking
+//!
+//! `-z/--decompress` searches the decompressed contents of a gzip file, while match
+//! offsets stay logical: they count decompressed bytes. To help locate a match in the
+//! compressed file, [`GzipReader`] records how much compressed input had been consumed
+//! at each buffer boundary of the decompressed stream. A match is then reported with
+//! the compressed range consumed while its buffer was decompressed.
+//!
+//! The range is an approximation with a granularity of one buffer (`buffer_size`
+//! decompressed bytes): deflate blocks do not start at byte boundaries known outside
+//! the decoder, so a match cannot be mapped to an exact compressed offset.
+
+use flate2::bufread::MultiGzDecoder;
+use std::io::{self, BufRead, Read};
+use std::ops::Range;
+use std::sync::{Arc, Mutex};
+
+/// Reader decompressing a gzip stream, all members in turn like `gzip -d`
+pub struct GzipReader<R: BufRead> {
+    decoder: MultiGzDecoder<CountingReader<R>>,
+    /// Decompressed bytes produced so far
+    produced: u64,
+    /// Decompressed offset at which the next checkpoint is recorded
+    next_checkpoint: u64,
+    interval: u64,
+    offsets: CompressedOffsets,
+}
+
+impl<R: BufRead> GzipReader<R> {
+    /// Decompress `inner`, recording the compressed position every `interval` decompressed bytes
+    pub fn new(inner: R, interval: usize) -> Self {
+        let interval = interval.max(1) as u64;
+        Self {
+            decoder: MultiGzDecoder::new(CountingReader { inner, consumed: 0 }),
+            produced: 0,
+            next_checkpoint: interval,
+            interval,
+            offsets: CompressedOffsets::default(),
+        }
+    }
+
+    /// Handle mapping decompressed offsets to the compressed input, shared with this reader
+    pub fn offsets(&self) -> CompressedOffsets {
+        self.offsets.clone()
+    }
+}
+
+impl<R: BufRead> Read for GzipReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let bytes_read = self.decoder.read(buf)?;
+        self.produced += bytes_read as u64;
+        let consumed = self.decoder.get_ref().consumed;
+
+        let mut map = self.offsets.lock();
+        map.current = (self.produced, consumed);
+        if self.produced >= self.next_checkpoint {
+            map.checkpoints.push((self.produced, consumed));
+            self.next_checkpoint = self.produced + self.interval;
+        }
+        Ok(bytes_read)
+    }
+}
+
+/// Buffered reader counting the bytes the decoder consumes from it
+struct CountingReader<R> {
+    inner: R,
+    consumed: u64,
+}
+
+impl<R: BufRead> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let bytes_read = self.inner.read(buf)?;
+        self.consumed += bytes_read as u64;
+        Ok(bytes_read)
+    }
+}
+
+impl<R: BufRead> BufRead for CountingReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.consumed += amt as u64;
+        self.inner.consume(amt);
+    }
+}
+
+/// Shared map from decompressed offsets to ranges of the compressed input
+///
+/// Holds one checkpoint per buffer of decompressed data, 16 bytes each.
+#[derive(Clone, Default)]
+pub struct CompressedOffsets {
+    map: Arc<Mutex<OffsetMap>>,
+}
+
+#[derive(Default)]
+struct OffsetMap {
+    /// (decompressed, compressed) positions at the recorded buffer boundaries, ascending
+    checkpoints: Vec<(u64, u64)>,
+    /// Positions reached by the latest read
+    current: (u64, u64),
+}
+
+impl CompressedOffsets {
+    fn lock(&self) -> std::sync::MutexGuard<'_, OffsetMap> {
+        self.map.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Compressed range consumed while the buffer holding decompressed `offset` was produced
+    ///
+    /// Returns `None` for an offset not decompressed yet.
+    pub fn range_of(&self, offset: u64) -> Option<Range<u64>> {
+        let map = self.lock();
+        if offset >= map.current.0 {
+            return None;
+        }
+
+        // The first boundary past the offset ends its buffer; the one before starts it
+        let index = map.checkpoints.partition_point(|&(produced, _)| produced <= offset);
+        let start = index.checked_sub(1).map_or(0, |before| map.checkpoints[before].1);
+        let end = map.checkpoints.get(index).map_or(map.current.1, |&(_, consumed)| consumed);
+        Some(start..end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn test_decompresses_all_members() {
+        let mut compressed = gzip(b"first member, ");
+        compressed.extend(gzip(b"second member"));
+
+        let mut decompressed = String::new();
+        GzipReader::new(&compressed[..], 4).read_to_string(&mut decompressed).unwrap();
+        assert_eq!(decompressed, "first member, second member");
+    }
+
+    #[test]
+    fn test_ranges_grow_with_offset() {
+        // Incompressible data keeps the compressed and decompressed positions close
+        let data: Vec<u8> = (0u32..200_000).map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8).collect();
+        let compressed = gzip(&data);
+
+        let mut reader = GzipReader::new(&compressed[..], 4096);
+        let offsets = reader.offsets();
+        assert_eq!(offsets.range_of(0), None);
+        io::copy(&mut reader, &mut io::sink()).unwrap();
+
+        let early = offsets.range_of(100).unwrap();
+        let late = offsets.range_of(190_000).unwrap();
+        assert!(early.start < early.end && early.end <= late.start, "{:?} {:?}", early, late);
+        assert!(late.end <= compressed.len() as u64);
+        assert_eq!(offsets.range_of(data.len() as u64), None);
+    }
+}
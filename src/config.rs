@@ -1,5 +1,10 @@
 use crate::cli::Cli;
+use crate::diagnostics;
 use crate::error::{BingrepError, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
 
 /// Configuration constants and defaults for bingrep
 #[derive(Debug, Clone)]
@@ -10,6 +15,40 @@ pub struct Config {
     pub min_line_width: usize,
     pub max_file_size: u64,        // Maximum file size to process
     pub max_memory_usage: usize,   // Maximum memory usage in bytes
+    pub use_mmap: bool,            // Always search regular files through a memory mapping
+    pub mmap_threshold: u64,       // Memory-map regular files at least this large
+    pub cli_defaults: CliDefaults, // Option defaults from a config file
+}
+
+/// Defaults for command-line options, set by `default_*` keys in a config file
+///
+/// Options given on the command line always take precedence.
+#[derive(Debug, Clone, Default)]
+pub struct CliDefaults {
+    pub width: Option<usize>,
+    pub separator: Option<String>,
+    pub limit: Option<usize>,
+    pub format: Option<String>,
+}
+
+/// Keys accepted in a TOML config file
+#[derive(Deserialize)]
+struct ConfigFile {
+    buffer_size: Option<usize>,
+    buffer_padding: Option<usize>,
+    max_line_width: Option<usize>,
+    min_line_width: Option<usize>,
+    max_file_size: Option<u64>,
+    max_memory_usage: Option<usize>,
+    use_mmap: Option<bool>,
+    mmap_threshold: Option<u64>,
+    default_width: Option<usize>,
+    default_separator: Option<String>,
+    default_limit: Option<usize>,
+    default_format: Option<String>,
+    /// Keys not listed above, reported as warnings
+    #[serde(flatten)]
+    unknown: BTreeMap<String, toml::Value>,
 }
 
 impl Default for Config {
@@ -22,11 +61,64 @@ impl Default for Config {
             min_line_width: 1,                 // Minimum bytes per line
             max_file_size: 100 * 1024 * 1024 * 1024u64, // 100GB maximum file size
             max_memory_usage: 1024 * 1024 * 1024, // 1GB maximum memory usage
+            use_mmap: false,                   // Only files above the threshold unless --mmap is given
+            mmap_threshold: 256 * 1024 * 1024, // 256MB: mapping beats buffered reads on large files
+            cli_defaults: CliDefaults::default(),
         }
     }
 }
 
 impl Config {
+    /// Load a configuration from a TOML file
+    ///
+    /// Keys mirror the `Config` fields, and `default_width`, `default_separator`,
+    /// `default_limit` and `default_format` set defaults for `-w`, `-t`, `-n` and `-f`.
+    /// Missing keys keep their default values; unknown keys are warned about and ignored.
+    pub fn from_toml_file(path: &Path) -> Result<Config> {
+        let text = fs::read_to_string(path)
+            .map_err(|err| BingrepError::Config(format!("cannot read {}: {}", path.display(), err)))?;
+        let file: ConfigFile = toml::from_str(&text)
+            .map_err(|err| BingrepError::Config(format!("{}: {}", path.display(), err)))?;
+
+        for key in file.unknown.keys() {
+            diagnostics::warning(format!("unknown key '{}' in {} ignored", key, path.display()));
+        }
+
+        let defaults = Config::default();
+        let config = Config {
+            buffer_size: file.buffer_size.unwrap_or(defaults.buffer_size),
+            buffer_padding: file.buffer_padding.unwrap_or(defaults.buffer_padding),
+            max_line_width: file.max_line_width.unwrap_or(defaults.max_line_width),
+            min_line_width: file.min_line_width.unwrap_or(defaults.min_line_width),
+            max_file_size: file.max_file_size.unwrap_or(defaults.max_file_size),
+            max_memory_usage: file.max_memory_usage.unwrap_or(defaults.max_memory_usage),
+            use_mmap: file.use_mmap.unwrap_or(defaults.use_mmap),
+            mmap_threshold: file.mmap_threshold.unwrap_or(defaults.mmap_threshold),
+            cli_defaults: CliDefaults {
+                width: file.default_width,
+                separator: file.default_separator,
+                limit: file.default_limit,
+                format: file.default_format,
+            },
+        };
+
+        // The scanners step back by the padding, so it must leave room to advance
+        if config.buffer_padding >= config.buffer_size {
+            return Err(BingrepError::Config(format!(
+                "buffer_padding {} must be smaller than buffer_size {}",
+                config.buffer_padding, config.buffer_size
+            )));
+        }
+        if config.min_line_width == 0 || config.min_line_width > config.max_line_width {
+            return Err(BingrepError::Config(format!(
+                "min_line_width {} must be between 1 and max_line_width {}",
+                config.min_line_width, config.max_line_width
+            )));
+        }
+
+        Ok(config)
+    }
+
     /// Validate all input parameters from CLI
     pub fn validate_cli(&self, cli: &Cli) -> Result<()> {
         // Validate line width
@@ -34,6 +126,20 @@ impl Config {
             return Err(BingrepError::InvalidWidth(cli.line_width));
         }
 
+        // Validate group size so every block holds at least one byte
+        if cli.group_size == 0 {
+            return Err(BingrepError::InvalidPattern(
+                "Group size must be at least 1".to_string(),
+            ));
+        }
+
+        // Validate chunk size is non-zero so the chunk loops always advance
+        if cli.chunk_size == 0 {
+            return Err(BingrepError::InvalidPattern(
+                "Chunk size must be at least 1".to_string(),
+            ));
+        }
+
         // Validate chunk size doesn't exceed memory limits
         if cli.chunk_size > self.max_memory_usage / 4 {
             return Err(BingrepError::InvalidPattern(format!(
@@ -43,12 +149,63 @@ impl Config {
             )));
         }
 
+        // Validate alignment is a power of 2 within the supported range
+        if let Some(align) = cli.align {
+            if !align.is_power_of_two() || align > 65536 {
+                return Err(BingrepError::InvalidPattern(format!(
+                    "Alignment {} must be a power of 2 between 1 and 65536",
+                    align
+                )));
+            }
+        }
+
+        // Validate match alignment leaves at least one acceptable offset per boundary
+        if let Some(boundary) = cli.aligned {
+            if boundary == 0 || cli.aligned_offset >= boundary {
+                return Err(BingrepError::InvalidPattern(format!(
+                    "Aligned offset {} must be less than a non-zero alignment {}",
+                    cli.aligned_offset, boundary
+                )));
+            }
+        }
+
+        // Validate the minimum string length for --strings
+        if cli.strings_mode && cli.strings_min_len == 0 {
+            return Err(BingrepError::InvalidPattern(
+                "Minimum string length must be at least 1".to_string(),
+            ));
+        }
+
+        // Validate the collapse threshold for dense matches
+        if !cli.no_collapse && cli.max_matches_per_window == 0 {
+            return Err(BingrepError::InvalidPattern(
+                "--max-matches-per-offset must be at least 1".to_string(),
+            ));
+        }
+
+        // Validate the watch poll interval so polling cannot spin
+        if cli.watch && cli.poll_interval_ms == 0 {
+            return Err(BingrepError::InvalidPattern(
+                "--poll-ms must be at least 1".to_string(),
+            ));
+        }
+
         // Validate limit (must be non-negative, but usize ensures this)
         // No additional validation needed for limit
 
         // Validate position (must be non-negative, but u64 ensures this)
         // No additional validation needed for position
 
+        // Validate the end bound leaves a non-empty range
+        if let Some(end) = cli.end_offset() {
+            if end <= cli.position {
+                return Err(BingrepError::InvalidPattern(format!(
+                    "End offset {} must be greater than start position {}",
+                    end, cli.position
+                )));
+            }
+        }
+
         Ok(())
     }
 
@@ -84,13 +241,87 @@ impl Config {
         Ok(())
     }
 
+    /// Validate the start position lies within the file
+    ///
+    /// Starting exactly at the end is allowed and simply produces no output.
+    pub fn validate_position(&self, position: u64, size: u64) -> Result<()> {
+        if position > size {
+            return Err(BingrepError::InvalidPattern(format!(
+                "Start position {} exceeds file size {} bytes",
+                position, size
+            )));
+        }
+        Ok(())
+    }
+
     /// Get maximum file size limit
     pub fn get_max_file_size(&self) -> u64 {
         self.max_file_size
     }
 
+    /// Whether a regular file of this size is searched through a memory mapping
+    pub fn should_mmap(&self, file_size: u64) -> bool {
+        self.use_mmap || file_size >= self.mmap_threshold
+    }
+
     /// Get maximum memory usage limit
     pub fn get_max_memory_usage(&self) -> usize {
         self.max_memory_usage
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_toml_file() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("hxgrep.toml");
+        fs::write(
+            &path,
+            r#"
+buffer_size = 65536
+buffer_padding = 512
+max_line_width = 4096
+use_mmap = true
+default_width = 32
+default_separator = "-"
+default_limit = 10
+no_such_key = 1
+"#,
+        )?;
+
+        let config = Config::from_toml_file(&path)?;
+        assert_eq!(config.buffer_size, 65536);
+        assert_eq!(config.buffer_padding, 512);
+        assert_eq!(config.max_line_width, 4096);
+        assert!(config.use_mmap);
+        assert_eq!(config.cli_defaults.width, Some(32));
+        assert_eq!(config.cli_defaults.separator.as_deref(), Some("-"));
+        assert_eq!(config.cli_defaults.limit, Some(10));
+        assert_eq!(config.cli_defaults.format, None);
+
+        // Keys that are not set keep their defaults
+        let defaults = Config::default();
+        assert_eq!(config.min_line_width, defaults.min_line_width);
+        assert_eq!(config.mmap_threshold, defaults.mmap_threshold);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_toml_file_errors() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+
+        // A missing file is an error rather than a silent fallback to the defaults
+        assert!(Config::from_toml_file(&dir.path().join("missing.toml")).is_err());
+
+        let path = dir.path().join("hxgrep.toml");
+        fs::write(&path, "buffer_size = \"large\"")?;
+        assert!(Config::from_toml_file(&path).is_err());
+
+        fs::write(&path, "buffer_size = 4096\nbuffer_padding = 4096")?;
+        assert!(Config::from_toml_file(&path).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,181 @@
+//! Byte ranges actually read by a scan
+//!
+//! `--skip-holes`, `-s`/`--end`/`--length` and `--partition` leave parts of a file
+//! unread. `ScanCoverage` records the ranges the buffered readers read, merged as they
+//! arrive, and reports them against the requested range: as a summary on stderr and as
+//! a `coverage` record in the structured output.
+
+use crate::progress::format_bytes;
+use crate::structured_output::CoverageRecord;
+
+/// Merged byte ranges read within a requested range
+#[derive(Debug, Clone)]
+pub struct ScanCoverage {
+    start: u64,
+    end: Option<u64>,
+    scanned: Vec<(u64, u64)>,
+}
+
+impl ScanCoverage {
+    /// Track the reads of a scan requested from `start` up to `end` (EOF if `None`)
+    ///
+    /// With no end bound the requested range ends where the last read did.
+    pub fn new(start: u64, end: Option<u64>) -> Self {
+        Self {
+            start,
+            end,
+            scanned: Vec::new(),
+        }
+    }
+
+    /// Record `bytes` read at `pos`
+    ///
+    /// Reads arrive in offset order, so a read overlapping or touching the last range
+    /// extends it; only a read starting before it needs the ranges to be merged again.
+    pub fn record_read(&mut self, pos: u64, bytes: u64) {
+        if bytes == 0 {
+            return;
+        }
+        let end = pos.saturating_add(bytes);
+
+        match self.scanned.last_mut() {
+            Some(last) if pos >= last.0 && pos <= last.1 => last.1 = last.1.max(end),
+            Some(last) if pos < last.0 => {
+                self.scanned.push((pos, end));
+                self.scanned = merge_ranges(std::mem::take(&mut self.scanned));
+            }
+            _ => self.scanned.push((pos, end)),
+        }
+    }
+
+    /// Requested range (start inclusive, end exclusive)
+    pub fn requested(&self) -> (u64, u64) {
+        let scanned_end = self.scanned.last().map_or(self.start, |&(_, end)| end);
+        (self.start, self.end.unwrap_or(scanned_end).max(self.start))
+    }
+
+    /// Byte ranges read so far, merged (start inclusive, end exclusive)
+    ///
+    /// Bytes read twice, such as the overlap kept between search buffers, are counted once.
+    pub fn scanned_ranges(&self) -> &[(u64, u64)] {
+        &self.scanned
+    }
+
+    /// Number of bytes of the requested range that were read
+    pub fn scanned_bytes(&self) -> u64 {
+        let (start, end) = self.requested();
+        self.scanned
+            .iter()
+            .map(|&(range_start, range_end)| range_end.min(end).saturating_sub(range_start.max(start)))
+            .sum()
+    }
+
+    /// Share of the requested range covered by the scanned ranges, in percent
+    pub fn percent(&self) -> f64 {
+        let (start, end) = self.requested();
+        if end == start {
+            return 100.0;
+        }
+        self.scanned_bytes() as f64 * 100.0 / (end - start) as f64
+    }
+
+    /// Summary of the scanned ranges for the end-of-run report
+    pub fn summary(&self) -> String {
+        let (start, end) = self.requested();
+        let (size_value, size_unit) = format_bytes(end - start);
+        let mut summary = format!(
+            "Scanned {:.1}% of {:.1} {} ({:X}h-{:X}h) in {} range(s)",
+            self.percent(),
+            size_value,
+            size_unit,
+            start,
+            end,
+            self.scanned.len()
+        );
+        for (start, end) in &self.scanned {
+            summary.push_str(&format!("\n  scanned: {:X}h-{:X}h", start, end));
+        }
+        summary
+    }
+
+    /// Structured form of the coverage, written as the `coverage` record
+    pub fn record(&self) -> CoverageRecord {
+        let (start, end) = self.requested();
+        CoverageRecord {
+            requested: [start, end],
+            ranges: self.scanned.iter().map(|&(start, end)| [start, end]).collect(),
+            percent: self.percent(),
+        }
+    }
+}
+
+/// Sort byte ranges (start inclusive, end exclusive) and merge the ones that overlap or touch
+///
+/// Empty ranges are dropped.
+pub fn merge_ranges(mut ranges: Vec<(u64, u64)>) -> Vec<(u64, u64)> {
+    ranges.retain(|(start, end)| start < end);
+    ranges.sort_unstable();
+
+    let mut merged: Vec<(u64, u64)> = Vec::with_capacity(ranges.len());
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_ranges() {
+        assert_eq!(
+            merge_ranges(vec![(20, 30), (0, 10), (5, 12), (12, 15), (40, 40)]),
+            vec![(0, 15), (20, 30)]
+        );
+        assert_eq!(merge_ranges(Vec::new()), Vec::new());
+    }
+
+    #[test]
+    fn test_reads_in_order_extend_the_last_range() {
+        // Buffers overlapping by their padding, then a jump over a hole
+        let mut coverage = ScanCoverage::new(0, Some(400));
+        coverage.record_read(0, 60);
+        coverage.record_read(50, 50);
+        coverage.record_read(100, 0);
+        coverage.record_read(200, 100);
+        assert_eq!(coverage.scanned_ranges(), &[(0, 100), (200, 300)]);
+        assert_eq!(coverage.scanned_bytes(), 200);
+        assert_eq!(coverage.percent(), 50.0);
+
+        // A read before the last range is merged in
+        coverage.record_read(90, 20);
+        assert_eq!(coverage.scanned_ranges(), &[(0, 110), (200, 300)]);
+    }
+
+    #[test]
+    fn test_percent_of_requested_range() {
+        // -s 0x100 --length 0x100: the whole requested range was read
+        let mut coverage = ScanCoverage::new(0x100, Some(0x200));
+        coverage.record_read(0x100, 0x100);
+        assert_eq!(coverage.percent(), 100.0);
+        assert_eq!(
+            coverage.summary(),
+            "Scanned 100.0% of 256.0 B (100h-200h) in 1 range(s)\n  scanned: 100h-200h"
+        );
+
+        // Without an end bound the range ends where reading did
+        let mut coverage = ScanCoverage::new(16, None);
+        coverage.record_read(16, 16);
+        assert_eq!(coverage.requested(), (16, 32));
+        let record = coverage.record();
+        assert_eq!(record.requested, [16, 32]);
+        assert_eq!(record.ranges, vec![[16, 32]]);
+
+        // Nothing requested counts as fully covered
+        assert_eq!(ScanCoverage::new(8, Some(8)).percent(), 100.0);
+    }
+}
@@ -0,0 +1,177 @@
+//! Notices written to stderr, classified by severity
+//!
+//! Notices go through [`report`] instead of `eprintln!` so that warnings and errors
+//! are counted for the whole run. With `--fail-on-warning` a run that met any of them
+//! ends with exit code 2 and lists them, which lets pipelines treat skipped files or
+//! truncated results as failures. Only warnings and errors are kept, and only the first
+//! [`MAX_RECORDED`] of them, so a run reporting millions of skipped files stays bounded.
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+/// Number of warnings and errors kept for the `--fail-on-warning` listing; the rest are counted
+pub const MAX_RECORDED: usize = 100;
+
+static RECORDED: Mutex<Recorded> = Mutex::new(Recorded::new());
+static FAIL_ON_WARNING: AtomicBool = AtomicBool::new(false);
+
+/// How serious a notice is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Expected outcome worth mentioning, such as a file left out by `--exclude`
+    Info,
+    /// Results may be incomplete, such as a skipped file or a truncated scan
+    Warning,
+    /// Part of the input could not be processed
+    Error,
+}
+
+/// A notice reported during the run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    /// Info notices are printed as they are, the others behind `Warning: ` or `Error: `
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.severity {
+            Severity::Info => write!(f, "{}", self.message),
+            Severity::Warning => write!(f, "Warning: {}", self.message),
+            Severity::Error => write!(f, "Error: {}", self.message),
+        }
+    }
+}
+
+/// Warnings and errors met so far
+struct Recorded {
+    count: usize,
+    listed: Vec<Diagnostic>,
+}
+
+impl Recorded {
+    const fn new() -> Self {
+        Self {
+            count: 0,
+            listed: Vec::new(),
+        }
+    }
+
+    /// Count a warning or error, keeping it if fewer than [`MAX_RECORDED`] are kept
+    fn push(&mut self, diagnostic: Diagnostic) {
+        if diagnostic.severity < Severity::Warning {
+            return;
+        }
+        self.count += 1;
+        if self.listed.len() < MAX_RECORDED {
+            self.listed.push(diagnostic);
+        }
+    }
+}
+
+/// Print a notice to stderr and record it
+pub fn report(severity: Severity, message: impl Into<String>) {
+    let diagnostic = Diagnostic {
+        severity,
+        message: message.into(),
+    };
+    eprintln!("{}", diagnostic);
+    record_diagnostic(diagnostic);
+}
+
+/// Record a notice without printing it, for notices already written as structured records
+pub fn record(severity: Severity, message: impl Into<String>) {
+    record_diagnostic(Diagnostic {
+        severity,
+        message: message.into(),
+    });
+}
+
+/// Print and record an info notice
+pub fn info(message: impl Into<String>) {
+    report(Severity::Info, message);
+}
+
+/// Print and record a warning
+pub fn warning(message: impl Into<String>) {
+    report(Severity::Warning, message);
+}
+
+fn record_diagnostic(diagnostic: Diagnostic) {
+    if let Ok(mut recorded) = RECORDED.lock() {
+        recorded.push(diagnostic);
+    }
+}
+
+/// First [`MAX_RECORDED`] warnings and errors recorded so far, in the order they were reported
+pub fn warnings() -> Vec<Diagnostic> {
+    RECORDED
+        .lock()
+        .map(|recorded| recorded.listed.clone())
+        .unwrap_or_default()
+}
+
+/// Number of warnings and errors recorded so far, including those past [`MAX_RECORDED`]
+pub fn warning_count() -> usize {
+    RECORDED.lock().map(|recorded| recorded.count).unwrap_or_default()
+}
+
+/// Make warnings fail an otherwise successful run (`--fail-on-warning`)
+pub fn set_fail_on_warning(fail: bool) {
+    FAIL_ON_WARNING.store(fail, Ordering::SeqCst);
+}
+
+/// Whether `--fail-on-warning` is set
+pub fn fail_on_warning() -> bool {
+    FAIL_ON_WARNING.load(Ordering::SeqCst)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_by_severity() {
+        let diagnostic = |severity| Diagnostic {
+            severity,
+            message: "a.bin skipped (excluded)".to_string(),
+        };
+        assert_eq!(diagnostic(Severity::Info).to_string(), "a.bin skipped (excluded)");
+        assert_eq!(diagnostic(Severity::Warning).to_string(), "Warning: a.bin skipped (excluded)");
+        assert_eq!(diagnostic(Severity::Error).to_string(), "Error: a.bin skipped (excluded)");
+    }
+
+    #[test]
+    fn test_only_warnings_and_errors_are_listed() {
+        record(Severity::Info, "info notice for test");
+        record(Severity::Warning, "warning notice for test");
+        record(Severity::Error, "error notice for test");
+
+        let messages: Vec<String> = warnings().into_iter().map(|diagnostic| diagnostic.message).collect();
+        assert!(messages.contains(&"warning notice for test".to_string()));
+        assert!(messages.contains(&"error notice for test".to_string()));
+        assert!(!messages.contains(&"info notice for test".to_string()));
+    }
+
+    #[test]
+    fn test_recorded_warnings_are_capped() {
+        let mut recorded = Recorded::new();
+        recorded.push(Diagnostic {
+            severity: Severity::Info,
+            message: "info notice".to_string(),
+        });
+        for index in 0..MAX_RECORDED + 10 {
+            recorded.push(Diagnostic {
+                severity: Severity::Warning,
+                message: format!("warning {}", index),
+            });
+        }
+
+        // Info notices are not kept; every warning is counted, but the list stays at the cap
+        assert_eq!(recorded.count, MAX_RECORDED + 10);
+        assert_eq!(recorded.listed.len(), MAX_RECORDED);
+        assert_eq!(recorded.listed[0].message, "warning 0");
+    }
+}
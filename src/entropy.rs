@@ -0,0 +1,78 @@
+//! Shannon entropy of output lines
+//!
+//! High entropy (close to 8 bits per byte) suggests encrypted or compressed data,
+//! while low entropy suggests structured data or padding.
+
+/// Compute the Shannon entropy of the data in bits per byte (0.0 to 8.0)
+pub fn shannon_entropy(data: &[u8]) -> f64 {
+    if data.is_empty() {
+        return 0.0;
+    }
+
+    let mut counts = [0usize; 256];
+    for &byte in data {
+        counts[byte as usize] += 1;
+    }
+
+    let len = data.len() as f64;
+    counts
+        .iter()
+        .filter(|&&count| count > 0)
+        .map(|&count| {
+            let p = count as f64 / len;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// Entropy display settings with an optional range filter
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EntropyFilter {
+    /// Minimum entropy of lines to display
+    pub min: Option<f64>,
+    /// Maximum entropy of lines to display
+    pub max: Option<f64>,
+}
+
+impl EntropyFilter {
+    /// Create a new entropy filter
+    pub fn new(min: Option<f64>, max: Option<f64>) -> Self {
+        Self { min, max }
+    }
+
+    /// Check whether a line with the given entropy should be displayed
+    pub fn accepts(&self, entropy: f64) -> bool {
+        self.min.map_or(true, |min| entropy >= min) && self.max.map_or(true, |max| entropy <= max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entropy_of_zeros() {
+        assert_eq!(shannon_entropy(&[0u8; 1024]), 0.0);
+        assert_eq!(shannon_entropy(&[]), 0.0);
+    }
+
+    #[test]
+    fn test_entropy_of_uniform_data() {
+        // Every byte value exactly once is the maximum of 8 bits per byte
+        let data: Vec<u8> = (0..=255).collect();
+        assert!((shannon_entropy(&data) - 8.0).abs() < 1e-9);
+
+        // Two equally likely values carry one bit per byte
+        assert!((shannon_entropy(b"abababab") - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_entropy_filter() {
+        let filter = EntropyFilter::new(Some(2.0), Some(6.0));
+        assert!(filter.accepts(2.0));
+        assert!(filter.accepts(6.0));
+        assert!(!filter.accepts(1.99));
+        assert!(!filter.accepts(7.5));
+        assert!(EntropyFilter::default().accepts(0.0));
+    }
+}
@@ -8,8 +8,13 @@ pub enum BingrepError {
     InvalidWidth(usize),
     InvalidPath(String),
     RegexCompilation(String),
+    /// The compiled regex exceeded the size limit (in bytes) set with `--regex-size-limit`
+    PatternTooComplex(usize),
     GlobPattern(String),
     GlobPath(String),
+    Config(String),
+    /// Files of a multi-file search that could not be searched, with none searched or `--strict`
+    FilesFailed(usize),
 }
 
 impl fmt::Display for BingrepError {
@@ -29,8 +34,16 @@ impl fmt::Display for BingrepError {
             }
             BingrepError::InvalidPath(msg) => write!(f, "Invalid file path: {}", msg),
             BingrepError::RegexCompilation(msg) => write!(f, "Regex compilation error: {}", msg),
+            BingrepError::PatternTooComplex(limit) => write!(
+                f,
+                "Pattern too complex: the compiled regex exceeds the size limit of {} bytes \
+                 (raise it with --regex-size-limit, or reduce large bounded repetitions)",
+                limit
+            ),
             BingrepError::GlobPattern(msg) => write!(f, "Glob pattern error: {}", msg),
             BingrepError::GlobPath(msg) => write!(f, "Glob path error: {}", msg),
+            BingrepError::Config(msg) => write!(f, "Config file error: {}", msg),
+            BingrepError::FilesFailed(count) => write!(f, "{} file(s) could not be searched", count),
         }
     }
 }
@@ -52,7 +65,10 @@ impl From<io::Error> for BingrepError {
 
 impl From<regex::Error> for BingrepError {
     fn from(err: regex::Error) -> Self {
-        BingrepError::RegexCompilation(err.to_string())
+        match err {
+            regex::Error::CompiledTooBig(limit) => BingrepError::PatternTooComplex(limit),
+            err => BingrepError::RegexCompilation(err.to_string()),
+        }
     }
 }
 
@@ -68,4 +84,27 @@ impl From<glob::GlobError> for BingrepError {
     }
 }
 
+/// Errors from the structured formatters, keeping the kind of I/O failures
+///
+/// A closed pipe then stays recognizable as [`io::ErrorKind::BrokenPipe`].
+impl From<Box<dyn std::error::Error>> for BingrepError {
+    fn from(err: Box<dyn std::error::Error>) -> Self {
+        let err = match err.downcast::<io::Error>() {
+            Ok(err) => return BingrepError::Io(*err),
+            Err(err) => err,
+        };
+        match err.downcast::<serde_json::Error>() {
+            Ok(err) => BingrepError::Io(io::Error::from(*err)),
+            Err(err) => BingrepError::Io(io::Error::other(err.to_string())),
+        }
+    }
+}
+
+impl BingrepError {
+    /// Whether the error is a write to a pipe whose reader has gone away
+    pub fn is_broken_pipe(&self) -> bool {
+        matches!(self, BingrepError::Io(err) if err.kind() == io::ErrorKind::BrokenPipe)
+    }
+}
+
 pub type Result<T> = std::result::Result<T, BingrepError>;
@@ -0,0 +1,274 @@
+//! Extraction of matched byte ranges to individual files
+//!
+//! Each match is written to `<extract_dir>/<source_filename>_offset_<HEX>.bin`.
+//! Only the final component of the source path is used, with any character outside
+//! `[A-Za-z0-9._-]` replaced, so a crafted source name cannot escape the directory.
+//!
+//! Every carved file is recorded in `manifest.jsonl` in the same directory, so that
+//! reruns can tell which artifacts are current. A match already recorded with the
+//! same source, offset and SHA-256 is not written again; different content at the
+//! same offset goes to a versioned name (`..._offset_<HEX>_v2.bin`) rather than
+//! replacing the earlier file. Existing files are never overwritten.
+
+use crate::diagnostics;
+use crate::error::{BingrepError, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// File name used when the source has no usable name (e.g. stdin)
+const FALLBACK_SOURCE_NAME: &str = "input";
+
+/// Name of the manifest kept in the extraction directory
+pub const MANIFEST_NAME: &str = "manifest.jsonl";
+
+/// One carved file, as recorded on a line of the manifest
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ManifestEntry {
+    /// Source path as given on the command line
+    pub source: String,
+    /// Absolute offset of the match in the source
+    pub offset: u64,
+    /// Number of bytes written
+    pub length: u64,
+    /// Lowercase hex SHA-256 of the written bytes
+    pub sha256: String,
+    /// Name of the carved file within the extraction directory
+    pub file: String,
+}
+
+/// Reduce a source path to a file name that is safe to join onto the extraction directory
+pub fn sanitize_source_name(source: &str) -> String {
+    let file_name = Path::new(source)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let sanitized: String = file_name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    // A name made only of dots could still refer to a parent or current directory
+    if sanitized.chars().all(|c| c == '.') {
+        FALLBACK_SOURCE_NAME.to_string()
+    } else {
+        sanitized
+    }
+}
+
+/// Build the output path for a match extracted from `source` at `offset`
+pub fn extraction_path(extract_dir: &Path, source: &str, offset: u64) -> PathBuf {
+    extract_dir.join(versioned_file_name(source, offset, 1))
+}
+
+/// Name of the `version`th file carved from `source` at `offset` (the first has no suffix)
+fn versioned_file_name(source: &str, offset: u64, version: usize) -> String {
+    let name = format!("{}_offset_{:X}", sanitize_source_name(source), offset);
+    if version <= 1 {
+        format!("{}.bin", name)
+    } else {
+        format!("{}_v{}.bin", name, version)
+    }
+}
+
+/// Writes matches to an extraction directory and keeps its manifest
+#[derive(Debug)]
+pub struct Extractor {
+    dir: PathBuf,
+    /// Manifest state, set up on the first write
+    manifest: Option<Manifest>,
+}
+
+/// Manifest entries indexed for lookups, with the manifest held open for appending
+#[derive(Debug)]
+struct Manifest {
+    entries: Vec<ManifestEntry>,
+    /// Indexes into `entries` by source and offset
+    by_match: HashMap<(String, u64), Vec<usize>>,
+    /// File names already recorded
+    files: HashSet<String>,
+    file: File,
+}
+
+impl Manifest {
+    /// Create `dir` if needed, read its manifest and open it for appending
+    fn open(dir: &Path) -> Result<Self> {
+        fs::create_dir_all(dir)?;
+        let path = dir.join(MANIFEST_NAME);
+        let mut manifest = Self {
+            entries: Vec::new(),
+            by_match: HashMap::new(),
+            files: HashSet::new(),
+            file: OpenOptions::new().create(true).append(true).open(&path)?,
+        };
+        for entry in read_manifest(&path)? {
+            manifest.index(entry);
+        }
+        Ok(manifest)
+    }
+
+    /// Add an entry to the lookup indexes
+    fn index(&mut self, entry: ManifestEntry) {
+        self.by_match
+            .entry((entry.source.clone(), entry.offset))
+            .or_default()
+            .push(self.entries.len());
+        self.files.insert(entry.file.clone());
+        self.entries.push(entry);
+    }
+
+    /// Entry recording `sha256` carved from `source` at `offset`, if any
+    fn find(&self, source: &str, offset: u64, sha256: &str) -> Option<&ManifestEntry> {
+        self.by_match
+            .get(&(source.to_string(), offset))?
+            .iter()
+            .map(|&index| &self.entries[index])
+            .find(|entry| entry.sha256 == sha256)
+    }
+
+    /// Append one entry to the manifest and index it
+    ///
+    /// The line is written with a single call on a file opened for appending, so a
+    /// concurrent or interrupted run never leaves half of it between other lines.
+    fn append(&mut self, entry: ManifestEntry) -> Result<()> {
+        let mut line = serde_json::to_string(&entry).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        line.push('\n');
+        self.file.write_all(line.as_bytes())?;
+        self.index(entry);
+        Ok(())
+    }
+}
+
+impl Extractor {
+    /// Create an extractor for `dir`, which is created on the first write
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir, manifest: None }
+    }
+
+    /// Write the matched bytes unless the manifest already records them
+    ///
+    /// Returns the path of the carved file, whether it was written now or by an earlier run.
+    pub fn write_match(&mut self, source: &str, offset: u64, data: &[u8]) -> Result<PathBuf> {
+        let manifest = match self.manifest.take() {
+            Some(manifest) => manifest,
+            None => Manifest::open(&self.dir)?,
+        };
+        let manifest = self.manifest.insert(manifest);
+        let sha256 = hex::encode(Sha256::digest(data));
+
+        if let Some(entry) = manifest.find(source, offset, &sha256) {
+            let path = self.dir.join(&entry.file);
+            if path.is_file() {
+                diagnostics::info(format!("{} already extracted", path.display()));
+                return Ok(path);
+            }
+        }
+
+        // Create the file exclusively, moving on to the next version when a name is taken
+        let mut version = 1;
+        let (file_name, mut file) = loop {
+            let file_name = versioned_file_name(source, offset, version);
+            if !manifest.files.contains(&file_name) {
+                match OpenOptions::new().write(true).create_new(true).open(self.dir.join(&file_name)) {
+                    Ok(file) => break (file_name, file),
+                    Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {}
+                    Err(err) => return Err(err.into()),
+                }
+            }
+            version += 1;
+        };
+        file.write_all(data)?;
+
+        let path = self.dir.join(&file_name);
+        manifest.append(ManifestEntry {
+            source: source.to_string(),
+            offset,
+            length: data.len() as u64,
+            sha256,
+            file: file_name,
+        })?;
+        Ok(path)
+    }
+}
+
+/// Read the entries of a manifest, or none if it does not exist yet
+fn read_manifest(path: &Path) -> Result<Vec<ManifestEntry>> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str::<ManifestEntry>(line).map_err(|err| {
+                BingrepError::Io(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{} line {}: {}", path.display(), index + 1, err),
+                ))
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_source_name() {
+        assert_eq!(sanitize_source_name("/evidence/disk image.bin"), "disk_image.bin");
+        assert_eq!(sanitize_source_name("../../etc/passwd"), "passwd");
+        assert_eq!(sanitize_source_name(".."), "input");
+        assert_eq!(sanitize_source_name(""), "input");
+        assert_eq!(sanitize_source_name("a/b\\c:d"), "b_c_d");
+    }
+
+    #[test]
+    fn test_extraction_path() {
+        let path = extraction_path(Path::new("/tmp/out"), "/data/blob.bin", 0x1F40);
+        assert_eq!(path, PathBuf::from("/tmp/out/blob.bin_offset_1F40.bin"));
+        assert_eq!(versioned_file_name("/data/blob.bin", 0x1F40, 1), "blob.bin_offset_1F40.bin");
+        assert_eq!(versioned_file_name("/data/blob.bin", 0x1F40, 3), "blob.bin_offset_1F40_v3.bin");
+    }
+
+    #[test]
+    fn test_rerun_skips_identical_and_versions_changed_matches() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let first = Extractor::new(dir.path().to_path_buf()).write_match("blob.bin", 0x10, b"\xFF\xD8").unwrap();
+        assert_eq!(first, dir.path().join("blob.bin_offset_10.bin"));
+
+        // A new run with the same bytes reuses the recorded file
+        let mut rerun = Extractor::new(dir.path().to_path_buf());
+        assert_eq!(rerun.write_match("blob.bin", 0x10, b"\xFF\xD8").unwrap(), first);
+
+        // Changed bytes at the same offset get a versioned name, the earlier file is kept
+        let changed = rerun.write_match("blob.bin", 0x10, b"\xFF\xD9").unwrap();
+        assert_eq!(changed, dir.path().join("blob.bin_offset_10_v2.bin"));
+        assert_eq!(fs::read(&first).unwrap(), b"\xFF\xD8");
+        assert_eq!(fs::read(&changed).unwrap(), b"\xFF\xD9");
+
+        // Entries appended in this run are found again without rereading the manifest
+        assert_eq!(rerun.write_match("blob.bin", 0x10, b"\xFF\xD9").unwrap(), changed);
+
+        let entries = read_manifest(&dir.path().join(MANIFEST_NAME)).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].file, "blob.bin_offset_10_v2.bin");
+        assert_eq!(entries[1].length, 2);
+        assert_eq!(entries[0].sha256, hex::encode(Sha256::digest(b"\xFF\xD8")));
+    }
+}
@@ -0,0 +1,176 @@
+//! C ABI for the scanner core
+//!
+//! Enabled with the `ffi` feature. Patterns are compiled once into an opaque handle and
+//! buffers owned by the caller are scanned in memory; nothing here performs any I/O, so
+//! the caller decides how data is read and where buffer boundaries fall.
+//! The C header `include/hxgrep.h` is generated from this module by cbindgen.
+
+use crate::regex_processor::{PatternSet, RegexProcessor};
+use regex::bytes::Regex;
+use std::ffi::{c_char, c_void, CStr};
+use std::ptr;
+
+/// Compiled patterns, created by `hxgrep_compile` and released with `hxgrep_free`
+pub struct HxgrepHandle {
+    regex: Regex,
+    patterns: PatternSet,
+}
+
+/// Called for each match with its absolute offset, length, the index of the pattern that
+/// matched (-1 if none could be determined) and the caller's `user_data`
+///
+/// Return `false` to stop the scan.
+pub type HxgrepMatchCallback =
+    extern "C" fn(offset: u64, length: usize, pattern_index: i32, user_data: *mut c_void) -> bool;
+
+/// Compile patterns written with the CLI's `-e` syntax, one per line
+///
+/// Blank lines and lines starting with `#` are skipped like in a `--pattern-file`, and
+/// pattern indexes count the remaining lines from 0. Returns NULL if `patterns` is NULL,
+/// not UTF-8, empty or contains an invalid pattern.
+///
+/// # Safety
+///
+/// `patterns` must be NULL or point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn hxgrep_compile(patterns: *const c_char) -> *mut HxgrepHandle {
+    if patterns.is_null() {
+        return ptr::null_mut();
+    }
+    let Ok(patterns) = CStr::from_ptr(patterns).to_str() else {
+        return ptr::null_mut();
+    };
+
+    let expressions: Vec<&str> = patterns
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect();
+    if expressions.is_empty() {
+        return ptr::null_mut();
+    }
+
+    let owned: Vec<String> = expressions.iter().map(|expression| expression.to_string()).collect();
+    match (RegexProcessor::compile_alternation(&expressions), PatternSet::new(&owned)) {
+        (Ok(regex), Ok(pattern_set)) => Box::into_raw(Box::new(HxgrepHandle { regex, patterns: pattern_set })),
+        _ => ptr::null_mut(),
+    }
+}
+
+/// Scan `len` bytes at `data`, reporting each match through `callback`
+///
+/// `base_offset` is added to the reported offsets, so a caller scanning a larger input
+/// in pieces gets absolute offsets; offsets past `u64::MAX` saturate instead of wrapping.
+/// Matches are not extended past the end of the buffer;
+/// overlapping consecutive buffers is up to the caller. Returns the number of matches
+/// reported, or -1 if `handle`, `callback` or a non-empty `data` is NULL.
+///
+/// # Safety
+///
+/// `handle` must come from `hxgrep_compile` and not be freed yet, and `data` must be
+/// valid for reads of `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn hxgrep_scan_buffer(
+    handle: *const HxgrepHandle,
+    data: *const u8,
+    len: usize,
+    base_offset: u64,
+    callback: Option<HxgrepMatchCallback>,
+    user_data: *mut c_void,
+) -> i64 {
+    let (Some(handle), Some(callback)) = (handle.as_ref(), callback) else {
+        return -1;
+    };
+    let data = match (data.is_null(), len) {
+        (_, 0) => &[][..],
+        (true, _) => return -1,
+        (false, _) => std::slice::from_raw_parts(data, len),
+    };
+
+    let mut reported = 0;
+    for mat in handle.regex.find_iter(data) {
+        let pattern_index = handle
+            .patterns
+            .index_at(&data[mat.start()..])
+            .and_then(|index| i32::try_from(index).ok())
+            .unwrap_or(-1);
+
+        reported += 1;
+        if !callback(base_offset.saturating_add(mat.start() as u64), mat.len(), pattern_index, user_data) {
+            break;
+        }
+    }
+    reported
+}
+
+/// Release a handle returned by `hxgrep_compile`
+///
+/// # Safety
+///
+/// `handle` must be NULL or come from `hxgrep_compile`, and must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn hxgrep_free(handle: *mut HxgrepHandle) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    extern "C" fn collect(offset: u64, length: usize, pattern_index: i32, user_data: *mut c_void) -> bool {
+        let found = unsafe { &mut *(user_data as *mut Vec<(u64, usize, i32)>) };
+        found.push((offset, length, pattern_index));
+        true
+    }
+
+    #[test]
+    fn test_scan_buffer_reports_offsets_and_pattern_indexes() {
+        let handle = unsafe { hxgrep_compile(c"# header\n\\x4D\\x5A\n\n\\x50\\x4B\\x03\\x04".as_ptr()) };
+        assert!(!handle.is_null());
+
+        let data = b"..MZ..PK\x03\x04..MZ";
+        let mut found: Vec<(u64, usize, i32)> = Vec::new();
+        let count = unsafe {
+            hxgrep_scan_buffer(
+                handle,
+                data.as_ptr(),
+                data.len(),
+                0x1000,
+                Some(collect),
+                &mut found as *mut _ as *mut c_void,
+            )
+        };
+
+        assert_eq!(count, 3);
+        assert_eq!(found, vec![(0x1002, 2, 0), (0x1006, 4, 1), (0x100C, 2, 0)]);
+
+        // A base offset near the end of the u64 range saturates instead of overflowing
+        found.clear();
+        let count = unsafe {
+            hxgrep_scan_buffer(
+                handle,
+                data.as_ptr(),
+                data.len(),
+                u64::MAX - 4,
+                Some(collect),
+                &mut found as *mut _ as *mut c_void,
+            )
+        };
+        assert_eq!(count, 3);
+        assert_eq!(found, vec![(u64::MAX - 2, 2, 0), (u64::MAX, 4, 1), (u64::MAX, 2, 0)]);
+        unsafe { hxgrep_free(handle) };
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        unsafe {
+            assert!(hxgrep_compile(ptr::null()).is_null());
+            assert!(hxgrep_compile(c"(".as_ptr()).is_null());
+            assert!(hxgrep_compile(c"\n# only comments\n".as_ptr()).is_null());
+            assert_eq!(hxgrep_scan_buffer(ptr::null(), ptr::null(), 0, 0, Some(collect), ptr::null_mut()), -1);
+            hxgrep_free(ptr::null_mut());
+        }
+    }
+}
@@ -1,28 +1,52 @@
 //! Forensic image format support
 //!
 //! This module provides functionality to read various forensic image formats
-//! including E01 (EWF) and VMDK files using the exhume_body library.
+//! including E01 (EWF) and VMDK files using the exhume_body library, and split raw
+//! images (`.001`, `.002`, ...) that are stitched together here. Single-file raw images
+//! (`.dd`, `.raw`, `.img`) hold the media bytes as they are, so they are read directly
+//! like any other file.
 
 use crate::error::{BingrepError, Result};
+use std::fs::File;
 use std::io::{Read, Seek, SeekFrom};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-/// Forensic image reader that handles E01 and VMDK forensic image files
+/// Forensic image reader that handles E01, VMDK and split raw image files
 pub struct ForensicImageReader {
-    #[cfg(feature = "exhume")]
-    body: exhume_body::Body,
-    #[cfg(not(feature = "exhume"))]
-    _placeholder: std::marker::PhantomData<()>,
+    source: ImageSource,
+    /// Size of the media stored in the image, 0 if it could not be determined
     size: u64,
 }
 
+/// Where the media bytes of an image come from
+enum ImageSource {
+    #[cfg(feature = "exhume")]
+    Body {
+        body: exhume_body::Body,
+        /// Current read position, tracked to stop reading at `size`
+        position: u64,
+    },
+    Split(SplitRawReader),
+}
+
 impl ForensicImageReader {
     /// Create a new forensic image reader from a file path
     ///
-    /// This will automatically detect the format (E01, VMDK, etc.) and load the image
-    #[cfg(feature = "exhume")]
+    /// This will automatically detect the format (E01, VMDK, split raw) and load the image
     pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
         let path = path.as_ref();
+        if is_split_raw_image(path) {
+            let reader = SplitRawReader::open(path)?;
+            return Ok(ForensicImageReader {
+                size: reader.size(),
+                source: ImageSource::Split(reader),
+            });
+        }
+        Self::open_body(path)
+    }
+
+    #[cfg(feature = "exhume")]
+    fn open_body(path: &Path) -> Result<Self> {
         let path_str = path.to_str()
             .ok_or_else(|| BingrepError::Io(std::io::Error::new(
                 std::io::ErrorKind::InvalidInput,
@@ -42,27 +66,21 @@ impl ForensicImageReader {
             }
         };
 
-        // Get the size - not used for progress but kept for compatibility
+        // The media size pads offsets, drives the progress bar and bounds reads
         let size = match body.seek(SeekFrom::End(0)) {
-            Ok(end_pos) => {
-                // Reset position to beginning
-                let _ = body.seek(SeekFrom::Start(0));
-                end_pos
-            }
-            Err(_) => {
-                0 // If seek fails, fall back to 0
-            }
+            Ok(end_pos) => end_pos,
+            Err(_) => 0, // Unknown; reads then run until the body returns 0
         };
+        body.seek(SeekFrom::Start(0))?;
 
         Ok(ForensicImageReader {
-            body,
+            source: ImageSource::Body { body, position: 0 },
             size,
         })
     }
 
     #[cfg(not(feature = "exhume"))]
-    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let path = path.as_ref();
+    fn open_body(path: &Path) -> Result<Self> {
         Err(BingrepError::Io(std::io::Error::new(
             std::io::ErrorKind::Unsupported,
             format!(
@@ -75,49 +93,175 @@ impl ForensicImageReader {
         )))
     }
 
-    /// Get the total size of the forensic image
+    /// Get the size of the media stored in the forensic image (0 if unknown)
     pub fn size(&self) -> u64 {
         self.size
     }
 }
 
-#[cfg(feature = "exhume")]
 impl Read for ForensicImageReader {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
-        self.body.read(buf)
+        match &mut self.source {
+            #[cfg(feature = "exhume")]
+            ImageSource::Body { body, position } => {
+                // Stop at the end of the media rather than relying on the body returning 0
+                let len = match self.size {
+                    0 => buf.len(),
+                    size => buf.len().min(size.saturating_sub(*position).try_into().unwrap_or(usize::MAX)),
+                };
+                if len == 0 {
+                    return Ok(0);
+                }
+
+                let bytes_read = body.read(&mut buf[..len])?;
+                *position += bytes_read as u64;
+                Ok(bytes_read)
+            }
+            ImageSource::Split(reader) => reader.read(buf),
+        }
     }
 }
 
-#[cfg(feature = "exhume")]
 impl Seek for ForensicImageReader {
     fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
-        self.body.seek(pos)
+        match &mut self.source {
+            #[cfg(feature = "exhume")]
+            ImageSource::Body { body, position } => {
+                *position = body.seek(pos)?;
+                Ok(*position)
+            }
+            ImageSource::Split(reader) => reader.seek(pos),
+        }
     }
 }
 
-#[cfg(not(feature = "exhume"))]
-impl Read for ForensicImageReader {
-    fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
-        Err(std::io::Error::new(
-            std::io::ErrorKind::Unsupported,
-            "Forensic image support not enabled"
-        ))
+/// Raw image split into segments (`name.001`, `name.002`, ...) read as one stream
+///
+/// The segments are plain byte ranges concatenated in order, as written by
+/// `split -d -a 3` or the segmented raw export of imaging tools, so reads and seeks
+/// only map a media offset to a segment and an offset within it.
+pub struct SplitRawReader {
+    /// Segment files with the media offset each one starts at
+    segments: Vec<(File, u64)>,
+    size: u64,
+    position: u64,
+}
+
+impl SplitRawReader {
+    /// Open the segments of a split raw image, given the path of its first (`.001`) segment
+    ///
+    /// Segments are taken in numbering order until the first missing one.
+    pub fn open<P: AsRef<Path>>(first_segment: P) -> Result<Self> {
+        let mut segments = Vec::new();
+        let mut size = 0u64;
+        for path in split_segment_paths(first_segment.as_ref()) {
+            let file = File::open(&path)?;
+            let len = file.metadata()?.len();
+            segments.push((file, size));
+            size += len;
+        }
+
+        if segments.is_empty() {
+            return Err(BingrepError::Io(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("No segments found for split raw image: {}", first_segment.as_ref().display()),
+            )));
+        }
+
+        Ok(SplitRawReader { segments, size, position: 0 })
+    }
+
+    /// Total size of all segments
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Number of segment files
+    pub fn segment_count(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Index of the segment holding the byte at `offset`, which must be below `size`
+    fn segment_at(&self, offset: u64) -> usize {
+        self.segments.partition_point(|(_, start)| *start <= offset) - 1
+    }
+
+    fn segment_end(&self, index: usize) -> u64 {
+        self.segments.get(index + 1).map_or(self.size, |(_, start)| *start)
     }
 }
 
-#[cfg(not(feature = "exhume"))]
-impl Seek for ForensicImageReader {
-    fn seek(&mut self, _pos: SeekFrom) -> std::io::Result<u64> {
-        Err(std::io::Error::new(
-            std::io::ErrorKind::Unsupported,
-            "Forensic image support not enabled"
-        ))
+impl Read for SplitRawReader {
+    /// Fill `buf` across segment boundaries, like a read from a single file
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let mut filled = 0;
+        while filled < buf.len() && self.position < self.size {
+            let index = self.segment_at(self.position);
+            let segment_end = self.segment_end(index);
+            let (file, start) = &mut self.segments[index];
+
+            let len = (buf.len() - filled).min((segment_end - self.position).try_into().unwrap_or(usize::MAX));
+            file.seek(SeekFrom::Start(self.position - *start))?;
+            let bytes_read = file.read(&mut buf[filled..filled + len])?;
+            if bytes_read == 0 {
+                // The segment shrank since it was opened
+                break;
+            }
+            filled += bytes_read;
+            self.position += bytes_read as u64;
+        }
+        Ok(filled)
     }
 }
 
-/// Check if a file path has a forensic image extension (E01 or VMDK)
+impl Seek for SplitRawReader {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.position = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(delta) => self.size.checked_add_signed(delta),
+            SeekFrom::Current(delta) => self.position.checked_add_signed(delta),
+        }
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "invalid seek to a negative or overflowing position"))?;
+        Ok(self.position)
+    }
+}
+
+/// Paths of the existing segments of a split raw image, starting at its first segment
+fn split_segment_paths(first_segment: &Path) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    for number in 1..=999 {
+        let path = first_segment.with_extension(format!("{:03}", number));
+        if !path.is_file() {
+            break;
+        }
+        paths.push(path);
+    }
+    paths
+}
+
+/// Check if a file path has a forensic image extension (E01, VMDK or the first split raw segment)
+///
+/// Single-file raw images are not included, as they need no reader of their own.
 pub fn is_forensic_image<P: AsRef<Path>>(path: P) -> bool {
-    is_e01_file(&path) || is_vmdk_file(&path)
+    is_e01_file(&path) || is_vmdk_file(&path) || is_split_raw_image(&path)
+}
+
+/// Check if a file path is the first segment of a split raw image (`.001`)
+pub fn is_split_raw_image<P: AsRef<Path>>(path: P) -> bool {
+    path.as_ref()
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext == "001")
+        .unwrap_or(false)
+}
+
+/// Check if a file path has a single-file raw image extension (`.dd`, `.raw` or `.img`)
+pub fn is_raw_image<P: AsRef<Path>>(path: P) -> bool {
+    path.as_ref()
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| matches!(ext.to_lowercase().as_str(), "dd" | "raw" | "img"))
+        .unwrap_or(false)
 }
 
 /// Check if a file path has an E01 extension
@@ -145,6 +289,10 @@ pub fn get_format_name<P: AsRef<Path>>(path: P) -> Option<&'static str> {
         Some("E01/EWF")
     } else if is_vmdk_file(&path) {
         Some("VMDK")
+    } else if is_split_raw_image(&path) {
+        Some("split raw")
+    } else if is_raw_image(&path) {
+        Some("raw")
     } else {
         None
     }
@@ -180,19 +328,76 @@ mod tests {
         assert!(is_forensic_image("test.vmdk"));
         assert!(is_forensic_image("TEST.E01"));
         assert!(is_forensic_image("TEST.VMDK"));
+        assert!(is_forensic_image("disk.001"));
+        assert!(!is_forensic_image("disk.002"));
         assert!(!is_forensic_image("test.dd"));
         assert!(!is_forensic_image("test.raw"));
         assert!(!is_forensic_image("test"));
     }
 
+    #[test]
+    fn test_is_raw_image() {
+        assert!(is_raw_image("disk.dd"));
+        assert!(is_raw_image("DISK.RAW"));
+        assert!(is_raw_image("/path/to/disk.img"));
+        assert!(!is_raw_image("disk.001"));
+        assert!(!is_raw_image("disk.e01"));
+    }
+
     #[test]
     fn test_get_format_name() {
         assert_eq!(get_format_name("test.e01"), Some("E01/EWF"));
         assert_eq!(get_format_name("test.vmdk"), Some("VMDK"));
         assert_eq!(get_format_name("TEST.E01"), Some("E01/EWF"));
         assert_eq!(get_format_name("TEST.VMDK"), Some("VMDK"));
-        assert_eq!(get_format_name("test.dd"), None);
-        assert_eq!(get_format_name("test.raw"), None);
+        assert_eq!(get_format_name("disk.001"), Some("split raw"));
+        assert_eq!(get_format_name("test.dd"), Some("raw"));
+        assert_eq!(get_format_name("test.bin"), None);
+    }
+
+    #[test]
+    fn test_split_raw_reader() {
+        let dir = tempfile::tempdir().unwrap();
+        let data: Vec<u8> = (0..=255u8).cycle().take(1000).collect();
+        std::fs::write(dir.path().join("disk.001"), &data[..600]).unwrap();
+        std::fs::write(dir.path().join("disk.002"), &data[600..900]).unwrap();
+        std::fs::write(dir.path().join("disk.003"), &data[900..]).unwrap();
+        // Not part of the set, because disk.004 is missing
+        std::fs::write(dir.path().join("disk.005"), b"\xFF").unwrap();
+
+        let mut reader = ForensicImageReader::new(dir.path().join("disk.001")).unwrap();
+        assert_eq!(reader.size(), 1000);
+
+        // A single read fills the buffer across both segment boundaries
+        let mut all = Vec::new();
+        reader.read_to_end(&mut all).unwrap();
+        assert_eq!(all, data);
+
+        reader.seek(SeekFrom::Start(590)).unwrap();
+        let mut buf = [0u8; 320];
+        assert_eq!(reader.read(&mut buf).unwrap(), 320);
+        assert_eq!(buf[..], data[590..910]);
+
+        reader.seek(SeekFrom::End(-4)).unwrap();
+        assert_eq!(reader.read(&mut buf).unwrap(), 4);
+        assert_eq!(reader.read(&mut buf).unwrap(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "exhume")]
+    #[ignore = "needs a sample E01 or VMDK image in HXGREP_SAMPLE_IMAGE"]
+    fn test_forensic_reader_size() {
+        let path = std::env::var_os("HXGREP_SAMPLE_IMAGE").expect("HXGREP_SAMPLE_IMAGE is not set");
+
+        let mut reader = ForensicImageReader::new(&path).unwrap();
+        let size = reader.size();
+        assert!(size > 0);
+
+        // Reads stop at the end of the media
+        reader.seek(SeekFrom::Start(size - 1)).unwrap();
+        let mut buf = [0u8; 16];
+        assert_eq!(reader.read(&mut buf).unwrap(), 1);
+        assert_eq!(reader.read(&mut buf).unwrap(), 0);
     }
 
     #[test]
@@ -0,0 +1,186 @@
+//! Interactive pattern testing mode
+//!
+//! Loads the input once and then reads patterns line-by-line, printing the first
+//! matches for each immediately. Lines starting with `:` are commands:
+//!
+//! * `:width N` - number of bytes to display per match
+//! * `:limit N` - number of matches to display per pattern (0 for unlimited)
+//! * `:quit` - leave interactive mode
+
+use crate::error::Result;
+use crate::options::SearchOptions;
+use crate::output::OutputFormatter;
+use crate::regex_processor::RegexProcessor;
+use regex::bytes::Regex;
+use std::io::{BufRead, Write};
+
+/// Default number of matches displayed per pattern when no limit is given
+pub const DEFAULT_INTERACTIVE_LIMIT: usize = 10;
+
+/// Prompt written to the error stream before each pattern
+const PROMPT: &str = "hxgrep> ";
+
+/// In-memory session for iterating on patterns against loaded data
+pub struct InteractiveSession {
+    data: Vec<u8>,
+    base_offset: u64,
+    options: SearchOptions,
+    hex_offset_length: usize,
+}
+
+impl InteractiveSession {
+    /// Create a new session over the loaded data
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Loaded bytes to search in
+    /// * `base_offset` - File offset of the first loaded byte
+    /// * `options` - Initial width and match limit per pattern, separator, grouping and
+    ///   offset display; `:width` and `:limit` change them during the session
+    pub fn new(data: Vec<u8>, base_offset: u64, options: SearchOptions) -> Self {
+        let hex_offset_length =
+            OutputFormatter::calculate_hex_offset_length(base_offset + data.len() as u64);
+
+        Self {
+            data,
+            base_offset,
+            options,
+            hex_offset_length,
+        }
+    }
+
+    /// Run the read-eval-print loop until `:quit` or end of input
+    ///
+    /// Match lines are written to `output`; the prompt and diagnostics go to `messages`.
+    pub fn run<R: BufRead, W: Write, E: Write>(
+        &mut self,
+        input: R,
+        output: &mut W,
+        messages: &mut E,
+    ) -> Result<()> {
+        write!(messages, "{}", PROMPT)?;
+        messages.flush()?;
+
+        for line in input.lines() {
+            let line = line?;
+            let line = line.trim();
+
+            if let Some(command) = line.strip_prefix(':') {
+                if !self.handle_command(command, messages)? {
+                    break;
+                }
+            } else if !line.is_empty() {
+                match RegexProcessor::compile_pattern(line) {
+                    Ok(regex) => {
+                        let lines = self.search(&regex);
+                        for match_line in &lines {
+                            writeln!(output, "{}", match_line)?;
+                        }
+                        output.flush()?;
+                        writeln!(messages, "-- {} match(es) shown", lines.len())?;
+                    }
+                    Err(err) => writeln!(messages, "Error: {}", err)?,
+                }
+            }
+
+            write!(messages, "{}", PROMPT)?;
+            messages.flush()?;
+        }
+
+        Ok(())
+    }
+
+    /// Format the first matches of the regex in the loaded data
+    pub fn search(&self, regex: &Regex) -> Vec<String> {
+        let options = &self.options;
+        let take = if options.limit > 0 { options.limit } else { usize::MAX };
+
+        regex
+            .find_iter(&self.data)
+            .take(take)
+            .map(|mat| {
+                let end_pos = (mat.start() + options.width).min(self.data.len());
+                let hex_string = OutputFormatter::format_bytes_grouped(
+                    &self.data[mat.start()..end_pos],
+                    &options.separator,
+                    options.group_size,
+                    options.hex_case,
+                );
+                if options.show_offset {
+                    OutputFormatter::format_line_with_offset(
+                        self.base_offset + mat.start() as u64,
+                        &hex_string,
+                        self.hex_offset_length,
+                    )
+                } else {
+                    hex_string
+                }
+            })
+            .collect()
+    }
+
+    /// Handle a `:command`, returning `false` when the session should end
+    fn handle_command<E: Write>(&mut self, command: &str, messages: &mut E) -> Result<bool> {
+        let mut parts = command.split_whitespace();
+        let name = parts.next().unwrap_or("");
+        let value = parts.next().map(str::parse::<usize>);
+
+        match (name, value) {
+            ("quit" | "q", _) => return Ok(false),
+            ("width", Some(Ok(width))) if width > 0 => {
+                self.options.width = width;
+                writeln!(messages, "width = {}", width)?;
+            }
+            ("limit", Some(Ok(limit))) => {
+                self.options.limit = limit;
+                writeln!(messages, "limit = {}", limit)?;
+            }
+            ("width", None) => writeln!(messages, "width = {}", self.options.width)?,
+            ("limit", None) => writeln!(messages, "limit = {}", self.options.limit)?,
+            _ => writeln!(messages, "Error: unknown command ':{}' (:width N, :limit N, :quit)", command)?,
+        }
+
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn run_session(session: &mut InteractiveSession, input: &str) -> (String, String) {
+        let mut output = Vec::new();
+        let mut messages = Vec::new();
+        session
+            .run(Cursor::new(input.as_bytes()), &mut output, &mut messages)
+            .unwrap();
+        (
+            String::from_utf8(output).unwrap(),
+            String::from_utf8(messages).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_patterns_and_commands() {
+        let mut session = InteractiveSession::new(b"MZ\x90\x00MZ\x90\x00".to_vec(), 0, SearchOptions::new().width(4).limit(10));
+
+        let (output, messages) = run_session(&mut session, "MZ\n:width 2\n:limit 1\nMZ\n:quit\nMZ\n");
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines, vec!["0h : 4D 5A 90 00", "4h : 4D 5A 90 00", "0h : 4D 5A"]);
+        assert!(messages.contains("width = 2"));
+        assert!(messages.contains("limit = 1"));
+    }
+
+    #[test]
+    fn test_invalid_pattern_and_command() {
+        let mut session = InteractiveSession::new(b"abc".to_vec(), 0x100, SearchOptions::new().width(2).show_offset(false));
+
+        let (output, messages) = run_session(&mut session, "(\n:bogus\nb\n");
+
+        assert_eq!(output, "62 63\n");
+        assert!(messages.contains("Error: "));
+        assert!(messages.contains("unknown command ':bogus'"));
+    }
+}
@@ -9,43 +9,83 @@
 //!
 //! * `cli` - Command-line interface handling
 //! * `config` - Configuration and validation
+//! * `options` - Search and hex dump settings
 //! * `regex_processor` - Regular expression compilation and processing
 //! * `stream` - File streaming and pattern matching
 //! * `buffer_manager` - Efficient buffer management for large files
+//! * `compressed` - gzip decompression with compressed offsets of matches
 //! * `output` - Hexadecimal output formatting
+//! * `partition` - MBR and GPT partition tables of disk images
+//! * `coverage` - Byte ranges read by scans limited to part of the input
 //! * `error` - Error types and handling
+//! * `diagnostics` - Severity-classified stderr notices and `--fail-on-warning`
+//! * `schema` - JSON Schema documents for the structured output
+//! * `util` - Output files written atomically through a temporary file
 //!
 //! ## Example Usage
 //!
-//! ```no_run
-//! use hxgrep::{Config, RegexProcessor, FileProcessor};
-//! use std::fs::File;
+//! ```
+//! use hxgrep::{Config, FileProcessor, RegexProcessor, SearchOptions};
+//! use hxgrep::structured_output::BinaryMatch;
+//! use std::io::Cursor;
 //!
 //! let config = Config::default();
 //! let mut processor = FileProcessor::new(config);
+//! processor.set_source_name("memory");
 //! let regex = RegexProcessor::compile_pattern("\\x00\\x01").unwrap();
-//! // Process file with regex...
+//! let mut reader = Cursor::new(b"\x00\x01AB\x00\x01CD".to_vec());
+//!
+//! let options = SearchOptions::new().width(4);
+//! let matches: Vec<BinaryMatch> = processor
+//!     .search(&mut reader, &regex, &options)
+//!     .collect::<hxgrep::Result<_>>()
+//!     .unwrap();
+//! assert_eq!(matches.len(), 2);
+//! assert_eq!(matches[1].offset, 4);
+//! assert_eq!(matches[1].hex_data, "00 01 43 44");
+//! assert_eq!(matches[1].file_path, "memory");
 //! ```
 
 pub mod buffer_manager;
+pub mod charset;
 pub mod cli;
+pub mod collapse;
 pub mod color_context;
+pub mod compressed;
 pub mod config;
+pub mod coverage;
+pub mod diagnostics;
+pub mod entropy;
 pub mod error;
+pub mod extract;
+#[cfg(feature = "ffi")]
+pub mod ffi;
 pub mod forensic_image;
+pub mod interactive;
+pub mod interrupt;
 pub mod multifile;
+pub mod options;
 pub mod output;
 pub mod parallel;
+pub mod partition;
 pub mod progress;
 pub mod regex_processor;
+pub mod rewind;
+pub mod scan_stats;
+pub mod schema;
+pub mod sparse;
 pub mod stream;
+pub mod strings;
 pub mod structured_output;
+pub mod util;
 
 pub use cli::Cli;
 pub use config::Config;
 pub use error::{BingrepError, Result};
 pub use regex_processor::RegexProcessor;
-pub use stream::FileProcessor;
+pub use options::{DumpOptions, SearchOptions};
+pub use output::HexCase;
+pub use stream::{FileProcessor, Match};
 
 pub use regex::bytes::Regex;
 /// Re-export commonly used types
@@ -1,16 +1,30 @@
+use hxgrep::charset::Charset;
 use hxgrep::cli::Cli;
+use hxgrep::compressed::GzipReader;
 use hxgrep::config::Config;
+use hxgrep::coverage::ScanCoverage;
+use hxgrep::diagnostics;
+use hxgrep::interactive::{InteractiveSession, DEFAULT_INTERACTIVE_LIMIT};
 use hxgrep::error::Result;
 use hxgrep::multifile::MultiFileProcessor;
-use hxgrep::output::OutputFormatter;
+use hxgrep::output::{OD_LINE_WIDTH, XXD_LINE_WIDTH};
 use hxgrep::parallel::{ParallelHexDump, ParallelProcessor};
-use hxgrep::progress::ProgressIndicator;
+use hxgrep::partition::{read_partitions, Partition, PartitionScheme};
+use hxgrep::progress::{ProgressIndicator, ProgressLine, ProgressWriter};
 use hxgrep::regex_processor::RegexProcessor;
+use hxgrep::rewind::RewindReader;
+use hxgrep::scan_stats::ScanStats;
+use hxgrep::sparse::HoleSkipper;
 use hxgrep::stream::FileProcessor;
-use clap::Parser;
+use hxgrep::structured_output::{OutputFormat, StructuredFormatter};
+use clap::{CommandFactory, FromArgMatches};
 use std::fs::File;
-use std::io::{self, Read, Seek, SeekFrom};
+use std::io::{self, Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Exit code of a run whose pattern exceeds the regex size limit
+const PATTERN_TOO_COMPLEX_EXIT_CODE: i32 = 3;
 
 /// Validate and canonicalize file path to prevent path traversal attacks
 fn validate_file_path(path: &str) -> Result<PathBuf> {
@@ -42,18 +56,312 @@ fn validate_file_path(path: &str) -> Result<PathBuf> {
     }
 }
 
+/// Catch a glob pattern given without --multi, whether quoted or already expanded by the shell
+fn check_input_paths(cli: &Cli) -> Result<()> {
+    if !cli.extra_paths.is_empty() && !cli.multi_file {
+        return Err(hxgrep::error::BingrepError::InvalidPath(format!(
+            "{} input paths given (a glob expanded by the shell?); add --multi to search them all",
+            cli.extra_paths.len() + 1
+        )));
+    }
+
+    if let Some(path) = &cli.file_path {
+        let looks_like_glob = path.contains(['*', '?', '[']);
+        if looks_like_glob && !cli.multi_file && !cli.recursive && !Path::new(path).exists() {
+            return Err(hxgrep::error::BingrepError::InvalidPath(format!(
+                "'{}' looks like a glob pattern; add --multi to search every file it matches",
+                path
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Writer for the data output: the `-o/--output` file, or stdout
+///
+/// The file is created up front, so a missing directory fails before any input is read.
+/// An output path naming an input file is refused rather than truncating the input.
+fn open_output(cli: &Cli) -> Result<Box<dyn Write>> {
+    let Some(path) = &cli.output else {
+        return Ok(Box::new(io::stdout().lock()));
+    };
+
+    if let Ok(output) = path.canonicalize() {
+        let is_input = cli
+            .file_path
+            .iter()
+            .chain(&cli.extra_paths)
+            .filter_map(|input| Path::new(input).canonicalize().ok())
+            .any(|input| input == output);
+        if is_input {
+            return Err(hxgrep::error::BingrepError::InvalidPath(format!(
+                "output file {} is also an input",
+                path.display()
+            )));
+        }
+    }
+
+    let file = File::create(path).map_err(|err| {
+        hxgrep::error::BingrepError::InvalidPath(format!("cannot create output file {}: {}", path.display(), err))
+    })?;
+    Ok(Box::new(file))
+}
+
 fn main() -> Result<()> {
-    let cli = Cli::parse();
+    let result = match run() {
+        // The reader went away (e.g. `| head`); like grep, that is not an error
+        Err(err) if err.is_broken_pipe() => Ok(()),
+        // Its own exit code lets scripts retry with a higher --regex-size-limit
+        Err(err @ hxgrep::error::BingrepError::PatternTooComplex(_)) => {
+            eprintln!("Error: {}", err);
+            std::process::exit(PATTERN_TOO_COMPLEX_EXIT_CODE);
+        }
+        // The failed files and the summary have been reported as warnings already
+        Err(err @ hxgrep::error::BingrepError::FilesFailed(_)) => {
+            eprintln!("Error: {}", err);
+            std::process::exit(2);
+        }
+        result => result,
+    };
+
+    // With --fail-on-warning a run that met any warning fails, listing them at the end
+    if result.is_ok() && diagnostics::fail_on_warning() {
+        let count = diagnostics::warning_count();
+        if count > 0 {
+            eprintln!("Error: {} warning(s) with --fail-on-warning:", count);
+            let warnings = diagnostics::warnings();
+            if count > warnings.len() {
+                eprintln!("  (first {} listed)", warnings.len());
+            }
+            for warning in warnings {
+                eprintln!("  {}", warning);
+            }
+            std::process::exit(2);
+        }
+    }
+    result
+}
+
+fn run() -> Result<()> {
+    // Keep the matches to tell options given on the command line from their defaults
+    let matches = Cli::command().get_matches();
+    let mut cli = Cli::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
+
+    // A --config file is applied first so that command-line options override it
+    let base_config = match &cli.config_file {
+        Some(path) => match Config::from_toml_file(path) {
+            Ok(config) => config,
+            Err(err) if cli.quiet => {
+                eprintln!("Error: {}", err);
+                std::process::exit(2);
+            }
+            Err(err) => return Err(err),
+        },
+        None => Config::default(),
+    };
+    cli.apply_config_defaults(&base_config.cli_defaults, &matches);
+
+    // The schema of the structured records is printed on its own, without reading any input
+    if let Some(kind) = cli.schema {
+        writeln!(open_output(&cli)?, "{:#}", hxgrep::schema::schema(kind))?;
+        return Ok(());
+    }
+
+    diagnostics::set_fail_on_warning(cli.fail_on_warning);
+    if let Some(limit) = cli.regex_size_limit {
+        hxgrep::regex_processor::set_regex_size_limit(limit);
+    }
 
     // Set global color choice
     hxgrep::color_context::set_color_choice(cli.color.clone());
+    let default_theme = hxgrep::color_context::ColorTheme::default();
+    hxgrep::color_context::set_color_theme(hxgrep::color_context::ColorTheme {
+        highlight: cli.highlight_color.unwrap_or(default_theme.highlight),
+        byte_categories: cli.color_bytes,
+    });
+    hxgrep::color_context::set_output_to_file(cli.output.is_some());
+
+    // xxd and od lines always hold 16 bytes
+    if cli.xxd_output() {
+        cli.line_width = XXD_LINE_WIDTH;
+    } else if cli.od_output() {
+        cli.line_width = OD_LINE_WIDTH;
+    }
+
+    // Select the byte mapping for the text column and structured ascii_data
+    if let Some(spec) = &cli.charset {
+        hxgrep::charset::set_charset(Charset::parse(spec)?);
+    }
+
+    // Patterns from --pattern-file follow the -e patterns, and all of them are searched as one alternation
+    if let Some(path) = &cli.pattern_file {
+        let file_patterns = RegexProcessor::read_pattern_file(path)?;
+        cli.patterns.extend(file_patterns);
+    }
+    cli.expression = RegexProcessor::combine_patterns(&cli.patterns)?;
+
+    // Literal strings (-F), wildcard hex strings (--hex-pattern), bit masks (--mask) and
+    // integer values (--u32, ...) are turned into escaped expressions so every mode can use them
+    let literal_pattern = if let Some(text) = cli.string.take() {
+        Some(RegexProcessor::literal_pattern(&text, cli.string_encoding))
+    } else if let Some(hex) = cli.hex_pattern.take() {
+        Some(RegexProcessor::hex_wildcard_pattern(&hex))
+    } else if let Some(mask) = cli.mask.take() {
+        Some(RegexProcessor::mask_pattern(&mask))
+    } else {
+        cli.integer_search()
+            .map(|(value, integer_type, endian)| RegexProcessor::integer_pattern(value, integer_type, endian))
+    };
+    if let Some(literal_pattern) = literal_pattern {
+        match literal_pattern {
+            Ok(pattern) => cli.expression = Some(pattern),
+            Err(err) if cli.quiet => {
+                eprintln!("Error: {}", err);
+                std::process::exit(2);
+            }
+            Err(err) => return Err(err),
+        }
+    }
+
+    // --list-partitions prints the partition table; --partition narrows the range to one entry
+    if cli.list_partitions || cli.partition.is_some() {
+        let table = read_image_partitions(&cli)?;
+        if cli.list_partitions {
+            print_partitions(&mut open_output(&cli)?, table)?;
+            return Ok(());
+        }
+        select_partition(&mut cli, table)?;
+    }
+
+    if let Err(err) = check_input_paths(&cli) {
+        if cli.quiet {
+            eprintln!("Error: {}", err);
+            std::process::exit(2);
+        }
+        return Err(err);
+    }
+
+    // Quiet mode reports the result only through the exit code (grep compatible)
+    if cli.quiet {
+        let exit_code = match handle_quiet_mode(&cli, &base_config) {
+            Ok(true) => 0,
+            Ok(false) => 1,
+            Err(err) => {
+                eprintln!("Error: {}", err);
+                match err {
+                    hxgrep::error::BingrepError::PatternTooComplex(_) => PATTERN_TOO_COMPLEX_EXIT_CODE,
+                    _ => 2,
+                }
+            }
+        };
+        std::process::exit(exit_code);
+    }
+
+    // -l/-L on a single file or stdin print its name when it does (or does not) match
+    let multiple_inputs = cli.multi_file || cli.recursive || cli.files_from.is_some();
+    if (cli.files_with_matches || cli.files_without_match) && !multiple_inputs {
+        let expression = list_expression(&cli)?;
+        if input_contains_match(&cli, &base_config, expression)? == cli.files_with_matches {
+            let mut out = open_output(&cli)?;
+            match cli.file_path.as_deref() {
+                Some("-") => writeln!(out, "(standard input)")?,
+                Some(path) => writeln!(out, "{}", path)?,
+                None => {}
+            }
+        }
+        return Ok(());
+    }
+
+    // Interactive mode reads patterns from stdin against data loaded once
+    if cli.interactive {
+        return handle_interactive_mode(&cli, &base_config);
+    }
+
+    // Inverse matching dumps the complement of the matches, so it needs a pattern
+    if cli.invert_match && cli.expression.is_none() {
+        return Err(hxgrep::error::BingrepError::InvalidPattern(
+            "-v/--invert-match requires a regex pattern (-e)".to_string()
+        ));
+    }
+    if cli.byte_offset && cli.expression.is_none() {
+        return Err(hxgrep::error::BingrepError::InvalidPattern(
+            "-b/--byte-offset requires a regex pattern (-e)".to_string()
+        ));
+    }
+
+    let search_options = cli.search_options();
+    let dump_options = cli.dump_options();
+
+    // With progress on the same terminal, data writes clear and redraw the progress line.
+    // Data written to a -o file never meets the progress, which then has stderr to itself.
+    let progress_line = match (cli.show_progress(), &cli.output) {
+        (false, _) => None,
+        (true, Some(_)) => Some(ProgressLine::new(Box::new(io::stderr()))),
+        (true, None) => ProgressLine::for_terminals(),
+    };
+    let data_line = if cli.output.is_some() { None } else { progress_line.clone() };
+
+    // Output is buffered rather than locking stdout for every line
+    let mut out = io::BufWriter::new(ProgressWriter::new(open_output(&cli)?, data_line));
+
+    // Structured records are produced by the sequential processor, so they bypass the parallel path
+    let output_format = if cli.msgpack {
+        OutputFormat::Msgpack
+    } else if cli.xxd_compat {
+        OutputFormat::Xxd
+    } else if cli.od_output() {
+        OutputFormat::Od(cli.od_radix)
+    } else {
+        OutputFormat::from_str(&cli.output_format).ok_or_else(|| {
+            hxgrep::error::BingrepError::InvalidPattern(format!(
+                "Unknown output format: {}",
+                cli.output_format
+            ))
+        })?
+    };
+
+    // Offsets are printed as plain text, so they have no place in structured records
+    if cli.byte_offset && matches!(output_format, OutputFormat::Json | OutputFormat::Ndjson | OutputFormat::Msgpack) {
+        return Err(hxgrep::error::BingrepError::InvalidPattern(
+            "-b/--byte-offset cannot be combined with -f json, -f ndjson, -f msgpack or --msgpack".to_string()
+        ));
+    }
+
+    // Paths listed with --files-from, or several paths given with --multi, are searched as a list
+    let file_list = match (&cli.files_from, &cli.file_path) {
+        (Some(source), _) => Some(MultiFileProcessor::read_file_list(source, cli.null_separated)?),
+        (None, Some(first)) if !cli.extra_paths.is_empty() => {
+            Some(std::iter::once(first).chain(&cli.extra_paths).cloned().collect())
+        }
+        _ => None,
+    };
+    if let Some(paths) = file_list {
+        let multi_processor = multi_file_processor(&cli, &base_config, output_format, progress_line.clone())?;
+        let paths: Vec<&str> = paths.iter().map(String::as_str).collect();
+
+        // List only file names (-l / -L)
+        if cli.files_with_matches || cli.files_without_match {
+            multi_processor.list_files_by_list(
+                &mut out,
+                paths,
+                list_expression(&cli)?,
+                cli.files_with_matches,
+                cli.parallel,
+                cli.chunk_size,
+            )?;
+            out.flush()?;
+            return Ok(());
+        }
+
+        return multi_processor.search_list(&mut out, paths, cli.expression.as_deref(), &search_options);
+    }
 
     // Check file path or stdin
     let file_path = match &cli.file_path {
         Some(path) => {
             if path == "-" {
                 // Handle stdin input
-                return handle_stdin_input(&cli);
+                return handle_stdin_input(&cli, &base_config, &mut out);
             }
             // Validate file path for security
             validate_file_path(path)?
@@ -68,62 +376,181 @@ fn main() -> Result<()> {
         }
     };
 
-    // Handle multi-file processing
-    if cli.multi_file {
-        let config = Config::default();
-        config.validate_cli(&cli)?;
+    // Handle multi-file and recursive directory processing
+    if cli.multi_file || cli.recursive {
+        let multi_processor = multi_file_processor(&cli, &base_config, output_format, progress_line.clone())?;
 
-        let multi_processor = MultiFileProcessor::new(config);
+        // List only file names (-l / -L)
+        if cli.files_with_matches || cli.files_without_match {
+            let expression = list_expression(&cli)?;
+            if cli.recursive {
+                multi_processor.list_files_recursive(
+                    &mut out,
+                    &file_path,
+                    expression,
+                    cli.files_with_matches,
+                    cli.parallel,
+                    cli.chunk_size,
+                )?;
+            } else {
+                multi_processor.list_files_by_glob(
+                    &mut out,
+                    &file_path.to_string_lossy(),
+                    expression,
+                    cli.files_with_matches,
+                    cli.parallel,
+                    cli.chunk_size,
+                )?;
+            }
+            out.flush()?;
+            return Ok(());
+        }
 
-        return multi_processor.process_files_by_glob(
-            &file_path.to_string_lossy(),
-            cli.expression.as_deref(),
-            cli.line_width,
-            cli.limit,
-            &cli.separator,
-            !cli.no_offset,
-            cli.parallel,
-            cli.chunk_size,
-            cli.global_limit,
-        );
+        if cli.recursive {
+            return multi_processor.search_directory(&mut out, &file_path, cli.expression.as_deref(), &search_options);
+        }
+
+        return multi_processor.search_glob(&mut out, &file_path.to_string_lossy(), cli.expression.as_deref(), &search_options);
     }
 
     // Create configuration and validate CLI parameters
-    let config = Config::default();
+    let config = Config {
+        use_mmap: cli.mmap || base_config.use_mmap,
+        ..base_config
+    };
     config.validate_cli(&cli)?;
 
     let mut processor = FileProcessor::new(config.clone());
 
+    let streams_records = matches!(output_format, OutputFormat::Ndjson | OutputFormat::Json | OutputFormat::Msgpack);
+    let compat_output = matches!(output_format, OutputFormat::Xxd | OutputFormat::Od(_));
+    processor.set_output_format(output_format);
+    processor.set_source_name(&file_path.to_string_lossy());
+    processor.set_start_offset(cli.position);
+    processor.set_end_offset(cli.end_offset());
+    processor.set_extract_dir(cli.extract_dir.clone());
+    processor.set_match_alignment(cli.match_alignment());
+    processor.set_entropy_filter(cli.entropy_filter());
+    processor.set_pattern_set(cli.pattern_set()?);
+    // Only hex lines are collapsed; structured and compat formats keep every match
+    if matches!(output_format, OutputFormat::Hex) {
+        processor.set_match_collapser(cli.match_collapser());
+    }
+
+    // Follow a growing file, reporting matches in appended bytes until Ctrl-C
+    if cli.watch {
+        if let Some(expression) = &cli.expression {
+            let regex = RegexProcessor::compile_pattern(expression)?;
+            let mut file = File::open(&file_path)?;
+            file.seek(SeekFrom::Start(cli.position))?;
+            return processor.watch_file(
+                &mut file,
+                &mut out,
+                &regex,
+                &search_options,
+                Duration::from_millis(cli.poll_interval_ms),
+            );
+        }
+    }
+
+    // Search the decompressed contents of a gzip file, reporting where matches lie in it
+    if cli.decompress {
+        let file = File::open(&file_path)?;
+        let reader = GzipReader::new(io::BufReader::new(file), config.buffer_size);
+        processor.set_compressed_offsets(Some(reader.offsets()));
+
+        // The decompressed stream cannot seek, so rewind within recently read bytes like stdin
+        processor.set_lookahead_limit(Some(config.buffer_size));
+        let mut reader = RewindReader::new(reader, rewind_retain(&config));
+        reader.seek(SeekFrom::Start(cli.position))?;
+
+        // The decompressed size is unknown until the end, so pad offsets to 8 digits like stdin
+        let size_hint = cli.end_offset().unwrap_or(u64::from(u32::MAX));
+        let mut progress = if cli.show_progress() {
+            ProgressIndicator::new_silent_only(true)
+        } else {
+            ProgressIndicator::disabled()
+        };
+        progress.set_line(progress_line);
+
+        if cli.strings_mode {
+            processor.find_strings(
+                &mut reader,
+                &mut out,
+                cli.strings_min_len,
+                cli.limit,
+                !cli.hide_offset,
+                size_hint,
+                &mut progress,
+            )?;
+        } else if let Some(expression) = &cli.expression {
+            let regex = RegexProcessor::compile_pattern(expression)?;
+            if cli.invert_match {
+                processor.dump_inverted(&mut reader, &mut out, &regex, &dump_options, size_hint, &mut progress)?;
+            } else {
+                processor.grep_reader(&mut reader, &mut out, &regex, &search_options, size_hint, &mut progress)?;
+            }
+        } else {
+            processor.dump_reader(&mut reader, &mut out, &dump_options, size_hint, &mut progress)?;
+        }
+
+        out.flush()?;
+        return Ok(());
+    }
+
+    // A limited scan ends JSON regex output with its coverage, so the matches go into a report document
+    let json_coverage_report = cli.limits_scan()
+        && matches!(output_format, OutputFormat::Json)
+        && cli.expression.is_some()
+        && !cli.strings_mode
+        && !cli.invert_match;
+    if json_coverage_report {
+        StructuredFormatter::new(OutputFormat::Json)
+            .begin_report_json(&mut out)
+            .map_err(hxgrep::error::BingrepError::from)?;
+        processor.set_json_elements_only(true);
+    }
+
     // Check if this is a forensic image file (E01, VMDK) and handle accordingly
     if hxgrep::forensic_image::is_forensic_image(&file_path) {
         // Process forensic image file - parallel processing not supported for forensic images yet
         let format_name = hxgrep::forensic_image::get_format_name(&file_path)
             .unwrap_or("Unknown");
-        eprintln!("Detected {} forensic image: {}", format_name, file_path.display());
+        diagnostics::info(format!("Detected {} forensic image: {}", format_name, file_path.display()));
 
-        // Forensic images (E01) do not support progress due to exhume_body library limitations
-        let mut progress = ProgressIndicator::disabled();
+        // The image size is only known once it is opened, which sets the progress total
+        let mut progress = if cli.show_progress() {
+            ProgressIndicator::new_silent_only(true)
+        } else {
+            ProgressIndicator::disabled()
+        };
+        progress.set_line(progress_line);
 
-        if let Some(expression) = cli.expression {
-            let regex = RegexProcessor::compile_pattern(&expression)?;
-            processor.process_stream_by_regex_from_path(
+        if cli.limits_scan() {
+            processor.set_coverage(Some(ScanCoverage::new(cli.position, cli.end_offset())));
+        }
+
+        if cli.strings_mode {
+            processor.find_strings_from_path(
                 &file_path,
-                &regex,
-                cli.line_width,
+                &mut out,
+                cli.strings_min_len,
                 cli.limit,
-                &cli.separator,
-                !cli.no_offset,
+                !cli.hide_offset,
                 &mut progress,
             )?;
+        } else if let Some(expression) = cli.expression.as_deref().filter(|_| cli.invert_match) {
+            let regex = RegexProcessor::compile_pattern(expression)?;
+            processor.dump_inverted_path(&file_path, &mut out, &regex, &dump_options, &mut progress)?;
+        } else if let Some(expression) = &cli.expression {
+            let regex = RegexProcessor::compile_pattern(expression)?;
+            processor.grep_path(&file_path, &mut out, &regex, &search_options, &mut progress)?;
         } else {
-            processor.process_file_stream_from_path(
-                &file_path,
-                cli.line_width,
-                cli.limit,
-                &cli.separator,
-                !cli.no_offset,
-                &mut progress,
-            )?;
+            processor.dump_path(&file_path, &mut out, &dump_options, &mut progress)?;
+        }
+
+        if let Some(coverage) = processor.take_coverage() {
+            report_coverage(&mut out, output_format, &coverage, json_coverage_report)?;
         }
     } else {
         // Open regular file
@@ -132,155 +559,526 @@ fn main() -> Result<()> {
 
         // Validate file size doesn't exceed limits
         config.validate_file_size(file_size)?;
+        config.validate_position(cli.position, file_size)?;
+
+        // Point out a likely -s/-n mix-up before a possibly huge output starts
+        if !cli.no_hints {
+            for hint in cli.argument_hints(file_size) {
+                eprintln!("Hint: {} (hide with --no-hints)", hint);
+            }
+        }
 
         // Seek to starting position
         file.seek(SeekFrom::Start(cli.position))?;
 
+        // Searched range ends at --end/--length or at EOF; offsets stay absolute
+        let range_end = cli.end_offset().map_or(file_size, |end| end.min(file_size));
+
         // Create progress indicator if requested
-        let show_progress = cli.show_progress && ProgressIndicator::should_show_progress();
-        let mut progress = if show_progress {
-            ProgressIndicator::new(file_size - cli.position, true)
+        let mut progress = if cli.show_progress() {
+            ProgressIndicator::new(range_end.saturating_sub(cli.position), true)
         } else {
             ProgressIndicator::disabled()
         };
+        progress.set_line(progress_line);
 
-        // Process file with or without regex
-        if let Some(expression) = cli.expression {
-            let regex = RegexProcessor::compile_pattern(&expression)?;
+        // Compile the pattern up front so hole skipping can check it against zeros
+        let regex = match &cli.expression {
+            Some(expression) => Some(RegexProcessor::compile_pattern(expression)?),
+            None => None,
+        };
 
-            if cli.parallel && file_size > cli.chunk_size as u64 {
-                // Use parallel processing for large files
-                ParallelProcessor::process_file_parallel(
+        // Holes read back as zeros, so skipping them is only safe if the pattern cannot match zeros
+        let mut skip_holes = cli.skip_holes;
+        if skip_holes && regex.as_ref().is_some_and(RegexProcessor::can_match_zero_byte) {
+            diagnostics::warning("pattern can match zero bytes; --skip-holes disabled");
+            skip_holes = false;
+        }
+        if skip_holes {
+            match HoleSkipper::for_file(&file) {
+                Some(skipper) => processor.set_hole_skipper(Some(skipper)),
+                None => {
+                    diagnostics::warning("hole detection not supported for this file; --skip-holes disabled");
+                    skip_holes = false;
+                }
+            }
+        }
+
+        if cli.limits_scan() {
+            processor.set_coverage(Some(ScanCoverage::new(cli.position, Some(range_end))));
+        }
+
+        // Hole skipping, extraction, entropy and inverse matching are handled by the sequential processor only
+        let use_parallel = cli.parallel
+            && !cli.invert_match
+            && !streams_records
+            && !compat_output
+            && !skip_holes
+            && cli.extract_dir.is_none()
+            && !cli.show_entropy
+            && cli.charset.is_none()
+            && !cli.with_pattern
+            && file_size > cli.chunk_size as u64;
+
+        // Process file with or without regex
+        if cli.strings_mode {
+            // JSON records are collected by the sequential processor
+            if use_parallel && !matches!(output_format, OutputFormat::Json) {
+                ParallelProcessor::find_strings_parallel(
                     &mut file,
-                    &regex,
+                    &mut out,
                     cli.chunk_size,
-                    cli.line_width,
+                    cli.strings_min_len,
                     cli.limit,
-                    &cli.separator,
-                    !cli.no_offset,
-                    file_size,
+                    !cli.hide_offset,
+                    range_end,
                 )?;
             } else {
-                // Use regular processing
-                processor.process_stream_by_regex(
+                processor.find_strings(
                     &mut file,
-                    &regex,
-                    cli.line_width,
+                    &mut out,
+                    cli.strings_min_len,
                     cli.limit,
-                    &cli.separator,
-                    !cli.no_offset,
+                    !cli.hide_offset,
+                    file_size,
                     &mut progress,
                 )?;
             }
-        } else {
-            if cli.parallel && file_size > cli.chunk_size as u64 {
-                // Use parallel processing for hex dump
-                ParallelHexDump::process_file_parallel(
+        } else if let Some(regex) = regex.as_ref().filter(|_| cli.invert_match) {
+            processor.dump_inverted(&mut file, &mut out, regex, &dump_options, file_size, &mut progress)?;
+        } else if let Some(regex) = regex {
+            if use_parallel {
+                // Use parallel processing for large files
+                let stats = cli.stats.then(|| ScanStats::new(rayon::current_num_threads()));
+                ParallelProcessor::search_file(
                     &mut file,
-                    cli.chunk_size,
-                    cli.line_width,
-                    cli.limit,
-                    &cli.separator,
-                    !cli.no_offset,
-                    file_size,
+                    &mut out,
+                    &regex,
+                    &search_options,
+                    cli.match_alignment(),
+                    range_end,
+                    stats.as_ref(),
+                    cli.match_collapser(),
+                    &mut progress,
                 )?;
+                if let Some(stats) = stats {
+                    out.flush()?;
+                    eprintln!("{}", stats.summary());
+                }
             } else {
                 // Use regular processing
-                processor.process_file_stream(
-                    &mut file,
-                    cli.line_width,
-                    cli.limit,
-                    &cli.separator,
-                    !cli.no_offset,
-                    file_size,
-                    &mut progress,
-                )?;
+                processor.grep_file(&mut file, &mut out, &regex, &search_options, &mut progress)?;
             }
+        } else {
+            if use_parallel {
+                // Use parallel processing for hex dump
+                ParallelHexDump::dump_file(&mut file, &mut out, &dump_options, range_end, &mut progress)?;
+            } else {
+                // Use regular processing
+                processor.dump_file(&mut file, &mut out, &dump_options, file_size, &mut progress)?;
+            }
+        }
+
+        if let Some(skipper) = processor.take_hole_skipper() {
+            out.flush()?;
+            eprintln!("{}", skipper.summary());
+        }
+
+        if let Some(mut coverage) = processor.take_coverage() {
+            // The parallel processors read the whole range in chunks
+            if use_parallel {
+                coverage.record_read(cli.position, range_end.saturating_sub(cli.position));
+            }
+            report_coverage(&mut out, output_format, &coverage, json_coverage_report)?;
         }
     }
 
+    out.flush()?;
     Ok(())
 }
 
-/// Handle stdin input processing
-fn handle_stdin_input(cli: &Cli) -> Result<()> {
-    let config = Config::default();
+/// Report the byte ranges a limited scan read
+///
+/// The summary goes to stderr. NDJSON output ends with a `coverage` record, and a JSON
+/// report document opened for the matches is closed with a `coverage` field.
+fn report_coverage(out: &mut dyn Write, output_format: OutputFormat, coverage: &ScanCoverage, json_report: bool) -> Result<()> {
+    let formatter = StructuredFormatter::new(output_format);
+    let written = match output_format {
+        OutputFormat::Ndjson => formatter.output_coverage_ndjson(&coverage.record(), out),
+        OutputFormat::Json if json_report => formatter.end_coverage_report_json(&coverage.record(), out),
+        _ => Ok(()),
+    };
+    written.map_err(hxgrep::error::BingrepError::from)?;
+    out.flush()?;
+    eprintln!("{}", coverage.summary());
+    Ok(())
+}
+
+/// Multi-file processor for `-m`, `-r` and `--files-from`, set up from the command line
+fn multi_file_processor(
+    cli: &Cli,
+    base_config: &Config,
+    output_format: OutputFormat,
+    progress_line: Option<ProgressLine>,
+) -> Result<MultiFileProcessor> {
+    let config = Config {
+        max_file_size: cli.max_filesize.unwrap_or(base_config.max_file_size),
+        ..base_config.clone()
+    };
+    config.validate_cli(cli)?;
+
+    let mut multi_processor = MultiFileProcessor::new(config);
+    multi_processor.set_output_format(output_format);
+    multi_processor.set_exclude_patterns(&cli.exclude)?;
+    multi_processor.set_include_patterns(&cli.include)?;
+    multi_processor.set_respect_gitignore(cli.respect_gitignore);
+    multi_processor.set_follow_links(cli.follow);
+    multi_processor.set_max_depth(cli.max_depth);
+    multi_processor.set_jobs(cli.jobs.unwrap_or(1));
+    multi_processor.set_sort_order(cli.sort_files.unwrap_or_default(), cli.sort_descending);
+    multi_processor.set_strict(cli.strict);
+    multi_processor.set_progress(cli.show_progress(), progress_line);
+    Ok(multi_processor)
+}
+
+/// Pattern searched by `-l`/`-L`, which need one
+fn list_expression(cli: &Cli) -> Result<&str> {
+    cli.expression.as_deref().ok_or_else(|| {
+        hxgrep::error::BingrepError::InvalidPattern(
+            "-l/-L requires a regex pattern (-e)".to_string()
+        )
+    })
+}
+
+/// Handle quiet mode processing
+///
+/// Returns whether the pattern was found. Scanning stops at the first match.
+fn handle_quiet_mode(cli: &Cli, base_config: &Config) -> Result<bool> {
+    let expression = cli.expression.as_deref().ok_or_else(|| {
+        hxgrep::error::BingrepError::InvalidPattern(
+            "Quiet mode requires a regex pattern (-e)".to_string()
+        )
+    })?;
+    input_contains_match(cli, base_config, expression)
+}
+
+/// Whether the input (a file, stdin or the `--multi` patterns) contains a match
+///
+/// Scanning stops at the first match. Shared by quiet mode and `-l`/`-L` on a single input.
+fn input_contains_match(cli: &Cli, base_config: &Config, expression: &str) -> Result<bool> {
+    let config = base_config.clone();
     config.validate_cli(cli)?;
 
-    // Read all data from stdin into a buffer
-    let mut stdin_data = Vec::new();
-    io::stdin().read_to_end(&mut stdin_data)?;
+    let path = cli.file_path.as_deref().ok_or_else(|| {
+        hxgrep::error::BingrepError::InvalidPath("No input file specified".to_string())
+    })?;
+
+    let regex = RegexProcessor::compile_pattern(expression)?;
+
+    if path == "-" {
+        let mut processor = FileProcessor::new(config.clone());
+        processor.set_end_offset(cli.end_offset());
+        processor.set_match_alignment(cli.match_alignment());
+        let mut stdin = open_stdin(&config, cli.position)?;
+        return processor.contains_match(&mut stdin, &regex);
+    }
 
-    if stdin_data.is_empty() {
-        eprintln!("Warning: No data received from stdin");
+    let file_path = validate_file_path(path)?;
+
+    if cli.multi_file {
+        // Each path, including the ones after the first, is searched as a glob pattern
+        let multi_processor = MultiFileProcessor::new(config);
+        for pattern in std::iter::once(file_path).chain(cli.extra_paths.iter().map(PathBuf::from)) {
+            let pattern = pattern.to_string_lossy();
+            if multi_processor.contains_match_by_glob(&pattern, expression, cli.parallel, cli.chunk_size)? {
+                return Ok(true);
+            }
+        }
+        return Ok(false);
+    }
+
+    let mut processor = FileProcessor::new(config.clone());
+    processor.set_start_offset(cli.position);
+    processor.set_end_offset(cli.end_offset());
+    processor.set_match_alignment(cli.match_alignment());
+
+    if hxgrep::forensic_image::is_forensic_image(&file_path) {
+        return processor.contains_match_from_path(&file_path, &regex);
+    }
+
+    let mut file = File::open(&file_path)?;
+    let file_size = file.metadata()?.len();
+    config.validate_file_size(file_size)?;
+    config.validate_position(cli.position, file_size)?;
+    file.seek(SeekFrom::Start(cli.position))?;
+    let range_end = cli.end_offset().map_or(file_size, |end| end.min(file_size));
+
+    if cli.parallel && file_size > cli.chunk_size as u64 {
+        ParallelProcessor::contains_match(&mut file, &regex, cli.chunk_size, cli.match_alignment(), range_end)
+    } else {
+        processor.contains_match(&mut file, &regex)
+    }
+}
+
+/// Handle interactive pattern testing mode
+///
+/// Loads the file (up to `--interactive-mb`) once, then reads patterns line-by-line from stdin.
+fn handle_interactive_mode(cli: &Cli, config: &Config) -> Result<()> {
+    config.validate_cli(cli)?;
+
+    let path = match cli.file_path.as_deref() {
+        Some("-") => {
+            return Err(hxgrep::error::BingrepError::InvalidPath(
+                "Interactive mode reads patterns from stdin and cannot load data from it".to_string()
+            ));
+        }
+        Some(path) => validate_file_path(path)?,
+        None => {
+            return Err(hxgrep::error::BingrepError::InvalidPath("No input file specified".to_string()));
+        }
+    };
+
+    let max_bytes = cli.interactive_mb.saturating_mul(1024 * 1024);
+    let max_bytes = cli
+        .end_offset()
+        .map_or(max_bytes, |end| max_bytes.min(end.saturating_sub(cli.position)));
+    let mut data = Vec::new();
+
+    if hxgrep::forensic_image::is_forensic_image(&path) {
+        let mut reader = hxgrep::forensic_image::ForensicImageReader::new(&path)?;
+        reader.seek(SeekFrom::Start(cli.position))?;
+        reader.take(max_bytes).read_to_end(&mut data)?;
+    } else {
+        let mut file = File::open(&path)?;
+        let file_size = file.metadata()?.len();
+        config.validate_position(cli.position, file_size)?;
+        file.seek(SeekFrom::Start(cli.position))?;
+        file.take(max_bytes).read_to_end(&mut data)?;
+
+        if file_size.saturating_sub(cli.position) > max_bytes {
+            diagnostics::warning(format!("only the first {} MB were loaded", cli.interactive_mb));
+        }
+    }
+
+    diagnostics::info(format!("Loaded {} bytes from {}", data.len(), path.display()));
+
+    let limit = if cli.limit > 0 { cli.limit } else { DEFAULT_INTERACTIVE_LIMIT };
+    let options = cli.search_options().width(cli.line_width).limit(limit);
+    let mut session = InteractiveSession::new(data, cli.position, options);
+
+    let stdin = io::stdin();
+    session.run(stdin.lock(), &mut io::stdout(), &mut io::stderr())
+}
+
+/// Handle stdin input processing
+///
+/// Stdin is streamed through the same buffered scanners as regular files, so memory use
+/// stays bounded however much data is piped in.
+fn handle_stdin_input(cli: &Cli, base_config: &Config, out: &mut dyn Write) -> Result<()> {
+    let config = base_config.clone();
+    config.validate_cli(cli)?;
+    reject_stdin_options(cli)?;
+
+    let mut stdin = open_stdin(&config, cli.position)?;
+    if cli.end_offset().is_some_and(|end| end <= cli.position) || stdin.is_exhausted()? {
+        diagnostics::warning("No data received from stdin");
         return Ok(());
     }
 
-    let data_size = stdin_data.len() as u64;
+    let output_format = if cli.xxd_output() {
+        OutputFormat::Xxd
+    } else if cli.od_output() {
+        OutputFormat::Od(cli.od_radix)
+    } else {
+        OutputFormat::Hex
+    };
+
+    let mut processor = FileProcessor::new(config.clone());
+    processor.set_output_format(output_format);
+    processor.set_source_name("stdin");
+    processor.set_lookahead_limit(Some(config.buffer_size));
+    processor.set_end_offset(cli.end_offset());
+    processor.set_extract_dir(cli.extract_dir.clone());
+    processor.set_match_alignment(cli.match_alignment());
+    processor.set_entropy_filter(cli.entropy_filter());
+    processor.set_pattern_set(cli.pattern_set()?);
+    if matches!(output_format, OutputFormat::Hex) {
+        processor.set_match_collapser(cli.match_collapser());
+    }
+
+    // Offsets are padded like the redirected file; a pipe's length is unknown, so use xxd's 8 digits
+    let size_hint = stdin_file_size()
+        .or(cli.end_offset())
+        .unwrap_or(u64::from(u32::MAX));
+    let mut progress = ProgressIndicator::disabled();
+    if cli.limits_scan() {
+        processor.set_coverage(Some(ScanCoverage::new(cli.position, cli.end_offset())));
+    }
 
     // Process data with or without regex
-    if let Some(expression) = &cli.expression {
+    if cli.strings_mode {
+        processor.find_strings(
+            &mut stdin,
+            out,
+            cli.strings_min_len,
+            cli.limit,
+            !cli.hide_offset,
+            size_hint,
+            &mut progress,
+        )?;
+    } else if let Some(expression) = &cli.expression {
         let regex = RegexProcessor::compile_pattern(expression)?;
-        process_stdin_with_regex(&stdin_data, &regex, cli, data_size)?;
+        if cli.invert_match {
+            processor.dump_inverted(&mut stdin, out, &regex, &cli.dump_options(), size_hint, &mut progress)?;
+        } else {
+            processor.grep_reader(&mut stdin, out, &regex, &cli.search_options(), size_hint, &mut progress)?;
+        }
     } else {
-        process_stdin_hex_dump(&stdin_data, cli, data_size)?;
+        processor.dump_reader(&mut stdin, out, &cli.dump_options(), size_hint, &mut progress)?;
+    }
+
+    if let Some(coverage) = processor.take_coverage() {
+        report_coverage(out, output_format, &coverage, false)?;
     }
 
+    out.flush()?;
     Ok(())
 }
 
-/// Process stdin data with regex search
-fn process_stdin_with_regex(
-    data: &[u8],
-    regex: &regex::bytes::Regex,
-    cli: &Cli,
-    data_size: u64,
-) -> Result<()> {
-    let hex_offset_length = OutputFormatter::calculate_hex_offset_length(data_size);
-    let mut match_count = 0;
+/// Read the partition table of the input disk image, which may also be a forensic image
+fn read_image_partitions(cli: &Cli) -> Result<Option<(PartitionScheme, Vec<Partition>)>> {
+    let path = match cli.file_path.as_deref() {
+        Some("-") => {
+            return Err(hxgrep::error::BingrepError::InvalidPath(
+                "Partition tables cannot be read from stdin (-)".to_string()
+            ));
+        }
+        Some(path) => validate_file_path(path)?,
+        None => {
+            return Err(hxgrep::error::BingrepError::InvalidPath("No input file specified".to_string()));
+        }
+    };
 
-    for mat in regex.find_iter(data) {
-        let match_offset = mat.start() as u64;
-        let end_pos = (mat.start() + cli.line_width).min(data.len());
-        let display_bytes = &data[mat.start()..end_pos];
+    if hxgrep::forensic_image::is_forensic_image(&path) {
+        let mut reader = hxgrep::forensic_image::ForensicImageReader::new(&path)?;
+        Ok(read_partitions(&mut reader)?)
+    } else {
+        Ok(read_partitions(&mut File::open(&path)?)?)
+    }
+}
 
-        let hex_string = OutputFormatter::format_bytes_as_hex(display_bytes, &cli.separator);
-        OutputFormatter::print_line(
-            match_offset,
-            &hex_string,
-            !cli.no_offset,
-            hex_offset_length,
-        );
+/// Print the partition table for `--list-partitions`
+fn print_partitions(out: &mut dyn Write, table: Option<(PartitionScheme, Vec<Partition>)>) -> io::Result<()> {
+    let Some((scheme, partitions)) = table else {
+        return writeln!(out, "No partition table found");
+    };
 
-        match_count += 1;
-        if cli.limit > 0 && match_count >= cli.limit {
-            break;
-        }
+    let scheme = match scheme {
+        PartitionScheme::Mbr => "MBR",
+        PartitionScheme::Gpt => "GPT",
+    };
+    writeln!(out, "{} partition table, {} partition(s)", scheme, partitions.len())?;
+    writeln!(out, "{:>3}  {:>12}  {:>12}  {:>14}  {:>14}  Type", "#", "Start LBA", "Sectors", "Start", "Size")?;
+    for partition in &partitions {
+        writeln!(
+            out,
+            "{:>3}  {:>12}  {:>12}  {:>#14X}  {:>14}  {}",
+            partition.number,
+            partition.start_lba,
+            partition.sectors,
+            partition.start(),
+            partition.size(),
+            partition.type_name
+        )?;
     }
+    Ok(())
+}
 
+/// Limit the searched range to the partition selected with `--partition`
+///
+/// `-s`, `--end` and `--length` are taken relative to the partition start and clamped
+/// to its end, while the reported offsets stay absolute within the image.
+fn select_partition(cli: &mut Cli, table: Option<(PartitionScheme, Vec<Partition>)>) -> Result<()> {
+    let number = cli.partition.unwrap_or_default();
+    let partition = table
+        .and_then(|(_, partitions)| partitions.into_iter().find(|partition| partition.number == number))
+        .ok_or_else(|| {
+            hxgrep::error::BingrepError::InvalidPattern(format!(
+                "Partition {} not found (see --list-partitions)",
+                number
+            ))
+        })?;
+
+    let relative_end = cli.end_offset();
+    cli.position = partition.start().saturating_add(cli.position).min(partition.end());
+    cli.end = Some(relative_end.map_or(partition.end(), |end| {
+        partition.start().saturating_add(end).min(partition.end())
+    }));
+    cli.length = None;
     Ok(())
 }
 
-/// Process stdin data as hex dump
-fn process_stdin_hex_dump(data: &[u8], cli: &Cli, data_size: u64) -> Result<()> {
-    let hex_offset_length = OutputFormatter::calculate_hex_offset_length(data_size);
-    let mut pos = 0;
-    let mut line = 0;
+/// Reject options that only work on seekable files instead of silently ignoring them
+fn reject_stdin_options(cli: &Cli) -> Result<()> {
+    let unsupported = [
+        (cli.parallel, "--parallel"),
+        (cli.progress.is_some(), "--progress"),
+        (cli.skip_holes, "--skip-holes"),
+        (cli.decompress, "--decompress"),
+        (cli.mmap, "--mmap"),
+        (cli.msgpack, "--msgpack"),
+        (cli.watch, "--watch"),
+    ];
+    if let Some((_, flag)) = unsupported.iter().find(|(set, _)| *set) {
+        return Err(hxgrep::error::BingrepError::InvalidPath(format!(
+            "{} is not supported when reading from stdin (-)",
+            flag
+        )));
+    }
 
-    while pos < data.len() {
-        let end_pos = (pos + cli.line_width).min(data.len());
-        let line_bytes = &data[pos..end_pos];
+    // Stdin output is always text: hex lines or the xxd/od layouts
+    if !["hex", "xxd", "od"].iter().any(|format| cli.output_format.eq_ignore_ascii_case(format)) {
+        return Err(hxgrep::error::BingrepError::InvalidPath(format!(
+            "-f {} is not supported when reading from stdin (-)",
+            cli.output_format
+        )));
+    }
+    Ok(())
+}
 
-        let hex_string = OutputFormatter::format_bytes_as_hex(line_bytes, &cli.separator);
-        OutputFormatter::print_line(pos as u64, &hex_string, !cli.no_offset, hex_offset_length);
+/// Wrap stdin so the buffered scanners can seek back within recently read bytes
+///
+/// Stdin cannot seek, so the bytes before the start position are read and discarded.
+fn open_stdin(config: &Config, position: u64) -> Result<RewindReader<io::StdinLock<'static>>> {
+    let mut reader = RewindReader::new(io::stdin().lock(), rewind_retain(config));
+    reader.seek(SeekFrom::Start(position))?;
+    Ok(reader)
+}
 
-        pos += cli.line_width;
-        line += 1;
+/// Bytes a [`RewindReader`] keeps seekable for the buffered scanners
+///
+/// The scanners step back at most one buffer plus padding and inverse matching one line
+/// more, while a match cut off at the end of a buffer is followed for up to one more buffer
+/// (the lookahead limit set on the processor).
+fn rewind_retain(config: &Config) -> usize {
+    config
+        .buffer_size
+        .saturating_mul(2)
+        .saturating_add(config.buffer_padding)
+        .saturating_add(config.max_line_width)
+}
 
-        if cli.limit > 0 && line >= cli.limit {
-            break;
-        }
-    }
+/// Size of stdin when it is redirected from a regular file
+#[cfg(unix)]
+fn stdin_file_size() -> Option<u64> {
+    use std::os::fd::AsFd;
 
-    Ok(())
+    let fd = io::stdin().as_fd().try_clone_to_owned().ok()?;
+    let metadata = File::from(fd).metadata().ok()?;
+    metadata.is_file().then_some(metadata.len())
+}
+
+#[cfg(not(unix))]
+fn stdin_file_size() -> Option<u64> {
+    None
 }
@@ -1,22 +1,217 @@
+use crate::cli::SortOrder;
 use crate::config::Config;
-use crate::error::Result;
+use crate::diagnostics::{self, Severity};
+use crate::error::{BingrepError, Result};
+use crate::options::SearchOptions;
 use crate::parallel::{ParallelHexDump, ParallelProcessor};
-use crate::progress::ProgressIndicator;
+use crate::progress::{ProgressIndicator, ProgressLine};
 use crate::regex_processor::RegexProcessor;
 use crate::stream::FileProcessor;
-use glob::glob;
-use std::fs::File;
-use std::path::Path;
+use crate::structured_output::{MultiFileSummary, OutputFormat, SkipReason, SkippedFile, StructuredFormatter};
+use glob::{glob, Pattern};
+use ignore::WalkBuilder;
+use regex::bytes::Regex;
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{self, ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Condvar, Mutex, PoisonError};
+use std::thread;
+use std::time::SystemTime;
+
+/// How far ahead of the output the workers of a concurrent search may run
+struct OutputWindow {
+    /// Number of files handed over so far, and whether the search stopped
+    state: Mutex<(usize, bool)>,
+    changed: Condvar,
+    size: usize,
+}
+
+impl OutputWindow {
+    fn new(size: usize) -> Self {
+        Self {
+            state: Mutex::new((0, false)),
+            changed: Condvar::new(),
+            size: size.max(1),
+        }
+    }
+
+    /// Wait until file `index` is within the window, returning `false` if the search stopped
+    fn wait_for(&self, index: usize) -> bool {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        while index >= state.0 + self.size && !state.1 {
+            state = self.changed.wait(state).unwrap_or_else(PoisonError::into_inner);
+        }
+        !state.1
+    }
+
+    /// Record that the files before `handed_over` are done, letting workers move on
+    fn advance(&self, handed_over: usize) {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner).0 = handed_over;
+        self.changed.notify_all();
+    }
+
+    /// Stop the workers once their current file is done
+    fn stop(&self) {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner).1 = true;
+        self.changed.notify_all();
+    }
+}
 
 /// Multi-file processor for handling glob patterns and multiple files
 pub struct MultiFileProcessor {
     config: Config,
+    output_format: OutputFormat,
+    exclude: Vec<Pattern>,
+    include: Vec<Pattern>,
+    respect_gitignore: bool,
+    follow_links: bool,
+    max_depth: Option<usize>,
+    jobs: usize,
+    sort_order: SortOrder,
+    sort_descending: bool,
+    strict: bool,
+    show_progress: bool,
+    progress_line: Option<ProgressLine>,
 }
 
 impl MultiFileProcessor {
     /// Create a new MultiFileProcessor
     pub fn new(config: Config) -> Self {
-        Self { config }
+        Self {
+            config,
+            output_format: OutputFormat::Hex,
+            exclude: Vec::new(),
+            include: Vec::new(),
+            respect_gitignore: false,
+            follow_links: false,
+            max_depth: None,
+            jobs: 1,
+            sort_order: SortOrder::Name,
+            sort_descending: false,
+            strict: false,
+            show_progress: false,
+            progress_line: None,
+        }
+    }
+
+    /// Set the output format used for each file and for the skipped-file records
+    pub fn set_output_format(&mut self, format: OutputFormat) {
+        self.output_format = format;
+    }
+
+    /// Skip files matching any of the glob patterns
+    ///
+    /// A pattern matches either the whole path or just the file name, so `*.log`
+    /// excludes log files in every directory.
+    pub fn set_exclude_patterns(&mut self, patterns: &[String]) -> Result<()> {
+        self.exclude = Self::compile_globs(patterns)?;
+        Ok(())
+    }
+
+    /// Only search files matching at least one of the glob patterns
+    ///
+    /// Patterns match like `--exclude` ones, and an excluded file stays excluded even
+    /// when it also matches an include pattern.
+    pub fn set_include_patterns(&mut self, patterns: &[String]) -> Result<()> {
+        self.include = Self::compile_globs(patterns)?;
+        Ok(())
+    }
+
+    /// Leave out files ignored by `.gitignore` and `.ignore` files in recursive mode
+    pub fn set_respect_gitignore(&mut self, respect: bool) {
+        self.respect_gitignore = respect;
+    }
+
+    /// Follow symbolic links to directories in recursive mode
+    ///
+    /// A link back to a directory that is already being walked is reported as a
+    /// warning and not entered again.
+    pub fn set_follow_links(&mut self, follow: bool) {
+        self.follow_links = follow;
+    }
+
+    /// Limit how deep recursive mode descends below the directory
+    ///
+    /// Files directly in the directory are at depth 1, so `Some(1)` leaves every
+    /// subdirectory out. `None` walks the whole tree.
+    pub fn set_max_depth(&mut self, max_depth: Option<usize>) {
+        self.max_depth = max_depth;
+    }
+
+    /// Search up to `jobs` files at the same time
+    ///
+    /// Each file is searched into its own buffer and the buffers are written in the
+    /// usual order, so the output is the same as with one job. Searches with a global
+    /// limit stay sequential, since each file's limit depends on the files before it.
+    pub fn set_jobs(&mut self, jobs: usize) {
+        self.jobs = jobs.max(1);
+    }
+
+    /// Set the order in which glob and recursive mode process files
+    ///
+    /// The default is byte-wise path order, which does not depend on the locale or the
+    /// filesystem, so reports list the same files in the same order on every machine.
+    /// Files listed with `--files-from` keep the order of the list.
+    pub fn set_sort_order(&mut self, order: SortOrder, descending: bool) {
+        self.sort_order = order;
+        self.sort_descending = descending;
+    }
+
+    /// Fail the run when any file could not be searched (`--strict`)
+    ///
+    /// A file that fails midway is always reported as a warning and the search goes on
+    /// with the next one. Without `strict` the run only fails when no file could be searched.
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    /// Show the current file and the overall progress on stderr while searching
+    ///
+    /// `line` is the progress line shared with the data writer when stdout is the same
+    /// terminal; without it the progress is drawn to stderr on its own.
+    pub fn set_progress(&mut self, show_progress: bool, line: Option<ProgressLine>) {
+        self.show_progress = show_progress;
+        self.progress_line = line;
+    }
+
+    /// Progress indicator for searching `paths`, sized by the files that will not be skipped
+    fn files_progress<'a>(&self, paths: impl Iterator<Item = &'a Path>) -> ProgressIndicator {
+        if !self.show_progress {
+            return ProgressIndicator::disabled();
+        }
+
+        let sizes: Vec<u64> = paths
+            .filter(|path| !Self::matches_any(&self.exclude, path))
+            .filter(|path| self.include.is_empty() || Self::matches_any(&self.include, path))
+            .filter_map(|path| fs::metadata(path).ok())
+            .filter(|metadata| metadata.is_file() && metadata.len() <= self.config.get_max_file_size())
+            .map(|metadata| metadata.len())
+            .collect();
+
+        let mut progress = ProgressIndicator::multi_file_new(sizes.len(), sizes.iter().sum());
+        if let Some(line) = &self.progress_line {
+            progress.set_line(Some(line.clone()));
+        }
+        progress
+    }
+
+    fn compile_globs(patterns: &[String]) -> Result<Vec<Pattern>> {
+        patterns
+            .iter()
+            .map(|pattern| Pattern::new(pattern).map_err(BingrepError::from))
+            .collect()
+    }
+
+    /// Whether any pattern matches the whole path or its file name
+    fn matches_any(patterns: &[Pattern], path: &Path) -> bool {
+        patterns.iter().any(|pattern| {
+            pattern.matches_path(path)
+                || path
+                    .file_name()
+                    .is_some_and(|name| pattern.matches(&name.to_string_lossy()))
+        })
     }
 
     /// Process multiple files using glob pattern
@@ -25,271 +220,1045 @@ impl MultiFileProcessor {
     ///
     /// * `pattern` - Glob pattern to match files (e.g., "*.bin", "data/**/*.txt")
     /// * `expression` - Optional regex expression to search for
-    /// * `line_width` - Number of bytes to display per line
-    /// * `limit` - Maximum number of matches/lines per file (0 for unlimited)
-    /// * `separator` - String to separate hex bytes
-    /// * `show_offset` - Whether to display offset values
-    /// * `parallel` - Whether to use parallel processing
-    /// * `chunk_size` - Chunk size for parallel processing
-    /// * `global_limit` - Global limit across all files (0 for unlimited)
-    pub fn process_files_by_glob(
+    /// * `options` - Display settings, per-file and global limits and parallel processing
+    pub fn search_glob(
         &self,
+        out: &mut dyn Write,
         pattern: &str,
         expression: Option<&str>,
-        line_width: usize,
-        limit: usize,
-        separator: &str,
-        show_offset: bool,
-        parallel: bool,
-        chunk_size: usize,
-        global_limit: usize,
+        options: &SearchOptions,
     ) -> Result<()> {
-        let paths = glob(pattern)?;
-        let mut total_processed = 0;
+        self.process_paths(out, self.sorted_paths(pattern)?, Vec::new(), expression, options)
+    }
+
+    /// Search every regular file below a directory
+    ///
+    /// Subdirectories are walked recursively. Symbolic links to directories are only
+    /// followed with [`Self::set_follow_links`], and link loops are never entered, so
+    /// the walk cannot recurse forever. Files are searched in the order set with
+    /// [`Self::set_sort_order`] with the same banners, skip handling and limits as
+    /// [`Self::search_glob`]. With [`Self::set_respect_gitignore`], ignored
+    /// files are left out of the walk without being reported as skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - Directory to walk
+    /// * `expression` - Optional regex expression to search for
+    /// * `options` - Display settings, per-file and global limits and parallel processing
+    pub fn search_directory(
+        &self,
+        out: &mut dyn Write,
+        dir: &Path,
+        expression: Option<&str>,
+        options: &SearchOptions,
+    ) -> Result<()> {
+        let (paths, skipped) = self.collect_directory(out, dir)?;
+
+        self.process_paths(out, paths, skipped, expression, options)
+    }
+
+    /// Collect the files below `dir` in the sort order, along with the entries skipped on the way
+    fn collect_directory(&self, out: &mut dyn Write, dir: &Path) -> Result<(Vec<PathBuf>, Vec<SkippedFile>)> {
+        let mut paths = Vec::new();
+        let mut skipped = Vec::new();
+        if self.respect_gitignore {
+            self.collect_unignored_files(out, dir, &mut paths, &mut skipped)?;
+        } else {
+            self.collect_files(out, dir, 0, &mut paths, &mut skipped, &mut Vec::new())?;
+        }
+        self.sort_paths(&mut paths);
+        Ok((paths, skipped))
+    }
+
+    /// Collect the files below `dir`, depth first with entries in byte-wise order
+    ///
+    /// Unreadable directories are reported as skipped, as are dangling symbolic links.
+    /// `depth` is the depth of `dir` below the walked directory, whose own depth is 0;
+    /// entries deeper than [`Self::set_max_depth`] are left out. `ancestors` holds the canonical paths of the directories being walked when links
+    /// are followed, to tell a link loop from a directory reached for the first time.
+    fn collect_files(
+        &self,
+        out: &mut dyn Write,
+        dir: &Path,
+        depth: usize,
+        files: &mut Vec<PathBuf>,
+        skipped: &mut Vec<SkippedFile>,
+        ancestors: &mut Vec<PathBuf>,
+    ) -> Result<()> {
+        if self.max_depth.is_some_and(|max_depth| depth >= max_depth) {
+            return Ok(());
+        }
+
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == ErrorKind::PermissionDenied => {
+                return self.report_skipped(out, dir, SkipReason::PermissionDenied, skipped);
+            }
+            Err(err) => return Err(err.into()),
+        };
+        let mut entries = entries.collect::<io::Result<Vec<_>>>()?;
+        entries.sort_by(|a, b| a.file_name().as_encoded_bytes().cmp(b.file_name().as_encoded_bytes()));
+
+        if self.follow_links {
+            let canonical = fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf());
+            if ancestors.contains(&canonical) {
+                diagnostics::warning(format!("{} skipped (symbolic link loop)", dir.display()));
+                return Ok(());
+            }
+            ancestors.push(canonical);
+        }
+
+        for entry in entries {
+            let path = entry.path();
+            // Without --follow the entry's own type, so a symbolic link to a directory is not entered
+            let is_dir = if self.follow_links {
+                fs::metadata(&path).is_ok_and(|metadata| metadata.is_dir())
+            } else {
+                entry.file_type()?.is_dir()
+            };
+            if is_dir {
+                self.collect_files(out, &path, depth + 1, files, skipped, ancestors)?;
+                continue;
+            }
+            match fs::metadata(&path) {
+                Ok(metadata) if metadata.is_dir() => {}
+                Ok(_) => files.push(path),
+                Err(_) => self.report_skipped(out, &path, SkipReason::NotRegular, skipped)?,
+            }
+        }
+
+        if self.follow_links {
+            ancestors.pop();
+        }
+        Ok(())
+    }
 
-        for path_result in paths {
-            let path = path_result?;
+    /// Collect the files below `dir` that no `.gitignore` or `.ignore` file excludes
+    ///
+    /// Ignore files are honored outside git repositories too, and hidden files are kept.
+    /// Entries that cannot be read and link loops met with [`Self::set_follow_links`] are
+    /// reported as warnings.
+    fn collect_unignored_files(
+        &self,
+        out: &mut dyn Write,
+        dir: &Path,
+        files: &mut Vec<PathBuf>,
+        skipped: &mut Vec<SkippedFile>,
+    ) -> Result<()> {
+        let walker = WalkBuilder::new(dir)
+            .hidden(false)
+            .require_git(false)
+            .follow_links(self.follow_links)
+            .max_depth(self.max_depth)
+            .sort_by_file_name(|a, b| a.as_encoded_bytes().cmp(b.as_encoded_bytes()))
+            .build();
+
+        for entry in walker {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(err) => {
+                    diagnostics::warning(err.to_string());
+                    continue;
+                }
+            };
+            // Directories, including followed links to them, are walked by the walker itself
+            if entry.file_type().is_some_and(|file_type| file_type.is_dir()) {
+                continue;
+            }
+            let path = entry.into_path();
+            match fs::metadata(&path) {
+                Ok(metadata) if metadata.is_dir() => {}
+                Ok(_) => files.push(path),
+                Err(_) => self.report_skipped(out, &path, SkipReason::NotRegular, skipped)?,
+            }
+        }
+        Ok(())
+    }
 
+    /// Search the given files in order, printing banners and totals
+    ///
+    /// A file whose search fails is warned about and the search goes on with the next
+    /// one; [`Self::set_strict`] decides whether the run fails in the end.
+    fn process_paths(
+        &self,
+        out: &mut dyn Write,
+        paths: Vec<PathBuf>,
+        mut skipped: Vec<SkippedFile>,
+        expression: Option<&str>,
+        options: &SearchOptions,
+    ) -> Result<()> {
+        // JSON matches of all files go into the one document finished by `finish_run`
+        if matches!(self.output_format, OutputFormat::Json) {
+            StructuredFormatter::new(OutputFormat::Json)
+                .begin_report_json(out)
+                .map_err(BingrepError::from)?;
+        }
+
+        if self.jobs > 1 && options.global_limit == 0 {
+            return self.process_paths_concurrently(out, paths, skipped, expression, options);
+        }
+
+        let mut total_processed = 0;
+        let mut files_processed = 0;
+        let mut files_failed = 0;
+        let mut wrote_output = false;
+        let mut progress = self.files_progress(paths.iter().map(PathBuf::as_path));
+
+        for path in paths {
             // Skip directories
             if path.is_dir() {
                 continue;
             }
 
-            println!("=== Processing: {} ===", path.display());
+            if let Some(reason) = self.skip_reason(&path) {
+                self.report_skipped(out, &path, reason, &mut skipped)?;
+                continue;
+            }
+
+            if self.prints_banners() {
+                writeln!(out, "=== Processing: {} ===", path.display())?;
+            }
 
-            let processed_count = self.process_single_file(
-                &path,
-                expression,
-                line_width,
-                limit,
-                separator,
-                show_offset,
-                parallel,
-                chunk_size,
-            )?;
+            progress.set_description(&path.display().to_string());
+            let file_options = Self::file_options(options, total_processed);
+            let mut file_out = SeparatedWriter::new(out, self.file_separator(wrote_output));
+            let result = self.process_single_file(&mut file_out, &path, expression, &file_options, &mut progress);
+            wrote_output |= file_out.started;
+            let processed_count = match result {
+                Ok(processed_count) => processed_count,
+                Err(err) => {
+                    Self::report_failed(&path, err)?;
+                    files_failed += 1;
+                    continue;
+                }
+            };
 
             total_processed += processed_count;
+            files_processed += 1;
 
             // Check global limit
-            if global_limit > 0 && total_processed >= global_limit {
-                println!("=== Global limit of {} reached ===", global_limit);
+            if options.global_limit > 0 && total_processed >= options.global_limit {
+                if self.prints_banners() {
+                    writeln!(out, "=== Global limit of {} reached ===", options.global_limit)?;
+                }
                 break;
             }
         }
 
-        println!("=== Total matches/lines processed: {} ===", total_processed);
+        progress.finish_files();
+        self.finish_run(out, total_processed, files_processed, &skipped)?;
+        self.check_failures(files_failed, files_processed)
+    }
+
+    /// Search the given files on [`Self::set_jobs`] threads, writing them in order
+    ///
+    /// Banners, skipped-file notices, progress and totals are produced as each file's
+    /// output is written, so they appear exactly as in a sequential run.
+    fn process_paths_concurrently(
+        &self,
+        out: &mut dyn Write,
+        paths: Vec<PathBuf>,
+        mut skipped: Vec<SkippedFile>,
+        expression: Option<&str>,
+        options: &SearchOptions,
+    ) -> Result<()> {
+        let mut total_processed = 0;
+        let mut files_processed = 0;
+        let mut files_failed = 0;
+        let mut wrote_output = false;
+        let mut progress = self.files_progress(paths.iter().map(PathBuf::as_path));
+
+        self.search_in_order(&paths, self.jobs, expression, options, |path, outcome| {
+            match outcome {
+                FileOutcome::Directory => {}
+                FileOutcome::Skipped(reason) => self.report_skipped(out, path, reason, &mut skipped)?,
+                FileOutcome::Searched { output, processed, file_size } => {
+                    SeparatedWriter::new(out, self.file_separator(wrote_output)).write_all(&output)?;
+                    wrote_output |= !output.is_empty();
+                    progress.set_description(&path.display().to_string());
+                    progress.start_file(file_size);
+                    progress.update(file_size);
+                    total_processed += processed;
+                    files_processed += 1;
+                }
+                FileOutcome::Failed { output, error } => {
+                    SeparatedWriter::new(out, self.file_separator(wrote_output)).write_all(&output)?;
+                    wrote_output |= !output.is_empty();
+                    Self::report_failed(path, error)?;
+                    files_failed += 1;
+                }
+            }
+            Ok(())
+        })?;
+
+        progress.finish_files();
+        self.finish_run(out, total_processed, files_processed, &skipped)?;
+        self.check_failures(files_failed, files_processed)
+    }
+
+    /// Warn about a file whose search failed midway, so the run can go on with the next one
+    ///
+    /// A closed output pipe ends the whole run and is returned as is.
+    fn report_failed(path: &Path, err: BingrepError) -> Result<()> {
+        if err.is_broken_pipe() {
+            return Err(err);
+        }
+        diagnostics::warning(format!("{}: {}", path.display(), err));
         Ok(())
     }
 
-    /// Process a list of specific files
+    /// Summarize the files that failed, failing the run if none succeeded or with [`Self::set_strict`]
+    fn check_failures(&self, files_failed: usize, files_processed: usize) -> Result<()> {
+        if files_failed == 0 {
+            return Ok(());
+        }
+        diagnostics::warning(format!("{} file(s) failed, {} processed", files_failed, files_processed));
+        if self.strict || files_processed == 0 {
+            return Err(BingrepError::FilesFailed(files_failed));
+        }
+        Ok(())
+    }
+
+    /// Search `paths` on `jobs` threads and hand each outcome to `on_file` in path order
+    ///
+    /// Workers take the files in order, so a file's output is handed over as soon as
+    /// every file before it is done. A worker does not start a file `jobs` or more ahead
+    /// of the next one to hand over, so at most `jobs` outputs are held in memory however
+    /// slow an earlier file is. The first error, from a search or from `on_file`, stops
+    /// the workers once their current file is done and is returned.
+    fn search_in_order(
+        &self,
+        paths: &[PathBuf],
+        jobs: usize,
+        expression: Option<&str>,
+        options: &SearchOptions,
+        mut on_file: impl FnMut(&Path, FileOutcome) -> Result<()>,
+    ) -> Result<()> {
+        let next_path = AtomicUsize::new(0);
+        let window = OutputWindow::new(jobs);
+        let (sender, receiver) = mpsc::channel();
+
+        thread::scope(|scope| {
+            for _ in 0..jobs.min(paths.len()) {
+                let sender = sender.clone();
+                let (next_path, window) = (&next_path, &window);
+                scope.spawn(move || loop {
+                    let index = next_path.fetch_add(1, Ordering::Relaxed);
+                    let Some(path) = paths.get(index) else {
+                        break;
+                    };
+                    if !window.wait_for(index) {
+                        break;
+                    }
+                    let outcome = self.search_to_buffer(path, expression, options);
+                    if sender.send((index, outcome)).is_err() {
+                        break;
+                    }
+                });
+            }
+            drop(sender);
+
+            // Outcomes arrive in completion order and are handed over in path order
+            let mut pending = BTreeMap::new();
+            let mut next_output = 0;
+            let mut handle_outcomes = || -> Result<()> {
+                for (index, outcome) in &receiver {
+                    pending.insert(index, outcome);
+                    while let Some(outcome) = pending.remove(&next_output) {
+                        on_file(paths[next_output].as_path(), outcome?)?;
+                        next_output += 1;
+                        window.advance(next_output);
+                    }
+                }
+                Ok(())
+            };
+            let result = handle_outcomes();
+            window.stop();
+            result
+        })
+    }
+
+    /// Search one file into a buffer, as [`Self::process_paths`] would write it
+    fn search_to_buffer(&self, path: &Path, expression: Option<&str>, options: &SearchOptions) -> Result<FileOutcome> {
+        if path.is_dir() {
+            return Ok(FileOutcome::Directory);
+        }
+        if let Some(reason) = self.skip_reason(path) {
+            return Ok(FileOutcome::Skipped(reason));
+        }
+
+        let mut output = Vec::new();
+        if self.prints_banners() {
+            writeln!(output, "=== Processing: {} ===", path.display())?;
+        }
+        let file_size = fs::metadata(path).map_or(0, |metadata| metadata.len());
+        match self.process_single_file(&mut output, path, expression, options, &mut ProgressIndicator::disabled()) {
+            Ok(processed) => Ok(FileOutcome::Searched {
+                output,
+                processed,
+                file_size,
+            }),
+            // The output written before the failure is kept, like in a sequential run
+            Err(error) => Ok(FileOutcome::Failed { output, error }),
+        }
+    }
+
+    /// Check whether any file matching the glob pattern contains the regex expression
+    ///
+    /// Stops at the first file with a match without producing any output (quiet mode).
     ///
     /// # Arguments
     ///
-    /// * `file_paths` - Vector of file paths to process
-    /// * `expression` - Optional regex expression to search for
-    /// * `line_width` - Number of bytes to display per line
-    /// * `limit` - Maximum number of matches/lines per file (0 for unlimited)
-    /// * `separator` - String to separate hex bytes
-    /// * `show_offset` - Whether to display offset values
+    /// * `pattern` - Glob pattern to match files
+    /// * `expression` - Regex expression to search for
     /// * `parallel` - Whether to use parallel processing
     /// * `chunk_size` - Chunk size for parallel processing
-    /// * `global_limit` - Global limit across all files (0 for unlimited)
-    pub fn process_files_by_list(
+    pub fn contains_match_by_glob(
+        &self,
+        pattern: &str,
+        expression: &str,
+        parallel: bool,
+        chunk_size: usize,
+    ) -> Result<bool> {
+        let regex = RegexProcessor::compile_pattern(expression)?;
+        let mut processor = FileProcessor::new(self.config.clone());
+
+        for path in self.sorted_paths(pattern)? {
+            // Skip directories
+            if path.is_dir() {
+                continue;
+            }
+
+            if Self::file_contains_match(&mut processor, &path, &regex, parallel, chunk_size)? {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Print the paths of files matching the glob pattern that do (or do not) contain a match
+    ///
+    /// Scanning of each file stops at its first match, and no hex output or
+    /// `=== Processing ===` banners are printed.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - Glob pattern to match files
+    /// * `expression` - Regex expression to search for
+    /// * `with_matches` - Print files with a match (`-l`) when true, files without one (`-L`) when false
+    /// * `parallel` - Whether to use parallel processing
+    /// * `chunk_size` - Chunk size for parallel processing
+    pub fn list_files_by_glob(
         &self,
+        out: &mut dyn Write,
+        pattern: &str,
+        expression: &str,
+        with_matches: bool,
+        parallel: bool,
+        chunk_size: usize,
+    ) -> Result<()> {
+        self.list_paths(out, self.sorted_paths(pattern)?, expression, with_matches, parallel, chunk_size)
+    }
+
+    /// Print the paths of files below a directory that do (or do not) contain a match
+    ///
+    /// The directory is walked like [`Self::search_directory`], and each file
+    /// is listed like [`Self::list_files_by_glob`].
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - Directory to walk
+    /// * `expression` - Regex expression to search for
+    /// * `with_matches` - Print files with a match (`-l`) when true, files without one (`-L`) when false
+    /// * `parallel` - Whether to use parallel processing
+    /// * `chunk_size` - Chunk size for parallel processing
+    pub fn list_files_recursive(
+        &self,
+        out: &mut dyn Write,
+        dir: &Path,
+        expression: &str,
+        with_matches: bool,
+        parallel: bool,
+        chunk_size: usize,
+    ) -> Result<()> {
+        let (paths, _skipped) = self.collect_directory(out, dir)?;
+        self.list_paths(out, paths, expression, with_matches, parallel, chunk_size)
+    }
+
+    /// Print the paths of the listed files that do (or do not) contain a match
+    ///
+    /// The files are taken as given, like [`Self::search_list`], and each one is listed
+    /// like [`Self::list_files_by_glob`]. Paths that do not exist are warned about and left out.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_paths` - Files to check, in order
+    /// * `expression` - Regex expression to search for
+    /// * `with_matches` - Print files with a match (`-l`) when true, files without one (`-L`) when false
+    /// * `parallel` - Whether to use parallel processing
+    /// * `chunk_size` - Chunk size for parallel processing
+    pub fn list_files_by_list(
+        &self,
+        out: &mut dyn Write,
         file_paths: Vec<&str>,
-        expression: Option<&str>,
-        line_width: usize,
-        limit: usize,
-        separator: &str,
-        show_offset: bool,
+        expression: &str,
+        with_matches: bool,
         parallel: bool,
         chunk_size: usize,
-        global_limit: usize,
     ) -> Result<()> {
-        let mut total_processed = 0;
+        let paths = file_paths
+            .into_iter()
+            .map(PathBuf::from)
+            .filter(|path| {
+                let exists = path.exists();
+                if !exists {
+                    diagnostics::warning(format!("File {} does not exist, skipping", path.display()));
+                }
+                exists
+            })
+            .collect();
+        self.list_paths(out, paths, expression, with_matches, parallel, chunk_size)
+    }
 
-        for file_path in file_paths {
-            let path = Path::new(file_path);
+    /// Read the file list of `--files-from` from a file, or from stdin for `-`
+    ///
+    /// Paths are separated by newlines, or by NUL bytes with `null_separated` as written
+    /// by `find -print0`. Empty entries are ignored, and so is a `\r` ending a line.
+    pub fn read_file_list(source: &str, null_separated: bool) -> Result<Vec<String>> {
+        let mut content = Vec::new();
+        if source == "-" {
+            io::stdin().lock().read_to_end(&mut content)?;
+        } else {
+            File::open(source)?.read_to_end(&mut content)?;
+        }
 
-            // Skip if file doesn't exist or is a directory
-            if !path.exists() {
-                eprintln!("Warning: File {} does not exist, skipping", file_path);
+        let separator = if null_separated { b'\0' } else { b'\n' };
+        Ok(content
+            .split(|&byte| byte == separator)
+            .map(|entry| if null_separated { entry } else { entry.strip_suffix(b"\r").unwrap_or(entry) })
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| String::from_utf8_lossy(entry).into_owned())
+            .collect())
+    }
+
+    /// Print each of the given files that does (or does not) contain a match
+    ///
+    /// Files left out by the exclude, include or size filters are never listed, and
+    /// neither are files that fail to be read, which are handled like in [`Self::set_strict`].
+    fn list_paths(
+        &self,
+        out: &mut dyn Write,
+        paths: Vec<PathBuf>,
+        expression: &str,
+        with_matches: bool,
+        parallel: bool,
+        chunk_size: usize,
+    ) -> Result<()> {
+        let regex = RegexProcessor::compile_pattern(expression)?;
+        let mut processor = FileProcessor::new(self.config.clone());
+        let mut skipped = Vec::new();
+        let mut files_checked = 0;
+        let mut files_failed = 0;
+
+        for path in paths {
+            // Skip directories
+            if path.is_dir() {
                 continue;
             }
 
-            if path.is_dir() {
-                eprintln!("Warning: {} is a directory, skipping", file_path);
+            if let Some(reason) = self.skip_reason(&path) {
+                self.report_skipped(out, &path, reason, &mut skipped)?;
                 continue;
             }
 
-            println!("=== Processing: {} ===", path.display());
+            let found = match Self::file_contains_match(&mut processor, &path, &regex, parallel, chunk_size) {
+                Ok(found) => found,
+                Err(err) => {
+                    Self::report_failed(&path, err)?;
+                    files_failed += 1;
+                    continue;
+                }
+            };
+            files_checked += 1;
+            if found == with_matches {
+                writeln!(out, "{}", path.display())?;
+            }
+        }
 
-            let processed_count = self.process_single_file(
-                path,
-                expression,
-                line_width,
-                limit,
-                separator,
-                show_offset,
-                parallel,
-                chunk_size,
-            )?;
+        self.check_failures(files_failed, files_checked)
+    }
 
-            total_processed += processed_count;
+    /// Expand a glob pattern into its paths in the order set with [`Self::set_sort_order`]
+    fn sorted_paths(&self, pattern: &str) -> Result<Vec<PathBuf>> {
+        let mut paths = glob(pattern)?.collect::<std::result::Result<Vec<_>, _>>()?;
+        self.sort_paths(&mut paths);
+        Ok(paths)
+    }
 
-            // Check global limit
-            if global_limit > 0 && total_processed >= global_limit {
-                println!("=== Global limit of {} reached ===", global_limit);
-                break;
+    /// Sort paths in the order set with [`Self::set_sort_order`]
+    ///
+    /// Sizes and modification times are read once per path, and paths whose metadata
+    /// cannot be read sort as empty or oldest. Ties keep byte-wise path order.
+    fn sort_paths(&self, paths: &mut [PathBuf]) {
+        let path_bytes = |path: &PathBuf| path.as_os_str().as_encoded_bytes().to_vec();
+        match self.sort_order {
+            SortOrder::Name => {
+                paths.sort_by(|a, b| a.as_os_str().as_encoded_bytes().cmp(b.as_os_str().as_encoded_bytes()))
+            }
+            SortOrder::Size => paths.sort_by_cached_key(|path| {
+                (fs::metadata(path).map_or(0, |metadata| metadata.len()), path_bytes(path))
+            }),
+            SortOrder::Modified => paths.sort_by_cached_key(|path| {
+                let modified = fs::metadata(path).and_then(|metadata| metadata.modified());
+                (modified.unwrap_or(SystemTime::UNIX_EPOCH), path_bytes(path))
+            }),
+            SortOrder::None => {}
+        }
+        if self.sort_descending {
+            paths.reverse();
+        }
+    }
+
+    /// Text banners would corrupt structured output, so they are only printed for text formats
+    fn prints_banners(&self) -> bool {
+        !matches!(self.output_format, OutputFormat::Json | OutputFormat::Ndjson | OutputFormat::Msgpack)
+    }
+
+    /// Bytes written before a file's output: the comma separating its JSON matches from
+    /// those of earlier files, once any were written
+    fn file_separator(&self, wrote_output: bool) -> &'static [u8] {
+        if wrote_output && matches!(self.output_format, OutputFormat::Json) {
+            b","
+        } else {
+            b""
+        }
+    }
+
+    /// Decide whether a file is left out of the search, and why
+    ///
+    /// Errors other than a denied permission are left for the search itself to report.
+    fn skip_reason(&self, path: &Path) -> Option<SkipReason> {
+        // Excludes take precedence, and with include patterns a file must match one of them
+        if Self::matches_any(&self.exclude, path)
+            || (!self.include.is_empty() && !Self::matches_any(&self.include, path))
+        {
+            return Some(SkipReason::Excluded);
+        }
+
+        let metadata = match fs::metadata(path) {
+            Ok(metadata) => metadata,
+            Err(err) if err.kind() == ErrorKind::PermissionDenied => {
+                return Some(SkipReason::PermissionDenied);
+            }
+            Err(_) => return None,
+        };
+        if !metadata.is_file() {
+            return Some(SkipReason::NotRegular);
+        }
+        if metadata.len() > self.config.get_max_file_size() {
+            return Some(SkipReason::TooLarge);
+        }
+
+        match File::open(path) {
+            Err(err) if err.kind() == ErrorKind::PermissionDenied => Some(SkipReason::PermissionDenied),
+            _ => None,
+        }
+    }
+
+    /// Record a skipped file, streaming it right away as an NDJSON record or a stderr notice
+    ///
+    /// Files left out by `--exclude`/`--include` are info notices; any other skip is a warning.
+    fn report_skipped(&self, out: &mut dyn Write, path: &Path, reason: SkipReason, skipped: &mut Vec<SkippedFile>) -> Result<()> {
+        let record = SkippedFile {
+            path: path.display().to_string(),
+            reason,
+        };
+        let severity = match reason {
+            SkipReason::Excluded => Severity::Info,
+            _ => Severity::Warning,
+        };
+        let message = format!("{} skipped ({})", record.path, reason.as_str());
+
+        match self.output_format {
+            OutputFormat::Ndjson => {
+                let formatter = StructuredFormatter::new(OutputFormat::Ndjson);
+                formatter
+                    .output_skipped_ndjson(&record, out)
+                    .map_err(BingrepError::from)?;
+                out.flush()?;
+                diagnostics::record(severity, message);
             }
+            // Collected for the document written at the end of the run
+            OutputFormat::Json => diagnostics::record(severity, message),
+            _ => diagnostics::report(severity, message),
         }
 
-        println!("=== Total matches/lines processed: {} ===", total_processed);
+        skipped.push(record);
         Ok(())
     }
 
-    /// Process a single file and return the number of matches/lines processed
+    /// Print the totals of a multi-file search
+    ///
+    /// NDJSON ends with a `{"summary": ...}` record and JSON with a document holding the
+    /// skipped files and the summary; text formats print the usual banners.
+    fn finish_run(&self, out: &mut dyn Write, total_processed: usize, files_processed: usize, skipped: &[SkippedFile]) -> Result<()> {
+        let summary = MultiFileSummary {
+            files_processed,
+            files_skipped: skipped.len(),
+        };
+        let formatter = StructuredFormatter::new(self.output_format);
+
+        let written = match self.output_format {
+            OutputFormat::Ndjson => formatter.output_summary_ndjson(&summary, out),
+            OutputFormat::Json => formatter.end_multi_file_report_json(skipped, &summary, out),
+            OutputFormat::Msgpack => Ok(()),
+            _ => {
+                if !skipped.is_empty() {
+                    writeln!(out, "=== Skipped files: {} ===", skipped.len())?;
+                }
+                writeln!(out, "=== Total matches/lines processed: {} ===", total_processed)?;
+                Ok(())
+            }
+        };
+        written.map_err(BingrepError::from)?;
+        out.flush()?;
+        Ok(())
+    }
+
+    /// Check a single file for a match, stopping at the first hit
+    fn file_contains_match(
+        processor: &mut FileProcessor,
+        path: &Path,
+        regex: &Regex,
+        parallel: bool,
+        chunk_size: usize,
+    ) -> Result<bool> {
+        let mut file = File::open(path)?;
+        let file_size = file.metadata()?.len();
+
+        if parallel && file_size > chunk_size as u64 {
+            ParallelProcessor::contains_match(&mut file, regex, chunk_size, None, file_size)
+        } else {
+            processor.contains_match(&mut file, regex)
+        }
+    }
+
+    /// Process a list of specific files
+    ///
+    /// # Arguments
+    ///
+    /// * `file_paths` - Vector of file paths to process
+    /// * `expression` - Optional regex expression to search for
+    /// * `options` - Display settings, per-file and global limits and parallel processing
+    pub fn search_list(
+        &self,
+        out: &mut dyn Write,
+        file_paths: Vec<&str>,
+        expression: Option<&str>,
+        options: &SearchOptions,
+    ) -> Result<()> {
+        // Missing paths and directories are warned about up front and left out
+        let paths = file_paths
+            .into_iter()
+            .filter(|file_path| {
+                let path = Path::new(file_path);
+                if !path.exists() {
+                    diagnostics::warning(format!("File {} does not exist, skipping", file_path));
+                    return false;
+                }
+                if path.is_dir() {
+                    diagnostics::warning(format!("{} is a directory, skipping", file_path));
+                    return false;
+                }
+                true
+            })
+            .map(PathBuf::from)
+            .collect();
+
+        self.process_paths(out, paths, Vec::new(), expression, options)
+    }
+
+    /// Options for the next file, with its limit capped by what is left of the global limit
+    ///
+    /// Callers stop before the global limit is used up, so the cap is never 0 (unlimited).
+    fn file_options(options: &SearchOptions, total_processed: usize) -> SearchOptions {
+        let mut file_options = options.clone();
+        if options.global_limit > 0 {
+            let remaining = options.global_limit.saturating_sub(total_processed);
+            file_options.limit = match options.limit {
+                0 => remaining,
+                limit => limit.min(remaining),
+            };
+        }
+        file_options
+    }
+
+    /// Process a single file and return the number of matches/lines written
+    ///
+    /// `progress` is restarted for the file and advanced by both the sequential and parallel paths.
     fn process_single_file(
         &self,
+        out: &mut dyn Write,
         path: &Path,
         expression: Option<&str>,
-        line_width: usize,
-        limit: usize,
-        separator: &str,
-        show_offset: bool,
-        parallel: bool,
-        chunk_size: usize,
+        options: &SearchOptions,
+        progress: &mut ProgressIndicator,
     ) -> Result<usize> {
         let mut file = File::open(path)?;
         let file_size = file.metadata()?.len();
+        let mut counter = LineCounter::new(out);
+        progress.start_file(file_size);
+        // Structured records are produced by the sequential processor only
+        let parallel = options.parallel && self.prints_banners() && file_size > options.chunk_size as u64;
 
         if let Some(expr) = expression {
             // Regex search mode
             let regex = RegexProcessor::compile_pattern(expr)?;
-            let matches_before = Self::count_matches_in_output();
-
-            if parallel && file_size > chunk_size as u64 {
-                ParallelProcessor::process_file_parallel(
-                    &mut file,
-                    &regex,
-                    chunk_size,
-                    line_width,
-                    limit,
-                    separator,
-                    show_offset,
-                    file_size,
-                )?;
+
+            if parallel {
+                ParallelProcessor::search_file(&mut file, &mut counter, &regex, options, None, file_size, None, None, progress)?;
             } else {
-                let mut processor = FileProcessor::new(self.config.clone());
-                let mut progress = ProgressIndicator::disabled();
-                processor.process_stream_by_regex(
-                    &mut file,
-                    &regex,
-                    line_width,
-                    limit,
-                    separator,
-                    show_offset,
-                    &mut progress,
-                )?;
+                let mut processor = self.file_processor(path);
+                processor.grep_file(&mut file, &mut counter, &regex, options, progress)?;
             }
-
-            let matches_after = Self::count_matches_in_output();
-            Ok(matches_after - matches_before)
         } else {
             // Hex dump mode
-            let lines_before = Self::count_lines_in_output();
-
-            if parallel && file_size > chunk_size as u64 {
-                ParallelHexDump::process_file_parallel(
-                    &mut file,
-                    chunk_size,
-                    line_width,
-                    limit,
-                    separator,
-                    show_offset,
-                    file_size,
-                )?;
+            let dump_options = options.dump_options();
+            if parallel {
+                ParallelHexDump::dump_file(&mut file, &mut counter, &dump_options, file_size, progress)?;
             } else {
-                let mut processor = FileProcessor::new(self.config.clone());
-                let mut progress = ProgressIndicator::disabled();
-                processor.process_file_stream(
-                    &mut file,
-                    line_width,
-                    limit,
-                    separator,
-                    show_offset,
-                    file_size,
-                    &mut progress,
-                )?;
+                let mut processor = self.file_processor(path);
+                processor.dump_file(&mut file, &mut counter, &dump_options, file_size, progress)?;
             }
-
-            let lines_after = Self::count_lines_in_output();
-            Ok(lines_after - lines_before)
         }
-    }
 
-    /// Dummy function to count matches - in a real implementation,
-    /// this would capture output and count actual matches
-    fn count_matches_in_output() -> usize {
-        // This is a simplified implementation
-        // In practice, you'd want to capture stdout and count lines
-        0
+        // MessagePack output is binary, so its newline bytes are not lines
+        if matches!(self.output_format, OutputFormat::Msgpack) {
+            return Ok(0);
+        }
+        Ok(counter.lines)
     }
 
-    /// Dummy function to count lines - in a real implementation,
-    /// this would capture output and count actual lines
-    fn count_lines_in_output() -> usize {
-        // This is a simplified implementation
-        // In practice, you'd want to capture stdout and count lines
-        0
+    /// Sequential processor writing records for `path` in the run's output format
+    fn file_processor(&self, path: &Path) -> FileProcessor {
+        let mut processor = FileProcessor::new(self.config.clone());
+        processor.set_output_format(self.output_format);
+        processor.set_json_elements_only(matches!(self.output_format, OutputFormat::Json));
+        processor.set_source_name(&path.display().to_string());
+        processor
     }
 
     /// Process multiple files in parallel
     ///
-    /// This method processes multiple files concurrently using rayon
+    /// The files are searched on one thread per available core. Each file's output is
+    /// buffered and written in the order of `file_paths` as soon as the files before it
+    /// are done, so files never interleave. Only the banners and the file outputs are
+    /// written: missing paths, directories and skipped files are passed over silently.
+    pub fn search_files_parallel(
+        &self,
+        out: &mut dyn Write,
+        file_paths: Vec<&str>,
+        expression: Option<&str>,
+        options: &SearchOptions,
+    ) -> Result<()> {
+        let paths: Vec<PathBuf> = file_paths
+            .into_iter()
+            .map(PathBuf::from)
+            .filter(|path| path.exists())
+            .collect();
+        let jobs = thread::available_parallelism().map_or(1, usize::from);
+
+        self.search_in_order(&paths, jobs, expression, options, |_, outcome| match outcome {
+            FileOutcome::Searched { output, .. } => Ok(out.write_all(&output)?),
+            FileOutcome::Failed { error, .. } => Err(error),
+            FileOutcome::Directory | FileOutcome::Skipped(_) => Ok(()),
+        })
+    }
+}
+
+/// Positional-argument forms of the search methods, kept for existing callers
+impl MultiFileProcessor {
+    /// Search files matching a glob pattern; see [`MultiFileProcessor::search_glob`]
+    #[deprecated(note = "use `MultiFileProcessor::search_glob` with `SearchOptions`")]
+    pub fn process_files_by_glob(
+        &self,
+        out: &mut dyn Write,
+        pattern: &str,
+        expression: Option<&str>,
+        line_width: usize,
+        limit: usize,
+        separator: &str,
+        show_offset: bool,
+        align: Option<usize>,
+        parallel: bool,
+        chunk_size: usize,
+        global_limit: usize,
+    ) -> Result<()> {
+        let options = SearchOptions::new()
+            .width(line_width)
+            .limit(limit)
+            .separator(separator)
+            .show_offset(show_offset)
+            .align(align)
+            .parallel(parallel)
+            .chunk_size(chunk_size)
+            .global_limit(global_limit);
+        self.search_glob(out, pattern, expression, &options)
+    }
+
+    /// Search every file below a directory; see [`MultiFileProcessor::search_directory`]
+    #[deprecated(note = "use `MultiFileProcessor::search_directory` with `SearchOptions`")]
+    pub fn process_directory_recursive(
+        &self,
+        out: &mut dyn Write,
+        dir: &Path,
+        expression: Option<&str>,
+        line_width: usize,
+        limit: usize,
+        separator: &str,
+        show_offset: bool,
+        align: Option<usize>,
+        parallel: bool,
+        chunk_size: usize,
+        global_limit: usize,
+    ) -> Result<()> {
+        let options = SearchOptions::new()
+            .width(line_width)
+            .limit(limit)
+            .separator(separator)
+            .show_offset(show_offset)
+            .align(align)
+            .parallel(parallel)
+            .chunk_size(chunk_size)
+            .global_limit(global_limit);
+        self.search_directory(out, dir, expression, &options)
+    }
+
+    /// Search a list of files; see [`MultiFileProcessor::search_list`]
+    #[deprecated(note = "use `MultiFileProcessor::search_list` with `SearchOptions`")]
+    pub fn process_files_by_list(
+        &self,
+        out: &mut dyn Write,
+        file_paths: Vec<&str>,
+        expression: Option<&str>,
+        line_width: usize,
+        limit: usize,
+        separator: &str,
+        show_offset: bool,
+        align: Option<usize>,
+        parallel: bool,
+        chunk_size: usize,
+        global_limit: usize,
+    ) -> Result<()> {
+        let options = SearchOptions::new()
+            .width(line_width)
+            .limit(limit)
+            .separator(separator)
+            .show_offset(show_offset)
+            .align(align)
+            .parallel(parallel)
+            .chunk_size(chunk_size)
+            .global_limit(global_limit);
+        self.search_list(out, file_paths, expression, &options)
+    }
+
+    /// Search files concurrently; see [`MultiFileProcessor::search_files_parallel`]
+    #[deprecated(note = "use `MultiFileProcessor::search_files_parallel` with `SearchOptions`")]
     pub fn process_files_parallel(
         &self,
+        out: &mut dyn Write,
         file_paths: Vec<&str>,
         expression: Option<&str>,
         line_width: usize,
         limit: usize,
         separator: &str,
         show_offset: bool,
+        align: Option<usize>,
         parallel_processing: bool,
         chunk_size: usize,
     ) -> Result<()> {
-        use rayon::prelude::*;
+        let options = SearchOptions::new()
+            .width(line_width)
+            .limit(limit)
+            .separator(separator)
+            .show_offset(show_offset)
+            .align(align)
+            .parallel(parallel_processing)
+            .chunk_size(chunk_size);
+        self.search_files_parallel(out, file_paths, expression, &options)
+    }
+}
 
-        let results: Vec<Result<()>> = file_paths
-            .par_iter()
-            .map(|file_path| {
-                let path = Path::new(file_path);
+/// Result of searching one file on a worker thread, handed over in path order
+enum FileOutcome {
+    /// A directory, passed over without a notice like in a sequential run
+    Directory,
+    /// A file left out of the search, reported when its turn comes
+    Skipped(SkipReason),
+    /// The buffered output of a searched file
+    Searched {
+        output: Vec<u8>,
+        /// Matches/lines written, not counting the banner
+        processed: usize,
+        file_size: u64,
+    },
+    /// A file whose search failed midway, with the output written before the failure
+    Failed { output: Vec<u8>, error: BingrepError },
+}
 
-                if !path.exists() || path.is_dir() {
-                    return Ok(());
-                }
+/// Writer that writes a separator just before the first bytes written through it
+struct SeparatedWriter<'a> {
+    inner: &'a mut dyn Write,
+    separator: &'static [u8],
+    /// Whether anything has been written, and with it the separator
+    started: bool,
+}
 
-                println!("=== Processing: {} ===", path.display());
-
-                self.process_single_file(
-                    path,
-                    expression,
-                    line_width,
-                    limit,
-                    separator,
-                    show_offset,
-                    parallel_processing,
-                    chunk_size,
-                )
-                .map(|_| ())
-            })
-            .collect();
+impl<'a> SeparatedWriter<'a> {
+    fn new(inner: &'a mut dyn Write, separator: &'static [u8]) -> Self {
+        Self {
+            inner,
+            separator,
+            started: false,
+        }
+    }
+}
 
-        // Check for any errors
-        for result in results {
-            result?;
+impl Write for SeparatedWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if !self.started && !buf.is_empty() {
+            self.inner.write_all(self.separator)?;
+            self.started = true;
         }
+        self.inner.write(buf)
+    }
 
-        Ok(())
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Writer that counts the lines written through it
+struct LineCounter<'a> {
+    inner: &'a mut dyn Write,
+    lines: usize,
+}
+
+impl<'a> LineCounter<'a> {
+    fn new(inner: &'a mut dyn Write) -> Self {
+        Self { inner, lines: 0 }
+    }
+}
+
+impl Write for LineCounter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.lines += buf[..written].iter().filter(|&&byte| byte == b'\n').count();
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
     }
 }
@@ -0,0 +1,299 @@
+//! Per-run search and dump settings
+//!
+//! The display settings shared by the sequential, parallel and multi-file paths are
+//! grouped here so that a new setting only changes these structs, not every signature
+//! and call site. Both types start from the CLI defaults and are adjusted with
+//! chainable setters named after their fields.
+
+use crate::output::HexCase;
+
+/// Default chunk size for parallel processing (16MB)
+const DEFAULT_CHUNK_SIZE: usize = 16 * 1024 * 1024;
+
+/// Settings for a regex search
+///
+/// # Examples
+///
+/// ```
+/// use hxgrep::SearchOptions;
+///
+/// let options = SearchOptions::new().width(8).limit(10).separator("");
+/// assert_eq!(options.width, 8);
+/// assert_eq!(options.dump_options().separator, "");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchOptions {
+    /// Number of bytes displayed per match (0 to display exactly the matched bytes)
+    pub width: usize,
+    /// Maximum number of matches per file (0 for unlimited)
+    pub limit: usize,
+    /// String separating hex bytes
+    pub separator: String,
+    /// Number of bytes written together between separators
+    pub group_size: usize,
+    /// Letter case of the hex digits
+    pub hex_case: HexCase,
+    /// Whether to display offset values
+    pub show_offset: bool,
+    /// Whether hex dumps replace runs of all-zero lines with a single `*` line
+    pub skip_zeros: bool,
+    /// Boundary to align the displayed start offset to
+    pub align: Option<usize>,
+    /// Byte whose trailing run is trimmed from displayed and extracted match windows
+    pub trim_trailing: Option<u8>,
+    /// Whether to search large files in parallel chunks
+    pub parallel: bool,
+    /// Chunk size for parallel processing
+    pub chunk_size: usize,
+    /// Maximum number of matches across all files (0 for unlimited)
+    pub global_limit: usize,
+    /// Whether each match is printed as its decimal start offset only
+    pub offsets_only: bool,
+    /// Whether offsets printed with `offsets_only` end in NUL instead of a newline
+    pub null_terminated: bool,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            width: 16,
+            limit: 0,
+            separator: " ".to_string(),
+            group_size: 1,
+            hex_case: HexCase::Upper,
+            show_offset: true,
+            skip_zeros: false,
+            align: None,
+            trim_trailing: None,
+            parallel: false,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            global_limit: 0,
+            offsets_only: false,
+            null_terminated: false,
+        }
+    }
+}
+
+impl SearchOptions {
+    /// Create options with the CLI defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the number of bytes displayed per match (0 for the matched bytes)
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Set the maximum number of matches per file (0 for unlimited)
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Set the string separating hex bytes
+    pub fn separator(mut self, separator: &str) -> Self {
+        self.separator = separator.to_string();
+        self
+    }
+
+    /// Set the number of bytes written together between separators
+    pub fn group_size(mut self, group_size: usize) -> Self {
+        self.group_size = group_size;
+        self
+    }
+
+    /// Set the letter case of the hex digits
+    pub fn hex_case(mut self, hex_case: HexCase) -> Self {
+        self.hex_case = hex_case;
+        self
+    }
+
+    /// Set whether offset values are displayed
+    pub fn show_offset(mut self, show_offset: bool) -> Self {
+        self.show_offset = show_offset;
+        self
+    }
+
+    /// Set whether hex dumps replace runs of all-zero lines with a single `*` line
+    pub fn skip_zeros(mut self, skip_zeros: bool) -> Self {
+        self.skip_zeros = skip_zeros;
+        self
+    }
+
+    /// Set the boundary the displayed start offset is aligned to
+    pub fn align(mut self, align: Option<usize>) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Set the byte whose trailing run is trimmed from displayed and extracted match windows
+    pub fn trim_trailing(mut self, trim_trailing: Option<u8>) -> Self {
+        self.trim_trailing = trim_trailing;
+        self
+    }
+
+    /// Set whether large files are searched in parallel chunks
+    pub fn parallel(mut self, parallel: bool) -> Self {
+        self.parallel = parallel;
+        self
+    }
+
+    /// Set the chunk size for parallel processing
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    /// Set the maximum number of matches across all files (0 for unlimited)
+    pub fn global_limit(mut self, global_limit: usize) -> Self {
+        self.global_limit = global_limit;
+        self
+    }
+
+    /// Set whether each match is printed as its decimal start offset only
+    pub fn offsets_only(mut self, offsets_only: bool) -> Self {
+        self.offsets_only = offsets_only;
+        self
+    }
+
+    /// Set whether offsets printed with `offsets_only` end in NUL instead of a newline
+    pub fn null_terminated(mut self, null_terminated: bool) -> Self {
+        self.null_terminated = null_terminated;
+        self
+    }
+
+    /// Settings for hex dumping with the same display options
+    ///
+    /// `width` and `limit` then count bytes per line and lines.
+    pub fn dump_options(&self) -> DumpOptions {
+        DumpOptions {
+            width: self.width,
+            limit: self.limit,
+            separator: self.separator.clone(),
+            group_size: self.group_size,
+            hex_case: self.hex_case,
+            show_offset: self.show_offset,
+            skip_zeros: self.skip_zeros,
+            chunk_size: self.chunk_size,
+        }
+    }
+}
+
+/// Settings for a hex dump
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DumpOptions {
+    /// Number of bytes displayed per line
+    pub width: usize,
+    /// Maximum number of lines (0 for unlimited)
+    pub limit: usize,
+    /// String separating hex bytes
+    pub separator: String,
+    /// Number of bytes written together between separators
+    pub group_size: usize,
+    /// Letter case of the hex digits
+    pub hex_case: HexCase,
+    /// Whether to display offset values
+    pub show_offset: bool,
+    /// Whether runs of all-zero lines are replaced with a single `*` line
+    pub skip_zeros: bool,
+    /// Chunk size for parallel processing
+    pub chunk_size: usize,
+}
+
+impl Default for DumpOptions {
+    fn default() -> Self {
+        SearchOptions::default().dump_options()
+    }
+}
+
+impl DumpOptions {
+    /// Create options with the CLI defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the number of bytes displayed per line
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Set the maximum number of lines (0 for unlimited)
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Set the string separating hex bytes
+    pub fn separator(mut self, separator: &str) -> Self {
+        self.separator = separator.to_string();
+        self
+    }
+
+    /// Set the number of bytes written together between separators
+    pub fn group_size(mut self, group_size: usize) -> Self {
+        self.group_size = group_size;
+        self
+    }
+
+    /// Set the letter case of the hex digits
+    pub fn hex_case(mut self, hex_case: HexCase) -> Self {
+        self.hex_case = hex_case;
+        self
+    }
+
+    /// Set whether offset values are displayed
+    pub fn show_offset(mut self, show_offset: bool) -> Self {
+        self.show_offset = show_offset;
+        self
+    }
+
+    /// Set whether runs of all-zero lines are replaced with a single `*` line
+    pub fn skip_zeros(mut self, skip_zeros: bool) -> Self {
+        self.skip_zeros = skip_zeros;
+        self
+    }
+
+    /// Set the chunk size for parallel processing
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dump_options_keep_display_settings() {
+        let options = SearchOptions::new()
+            .width(8)
+            .limit(3)
+            .separator(":")
+            .group_size(2)
+            .hex_case(HexCase::Lower)
+            .show_offset(false)
+            .skip_zeros(true)
+            .align(Some(4))
+            .trim_trailing(Some(0xFF))
+            .chunk_size(1024)
+            .global_limit(10);
+
+        assert_eq!(
+            options.dump_options(),
+            DumpOptions::new()
+                .width(8)
+                .limit(3)
+                .separator(":")
+                .group_size(2)
+                .hex_case(HexCase::Lower)
+                .show_offset(false)
+                .skip_zeros(true)
+                .chunk_size(1024)
+        );
+        assert_eq!(DumpOptions::default().width, SearchOptions::default().width);
+    }
+}
@@ -1,42 +1,151 @@
 /// Utilities for formatting binary data as hexadecimal output
 use colored::*;
-use crate::cli::ColorChoice;
-use std::io::IsTerminal;
+use crate::cli::{ColorChoice, OdRadix};
+use crate::color_context::{self, ColorStream, ColorTheme};
+use std::io::{self, Write};
+use std::ops::Range;
 
 pub struct OutputFormatter;
 
+/// Letter case of the hex digits of formatted bytes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HexCase {
+    /// `AB CD`
+    #[default]
+    Upper,
+    /// `ab cd`, as written by Wireshark and most PCAP tools
+    Lower,
+}
+
+impl HexCase {
+    /// Format one byte as two hex digits
+    pub fn format_byte(self, byte: u8) -> String {
+        match self {
+            HexCase::Upper => format!("{:02X}", byte),
+            HexCase::Lower => format!("{:02x}", byte),
+        }
+    }
+}
+
+/// Number of bytes per line in `xxd` compatible output
+pub const XXD_LINE_WIDTH: usize = 16;
+
+/// Number of bytes per line in `od -tx1` compatible output
+pub const OD_LINE_WIDTH: usize = 16;
+
+/// Line standing for a run of omitted all-zero lines, as in `hexdump`
+pub const ZERO_RUN_MARKER: &str = "*";
+
+/// Width of the hex column of a full `xxd` line: 8 groups of 4 digits and 7 spaces
+const XXD_HEX_WIDTH: usize = XXD_LINE_WIDTH / 2 * 5 - 1;
+
 impl OutputFormatter {
     /// Format bytes as hexadecimal string with given separator
     pub fn format_bytes_as_hex(bytes: &[u8], separator: &str) -> String {
-        bytes
-            .iter()
-            .map(|b| format!("{:02X}", b))
+        Self::format_bytes_grouped(bytes, separator, 1, HexCase::Upper)
+    }
+
+    /// Format bytes as lowercase hexadecimal string with given separator
+    pub fn format_bytes_as_hex_lower(bytes: &[u8], separator: &str) -> String {
+        Self::format_bytes_grouped(bytes, separator, 1, HexCase::Lower)
+    }
+
+    /// Format bytes as hexadecimal string in blocks of `group_size` bytes, like `xxd -g`
+    ///
+    /// The bytes of a block are written without a gap and blocks are joined with the
+    /// separator, so a group size of 4 gives `DEADBEEF CAFEBABE`. A group size of 0 or 1
+    /// separates every byte.
+    pub fn format_bytes_grouped(bytes: &[u8], separator: &str, group_size: usize, case: HexCase) -> String {
+        Self::join_hex_cells(bytes.iter().map(|&b| case.format_byte(b)).collect(), separator, group_size)
+    }
+
+    /// Join two-character cells (hex bytes or `..` placeholders) into blocks of `group_size`
+    fn join_hex_cells(cells: Vec<String>, separator: &str, group_size: usize) -> String {
+        cells
+            .chunks(group_size.max(1))
+            .map(|group| group.concat())
             .collect::<Vec<_>>()
             .join(separator)
     }
 
+    /// Split hex data back into its bytes, whether they are separated or grouped
+    ///
+    /// Returns `None` if any block is not made of two-digit hex bytes, such as the `..`
+    /// alignment placeholders or text columns appended to the hex data.
+    pub fn parse_hex_bytes(hex_data: &str) -> Option<Vec<u8>> {
+        let mut bytes = Vec::new();
+        for block in hex_data.split_whitespace() {
+            if block.len() % 2 != 0 || !block.is_ascii() {
+                return None;
+            }
+            for cell in block.as_bytes().chunks(2) {
+                let cell = std::str::from_utf8(cell).ok()?;
+                bytes.push(u8::from_str_radix(cell, 16).ok()?);
+            }
+        }
+        Some(bytes)
+    }
+
     /// Format offset with proper padding based on file size
     pub fn format_offset(offset: u64, hex_offset_length: usize) -> String {
         format!("{:0width$X}h", offset, width = hex_offset_length)
     }
 
+    /// Write a match's start offset in decimal, as `-s` accepts it, followed by NUL or a newline
+    pub fn write_match_offset(out: &mut dyn Write, offset: u64, null_terminated: bool) -> io::Result<()> {
+        let terminator = if null_terminated { '\0' } else { '\n' };
+        write!(out, "{}{}", offset, terminator)
+    }
+
     /// Calculate the number of digits needed for hex offset display
     pub fn calculate_hex_offset_length(file_size: u64) -> usize {
         format!("{:X}", file_size).len()
     }
 
-    /// Print a line with optional offset
-    pub fn print_line(offset: u64, hex_data: &str, show_offset: bool, hex_offset_length: usize) {
-        Self::print_line_with_silent(offset, hex_data, show_offset, hex_offset_length, false);
+    /// Format bytes as hex so that the displayed line starts at an aligned offset
+    ///
+    /// Rounds `offset` down to the nearest multiple of `align` (a power of 2) and prepends
+    /// `..` placeholders for the leading bytes that were not read. Blocks of `group_size`
+    /// bytes count from the aligned offset, placeholders included.
+    /// Returns the aligned offset, the padded hex string and the number of placeholder bytes.
+    pub fn align_bytes_as_hex(
+        offset: u64,
+        bytes: &[u8],
+        separator: &str,
+        group_size: usize,
+        case: HexCase,
+        align: usize,
+    ) -> (u64, String, usize) {
+        let aligned_offset = offset & !(align.max(1) as u64 - 1);
+        let lead = (offset - aligned_offset) as usize;
+
+        let cells = std::iter::repeat_n("..".to_string(), lead)
+            .chain(bytes.iter().map(|&b| case.format_byte(b)))
+            .collect();
+
+        (aligned_offset, Self::join_hex_cells(cells, separator, group_size), lead)
     }
 
-    /// Print a line with optional offset and silent mode
-    pub fn print_line_with_silent(offset: u64, hex_data: &str, show_offset: bool, hex_offset_length: usize, silent: bool) {
+    /// Write a line with optional offset
+    pub fn write_line(out: &mut dyn Write, offset: u64, hex_data: &str, show_offset: bool, hex_offset_length: usize) -> io::Result<()> {
+        Self::write_line_with_silent(out, offset, hex_data, show_offset, hex_offset_length, false)
+    }
+
+    /// Write a line with optional offset and silent mode
+    pub fn write_line_with_silent(
+        out: &mut dyn Write,
+        offset: u64,
+        hex_data: &str,
+        show_offset: bool,
+        hex_offset_length: usize,
+        silent: bool,
+    ) -> io::Result<()> {
         if silent {
-            return; // Skip output when in silent mode
+            return Ok(()); // Skip output when in silent mode
         }
 
-        Self::print_line_with_color(
+        Self::write_line_with_color(
+            out,
             offset,
             hex_data,
             show_offset,
@@ -45,8 +154,9 @@ impl OutputFormatter {
         )
     }
 
-    /// Print a line with optional offset and color support, with match highlighting
-    pub fn print_line_with_match_highlight(
+    /// Write a line with optional offset and color support, with match highlighting
+    pub fn write_line_with_match_highlight(
+        out: &mut dyn Write,
         offset: u64,
         hex_data: &str,
         show_offset: bool,
@@ -54,12 +164,13 @@ impl OutputFormatter {
         color_choice: &ColorChoice,
         match_start: Option<usize>,
         match_length: Option<usize>,
-    ) {
-        Self::print_line_with_match_highlight_silent(offset, hex_data, show_offset, hex_offset_length, color_choice, match_start, match_length, false);
+    ) -> io::Result<()> {
+        Self::write_line_with_match_highlight_silent(out, offset, hex_data, show_offset, hex_offset_length, color_choice, match_start, match_length, false)
     }
 
-    /// Print a line with optional offset and color support, with match highlighting and silent mode
-    pub fn print_line_with_match_highlight_silent(
+    /// Write a line with optional offset and color support, with match highlighting and silent mode
+    pub fn write_line_with_match_highlight_silent(
+        out: &mut dyn Write,
         offset: u64,
         hex_data: &str,
         show_offset: bool,
@@ -68,47 +179,71 @@ impl OutputFormatter {
         match_start: Option<usize>,
         match_length: Option<usize>,
         silent: bool,
-    ) {
+    ) -> io::Result<()> {
         if silent {
-            return; // Skip output when in silent mode
+            return Ok(()); // Skip output when in silent mode
         }
 
-        let should_use_color = match color_choice {
-            ColorChoice::Always => true,
-            ColorChoice::Never => false,
-            ColorChoice::Auto => std::io::stdout().is_terminal(),
-        };
+        writeln!(
+            out,
+            "{}",
+            Self::format_line_with_match_highlight(
+                offset,
+                hex_data,
+                show_offset,
+                hex_offset_length,
+                Self::should_use_color(color_choice),
+                match_start,
+                match_length,
+            )
+        )
+    }
+
+    /// Decide whether output should be colored for the given choice
+    pub fn should_use_color(color_choice: &ColorChoice) -> bool {
+        crate::color_context::color_enabled(color_choice, ColorStream::Stdout)
+    }
 
+    /// Format a line with optional offset and match highlighting
+    pub fn format_line_with_match_highlight(
+        offset: u64,
+        hex_data: &str,
+        show_offset: bool,
+        hex_offset_length: usize,
+        use_color: bool,
+        match_start: Option<usize>,
+        match_length: Option<usize>,
+    ) -> String {
         if show_offset {
             let offset_str = Self::format_offset(offset, hex_offset_length);
 
-            if should_use_color {
-                println!(
+            if use_color {
+                format!(
                     "{} : {}",
                     offset_str.cyan().bold(),
-                    Self::colorize_hex_data_with_match(hex_data, match_start, match_length)
-                );
+                    Self::colorize_hex_data_with_match(hex_data, match_start, match_length, color_context::color_theme())
+                )
             } else {
-                println!("{} : {}", offset_str, hex_data);
+                format!("{} : {}", offset_str, hex_data)
             }
+        } else if use_color {
+            Self::colorize_hex_data_with_match(hex_data, match_start, match_length, color_context::color_theme())
         } else {
-            if should_use_color {
-                println!("{}", Self::colorize_hex_data_with_match(hex_data, match_start, match_length));
-            } else {
-                println!("{}", hex_data);
-            }
+            hex_data.to_string()
         }
     }
 
-    /// Print a line with optional offset and color support
-    pub fn print_line_with_color(
+    /// Write a line with optional offset and color support
+    pub fn write_line_with_color(
+        out: &mut dyn Write,
         offset: u64,
         hex_data: &str,
         show_offset: bool,
         hex_offset_length: usize,
         color_choice: &ColorChoice
-    ) {
-        Self::print_line_with_match_highlight(
+    ) -> io::Result<()> {
+        Self::write_line_with_match_highlight(
+            out,
             offset,
             hex_data,
             show_offset,
@@ -116,62 +251,168 @@ impl OutputFormatter {
             color_choice,
             None,
             None,
-        );
+        )
     }
 
     /// Apply colors to hex data with match highlighting
+    ///
+    /// Bytes are counted in two-character cells, so a block of grouped bytes is
+    /// highlighted byte by byte. Counting stops at the first block that is not made of
+    /// cells, such as the `|` before an appended text column. Matched bytes take the
+    /// theme's highlight color; the others are colored by category if the theme asks for it.
     fn colorize_hex_data_with_match(
         hex_data: &str,
         match_start: Option<usize>,
         match_length: Option<usize>,
+        theme: &ColorTheme,
     ) -> String {
-        let bytes: Vec<&str> = hex_data.split_whitespace().collect();
+        let mut index = 0;
+        let mut in_hex = true;
 
-        bytes
-            .iter()
-            .enumerate()
-            .map(|(i, byte)| {
-                // Check if this byte is part of a match
-                let is_match = if let (Some(start), Some(len)) = (match_start, match_length) {
-                    i >= start && i < start + len
-                } else {
-                    false
-                };
-
-                if is_match {
-                    // Highlight matches with dark red color
-                    byte.red().bold().to_string()
-                } else {
-                    // No color for non-matched bytes
-                    byte.to_string()
+        hex_data
+            .split_whitespace()
+            .map(|block| {
+                in_hex = in_hex && Self::is_hex_cells(block);
+                if !in_hex {
+                    return block.to_string();
                 }
+
+                block
+                    .as_bytes()
+                    .chunks(2)
+                    .map(|cell| {
+                        let cell = std::str::from_utf8(cell).unwrap_or_default();
+                        // Check if this byte is part of a match
+                        let is_match = if let (Some(start), Some(len)) = (match_start, match_length) {
+                            index >= start && index < start + len
+                        } else {
+                            false
+                        };
+                        index += 1;
+
+                        if is_match {
+                            cell.color(theme.highlight).bold().to_string()
+                        } else if theme.byte_categories {
+                            Self::colorize_byte_category(cell)
+                        } else {
+                            cell.to_string()
+                        }
+                    })
+                    .collect::<String>()
             })
             .collect::<Vec<_>>()
             .join(" ")
     }
 
-    /// Apply colors to hex data
-    #[allow(dead_code)]
-    fn colorize_hex_data(hex_data: &str) -> String {
-        hex_data
-            .split_whitespace()
-            .map(|byte| {
-                match u8::from_str_radix(byte, 16) {
-                    Ok(b) => match b {
-                        0x00 => byte.bright_black().to_string(),                    // NULL bytes - dark gray
-                        0x20..=0x7E => byte.green().to_string(),                    // Printable ASCII - green
-                        0xFF => byte.bright_red().bold().to_string(),               // 0xFF - bright red
-                        0x01..=0x1F | 0x7F..=0x9F => byte.yellow().to_string(),    // Control characters - yellow
-                        _ => byte.blue().to_string(),                               // Other bytes - blue
-                    },
-                    Err(_) => byte.to_string(), // Fallback for non-hex data
-                }
-            })
+    /// Whether a block consists of two-character hex bytes or `..` placeholders
+    fn is_hex_cells(block: &str) -> bool {
+        block.len() % 2 == 0
+            && block
+                .as_bytes()
+                .chunks(2)
+                .all(|cell| cell == b".." || cell.iter().all(u8::is_ascii_hexdigit))
+    }
+
+    /// Color one hex byte cell by the category of its value
+    fn colorize_byte_category(cell: &str) -> String {
+        match u8::from_str_radix(cell, 16) {
+            Ok(b) => match b {
+                0x00 => cell.bright_black().to_string(),                    // NULL bytes - dark gray
+                0x20..=0x7E => cell.green().to_string(),                    // Printable ASCII - green
+                0xFF => cell.bright_red().bold().to_string(),               // 0xFF - bright red
+                0x01..=0x1F | 0x7F..=0x9F => cell.yellow().to_string(),    // Control characters - yellow
+                _ => cell.blue().to_string(),                               // Other bytes - blue
+            },
+            Err(_) => cell.to_string(), // `..` placeholders
+        }
+    }
+
+    /// Format a line exactly like `xxd`'s default output
+    ///
+    /// `00000000: 4865 6c6c 6f20 576f 726c  Hello Worl` — lowercase offset of at least 8
+    /// digits, lowercase hex in groups of 2 bytes padded to the width of a 16-byte line,
+    /// two spaces, then the ASCII column with `.` for unprintable bytes.
+    pub fn format_line_xxd(offset: u64, bytes: &[u8]) -> String {
+        let hex: String = bytes
+            .chunks(2)
+            .map(|group| group.iter().map(|b| format!("{:02x}", b)).collect::<String>())
             .collect::<Vec<_>>()
-            .join(" ")
+            .join(" ");
+        let ascii: String = bytes
+            .iter()
+            .map(|&b| if (0x20..=0x7E).contains(&b) { b as char } else { '.' })
+            .collect();
+
+        format!("{:08x}: {:<width$}  {}", offset, hex, ascii, width = XXD_HEX_WIDTH)
+    }
+
+    /// Format a line like `od -tx1 -v`
+    ///
+    /// Each byte is a space followed by two lowercase hex digits. With an offset the line
+    /// starts with it in the given radix (`od -A`), otherwise it matches `od -An`.
+    pub fn format_line_od(offset: Option<u64>, radix: OdRadix, bytes: &[u8]) -> String {
+        let hex: String = bytes.iter().map(|b| format!(" {:02x}", b)).collect();
+        match offset {
+            Some(offset) => format!("{}{}", Self::format_od_offset(offset, radix), hex),
+            None => hex,
+        }
+    }
+
+    /// Format an offset like `od -A`: 7 octal or decimal digits, or 6 hex digits
+    ///
+    /// `od` also prints the end offset on its own as the last line of a dump.
+    pub fn format_od_offset(offset: u64, radix: OdRadix) -> String {
+        match radix {
+            OdRadix::O => format!("{:07o}", offset),
+            OdRadix::D => format!("{:07}", offset),
+            OdRadix::X => format!("{:06x}", offset),
+        }
+    }
+
+    /// Append the bytes decoded with the `--charset` table as a text column, if one was selected
+    pub fn append_charset_column(hex_data: &str, bytes: &[u8]) -> String {
+        match crate::charset::get_charset() {
+            Some(charset) => format!("{} | {}", hex_data, charset.decode(bytes)),
+            None => hex_data.to_string(),
+        }
+    }
+
+    /// Append the Shannon entropy of a line to its hex data (2 decimal places)
+    pub fn append_entropy(hex_data: &str, entropy: f64) -> String {
+        format!("{} | H={:.2}", hex_data, entropy)
     }
 
-    /// Format a line with offset (returns a string instead of printing)
+    /// Trim the trailing run of `byte` from a match window, keeping at least its first byte
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hxgrep::output::OutputFormatter;
+    ///
+    /// assert_eq!(OutputFormatter::trim_trailing(&[0x4D, 0x5A, 0xFF, 0xFF], 0xFF), &[0x4D, 0x5A]);
+    /// assert_eq!(OutputFormatter::trim_trailing(&[0xFF, 0xFF], 0xFF), &[0xFF]);
+    /// ```
+    pub fn trim_trailing(bytes: &[u8], byte: u8) -> &[u8] {
+        let kept = bytes.iter().rposition(|&b| b != byte).map_or(1, |last| last + 1);
+        &bytes[..kept.min(bytes.len())]
+    }
+
+    /// Append the number of trimmed trailing bytes and their value to hex data
+    pub fn append_trimmed(hex_data: &str, trimmed: usize, byte: u8, case: HexCase) -> String {
+        format!("{} | +{}*{}", hex_data, trimmed, case.format_byte(byte))
+    }
+
+    /// Append the 1-based number of the `-e` pattern that matched to its hex data
+    pub fn append_pattern_index(hex_data: &str, index: usize) -> String {
+        format!("{} | #{}", hex_data, index + 1)
+    }
+
+    /// Append the approximate compressed range of a match in a decompressed gzip stream
+    pub fn append_compressed_range(hex_data: &str, range: &Range<u64>) -> String {
+        format!("{} | gz {:X}h-{:X}h", hex_data, range.start, range.end)
+    }
+
+    /// Format a line with offset (returns a string instead of writing it)
     pub fn format_line_with_offset(
         offset: u64,
         hex_data: &str,
@@ -216,10 +457,131 @@ mod tests {
         assert_eq!(result, "001234h");
     }
 
+    #[test]
+    fn test_format_bytes_as_hex_case() {
+        let bytes = [0xAB, 0xCD];
+        assert_eq!(OutputFormatter::format_bytes_as_hex(&bytes, " "), "AB CD");
+        assert_eq!(OutputFormatter::format_bytes_as_hex_lower(&bytes, " "), "ab cd");
+        assert_eq!(OutputFormatter::format_bytes_grouped(&bytes, " ", 2, HexCase::Lower), "abcd");
+        assert_eq!(OutputFormatter::parse_hex_bytes("ab cd"), Some(bytes.to_vec()));
+    }
+
+    #[test]
+    fn test_format_bytes_grouped() {
+        let bytes = [0xDE, 0xAD, 0xBE, 0xEF, 0xCA, 0xFE, 0xBA, 0xBE, 0x01, 0x02];
+
+        let grouped = OutputFormatter::format_bytes_grouped(&bytes, " ", 4, HexCase::Upper);
+        assert_eq!(grouped, "DEADBEEF CAFEBABE 0102");
+        assert_eq!(OutputFormatter::format_bytes_grouped(&bytes[..4], "-", 2, HexCase::Upper), "DEAD-BEEF");
+        assert_eq!(OutputFormatter::format_bytes_grouped(&bytes[..3], " ", 1, HexCase::Upper), "DE AD BE");
+
+        assert_eq!(OutputFormatter::parse_hex_bytes(&grouped), Some(bytes.to_vec()));
+        assert_eq!(OutputFormatter::parse_hex_bytes(".. 4D 5A"), None);
+    }
+
+    #[test]
+    fn test_align_bytes_as_hex() {
+        let (offset, hex, lead) = OutputFormatter::align_bytes_as_hex(0x123, b"MZ", " ", 1, HexCase::Upper, 16);
+        assert_eq!(offset, 0x120);
+        assert_eq!(hex, ".. .. .. 4D 5A");
+        assert_eq!(lead, 3);
+
+        let (offset, hex, lead) = OutputFormatter::align_bytes_as_hex(0x200, b"MZ", " ", 1, HexCase::Upper, 512);
+        assert_eq!(offset, 0x200);
+        assert_eq!(hex, "4D 5A");
+        assert_eq!(lead, 0);
+
+        // Blocks start at the aligned offset
+        let (_, hex, _) = OutputFormatter::align_bytes_as_hex(0x123, b"MZ", " ", 2, HexCase::Upper, 16);
+        assert_eq!(hex, ".... ..4D 5A");
+
+        let (_, hex, _) = OutputFormatter::align_bytes_as_hex(0x123, b"\xAB\xCD", " ", 1, HexCase::Lower, 4);
+        assert_eq!(hex, ".. .. .. ab cd");
+    }
+
+    #[test]
+    fn test_match_highlight_counts_grouped_bytes() {
+        let line = OutputFormatter::format_line_with_match_highlight(0, "AABBCCDD EE | #1", false, 2, true, Some(1), Some(2));
+        let highlighted = format!("{}{}", "BB".red().bold(), "CC".red().bold());
+        assert_eq!(line, format!("AA{}DD EE | #1", highlighted));
+    }
+
+    #[test]
+    fn test_byte_categories_and_highlight_compose() {
+        let theme = ColorTheme {
+            highlight: Color::Yellow,
+            byte_categories: true,
+        };
+        let line = OutputFormatter::colorize_hex_data_with_match("00 41 1B FF 80 90 ..", Some(1), Some(1), &theme);
+        let expected = [
+            "00".bright_black().to_string(),
+            "41".yellow().bold().to_string(),
+            "1B".yellow().to_string(),
+            "FF".bright_red().bold().to_string(),
+            "80".yellow().to_string(),
+            "90".yellow().to_string(),
+            "..".to_string(),
+        ];
+        assert_eq!(line, expected.join(" "));
+
+        // Printable and other bytes outside the match
+        let line = OutputFormatter::colorize_hex_data_with_match("41 A0", None, None, &theme);
+        assert_eq!(line, format!("{} {}", "41".green(), "A0".blue()));
+
+        // Without byte categories only the match is colored
+        let theme = ColorTheme {
+            highlight: Color::BrightMagenta,
+            byte_categories: false,
+        };
+        let line = OutputFormatter::colorize_hex_data_with_match("00 41", Some(0), Some(1), &theme);
+        assert_eq!(line, format!("{} 41", "00".bright_magenta().bold()));
+    }
+
     #[test]
     fn test_calculate_hex_offset_length() {
         assert_eq!(OutputFormatter::calculate_hex_offset_length(0xFF), 2);
         assert_eq!(OutputFormatter::calculate_hex_offset_length(0x1000), 4);
         assert_eq!(OutputFormatter::calculate_hex_offset_length(0x100000), 6);
     }
+
+    #[test]
+    fn test_write_line_captures_output() {
+        let mut out = Vec::new();
+        OutputFormatter::write_line_with_color(&mut out, 0x10, "4D 5A", true, 4, &ColorChoice::Never).unwrap();
+        OutputFormatter::write_line_with_color(&mut out, 0x20, "90 00", false, 4, &ColorChoice::Never).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "0010h : 4D 5A\n90 00\n");
+    }
+
+    #[test]
+    fn test_format_line_xxd() {
+        assert_eq!(
+            OutputFormatter::format_line_xxd(0, b"Hello Worl"),
+            "00000000: 4865 6c6c 6f20 576f 726c                 Hello Worl"
+        );
+        assert_eq!(
+            OutputFormatter::format_line_xxd(0x10, b"Hello World\n\x00\x01\xff!"),
+            "00000010: 4865 6c6c 6f20 576f 726c 640a 0001 ff21  Hello World....!"
+        );
+        // Odd byte counts leave a single byte in the last group
+        assert_eq!(
+            OutputFormatter::format_line_xxd(0x123456789, b"abc"),
+            "123456789: 6162 63                                  abc"
+        );
+    }
+
+    #[test]
+    fn test_format_line_od() {
+        // Output of `od -tx1 -v` and `od -An -tx1 -v` on the same bytes
+        assert_eq!(
+            OutputFormatter::format_line_od(Some(16), OdRadix::O, b" and more bytes"),
+            "0000020 20 61 6e 64 20 6d 6f 72 65 20 62 79 74 65 73"
+        );
+        assert_eq!(
+            OutputFormatter::format_line_od(None, OdRadix::O, b"hello\xff"),
+            " 68 65 6c 6c 6f ff"
+        );
+        assert_eq!(OutputFormatter::format_od_offset(31, OdRadix::O), "0000037");
+        assert_eq!(OutputFormatter::format_od_offset(31, OdRadix::D), "0000031");
+        assert_eq!(OutputFormatter::format_od_offset(31, OdRadix::X), "00001f");
+    }
 }
@@ -1,9 +1,24 @@
+use crate::collapse::MatchCollapser;
 use crate::error::Result;
-use crate::output::OutputFormatter;
+use crate::options::{DumpOptions, SearchOptions};
+use crate::output::{OutputFormatter, ZERO_RUN_MARKER};
+use crate::progress::ProgressIndicator;
+use crate::regex_processor::MatchAlignment;
+use crate::scan_stats::ScanStats;
+use crate::strings::{printable_runs, StringScanner};
 use rayon::prelude::*;
 use regex::bytes::Regex;
 use std::fs::File;
-use std::io::{Read, Seek, SeekFrom};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::ops::Range;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+/// Bytes read past the end of each chunk so matches starting in it can complete
+///
+/// Matches the sequential path's buffer overlap (`Config::buffer_padding`), so both
+/// paths find the same matches regardless of where chunk or buffer boundaries fall.
+const CHUNK_OVERLAP: usize = 8 * 1024;
 
 /// Parallel file processor for improved performance on large files
 pub struct ParallelProcessor;
@@ -11,46 +26,346 @@ pub struct ParallelProcessor;
 impl ParallelProcessor {
     /// Process file with parallel chunked search
     ///
-    /// Divides the file into chunks and processes them in parallel for better performance.
+    /// Divides the searched range into chunks that are read with positional reads and
+    /// scanned on the rayon thread pool, one batch of chunks per available thread.
+    /// Results are merged in offset order, so the output matches the sequential path.
+    ///
+    /// Each batch is written as soon as it is scanned. Workers only record match
+    /// positions and lines are formatted while writing, so memory stays bounded by one
+    /// batch of chunks rather than the number of matches. With a limit, each chunk stops
+    /// collecting after `limit` matches and no further batches are read once it is reached.
     ///
     /// # Arguments
     ///
     /// * `file` - File to search in
+    /// * `out` - Writer receiving the output
     /// * `regex` - Compiled regex pattern to search for
-    /// * `chunk_size` - Size of each chunk in bytes
-    /// * `width` - Number of bytes to display per match
-    /// * `limit` - Maximum number of matches to output (0 for unlimited)
-    /// * `separator` - String to separate hex bytes
-    /// * `show_offset` - Whether to display offset values
-    /// * `file_size` - Total size of the file for offset formatting
+    /// * `options` - Chunk size, match width, match limit, separator, offset display and alignment
+    /// * `alignment` - Optional constraint on the absolute offsets of reported matches
+    /// * `file_size` - End of the searched range (the file size, or an earlier end bound)
+    /// * `stats` - Optional counters recording chunk, read and scan timings
+    /// * `collapser` - Optional filter collapsing dense runs of matches into one line
+    /// * `progress` - Advanced by the bytes read and the matches reported as each chunk is merged
+    pub fn search_file(
+        file: &mut File,
+        out: &mut dyn Write,
+        regex: &Regex,
+        options: &SearchOptions,
+        alignment: Option<MatchAlignment>,
+        file_size: u64,
+        stats: Option<&ScanStats>,
+        mut collapser: Option<MatchCollapser>,
+        progress: &mut ProgressIndicator,
+    ) -> Result<()> {
+        let SearchOptions { chunk_size, width, limit, .. } = *options;
+        let scan_start = Instant::now();
+        // Offsets are padded for the whole file, even when the searched range ends earlier
+        let hex_offset_length = OutputFormatter::calculate_hex_offset_length(file.metadata()?.len());
+        let use_color = crate::color_context::use_color(crate::color_context::ColorStream::Stdout);
+        let chunk_size = chunk_size.max(1);
+        let workers = rayon::current_num_threads().max(1);
+        progress.add_matches(0);
+
+        // Read far enough past each chunk for matches starting near its end to complete,
+        // and for the displayed bytes of the last match
+        let overlap_size = CHUNK_OVERLAP.max(width);
+
+        let scan_chunk = |data: &[u8], offset: u64| Self::find_chunk_matches(data, regex, offset, alignment, limit);
+        let format_match = |data: &[u8], data_offset: u64, range: &Range<u64>| {
+            Self::format_chunk_match(data, data_offset, range, options, hex_offset_length, use_color)
+        };
+
+        let mut batch_start = file.stream_position()?;
+        let file: &File = file;
+        // Bytes of the finished chunks, shared by the workers as they complete out of order
+        let bytes_done = AtomicU64::new(0);
+        let mut bytes_reported = 0;
+        let mut match_count = 0;
+        let mut last_match_end = 0;
+        let mut last_reported: Option<u64> = None;
+
+        while batch_start < file_size {
+            let chunk_starts: Vec<u64> = (0..workers as u64)
+                .map_while(|i| i.checked_mul(chunk_size as u64).and_then(|step| batch_start.checked_add(step)))
+                .take_while(|&start| start < file_size)
+                .collect();
+
+            // Read and scan every chunk of the batch in parallel
+            let chunks: Vec<Result<ScannedChunk>> = chunk_starts
+                .par_iter()
+                .map(|&chunk_start| -> Result<ScannedChunk> {
+                    let chunk_end = chunk_start.saturating_add(chunk_size as u64).min(file_size);
+                    // Read past the chunk end so boundary-spanning matches and their display are complete
+                    let read_end = chunk_end
+                        .saturating_add(overlap_size as u64)
+                        .saturating_add(width as u64)
+                        .min(file_size);
+
+                    let read_start = Instant::now();
+                    let mut data = vec![0u8; (read_end - chunk_start) as usize];
+                    let bytes_read = read_chunk_at(file, chunk_start, &mut data)?;
+                    data.truncate(bytes_read);
+                    if let Some(stats) = stats {
+                        stats.record_reader_wait(read_start.elapsed());
+                    }
+
+                    let chunk_scan_start = Instant::now();
+                    let matches = scan_chunk(&data, chunk_start);
+                    if let Some(stats) = stats {
+                        stats.record_chunk((chunk_end - chunk_start) as usize, chunk_scan_start.elapsed());
+                    }
+                    bytes_done.fetch_add(chunk_end - chunk_start, Ordering::Relaxed);
+
+                    Ok(ScannedChunk {
+                        start: chunk_start,
+                        end: chunk_end,
+                        data,
+                        matches,
+                    })
+                })
+                .collect();
+
+            // Merge in offset order
+            for chunk in chunks {
+                let chunk = chunk?;
+
+                // A match from the previous chunk ran into this one; rescan after it so the
+                // matches are the same non-overlapping ones a single pass would find
+                let matches = if last_match_end > chunk.start {
+                    let skip = ((last_match_end - chunk.start) as usize).min(chunk.data.len());
+                    scan_chunk(&chunk.data[skip..], last_match_end)
+                } else {
+                    chunk.matches
+                };
+
+                for range in &matches {
+                    // Matches in the overlap region are reported by the next chunk
+                    if range.start >= chunk.end {
+                        break;
+                    }
+                    // Each absolute offset is reported once, even for empty matches
+                    if range.start < last_match_end || matches!(last_reported, Some(offset) if range.start <= offset) {
+                        continue;
+                    }
+
+                    if options.offsets_only {
+                        OutputFormatter::write_match_offset(out, range.start, options.null_terminated)?;
+                    } else {
+                        let line = format_match(&chunk.data, chunk.start, range);
+                        match collapser.as_mut() {
+                            Some(collapser) => {
+                                for ready in collapser.push(range.start, line) {
+                                    writeln!(out, "{}", ready)?;
+                                }
+                            }
+                            None => writeln!(out, "{}", line)?,
+                        }
+                    }
+                    last_match_end = range.end;
+                    last_reported = Some(range.start);
+                    match_count += 1;
+                    progress.add_matches(1);
+
+                    if limit > 0 && match_count >= limit {
+                        Self::finish_collapser(out, collapser.as_mut())?;
+                        if let Some(stats) = stats {
+                            stats.record_wall_time(scan_start.elapsed());
+                        }
+                        progress.finish();
+                        return Ok(());
+                    }
+                }
+            }
+
+            // Progress is drawn from this thread, throttled like the sequential path
+            let done = bytes_done.load(Ordering::Relaxed);
+            progress.update(done - bytes_reported);
+            bytes_reported = done;
+
+            match chunk_starts
+                .last()
+                .and_then(|&start| start.checked_add(chunk_size as u64))
+            {
+                Some(next) => batch_start = next,
+                // Overflow would occur - we've reached the end
+                None => break,
+            }
+        }
+
+        Self::finish_collapser(out, collapser.as_mut())?;
+        if let Some(stats) = stats {
+            stats.record_wall_time(scan_start.elapsed());
+        }
+        progress.finish();
+
+        Ok(())
+    }
+
+    /// Search a file in parallel chunks; see [`ParallelProcessor::search_file`]
+    #[deprecated(note = "use `ParallelProcessor::search_file` with `SearchOptions`")]
     pub fn process_file_parallel(
         file: &mut File,
+        out: &mut dyn Write,
         regex: &Regex,
         chunk_size: usize,
         width: usize,
         limit: usize,
         separator: &str,
         show_offset: bool,
+        align: Option<usize>,
+        alignment: Option<MatchAlignment>,
         file_size: u64,
+        stats: Option<&ScanStats>,
+        collapser: Option<MatchCollapser>,
     ) -> Result<()> {
-        let hex_offset_length = OutputFormatter::calculate_hex_offset_length(file_size);
-        let mut all_matches = Vec::new();
-        let mut current_pos = file.stream_position()?;
-        let mut match_count = 0;
+        let options = SearchOptions::new()
+            .chunk_size(chunk_size)
+            .width(width)
+            .limit(limit)
+            .separator(separator)
+            .show_offset(show_offset)
+            .align(align);
+        Self::search_file(
+            file,
+            out,
+            regex,
+            &options,
+            alignment,
+            file_size,
+            stats,
+            collapser,
+            &mut ProgressIndicator::disabled(),
+        )
+    }
 
-        // Calculate overlap size based on potential pattern length
-        // This ensures patterns that span chunk boundaries are not missed
-        let overlap_size = 1024.min(chunk_size / 10); // 10% overlap, max 1KB
+    /// Write the lines still held back by the match collapser
+    fn finish_collapser(out: &mut dyn Write, collapser: Option<&mut MatchCollapser>) -> io::Result<()> {
+        if let Some(collapser) = collapser {
+            for ready in collapser.finish() {
+                writeln!(out, "{}", ready)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Extract printable ASCII strings from a file using parallel chunked scanning
+    ///
+    /// Workers find the printable runs of each chunk; the runs are then joined across
+    /// chunk boundaries in offset order, so no overlap between chunks is needed.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - File to extract strings from, starting at its current position
+    /// * `out` - Writer receiving the output
+    /// * `chunk_size` - Size of each chunk in bytes
+    /// * `min_len` - Minimum length of reported strings
+    /// * `limit` - Maximum number of strings to output (0 for unlimited)
+    /// * `show_offset` - Whether to display offset values
+    /// * `file_size` - End of the searched range (the file size, or an earlier end bound)
+    pub fn find_strings_parallel(
+        file: &mut File,
+        out: &mut dyn Write,
+        chunk_size: usize,
+        min_len: usize,
+        limit: usize,
+        show_offset: bool,
+        file_size: u64,
+    ) -> Result<()> {
+        let hex_offset_length = OutputFormatter::calculate_hex_offset_length(file.metadata()?.len());
+        let chunk_size = chunk_size.max(1);
+        let workers = rayon::current_num_threads().max(1);
+
+        let mut batch_start = file.stream_position()?;
+        let file: &File = file;
+        let mut scanner = StringScanner::new(min_len);
+        let mut count = 0;
+
+        let mut write_string = |(offset, value): (u64, String)| -> io::Result<bool> {
+            if show_offset {
+                writeln!(out, "{}", OutputFormatter::format_line_with_offset(offset, &value, hex_offset_length))?;
+            } else {
+                writeln!(out, "{}", value)?;
+            }
+            count += 1;
+            Ok(limit > 0 && count >= limit)
+        };
+
+        while batch_start < file_size {
+            let chunk_starts: Vec<u64> = (0..workers as u64)
+                .map_while(|i| i.checked_mul(chunk_size as u64).and_then(|step| batch_start.checked_add(step)))
+                .take_while(|&start| start < file_size)
+                .collect();
+
+            let chunks: Vec<Result<(u64, Vec<u8>, Vec<Range<usize>>)>> = chunk_starts
+                .par_iter()
+                .map(|&chunk_start| -> Result<(u64, Vec<u8>, Vec<Range<usize>>)> {
+                    let chunk_end = chunk_start.saturating_add(chunk_size as u64).min(file_size);
+                    let mut data = vec![0u8; (chunk_end - chunk_start) as usize];
+                    let bytes_read = read_chunk_at(file, chunk_start, &mut data)?;
+                    data.truncate(bytes_read);
+                    let runs = printable_runs(&data, min_len);
+                    Ok((chunk_start, data, runs))
+                })
+                .collect();
+
+            for chunk in chunks {
+                let (chunk_start, data, runs) = chunk?;
+                for found in scanner.scan_runs(&data, chunk_start, &runs) {
+                    if write_string(found)? {
+                        return Ok(());
+                    }
+                }
+            }
+
+            match chunk_starts
+                .last()
+                .and_then(|&start| start.checked_add(chunk_size as u64))
+            {
+                Some(next) => batch_start = next,
+                // Overflow would occur - we've reached the end
+                None => break,
+            }
+        }
+
+        if let Some(found) = scanner.finish() {
+            write_string(found)?;
+        }
+
+        Ok(())
+    }
+
+    /// Check whether the regex pattern occurs anywhere in the file using parallel chunked search
+    ///
+    /// Each chunk is split into overlapping sub-slices that are tested in parallel, and
+    /// scanning stops at the first chunk containing a match.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - File to search in
+    /// * `regex` - Compiled regex pattern to search for
+    /// * `chunk_size` - Size of each chunk in bytes
+    /// * `alignment` - Optional constraint on the absolute offsets of matches
+    /// * `file_size` - End of the searched range (the file size, or an earlier end bound)
+    pub fn contains_match(
+        file: &mut File,
+        regex: &Regex,
+        chunk_size: usize,
+        alignment: Option<MatchAlignment>,
+        file_size: u64,
+    ) -> Result<bool> {
+        const PARALLEL_CHUNK_SIZE: usize = 64 * 1024; // 64KB per thread
+        let overlap_size = CHUNK_OVERLAP;
+        let mut current_pos = file.stream_position()?;
 
         while current_pos < file_size {
             let remaining = file_size - current_pos;
             let actual_chunk_size = if remaining < chunk_size as u64 {
                 remaining as usize
             } else {
-                chunk_size + overlap_size
+                // Never read the overlap past the end of the searched range
+                (chunk_size as u64).saturating_add(overlap_size as u64).min(remaining) as usize
             };
 
-            // Read chunk with overlap
             let mut chunk_buffer = vec![0u8; actual_chunk_size];
             file.seek(SeekFrom::Start(current_pos))?;
             let bytes_read = file.read(&mut chunk_buffer)?;
@@ -60,94 +375,133 @@ impl ParallelProcessor {
                 break;
             }
 
-            // Process chunk and find matches
-            let chunk_matches = Self::process_chunk(
-                &chunk_buffer,
-                regex,
-                current_pos,
-                width,
-                separator,
-                show_offset,
-                hex_offset_length,
-            );
-
-            // Add matches to the collection
-            for (offset, line) in chunk_matches {
-                // Skip matches in overlap region except for the first chunk
-                if current_pos > 0 && offset >= current_pos + chunk_size as u64 {
-                    continue;
-                }
-
-                all_matches.push((offset, line));
-                match_count += 1;
-
-                // Check limit
-                if limit > 0 && match_count >= limit {
-                    break;
-                }
+            let found = (0..chunk_buffer.len())
+                .step_by(PARALLEL_CHUNK_SIZE)
+                .collect::<Vec<_>>()
+                .into_par_iter()
+                .any(|start| {
+                    let end = (start + PARALLEL_CHUNK_SIZE + overlap_size).min(chunk_buffer.len());
+                    let slice = &chunk_buffer[start..end];
+                    match alignment {
+                        Some(alignment) => alignment
+                            .find_iter(regex, slice, current_pos + start as u64)
+                            .next()
+                            .is_some(),
+                        None => regex.is_match(slice),
+                    }
+                });
+
+            if found {
+                return Ok(true);
             }
 
-            if limit > 0 && match_count >= limit {
-                break;
-            }
-
-            // Move to next chunk (without overlap to avoid double processing)
-            if let Some(new_pos) = current_pos.checked_add(chunk_size as u64) {
-                current_pos = new_pos;
-            } else {
-                // Overflow would occur - we've reached the end
-                break;
+            match current_pos.checked_add(chunk_size as u64) {
+                Some(new_pos) => current_pos = new_pos,
+                None => break,
             }
         }
 
-        // Sort matches by offset and print
-        all_matches.sort_by_key(|(offset, _)| *offset);
-        for (_, line) in all_matches
-            .into_iter()
-            .take(if limit > 0 { limit } else { usize::MAX })
-        {
-            println!("{}", line);
-        }
+        Ok(false)
+    }
 
-        Ok(())
+    /// Find the absolute byte ranges of regex matches in a chunk, in offset order
+    ///
+    /// Stops after `limit` matches when a limit is set (0 for unlimited).
+    fn find_chunk_matches(
+        data: &[u8],
+        regex: &Regex,
+        chunk_start_offset: u64,
+        alignment: Option<MatchAlignment>,
+        limit: usize,
+    ) -> Vec<Range<u64>> {
+        let found: Box<dyn Iterator<Item = regex::bytes::Match> + '_> = match alignment {
+            Some(alignment) => Box::new(alignment.find_iter(regex, data, chunk_start_offset)),
+            None => Box::new(regex.find_iter(data)),
+        };
+        let max_matches = if limit == 0 { usize::MAX } else { limit };
+
+        found
+            .take(max_matches)
+            .map(|mat| chunk_start_offset + mat.start() as u64..chunk_start_offset + mat.end() as u64)
+            .collect()
+    }
+
+    /// Format the display line of a match found by [`Self::find_chunk_matches`]
+    ///
+    /// `data` is the chunk the match was found in and `data_offset` its absolute offset.
+    fn format_chunk_match(
+        data: &[u8],
+        data_offset: u64,
+        range: &Range<u64>,
+        options: &SearchOptions,
+        hex_offset_length: usize,
+        use_color: bool,
+    ) -> String {
+        let match_offset = range.start;
+        let match_len = (range.end - range.start) as usize;
+
+        // Determine the range to display (width 0 displays exactly the matched bytes)
+        let start_pos = ((match_offset - data_offset) as usize).min(data.len());
+        let display_len = if options.width == 0 { match_len } else { options.width };
+        let end_pos = (start_pos + display_len).min(data.len());
+
+        let display_bytes = &data[start_pos..end_pos];
+        let display_bytes = match options.trim_trailing {
+            Some(byte) => OutputFormatter::trim_trailing(display_bytes, byte),
+            None => display_bytes,
+        };
+        let trimmed = end_pos - start_pos - display_bytes.len();
+        // The displayed window starts at the match, as in the sequential path
+        let match_byte_len = match_len.min(display_bytes.len());
+        let (display_offset, mut hex_string, match_byte_pos) = match options.align {
+            Some(align) => OutputFormatter::align_bytes_as_hex(
+                match_offset,
+                display_bytes,
+                &options.separator,
+                options.group_size,
+                options.hex_case,
+                align,
+            ),
+            None => (
+                match_offset,
+                OutputFormatter::format_bytes_grouped(display_bytes, &options.separator, options.group_size, options.hex_case),
+                0,
+            ),
+        };
+        if let Some(byte) = options.trim_trailing.filter(|_| trimmed > 0) {
+            hex_string = OutputFormatter::append_trimmed(&hex_string, trimmed, byte, options.hex_case);
+        }
+        OutputFormatter::format_line_with_match_highlight(
+            display_offset,
+            &hex_string,
+            options.show_offset,
+            hex_offset_length,
+            use_color,
+            Some(match_byte_pos),
+            Some(match_byte_len),
+        )
     }
 
     /// Process a chunk of data and find regex matches
+    ///
+    /// Returns the offset and formatted line of each match in offset order.
     fn process_chunk(
         data: &[u8],
         regex: &Regex,
         chunk_start_offset: u64,
-        width: usize,
-        separator: &str,
-        show_offset: bool,
+        options: &SearchOptions,
+        alignment: Option<MatchAlignment>,
         hex_offset_length: usize,
+        use_color: bool,
     ) -> Vec<(u64, String)> {
-        let mut matches = Vec::new();
-
-        for mat in regex.find_iter(data) {
-            let match_offset = chunk_start_offset + mat.start() as u64;
-
-            // Determine the range to display
-            let start_pos = mat.start();
-            let end_pos = (start_pos + width).min(data.len());
-
-            if start_pos < data.len() {
-                let display_bytes = &data[start_pos..end_pos];
-                let hex_string = OutputFormatter::format_bytes_as_hex(display_bytes, separator);
-                let formatted_line = if show_offset {
-                    OutputFormatter::format_line_with_offset(
-                        match_offset,
-                        &hex_string,
-                        hex_offset_length,
-                    )
-                } else {
-                    hex_string
-                };
-                matches.push((match_offset, formatted_line));
-            }
-        }
-
-        matches
+        Self::find_chunk_matches(data, regex, chunk_start_offset, alignment, 0)
+            .into_iter()
+            .filter(|range| range.start < chunk_start_offset + data.len() as u64)
+            .map(|range| {
+                let line = Self::format_chunk_match(data, chunk_start_offset, &range, options, hex_offset_length, use_color);
+                (range.start, line)
+            })
+            .collect()
     }
 
     /// Process multiple chunks in parallel
@@ -158,24 +512,16 @@ impl ParallelProcessor {
         data: &[u8],
         regex: &Regex,
         base_offset: u64,
-        width: usize,
-        separator: &str,
-        show_offset: bool,
+        options: &SearchOptions,
+        alignment: Option<MatchAlignment>,
         hex_offset_length: usize,
     ) -> Vec<(u64, String)> {
         const PARALLEL_CHUNK_SIZE: usize = 64 * 1024; // 64KB per thread
         const OVERLAP_SIZE: usize = 1024; // 1KB overlap
+        let use_color = crate::color_context::use_color(crate::color_context::ColorStream::Stdout);
 
         if data.len() <= PARALLEL_CHUNK_SIZE {
-            return Self::process_chunk(
-                data,
-                regex,
-                base_offset,
-                width,
-                separator,
-                show_offset,
-                hex_offset_length,
-            );
+            return Self::process_chunk(data, regex, base_offset, options, alignment, hex_offset_length, use_color);
         }
 
         let mut chunks = Vec::new();
@@ -194,20 +540,15 @@ impl ParallelProcessor {
         let all_matches: Vec<Vec<(u64, String)>> = chunks
             .into_par_iter()
             .map(|(chunk_data, chunk_offset)| {
-                Self::process_chunk(
-                    chunk_data,
-                    regex,
-                    chunk_offset,
-                    width,
-                    separator,
-                    show_offset,
-                    hex_offset_length,
-                )
+                Self::process_chunk(chunk_data, regex, chunk_offset, options, alignment, hex_offset_length, use_color)
             })
             .collect();
 
         // Flatten and sort results
-        let mut matches: Vec<(u64, String)> = all_matches.into_iter().flatten().collect();
+        let mut matches: Vec<(u64, String)> = all_matches
+            .into_iter()
+            .flatten()
+            .collect();
         matches.sort_by_key(|(offset, _)| *offset);
 
         // Remove duplicates that might occur in overlap regions
@@ -217,23 +558,68 @@ impl ParallelProcessor {
     }
 }
 
+/// A chunk read and scanned by a worker, kept until it is merged in offset order
+struct ScannedChunk {
+    start: u64,
+    end: u64,
+    data: Vec<u8>,
+    matches: Vec<Range<u64>>,
+}
+
+/// Read up to `buf.len()` bytes at `offset` without using the shared file cursor
+fn read_chunk_at(file: &File, offset: u64, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match read_at(file, &mut buf[filled..], offset + filled as u64) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(filled)
+}
+
+#[cfg(unix)]
+fn read_at(file: &File, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+    std::os::unix::fs::FileExt::read_at(file, buf, offset)
+}
+
+#[cfg(windows)]
+fn read_at(file: &File, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+    std::os::windows::fs::FileExt::seek_read(file, buf, offset)
+}
+
+/// Platforms without positional reads share the cursor, so reads are serialized
+#[cfg(not(any(unix, windows)))]
+fn read_at(file: &File, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+    static CURSOR: std::sync::Mutex<()> = std::sync::Mutex::new(());
+    let _guard = CURSOR.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let mut handle = file;
+    handle.seek(SeekFrom::Start(offset))?;
+    handle.read(buf)
+}
+
 /// Parallel hex dump processor for non-regex operations
 pub struct ParallelHexDump;
 
 impl ParallelHexDump {
     /// Process file in parallel for hex dump (non-regex mode)
-    pub fn process_file_parallel(
+    ///
+    /// `file_size` is the end of the dumped range (the file size, or an earlier end bound),
+    /// and `progress` is advanced as each chunk is written.
+    pub fn dump_file(
         file: &mut File,
-        chunk_size: usize,
-        width: usize,
-        limit: usize,
-        separator: &str,
-        show_offset: bool,
+        out: &mut dyn Write,
+        options: &DumpOptions,
         file_size: u64,
+        progress: &mut ProgressIndicator,
     ) -> Result<()> {
+        let DumpOptions { limit, chunk_size, .. } = *options;
         let hex_offset_length = OutputFormatter::calculate_hex_offset_length(file_size);
         let mut current_pos = file.stream_position()?;
         let mut lines_processed = 0;
+        let mut after_zero_run = false;
 
         // For hex dump, we don't need overlap since we're not searching for patterns
         while current_pos < file_size && (limit == 0 || lines_processed < limit) {
@@ -253,9 +639,7 @@ impl ParallelHexDump {
             let chunk_lines = Self::process_chunk_hex_dump(
                 &chunk_buffer,
                 current_pos,
-                width,
-                separator,
-                show_offset,
+                options,
                 hex_offset_length,
                 if limit > 0 {
                     limit - lines_processed
@@ -265,7 +649,17 @@ impl ParallelHexDump {
             );
 
             for line in chunk_lines {
-                println!("{}", line);
+                // A zero run spanning chunks is marked by both of them, but printed once
+                if line == ZERO_RUN_MARKER {
+                    if !after_zero_run {
+                        writeln!(out, "{}", line)?;
+                    }
+                    after_zero_run = true;
+                    continue;
+                }
+                after_zero_run = false;
+
+                writeln!(out, "{}", line)?;
                 lines_processed += 1;
                 if limit > 0 && lines_processed >= limit {
                     break;
@@ -273,31 +667,66 @@ impl ParallelHexDump {
             }
 
             current_pos += bytes_read as u64;
+            progress.update(bytes_read as u64);
         }
 
+        progress.finish();
         Ok(())
     }
 
+    /// Hex dump a file in parallel chunks; see [`ParallelHexDump::dump_file`]
+    #[deprecated(note = "use `ParallelHexDump::dump_file` with `DumpOptions`")]
+    pub fn process_file_parallel(
+        file: &mut File,
+        out: &mut dyn Write,
+        chunk_size: usize,
+        width: usize,
+        limit: usize,
+        separator: &str,
+        show_offset: bool,
+        file_size: u64,
+    ) -> Result<()> {
+        let options = DumpOptions::new()
+            .chunk_size(chunk_size)
+            .width(width)
+            .limit(limit)
+            .separator(separator)
+            .show_offset(show_offset);
+        Self::dump_file(file, out, &options, file_size, &mut ProgressIndicator::disabled())
+    }
+
     /// Process a chunk for hex dump output
+    ///
+    /// With `skip_zeros`, each run of all-zero lines becomes a `*` line that does not count
+    /// towards the limit, including a run reaching the end of the chunk.
     fn process_chunk_hex_dump(
         data: &[u8],
         start_offset: u64,
-        width: usize,
-        separator: &str,
-        show_offset: bool,
+        options: &DumpOptions,
         hex_offset_length: usize,
         remaining_limit: usize,
     ) -> Vec<String> {
+        let DumpOptions { width, ref separator, group_size, hex_case, show_offset, skip_zeros, .. } = *options;
         let mut lines = Vec::new();
         let mut pos = 0;
         let mut line_count = 0;
+        let mut in_zero_run = false;
 
         while pos < data.len() && (remaining_limit == 0 || line_count < remaining_limit) {
             let end = (pos + width).min(data.len());
             let line_bytes = &data[pos..end];
             let offset = start_offset + pos as u64;
 
-            let hex_string = OutputFormatter::format_bytes_as_hex(line_bytes, separator);
+            if skip_zeros && line_bytes.iter().all(|&b| b == 0) {
+                in_zero_run = true;
+                pos += width;
+                continue;
+            }
+            if std::mem::take(&mut in_zero_run) {
+                lines.push(ZERO_RUN_MARKER.to_string());
+            }
+
+            let hex_string = OutputFormatter::format_bytes_grouped(line_bytes, separator, group_size, hex_case);
             let formatted_line = if show_offset {
                 OutputFormatter::format_line_with_offset(offset, &hex_string, hex_offset_length)
             } else {
@@ -309,6 +738,9 @@ impl ParallelHexDump {
             line_count += 1;
         }
 
+        if in_zero_run {
+            lines.push(ZERO_RUN_MARKER.to_string());
+        }
         lines
     }
 }
@@ -0,0 +1,298 @@
+//! Partition table parsing for disk images
+//!
+//! Reads the MBR from the first sector of an image and, when it holds a GPT protective
+//! entry, the GPT header and entries that follow it. Only the primary tables are read:
+//! extended MBR partitions and the GPT backup header are not followed, and GPT checksums
+//! are not verified so that partly damaged images still list their partitions.
+
+use std::io::{self, Read, Seek, SeekFrom};
+
+/// Sector size assumed for MBR addresses
+const MBR_SECTOR_SIZE: u64 = 512;
+/// Logical sector sizes probed for the GPT header
+const GPT_SECTOR_SIZES: [u64; 2] = [512, 4096];
+/// MBR partition type of a GPT protective entry
+const GPT_PROTECTIVE_TYPE: u8 = 0xEE;
+/// Upper bound on GPT entries read, far above the 128 of the usual layout
+const MAX_GPT_ENTRIES: u32 = 4096;
+
+/// Partition table scheme
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartitionScheme {
+    Mbr,
+    Gpt,
+}
+
+/// A partition found in a disk image
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Partition {
+    /// 1-based number, in table order, as accepted by `--partition`
+    pub number: usize,
+    /// Partition type, by name when it is a well-known one
+    pub type_name: String,
+    /// First sector of the partition
+    pub start_lba: u64,
+    /// Number of sectors
+    pub sectors: u64,
+    /// Size of the sectors the addresses count
+    pub sector_size: u64,
+}
+
+impl Partition {
+    /// Byte offset of the partition in the image
+    pub fn start(&self) -> u64 {
+        self.start_lba.saturating_mul(self.sector_size)
+    }
+
+    /// Byte offset just past the end of the partition
+    pub fn end(&self) -> u64 {
+        self.start_lba.saturating_add(self.sectors).saturating_mul(self.sector_size)
+    }
+
+    /// Size of the partition in bytes
+    pub fn size(&self) -> u64 {
+        self.sectors.saturating_mul(self.sector_size)
+    }
+}
+
+/// Read the partitions of a disk image
+///
+/// Returns the scheme and the used entries, or `None` if the image starts with no
+/// partition table.
+pub fn read_partitions<R: Read + Seek>(reader: &mut R) -> io::Result<Option<(PartitionScheme, Vec<Partition>)>> {
+    let mut mbr = [0u8; MBR_SECTOR_SIZE as usize];
+    reader.seek(SeekFrom::Start(0))?;
+    if read_full(reader, &mut mbr)? < mbr.len() || mbr[510..512] != [0x55, 0xAA] {
+        return Ok(None);
+    }
+
+    let entries: Vec<&[u8]> = mbr[446..510].chunks(16).collect();
+    if entries.iter().any(|entry| entry[4] == GPT_PROTECTIVE_TYPE) {
+        for sector_size in GPT_SECTOR_SIZES {
+            if let Some(partitions) = read_gpt(reader, sector_size)? {
+                return Ok(Some((PartitionScheme::Gpt, partitions)));
+            }
+        }
+    }
+
+    let partitions = entries
+        .iter()
+        .enumerate()
+        .filter(|(_, entry)| entry[4] != 0)
+        .map(|(index, entry)| Partition {
+            number: index + 1,
+            type_name: mbr_type_name(entry[4]),
+            start_lba: u32::from_le_bytes(entry[8..12].try_into().unwrap()) as u64,
+            sectors: u32::from_le_bytes(entry[12..16].try_into().unwrap()) as u64,
+            sector_size: MBR_SECTOR_SIZE,
+        })
+        .collect();
+    Ok(Some((PartitionScheme::Mbr, partitions)))
+}
+
+/// Read the GPT header at LBA 1 and its entries, if the header signature is there
+fn read_gpt<R: Read + Seek>(reader: &mut R, sector_size: u64) -> io::Result<Option<Vec<Partition>>> {
+    let mut header = [0u8; 92];
+    reader.seek(SeekFrom::Start(sector_size))?;
+    if read_full(reader, &mut header)? < header.len() || !header.starts_with(b"EFI PART") {
+        return Ok(None);
+    }
+
+    let entries_lba = u64::from_le_bytes(header[72..80].try_into().unwrap());
+    let entry_count = u32::from_le_bytes(header[80..84].try_into().unwrap()).min(MAX_GPT_ENTRIES);
+    let entry_size = u32::from_le_bytes(header[84..88].try_into().unwrap());
+    // The specification requires 128 * 2^n bytes, and no real table uses entries larger than a sector
+    if entry_size < 128 || !entry_size.is_power_of_two() || u64::from(entry_size) > sector_size {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid GPT partition entry size {}", entry_size),
+        ));
+    }
+    let entry_size = entry_size as usize;
+
+    // At most MAX_GPT_ENTRIES entries of one sector each, so the table stays within 16 MiB
+    let table_size = (entry_count as usize).checked_mul(entry_size).ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidData, "GPT partition table too large")
+    })?;
+    let mut table = vec![0u8; table_size];
+    reader.seek(SeekFrom::Start(entries_lba.saturating_mul(sector_size)))?;
+    let table_len = read_full(reader, &mut table)?;
+    table.truncate(table_len - table_len % entry_size);
+
+    let partitions = table
+        .chunks(entry_size)
+        .enumerate()
+        .filter(|(_, entry)| entry[..16].iter().any(|&b| b != 0))
+        .map(|(index, entry)| {
+            let first_lba = u64::from_le_bytes(entry[32..40].try_into().unwrap());
+            let last_lba = u64::from_le_bytes(entry[40..48].try_into().unwrap());
+            Partition {
+                number: index + 1,
+                type_name: gpt_type_name(entry[..16].try_into().unwrap()),
+                start_lba: first_lba,
+                sectors: last_lba.saturating_add(1).saturating_sub(first_lba),
+                sector_size,
+            }
+        })
+        .collect();
+    Ok(Some(partitions))
+}
+
+/// Read until `buf` is full or the input ends, returning the number of bytes read
+fn read_full<R: Read>(reader: &mut R, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..])? {
+            0 => break,
+            read => filled += read,
+        }
+    }
+    Ok(filled)
+}
+
+/// Name of a well-known MBR partition type, or its hex value
+fn mbr_type_name(partition_type: u8) -> String {
+    let name = match partition_type {
+        0x01 => "FAT12",
+        0x04 | 0x06 | 0x0E => "FAT16",
+        0x05 | 0x0F => "Extended",
+        0x07 => "NTFS/exFAT",
+        0x0B | 0x0C => "FAT32",
+        0x27 => "Windows recovery",
+        0x82 => "Linux swap",
+        0x83 => "Linux",
+        0x8E => "Linux LVM",
+        0xA5 => "FreeBSD",
+        0xAF => "HFS/HFS+",
+        0xEE => "GPT protective",
+        0xEF => "EFI system",
+        0xFD => "Linux RAID",
+        _ => return format!("0x{:02X}", partition_type),
+    };
+    format!("{} (0x{:02X})", name, partition_type)
+}
+
+/// Name of a well-known GPT partition type GUID, or the GUID itself
+fn gpt_type_name(guid: [u8; 16]) -> String {
+    let guid = format_guid(&guid);
+    let name = match guid.as_str() {
+        "C12A7328-F81F-11D2-BA4B-00A0C93EC93B" => "EFI system",
+        "E3C9E316-0B5C-4DB8-817D-F92DF00215AE" => "Microsoft reserved",
+        "EBD0A0A2-B9E5-4433-87C0-68B6B72699C7" => "Microsoft basic data",
+        "DE94BBA4-06D1-4D40-A16A-BFD50179D6AC" => "Windows recovery",
+        "0FC63DAF-8483-4772-8E79-3D69D8477DE4" => "Linux filesystem",
+        "0657FD6D-A4AB-43C4-84E5-0933C84B4F4F" => "Linux swap",
+        "E6D6D379-F507-44C2-A23C-238F2A3DF928" => "Linux LVM",
+        "48465300-0000-11AA-AA11-00306543ECAC" => "Apple HFS+",
+        "7C3457EF-0000-11AA-AA11-00306543ECAC" => "Apple APFS",
+        _ => return guid,
+    };
+    name.to_string()
+}
+
+/// Format a GUID stored on disk, whose first three fields are little-endian
+fn format_guid(bytes: &[u8; 16]) -> String {
+    format!(
+        "{:08X}-{:04X}-{:04X}-{:02X}{:02X}-{}",
+        u32::from_le_bytes(bytes[0..4].try_into().unwrap()),
+        u16::from_le_bytes(bytes[4..6].try_into().unwrap()),
+        u16::from_le_bytes(bytes[6..8].try_into().unwrap()),
+        bytes[8],
+        bytes[9],
+        bytes[10..].iter().map(|b| format!("{:02X}", b)).collect::<String>()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// GPT type GUID of "Microsoft basic data" as stored on disk
+    const BASIC_DATA_GUID: [u8; 16] = [
+        0xA2, 0xA0, 0xD0, 0xEB, 0xE5, 0xB9, 0x33, 0x44, 0x87, 0xC0, 0x68, 0xB6, 0xB7, 0x26, 0x99, 0xC7,
+    ];
+
+    /// Disk image with a protective MBR, a GPT header and the given (type, first LBA, last LBA) entries
+    fn gpt_image(sectors: usize, entries: &[([u8; 16], u64, u64)]) -> Vec<u8> {
+        let mut image = vec![0u8; sectors * 512];
+        image[446 + 4] = GPT_PROTECTIVE_TYPE;
+        image[446 + 8..446 + 12].copy_from_slice(&1u32.to_le_bytes());
+        image[510..512].copy_from_slice(&[0x55, 0xAA]);
+
+        let header = &mut image[512..604];
+        header[..8].copy_from_slice(b"EFI PART");
+        header[72..80].copy_from_slice(&2u64.to_le_bytes());
+        header[80..84].copy_from_slice(&128u32.to_le_bytes());
+        header[84..88].copy_from_slice(&128u32.to_le_bytes());
+
+        for (index, (type_guid, first_lba, last_lba)) in entries.iter().enumerate() {
+            let entry = &mut image[1024 + index * 128..1024 + (index + 1) * 128];
+            entry[..16].copy_from_slice(type_guid);
+            entry[32..40].copy_from_slice(&first_lba.to_le_bytes());
+            entry[40..48].copy_from_slice(&last_lba.to_le_bytes());
+        }
+        image
+    }
+
+    #[test]
+    fn test_read_gpt_partitions() {
+        let linux = [
+            0xAF, 0x3D, 0xC6, 0x0F, 0x83, 0x84, 0x72, 0x47, 0x8E, 0x79, 0x3D, 0x69, 0xD8, 0x47, 0x7D, 0xE4,
+        ];
+        let image = gpt_image(128, &[(BASIC_DATA_GUID, 34, 63), (linux, 64, 127)]);
+
+        let (scheme, partitions) = read_partitions(&mut Cursor::new(image)).unwrap().unwrap();
+        assert_eq!(scheme, PartitionScheme::Gpt);
+        assert_eq!(partitions.len(), 2);
+        assert_eq!(partitions[0].type_name, "Microsoft basic data");
+        assert_eq!((partitions[0].start(), partitions[0].end()), (34 * 512, 64 * 512));
+        assert_eq!(partitions[1].type_name, "Linux filesystem");
+        assert_eq!((partitions[1].number, partitions[1].sectors), (2, 64));
+    }
+
+    #[test]
+    fn test_read_mbr_partitions() {
+        let mut image = vec![0u8; 1024];
+        let entry = &mut image[446 + 16..446 + 32];
+        entry[4] = 0x83;
+        entry[8..12].copy_from_slice(&2048u32.to_le_bytes());
+        entry[12..16].copy_from_slice(&4096u32.to_le_bytes());
+        image[510..512].copy_from_slice(&[0x55, 0xAA]);
+
+        let (scheme, partitions) = read_partitions(&mut Cursor::new(image)).unwrap().unwrap();
+        assert_eq!(scheme, PartitionScheme::Mbr);
+        assert_eq!(
+            partitions,
+            vec![Partition {
+                number: 2,
+                type_name: "Linux (0x83)".to_string(),
+                start_lba: 2048,
+                sectors: 4096,
+                sector_size: 512,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_invalid_gpt_entry_size_rejected() {
+        // Oversized, not a power of two, and larger than a sector
+        for entry_size in [u32::MAX, 200, 1024] {
+            let mut image = gpt_image(128, &[(BASIC_DATA_GUID, 34, 63)]);
+            image[512 + 84..512 + 88].copy_from_slice(&entry_size.to_le_bytes());
+            let err = read_partitions(&mut Cursor::new(image)).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData, "{}", entry_size);
+        }
+    }
+
+    #[test]
+    fn test_no_partition_table() {
+        assert_eq!(read_partitions(&mut Cursor::new(vec![0u8; 4096])).unwrap(), None);
+        assert_eq!(read_partitions(&mut Cursor::new(vec![0u8; 16])).unwrap(), None);
+    }
+
+    #[test]
+    fn test_format_guid() {
+        assert_eq!(format_guid(&BASIC_DATA_GUID), "EBD0A0A2-B9E5-4433-87C0-68B6B72699C7");
+    }
+}
@@ -1,6 +1,17 @@
-use std::io::{self, Write};
+use crate::color_context::{self, ColorStream};
+use colored::Colorize;
+use std::collections::VecDeque;
+use std::io::{self, IsTerminal, Write};
+use std::ops::Range;
+use std::sync::{Arc, Mutex, MutexGuard};
 use std::time::{Duration, Instant};
 
+/// Erases the current terminal line after returning to its start
+const CLEAR_LINE: &str = "\r\x1b[K";
+
+/// Number of recent update intervals the displayed throughput is averaged over
+const THROUGHPUT_WINDOW: usize = 10;
+
 /// Progress indicator for file processing
 pub struct ProgressIndicator {
     start_time: Instant,
@@ -9,6 +20,26 @@ pub struct ProgressIndicator {
     processed_bytes: u64,
     enabled: bool,
     show_progress: bool,
+    use_color: bool,
+    line: Option<ProgressLine>,
+    /// Text shown before the progress bar, such as the file being read
+    description: Option<String>,
+    /// Totals of a multi-file run, drawn on a second line
+    files: Option<FilesProgress>,
+    /// Processed byte counts at the most recent updates, for a moving-average throughput
+    throughput_window: VecDeque<(Instant, u64)>,
+    /// Input offset up to which [`Self::update_range`] has counted bytes
+    counted_to: Option<u64>,
+    /// Matches found so far in the run, once a search has started counting them
+    matches: Option<u64>,
+}
+
+/// Overall progress of a multi-file run
+struct FilesProgress {
+    file_count: usize,
+    files_started: usize,
+    total_bytes: u64,
+    processed_bytes: u64,
 }
 
 impl ProgressIndicator {
@@ -27,9 +58,72 @@ impl ProgressIndicator {
             processed_bytes: 0,
             enabled: show_progress && total_bytes > 0,
             show_progress,
+            use_color: color_context::use_color(ColorStream::Stderr),
+            line: None,
+            description: None,
+            files: None,
+            throughput_window: VecDeque::new(),
+            counted_to: None,
+            matches: None,
         }
     }
 
+    /// Create a progress indicator for a multi-file run
+    ///
+    /// The first line shows the current file, started with [`Self::start_file`], and the
+    /// second one the files and bytes of the whole run. Both are drawn through a progress
+    /// line on stderr, replaced by [`Self::set_line`] when shared with the data writer, so
+    /// the data output is never silenced.
+    pub fn multi_file_new(file_count: usize, total_bytes: u64) -> Self {
+        let mut progress = Self::new_silent_only(true);
+        progress.line = Some(ProgressLine::new(Box::new(io::stderr())));
+        progress.files = Some(FilesProgress {
+            file_count,
+            files_started: 0,
+            total_bytes,
+            processed_bytes: 0,
+        });
+        progress
+    }
+
+    /// Show `description` before the progress bar
+    pub fn set_description(&mut self, description: &str) {
+        self.description = Some(description.to_string());
+    }
+
+    /// Restart the per-file progress for the next file of a multi-file run
+    pub fn start_file(&mut self, file_size: u64) {
+        if let Some(files) = &mut self.files {
+            files.files_started += 1;
+        }
+        self.start_time = Instant::now();
+        self.total_bytes = file_size;
+        self.processed_bytes = 0;
+        self.throughput_window.clear();
+        self.counted_to = None;
+        if self.enabled {
+            self.display_progress();
+        }
+    }
+
+    /// Set the total once the input size is known, such as after opening a forensic image
+    ///
+    /// A known total turns the spinner into a percentage bar.
+    pub fn set_total(&mut self, total_bytes: u64) {
+        self.total_bytes = total_bytes;
+        if total_bytes > 0 {
+            self.enabled = self.show_progress;
+        }
+    }
+
+    /// Draw through a progress line shared with the data writer
+    ///
+    /// Data is then written while the progress is shown instead of being silenced, with
+    /// the progress line cleared and redrawn around it.
+    pub fn set_line(&mut self, line: Option<ProgressLine>) {
+        self.line = line;
+    }
+
     /// Update progress with the number of bytes processed
     ///
     /// # Arguments
@@ -37,6 +131,9 @@ impl ProgressIndicator {
     /// * `bytes_processed` - Additional bytes processed since last update
     pub fn update(&mut self, bytes_processed: u64) {
         self.processed_bytes = self.processed_bytes.saturating_add(bytes_processed);
+        if let Some(files) = &mut self.files {
+            files.processed_bytes = files.processed_bytes.saturating_add(bytes_processed);
+        }
 
         if !self.enabled {
             return;
@@ -46,20 +143,93 @@ impl ProgressIndicator {
 
         // Update progress every 100ms
         if now.duration_since(self.last_update) >= Duration::from_millis(100) {
+            self.record_throughput(now);
             self.display_progress();
             self.last_update = now;
         }
     }
 
+    /// Update progress with the input range just read
+    ///
+    /// Bytes read again, such as the overlap a buffer shares with the previous one, are
+    /// counted once, and bytes jumped over since the previous range, such as holes in a
+    /// sparse file, count as processed. The progress therefore never moves backwards.
+    pub fn update_range(&mut self, range: Range<u64>) {
+        let counted_to = self.counted_to.unwrap_or(range.start);
+        self.counted_to = Some(counted_to.max(range.end));
+        self.update(range.end.saturating_sub(counted_to));
+    }
+
+    /// Count `count` more matches, shown from then on as `N matches` on the progress line
+    ///
+    /// The count runs over the whole run and is not reset by [`Self::start_file`].
+    pub fn add_matches(&mut self, count: u64) {
+        self.matches = Some(self.matches.unwrap_or(0).saturating_add(count));
+    }
+
+    /// Remember the processed byte count at `now`, keeping the last `THROUGHPUT_WINDOW` intervals
+    fn record_throughput(&mut self, now: Instant) {
+        if self.throughput_window.is_empty() {
+            self.throughput_window.push_back((self.start_time, 0));
+        }
+        self.throughput_window.push_back((now, self.processed_bytes));
+        while self.throughput_window.len() > THROUGHPUT_WINDOW + 1 {
+            self.throughput_window.pop_front();
+        }
+    }
+
+    /// Bytes per second over the recent update intervals
+    ///
+    /// Unlike the average since the start, this follows changes in I/O speed. Before the
+    /// first interval is recorded the average since the start is used.
+    fn smoothed_throughput(&self) -> f64 {
+        let (start, start_bytes, end, end_bytes) = match (self.throughput_window.front(), self.throughput_window.back()) {
+            (Some(&(start, start_bytes)), Some(&(end, end_bytes))) if end > start => (start, start_bytes, end, end_bytes),
+            _ => (self.start_time, 0, Instant::now(), self.processed_bytes),
+        };
+
+        let seconds = end.duration_since(start).as_secs_f64();
+        if seconds > 0.0 {
+            end_bytes.saturating_sub(start_bytes) as f64 / seconds
+        } else {
+            0.0
+        }
+    }
+
     /// Set the progress to completed
+    ///
+    /// In a multi-file run this completes the current file only, and the lines stay until
+    /// [`Self::finish_files`].
     pub fn finish(&mut self) {
         if !self.enabled {
             return;
         }
 
+        if let Some(files) = &mut self.files {
+            files.processed_bytes += self.total_bytes.saturating_sub(self.processed_bytes);
+        }
         self.processed_bytes = self.total_bytes;
         self.display_progress();
-        eprintln!(); // New line after progress
+        if self.files.is_none() {
+            self.end_line();
+        }
+    }
+
+    /// Complete a multi-file run, leaving its final progress on screen
+    pub fn finish_files(&mut self) {
+        if !self.enabled {
+            return;
+        }
+
+        self.display_progress();
+        self.end_line();
+    }
+
+    fn end_line(&self) {
+        match &self.line {
+            Some(line) => line.finish(),
+            None => eprintln!(), // New line after progress
+        }
     }
 
     /// Display current progress
@@ -69,54 +239,91 @@ impl ProgressIndicator {
         }
 
         let elapsed = self.start_time.elapsed();
-        let bytes_per_sec = if elapsed.as_secs_f64() > 0.0 {
-            self.processed_bytes as f64 / elapsed.as_secs_f64()
-        } else {
-            0.0
-        };
+        let bytes_per_sec = self.smoothed_throughput();
 
         let (rate_value, rate_unit) = format_bytes_per_second(bytes_per_sec);
         let (processed_value, processed_unit) = format_bytes(self.processed_bytes);
 
-        if self.total_bytes > 0 {
+        let matches = match self.matches {
+            Some(count) => format!(", {} {}", format_count(count), if count == 1 { "match" } else { "matches" }),
+            None => String::new(),
+        };
+
+        let mut text = if self.total_bytes > 0 {
             // Known file size - show percentage progress bar
-            let percentage = (self.processed_bytes as f64 / self.total_bytes as f64 * 100.0) as u32;
+            let percentage = percentage(self.processed_bytes, self.total_bytes);
             let (total_value, total_unit) = format_bytes(self.total_bytes);
 
-            // Progress bar
-            let bar_width = 20;
-            let filled = (percentage as usize * bar_width) / 100;
-            let empty = bar_width - filled;
+            let remaining = self.total_bytes.saturating_sub(self.processed_bytes);
 
-            eprint!(
-                "\r[{}{}] {}% ({:.1} {}/{:.1} {}) {:.1} {}/s",
-                "=".repeat(filled),
-                " ".repeat(empty),
+            format!(
+                "{} {}% ({:.1} {}/{:.1} {}) {:.1} {}/s{}, ETA: {}",
+                self.bar(percentage),
                 percentage,
                 processed_value,
                 processed_unit,
                 total_value,
                 total_unit,
                 rate_value,
-                rate_unit
-            );
+                rate_unit,
+                matches,
+                format_eta(remaining, bytes_per_sec)
+            )
         } else {
             // Unknown file size - show spinner style
             let spinner_chars = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧'];
             let spinner_idx = (elapsed.as_millis() / 100) % spinner_chars.len() as u128;
             let spinner = spinner_chars[spinner_idx as usize];
 
-            eprint!(
-                "\r{} Processing... ({:.1} {}) {:.1} {}/s",
+            format!(
+                "{} Processing... ({:.1} {}) {:.1} {}/s{}",
                 spinner,
                 processed_value,
                 processed_unit,
                 rate_value,
-                rate_unit
+                rate_unit,
+                matches
+            )
+        };
+
+        if let Some(description) = &self.description {
+            text = format!("{} {}", description, text);
+        }
+        if let Some(files) = &self.files {
+            let percentage = percentage(files.processed_bytes, files.total_bytes);
+            let (processed_value, processed_unit) = format_bytes(files.processed_bytes);
+            let (total_value, total_unit) = format_bytes(files.total_bytes);
+            text = format!(
+                "{}\nFiles {}/{} {} {}% ({:.1} {}/{:.1} {})",
+                text,
+                files.files_started,
+                files.file_count,
+                self.bar(percentage),
+                percentage,
+                processed_value,
+                processed_unit,
+                total_value,
+                total_unit
             );
         }
 
-        let _ = io::stderr().flush();
+        match &self.line {
+            Some(line) => line.draw(&text),
+            None => {
+                eprint!("\r{}", text);
+                let _ = io::stderr().flush();
+            }
+        }
+    }
+
+    /// Progress bar for a percentage, 20 characters wide between brackets
+    fn bar(&self, percentage: u32) -> String {
+        let bar_width = 20;
+        let filled = (percentage.min(100) as usize * bar_width) / 100;
+        let empty = bar_width - filled;
+        let bar = "=".repeat(filled);
+        let bar = if self.use_color { bar.green().to_string() } else { bar };
+        format!("[{}{}]", bar, " ".repeat(empty))
     }
 
     /// Create a progress indicator that's always disabled
@@ -128,6 +335,13 @@ impl ProgressIndicator {
             processed_bytes: 0,
             enabled: false,
             show_progress: false,
+            use_color: false,
+            line: None,
+            description: None,
+            files: None,
+            throughput_window: VecDeque::new(),
+            counted_to: None,
+            matches: None,
         }
     }
 
@@ -142,25 +356,214 @@ impl ProgressIndicator {
             processed_bytes: 0,
             enabled: show_progress, // Enable for silent mode even with unknown size
             show_progress,
+            use_color: color_context::use_color(ColorStream::Stderr),
+            line: None,
+            description: None,
+            files: None,
+            throughput_window: VecDeque::new(),
+            counted_to: None,
+            matches: None,
         }
     }
 
     /// Check if progress should be shown based on output destination
     pub fn should_show_progress() -> bool {
         // Show progress only if stderr is a terminal (not redirected to file)
-        use std::os::unix::io::AsRawFd;
-        let stderr_fd = io::stderr().as_raw_fd();
-        unsafe { libc::isatty(stderr_fd) != 0 }
+        Self::shows_progress_on(&io::stderr())
+    }
+
+    /// Whether progress drawn to `stream` would be seen, which it is only on a terminal
+    fn shows_progress_on(stream: &impl IsTerminal) -> bool {
+        stream.is_terminal()
     }
 
-    /// Check if output should be silenced (when progress is enabled)
+    /// Check if output should be silenced (when progress is enabled without a shared line)
     pub fn is_silent(&self) -> bool {
-        self.enabled
+        self.enabled && self.line.is_none()
     }
 }
 
+/// Progress line shared by the progress indicator and the data writer
+///
+/// When stdout and stderr are the same terminal, the carriage-return progress line ends
+/// up in the middle of data lines. Through this line the data writer clears the progress
+/// before writing and redraws it once the written data ends at a line boundary; progress
+/// updates while a data line is unfinished are only remembered. Progress text with
+/// several lines is erased and redrawn as a block.
+#[derive(Clone)]
+pub struct ProgressLine {
+    state: Arc<Mutex<ProgressLineState>>,
+}
+
+struct ProgressLineState {
+    stderr: Box<dyn Write + Send>,
+    /// Last progress text, redrawn after data writes
+    text: String,
+    /// Whether the progress text is currently on screen
+    visible: bool,
+    /// Number of lines the progress text on screen takes
+    lines: usize,
+    /// Whether the data written so far ends in the middle of a line
+    mid_line: bool,
+}
+
+impl ProgressLine {
+    /// Create a progress line drawn to `stderr`
+    pub fn new(stderr: Box<dyn Write + Send>) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(ProgressLineState {
+                stderr,
+                text: String::new(),
+                visible: false,
+                lines: 0,
+                mid_line: false,
+            })),
+        }
+    }
+
+    /// Create a progress line if stdout and stderr are both terminals
+    pub fn for_terminals() -> Option<Self> {
+        (io::stdout().is_terminal() && io::stderr().is_terminal()).then(|| Self::new(Box::new(io::stderr())))
+    }
+
+    fn lock(&self) -> MutexGuard<'_, ProgressLineState> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Show new progress text, unless a data line is unfinished
+    pub fn draw(&self, text: &str) {
+        let mut state = self.lock();
+        state.text = text.to_string();
+        if !state.mid_line {
+            state.redraw();
+        }
+    }
+
+    /// End the progress line with a newline so later output starts below it
+    pub fn finish(&self) {
+        let mut state = self.lock();
+        if state.visible {
+            let _ = writeln!(state.stderr);
+            let _ = state.stderr.flush();
+        }
+        state.text.clear();
+        state.visible = false;
+    }
+
+    /// Remove the progress text from the screen before data is written
+    fn clear(&self) {
+        let mut state = self.lock();
+        if state.visible {
+            let erase = format!("{}{}", CLEAR_LINE, "\x1b[1A\x1b[K".repeat(state.lines - 1));
+            let _ = write!(state.stderr, "{}", erase);
+            let _ = state.stderr.flush();
+            state.visible = false;
+        }
+    }
+
+    /// Record written data, redrawing the progress if it ended a line
+    fn data_written(&self, ends_line: bool) {
+        let mut state = self.lock();
+        state.mid_line = !ends_line;
+        if ends_line {
+            state.redraw();
+        }
+    }
+}
+
+impl ProgressLineState {
+    fn redraw(&mut self) {
+        if self.text.is_empty() {
+            return;
+        }
+        // Return to the first line of a block drawn before
+        if self.visible && self.lines > 1 {
+            let _ = write!(self.stderr, "\x1b[{}A", self.lines - 1);
+        }
+        // Each line but the last is erased up to its end, as the new text may be shorter
+        let _ = write!(self.stderr, "\r{}", self.text.replace('\n', "\x1b[K\n"));
+        let _ = self.stderr.flush();
+        self.visible = true;
+        self.lines = self.text.lines().count();
+    }
+}
+
+/// Data writer that clears and redraws a shared progress line around each write
+///
+/// Without a progress line, writes pass straight through. Each write is flushed to the
+/// inner writer before the progress is redrawn, so place this below any buffering.
+pub struct ProgressWriter<W: Write> {
+    inner: W,
+    line: Option<ProgressLine>,
+}
+
+impl<W: Write> ProgressWriter<W> {
+    /// Wrap `inner`, coordinating with `line` if one is given
+    pub fn new(inner: W, line: Option<ProgressLine>) -> Self {
+        Self { inner, line }
+    }
+}
+
+impl<W: Write> Write for ProgressWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let Some(line) = self.line.as_ref().filter(|_| !buf.is_empty()) else {
+            return self.inner.write(buf);
+        };
+
+        line.clear();
+        let written = self.inner.write(buf)?;
+        self.inner.flush()?;
+        line.data_written(buf[..written].ends_with(b"\n"));
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Share of `total` already processed, 0 for an unknown total
+fn percentage(processed: u64, total: u64) -> u32 {
+    if total == 0 {
+        return 0;
+    }
+    (processed as f64 / total as f64 * 100.0).min(100.0) as u32
+}
+
+/// Time left to process `remaining` bytes at `bytes_per_sec`, as `mm:ss` or, from an
+/// hour on, `h:mm:ss`
+///
+/// `--:--` while no rate is known yet.
+fn format_eta(remaining: u64, bytes_per_sec: f64) -> String {
+    let seconds = if remaining == 0 {
+        0
+    } else if bytes_per_sec > 0.0 {
+        (remaining as f64 / bytes_per_sec).ceil() as u64
+    } else {
+        return "--:--".to_string();
+    };
+    if seconds < 3600 {
+        format!("{:02}:{:02}", seconds / 60, seconds % 60)
+    } else {
+        format!("{}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60)
+    }
+}
+
+/// Format a count with `,` between groups of three digits, such as `1,284`
+fn format_count(count: u64) -> String {
+    let digits = count.to_string();
+    let mut formatted = String::with_capacity(digits.len() + digits.len() / 3);
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index) % 3 == 0 {
+            formatted.push(',');
+        }
+        formatted.push(digit);
+    }
+    formatted
+}
+
 /// Format bytes with appropriate unit
-fn format_bytes(bytes: u64) -> (f64, &'static str) {
+pub(crate) fn format_bytes(bytes: u64) -> (f64, &'static str) {
     const UNITS: &[(&str, u64)] = &[
         ("TB", 1024_u64.pow(4)),
         ("GB", 1024_u64.pow(3)),
@@ -201,6 +604,12 @@ fn format_bytes_per_second(bytes_per_sec: f64) -> (f64, &'static str) {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_no_progress_when_stderr_is_redirected() {
+        let redirected = tempfile::tempfile().unwrap();
+        assert!(!ProgressIndicator::shows_progress_on(&redirected));
+    }
+
     #[test]
     fn test_format_bytes() {
         assert_eq!(format_bytes(0), (0.0, "B"));
@@ -241,6 +650,225 @@ mod tests {
         assert_eq!(progress.processed_bytes, 1000);
     }
 
+    #[test]
+    fn test_update_range_counts_overlap_once() {
+        let mut progress = ProgressIndicator::new(200, false);
+        // 64-byte buffers that overlap by 8 bytes
+        progress.update_range(0..64);
+        progress.update_range(56..120);
+        progress.update_range(112..176);
+        assert_eq!(progress.processed_bytes, 176);
+
+        // A range re-read behind the counted offset adds nothing
+        progress.update_range(100..150);
+        assert_eq!(progress.processed_bytes, 176);
+
+        // Skipped bytes before the next range count as processed
+        progress.update_range(190..200);
+        assert_eq!(progress.processed_bytes, 200);
+        assert_eq!(percentage(progress.processed_bytes, 200), 100);
+        assert_eq!(percentage(250, 200), 100);
+    }
+
+    /// Stdout and stderr writing to one terminal, recorded in order
+    #[derive(Clone, Default)]
+    struct Terminal(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Terminal {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Terminal {
+        fn take(&self) -> String {
+            String::from_utf8(std::mem::take(&mut *self.0.lock().unwrap())).unwrap()
+        }
+    }
+
+    #[test]
+    fn test_progress_line_cleared_and_redrawn_around_data() {
+        let terminal = Terminal::default();
+        let line = ProgressLine::new(Box::new(terminal.clone()));
+        let mut out = ProgressWriter::new(terminal.clone(), Some(line.clone()));
+
+        line.draw("[50%]");
+        out.write_all(b"0h : 4D 5A\n").unwrap();
+        assert_eq!(terminal.take(), "\r[50%]\r\x1b[K0h : 4D 5A\n\r[50%]");
+
+        // Progress is held back until the unfinished data line ends
+        out.write_all(b"10h : 50").unwrap();
+        line.draw("[60%]");
+        out.write_all(b" 4B\n").unwrap();
+        assert_eq!(terminal.take(), "\r\x1b[K10h : 50 4B\n\r[60%]");
+
+        line.finish();
+        out.write_all(b"20h : 00\n").unwrap();
+        assert_eq!(terminal.take(), "\n20h : 00\n");
+    }
+
+    #[test]
+    fn test_progress_writer_without_line_passes_through() {
+        let terminal = Terminal::default();
+        let mut out = ProgressWriter::new(terminal.clone(), None);
+        out.write_all(b"0h : 4D 5A\n").unwrap();
+        assert_eq!(terminal.take(), "0h : 4D 5A\n");
+    }
+
+    #[test]
+    fn test_shared_line_keeps_output() {
+        let mut progress = ProgressIndicator::new(1000, true);
+        assert!(progress.is_silent());
+
+        progress.set_line(Some(ProgressLine::new(Box::new(io::sink()))));
+        assert!(!progress.is_silent());
+    }
+
+    #[test]
+    fn test_disabled_draws_nothing() {
+        let terminal = Terminal::default();
+        let mut progress = ProgressIndicator::disabled();
+        progress.set_line(Some(ProgressLine::new(Box::new(terminal.clone()))));
+        progress.set_description("a.bin");
+
+        progress.start_file(1000);
+        progress.update(500);
+        std::thread::sleep(Duration::from_millis(110));
+        progress.update(500);
+        progress.finish();
+        progress.finish_files();
+
+        assert_eq!(terminal.take(), "");
+        assert!(!progress.is_silent());
+    }
+
+    #[test]
+    fn test_set_total_enables_progress() {
+        let mut progress = ProgressIndicator::new_silent_only(true);
+        progress.set_total(4096);
+        assert_eq!(progress.total_bytes, 4096);
+        assert!(progress.enabled);
+
+        let mut hidden = ProgressIndicator::new_silent_only(false);
+        hidden.set_total(4096);
+        assert!(!hidden.enabled);
+    }
+
+    #[test]
+    fn test_multi_file_progress() {
+        crate::color_context::set_color_choice(crate::cli::ColorChoice::Never);
+        let terminal = Terminal::default();
+        let mut progress = ProgressIndicator::multi_file_new(2, 300);
+        progress.set_line(Some(ProgressLine::new(Box::new(terminal.clone()))));
+        assert!(!progress.is_silent());
+
+        progress.set_description("a.bin");
+        progress.start_file(100);
+        let drawn = terminal.take();
+        assert!(drawn.starts_with("\ra.bin [                    ] 0% "), "{:?}", drawn);
+        assert!(drawn.contains("\x1b[K\nFiles 1/2 [                    ] 0% (0.0 B/300.0 B)"), "{:?}", drawn);
+
+        // Finishing a file completes its bar but keeps both lines for the next file
+        progress.finish();
+        let drawn = terminal.take();
+        assert!(drawn.starts_with("\x1b[1A\ra.bin [====================] 100% "), "{:?}", drawn);
+        assert!(drawn.contains("Files 1/2 [======              ] 33% (100.0 B/300.0 B)"), "{:?}", drawn);
+
+        progress.set_description("b.bin");
+        progress.start_file(200);
+        progress.finish_files();
+        let drawn = terminal.take();
+        assert!(drawn.contains("\rb.bin [                    ] 0% "), "{:?}", drawn);
+        assert!(drawn.ends_with("Files 2/2 [======              ] 33% (100.0 B/300.0 B)\n"), "{:?}", drawn);
+    }
+
+    #[test]
+    fn test_multi_line_progress_erased_as_block() {
+        let terminal = Terminal::default();
+        let line = ProgressLine::new(Box::new(terminal.clone()));
+        let mut out = ProgressWriter::new(terminal.clone(), Some(line.clone()));
+
+        line.draw("a.bin\nFiles 1/2");
+        out.write_all(b"0h : 4D 5A\n").unwrap();
+        assert_eq!(
+            terminal.take(),
+            "\ra.bin\x1b[K\nFiles 1/2\r\x1b[K\x1b[1A\x1b[K0h : 4D 5A\n\ra.bin\x1b[K\nFiles 1/2"
+        );
+    }
+
+    #[test]
+    fn test_smoothed_throughput_follows_recent_rate() {
+        let mut progress = ProgressIndicator::new(u64::MAX, false);
+        let start = progress.start_time;
+        let at = |millis: u64| start + Duration::from_millis(millis);
+
+        // 1 MB/s for 10 seconds, then 4 MB/s
+        for second in 1..=10 {
+            progress.processed_bytes += 1_000_000;
+            progress.record_throughput(at(second * 1000));
+        }
+        assert!((progress.smoothed_throughput() - 1_000_000.0).abs() < 1.0);
+
+        for second in 11..=30 {
+            progress.processed_bytes += 4_000_000;
+            progress.record_throughput(at(second * 1000));
+        }
+        // The window only holds the last 10 intervals, so the rate converges to the new speed
+        assert_eq!(progress.throughput_window.len(), THROUGHPUT_WINDOW + 1);
+        assert!((progress.smoothed_throughput() - 4_000_000.0).abs() < 1.0);
+
+        // The average since the start would still lag behind
+        let average = progress.processed_bytes as f64 / 30.0;
+        assert!(average < 3_100_000.0);
+    }
+
+    #[test]
+    fn test_format_eta() {
+        assert_eq!(format_eta(0, 0.0), "00:00");
+        assert_eq!(format_eta(1024, 0.0), "--:--");
+        assert_eq!(format_eta(1024, 1024.0), "00:01");
+        assert_eq!(format_eta(3599 * 100, 100.0), "59:59");
+        assert_eq!(format_eta(3600 * 100, 100.0), "1:00:00");
+        assert_eq!(format_eta(3 * 3600 * 100 + 61 * 100, 100.0), "3:01:01");
+    }
+
+    #[test]
+    fn test_format_count() {
+        assert_eq!(format_count(0), "0");
+        assert_eq!(format_count(999), "999");
+        assert_eq!(format_count(1284), "1,284");
+        assert_eq!(format_count(1_000_000), "1,000,000");
+        assert_eq!(format_count(u64::MAX), "18,446,744,073,709,551,615");
+    }
+
+    #[test]
+    fn test_match_count_on_progress_line() {
+        let terminal = Terminal::default();
+        let mut progress = ProgressIndicator::new(100, true);
+        progress.set_line(Some(ProgressLine::new(Box::new(terminal.clone()))));
+
+        // Nothing is shown until a search starts counting
+        progress.finish();
+        assert!(!terminal.take().contains("match"));
+
+        progress.start_file(100);
+        progress.add_matches(0);
+        progress.add_matches(1);
+        progress.finish();
+        assert!(terminal.take().contains("/s, 1 match, ETA: 00:00"));
+
+        // The count carries over to the next file
+        progress.start_file(100);
+        progress.add_matches(1283);
+        progress.finish();
+        assert!(terminal.take().contains("/s, 1,284 matches, ETA: 00:00"));
+    }
+
     #[test]
     fn test_progress_overflow() {
         let mut progress = ProgressIndicator::new(100, false);
@@ -1,5 +1,39 @@
+use crate::cli::{Endian, StringEncoding};
 use crate::error::{BingrepError, Result};
-use regex::bytes::Regex;
+use regex::bytes::{Match, Regex, RegexBuilder};
+use regex_syntax::hir::{Class, Hir, HirKind};
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Default size limit of a compiled regex (10MB, the regex crate's own default)
+pub const DEFAULT_REGEX_SIZE_LIMIT: usize = 10 * 1024 * 1024;
+
+static REGEX_SIZE_LIMIT: AtomicUsize = AtomicUsize::new(DEFAULT_REGEX_SIZE_LIMIT);
+
+/// Set the size limit of every regex compiled afterwards (`--regex-size-limit`)
+pub fn set_regex_size_limit(limit: usize) {
+    REGEX_SIZE_LIMIT.store(limit, Ordering::SeqCst);
+}
+
+/// Size limit of a compiled regex
+pub fn regex_size_limit() -> usize {
+    REGEX_SIZE_LIMIT.load(Ordering::SeqCst)
+}
+
+/// Compile a regex source with the configured size limit
+///
+/// Exceeding the limit is reported as [`BingrepError::PatternTooComplex`].
+fn build_regex(source: &str) -> Result<Regex> {
+    RegexBuilder::new(source)
+        .size_limit(regex_size_limit())
+        .build()
+        .map_err(BingrepError::from)
+}
+
+/// Upper bound for jumps in hex patterns, well within the overlap between buffers and
+/// parallel chunks so a match spanning a jump is still found at their boundaries
+pub const MAX_HEX_JUMP: usize = 1024;
 
 /// Processor for handling regular expression patterns with hexadecimal escape sequences
 pub struct RegexProcessor;
@@ -23,6 +57,11 @@ impl RegexProcessor {
     /// let regex_with_quantifier = RegexProcessor::compile_pattern("\\x58{2,3}").unwrap();
     /// ```
     pub fn compile_pattern(expression: &str) -> Result<Regex> {
+        build_regex(&Self::pattern_source(expression)?)
+    }
+
+    /// Convert a user expression into the regex source compiled by [`Self::compile_pattern`]
+    fn pattern_source(expression: &str) -> Result<String> {
         let pattern = if expression.contains("\\x") && !Self::has_regex_metacharacters(expression) {
             // Simple \xHH pattern - convert to binary then escape for regex
             let binary_pattern = Self::parse_hex_pattern(expression)?;
@@ -37,7 +76,137 @@ impl RegexProcessor {
             Self::convert_hex_escapes_in_pattern(expression)?
         };
 
-        Regex::new(&pattern).map_err(BingrepError::from)
+        Ok(pattern)
+    }
+
+    /// Combine several `-e` expressions into a single alternation
+    ///
+    /// A single expression is returned unchanged. With several, each is converted on its own
+    /// and wrapped in a group, so a match is reported once at each offset by the first
+    /// pattern (in command-line order) that matches there. Unicode mode is disabled once for
+    /// the whole alternation, so `.` and classes match single bytes in every pattern.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hxgrep::RegexProcessor;
+    /// let combined = RegexProcessor::combine_patterns(&["\\x4d\\x5a".to_string(), "PK".to_string()])
+    ///     .unwrap()
+    ///     .unwrap();
+    /// assert_eq!(combined, "(?-u)(?:(?:\\x4d\\x5a)|(?:PK))");
+    /// ```
+    pub fn combine_patterns(expressions: &[String]) -> Result<Option<String>> {
+        match expressions {
+            [] => Ok(None),
+            [expression] => Ok(Some(expression.clone())),
+            _ => {
+                let (prefix, branches) = Self::alternation_branches(expressions)?;
+                let groups: Vec<String> = branches.iter().map(|branch| format!("(?:{})", branch)).collect();
+                Ok(Some(format!("{}(?:{})", prefix, groups.join("|"))))
+            }
+        }
+    }
+
+    /// Converted sources of the branches of [`Self::combine_patterns`]'s alternation
+    ///
+    /// Returns the flags shared by all branches and each branch's source. With several
+    /// expressions the branches drop their own `(?-u)` in favour of the shared one.
+    fn alternation_branches(expressions: &[String]) -> Result<(&'static str, Vec<String>)> {
+        let sources = expressions
+            .iter()
+            .map(|expression| Self::pattern_source(expression))
+            .collect::<Result<Vec<_>>>()?;
+        if sources.len() < 2 {
+            return Ok(("", sources));
+        }
+
+        let branches = sources
+            .into_iter()
+            .map(|source| source.strip_prefix("(?-u)").map(str::to_string).unwrap_or(source))
+            .collect();
+        Ok(("(?-u)", branches))
+    }
+
+    /// Compile several expressions into one regex matching any of them
+    ///
+    /// This is the regex the CLI builds from several `-e` options: see
+    /// [`Self::combine_patterns`]. Each expression must be valid on its own.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hxgrep::RegexProcessor;
+    /// let regex = RegexProcessor::compile_alternation(&["\\x4d\\x5a", "\\x50\\x4b\\x03\\x04"]).unwrap();
+    /// assert!(regex.is_match(b"..PK\x03\x04.."));
+    /// ```
+    pub fn compile_alternation(expressions: &[&str]) -> Result<Regex> {
+        let expressions: Vec<String> = expressions.iter().map(|expression| expression.to_string()).collect();
+        match Self::combine_patterns(&expressions)? {
+            Some(combined) => Self::compile_pattern(&combined),
+            None => Err(BingrepError::InvalidPattern("No pattern given".to_string())),
+        }
+    }
+
+    /// Whether any match of `regex` could include a zero byte
+    ///
+    /// Checks every literal and class of the compiled pattern rather than probing a run of
+    /// zeros, so patterns needing a long run (`\x00{5000}`) or mixing zeros with other bytes
+    /// (`\x00+MZ`) are caught too. A pattern that fails to parse is assumed to match zeros.
+    pub fn can_match_zero_byte(regex: &Regex) -> bool {
+        match regex_syntax::ParserBuilder::new().utf8(false).build().parse(regex.as_str()) {
+            Ok(hir) => Self::hir_matches_zero_byte(&hir),
+            Err(_) => true,
+        }
+    }
+
+    fn hir_matches_zero_byte(hir: &Hir) -> bool {
+        match hir.kind() {
+            HirKind::Empty | HirKind::Look(_) => false,
+            HirKind::Literal(literal) => literal.0.contains(&0),
+            // Class ranges are sorted, so only the first can start at zero
+            HirKind::Class(Class::Bytes(class)) => class.ranges().first().is_some_and(|range| range.start() == 0),
+            HirKind::Class(Class::Unicode(class)) => class.ranges().first().is_some_and(|range| range.start() == '\0'),
+            HirKind::Repetition(repetition) => {
+                repetition.max != Some(0) && Self::hir_matches_zero_byte(&repetition.sub)
+            }
+            HirKind::Capture(capture) => Self::hir_matches_zero_byte(&capture.sub),
+            HirKind::Concat(hirs) | HirKind::Alternation(hirs) => hirs.iter().any(Self::hir_matches_zero_byte),
+        }
+    }
+
+    /// Read the expressions of a pattern file, one per line
+    ///
+    /// Blank lines and lines starting with `#` are skipped, and surrounding whitespace is
+    /// ignored (use `\x20` for a space at either end). Every expression is compiled up front
+    /// so an invalid one is reported with its line number.
+    pub fn read_pattern_file<P: AsRef<Path>>(path: P) -> Result<Vec<String>> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)?;
+
+        let mut expressions = Vec::new();
+        for (index, line) in content.lines().enumerate() {
+            let expression = line.trim();
+            if expression.is_empty() || expression.starts_with('#') {
+                continue;
+            }
+            Self::compile_pattern(expression).map_err(|err| {
+                BingrepError::InvalidPattern(format!(
+                    "Pattern file {} line {}: {}",
+                    path.display(),
+                    index + 1,
+                    err
+                ))
+            })?;
+            expressions.push(expression.to_string());
+        }
+
+        if expressions.is_empty() {
+            return Err(BingrepError::InvalidPattern(format!(
+                "Pattern file {} has no patterns",
+                path.display()
+            )));
+        }
+        Ok(expressions)
     }
 
     /// Parse \xHH sequences into bytes
@@ -92,6 +261,423 @@ impl RegexProcessor {
         Ok(result)
     }
 
+    /// Compile a literal string into a regex matching exactly its encoded bytes
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Literal text to search for; regex metacharacters have no special meaning
+    /// * `encoding` - Encoding used to convert the text into bytes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hxgrep::RegexProcessor;
+    /// use hxgrep::cli::StringEncoding;
+    /// let regex = RegexProcessor::compile_literal("PK.", StringEncoding::Utf8).unwrap();
+    /// assert!(regex.is_match(b"xxPK.xx"));
+    /// assert!(!regex.is_match(b"xxPKAxx"));
+    /// ```
+    pub fn compile_literal(text: &str, encoding: StringEncoding) -> Result<Regex> {
+        build_regex(&Self::literal_pattern(text, encoding)?)
+    }
+
+    /// Convert a literal string into an escaped regex pattern for its encoded bytes
+    ///
+    /// The result can be passed anywhere a `-e` expression is accepted.
+    pub fn literal_pattern(text: &str, encoding: StringEncoding) -> Result<String> {
+        if text.is_empty() {
+            return Err(BingrepError::InvalidPattern(
+                "Literal string must not be empty".to_string(),
+            ));
+        }
+
+        Ok(Self::escape_bytes_for_regex(&Self::encode_literal(text, encoding)))
+    }
+
+    /// Encode a literal string into bytes
+    ///
+    /// Non-ASCII characters are encoded to their UTF-8 or UTF-16 code units.
+    pub fn encode_literal(text: &str, encoding: StringEncoding) -> Vec<u8> {
+        match encoding {
+            StringEncoding::Utf8 => text.as_bytes().to_vec(),
+            StringEncoding::Utf16le => text.encode_utf16().flat_map(u16::to_le_bytes).collect(),
+            StringEncoding::Utf16be => text.encode_utf16().flat_map(u16::to_be_bytes).collect(),
+        }
+    }
+
+    /// Convert an integer value into an escaped regex pattern for its encoded bytes
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - Decimal or `0x`-prefixed hexadecimal value (negative values for signed types)
+    /// * `integer_type` - Width and signedness of the integer
+    /// * `endian` - Byte order of the encoded value
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hxgrep::RegexProcessor;
+    /// use hxgrep::cli::Endian;
+    /// use hxgrep::regex_processor::IntegerType;
+    /// let pattern = RegexProcessor::integer_pattern("0x41424344", IntegerType::U32, Endian::Le).unwrap();
+    /// assert_eq!(pattern, "(?-u)\\x44\\x43\\x42\\x41");
+    /// ```
+    pub fn integer_pattern(value: &str, integer_type: IntegerType, endian: Endian) -> Result<String> {
+        Ok(Self::escape_bytes_for_regex(&Self::encode_integer(value, integer_type, endian)?))
+    }
+
+    /// Compile an unsigned integer into a regex matching its encoded bytes
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - Value to search for; it must fit in `width` bytes
+    /// * `width` - Width of the integer in bytes (2, 4 or 8)
+    /// * `endian` - Byte order of the encoded value
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hxgrep::RegexProcessor;
+    /// use hxgrep::cli::Endian;
+    /// let regex = RegexProcessor::compile_uint(0xDEADBEEF, 4, Endian::Le).unwrap();
+    /// assert!(regex.is_match(b"\x00\xEF\xBE\xAD\xDE\x00"));
+    /// assert!(!regex.is_match(b"\x00\xDE\xAD\xBE\xEF\x00"));
+    /// ```
+    pub fn compile_uint(value: u64, width: u8, endian: Endian) -> Result<Regex> {
+        let integer_type = match width {
+            2 => IntegerType::U16,
+            4 => IntegerType::U32,
+            8 => IntegerType::U64,
+            _ => {
+                return Err(BingrepError::InvalidPattern(format!(
+                    "Unsupported integer width: {} bytes (expected 2, 4 or 8)",
+                    width
+                )))
+            }
+        };
+        let pattern = Self::integer_pattern(&value.to_string(), integer_type, endian)?;
+        build_regex(&pattern)
+    }
+
+    /// Encode an integer value into bytes of the given width and byte order
+    pub fn encode_integer(value: &str, integer_type: IntegerType, endian: Endian) -> Result<Vec<u8>> {
+        let number = Self::parse_integer(value)?;
+        let out_of_range = || {
+            BingrepError::InvalidPattern(format!(
+                "Value {} is out of range for {}",
+                value,
+                integer_type.name()
+            ))
+        };
+
+        let (le, be) = match integer_type {
+            IntegerType::U16 => {
+                let n = u16::try_from(number).map_err(|_| out_of_range())?;
+                (n.to_le_bytes().to_vec(), n.to_be_bytes().to_vec())
+            }
+            IntegerType::U32 => {
+                let n = u32::try_from(number).map_err(|_| out_of_range())?;
+                (n.to_le_bytes().to_vec(), n.to_be_bytes().to_vec())
+            }
+            IntegerType::U64 => {
+                let n = u64::try_from(number).map_err(|_| out_of_range())?;
+                (n.to_le_bytes().to_vec(), n.to_be_bytes().to_vec())
+            }
+            IntegerType::I32 => {
+                let n = i32::try_from(number).map_err(|_| out_of_range())?;
+                (n.to_le_bytes().to_vec(), n.to_be_bytes().to_vec())
+            }
+        };
+
+        Ok(match endian {
+            Endian::Le => le,
+            Endian::Be => be,
+        })
+    }
+
+    /// Parse a decimal or `0x`-prefixed hexadecimal integer, with an optional leading `-`
+    fn parse_integer(value: &str) -> Result<i128> {
+        let trimmed = value.trim();
+        let (negative, digits) = match trimmed.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, trimmed),
+        };
+
+        let parsed = match digits.strip_prefix("0x").or_else(|| digits.strip_prefix("0X")) {
+            Some(hex) => i128::from_str_radix(hex, 16),
+            None => digits.parse::<i128>(),
+        };
+
+        match parsed {
+            Ok(number) if negative => Ok(-number),
+            Ok(number) => Ok(number),
+            Err(_) => Err(BingrepError::InvalidPattern(format!(
+                "Invalid integer value: {}",
+                value
+            ))),
+        }
+    }
+
+    /// Convert a YARA-style hex string with wildcards into a regex pattern
+    ///
+    /// Tokens are pairs of hex digits separated by optional whitespace. `??` matches any
+    /// byte, and a single `?` nibble (`4?`, `?D`) matches any value in that nibble.
+    /// Jumps skip a variable number of arbitrary bytes: `[N]` exactly N, `[N-M]` between
+    /// N and M, and `[N-]` at least N (capped at [`MAX_HEX_JUMP`]). A `{n}`, `{n,}` or
+    /// `{n,m}` quantifier directly after a byte token repeats that byte.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hxgrep::RegexProcessor;
+    /// let pattern = RegexProcessor::hex_wildcard_pattern("4D 5A ?? 9?").unwrap();
+    /// assert_eq!(pattern, "(?-u)\\x4d\\x5a[\\x00-\\xff][\\x90-\\x9f]");
+    ///
+    /// let pattern = RegexProcessor::hex_wildcard_pattern("00{2} 01 [4-6] 65").unwrap();
+    /// assert_eq!(pattern, "(?-u)\\x00{2}\\x01[\\x00-\\xff]{4,6}\\x65");
+    /// ```
+    pub fn hex_wildcard_pattern(pattern: &str) -> Result<String> {
+        let mut result = String::from("(?-u)");
+        let mut token_count = 0;
+        // Quantifiers only apply to the byte token written just before them
+        let mut after_byte = false;
+
+        for word in pattern.split_whitespace() {
+            let mut rest = word;
+            while !rest.is_empty() {
+                if rest.starts_with('[') || rest.starts_with('{') {
+                    let close = if rest.starts_with('[') { ']' } else { '}' };
+                    let end = rest.find(close).ok_or_else(|| {
+                        BingrepError::InvalidPattern(format!("Unterminated token '{}' in hex pattern", rest))
+                    })?;
+                    let group = &rest[..=end];
+                    if close == ']' {
+                        result.push_str(&Self::hex_jump_token(group)?);
+                        token_count += 1;
+                    } else if !after_byte {
+                        return Err(BingrepError::InvalidPattern(format!(
+                            "Quantifier '{}' must directly follow a byte token",
+                            group
+                        )));
+                    } else {
+                        result.push_str(&Self::hex_quantifier_token(group)?);
+                    }
+                    after_byte = false;
+                    rest = &rest[end + 1..];
+                    continue;
+                }
+
+                let end = rest.find(['[', '{']).unwrap_or(rest.len());
+                let digits = &rest[..end];
+                let chars: Vec<char> = digits.chars().collect();
+                if chars.len() % 2 != 0 {
+                    return Err(BingrepError::InvalidPattern(format!(
+                        "Malformed hex token '{}': expected pairs of hex digits or '?'",
+                        digits
+                    )));
+                }
+
+                for pair in chars.chunks(2) {
+                    let token: String = pair.iter().collect();
+                    result.push_str(&Self::hex_wildcard_token(&token, pair[0], pair[1])?);
+                    token_count += 1;
+                    after_byte = true;
+                }
+                rest = &rest[end..];
+            }
+        }
+
+        if token_count == 0 {
+            return Err(BingrepError::InvalidPattern(
+                "Hex pattern must not be empty".to_string(),
+            ));
+        }
+
+        Ok(result)
+    }
+
+    /// Convert a `[N]`, `[N-M]` or `[N-]` jump into a bounded run of arbitrary bytes
+    fn hex_jump_token(token: &str) -> Result<String> {
+        let malformed = || {
+            BingrepError::InvalidPattern(format!(
+                "Malformed jump '{}': expected [N], [N-M] or [N-]",
+                token
+            ))
+        };
+        let bound = |text: &str| text.trim().parse::<usize>().map_err(|_| malformed());
+
+        let inner = &token[1..token.len() - 1];
+        let (min, max) = match inner.split_once('-') {
+            None => {
+                let n = bound(inner)?;
+                (n, n)
+            }
+            Some((low, "")) => {
+                let n = bound(low)?;
+                (n, n.max(MAX_HEX_JUMP))
+            }
+            Some((low, high)) => (bound(low)?, bound(high)?),
+        };
+
+        if min > max {
+            return Err(BingrepError::InvalidPattern(format!(
+                "Invalid jump '{}': lower bound exceeds upper bound",
+                token
+            )));
+        }
+        if max > MAX_HEX_JUMP {
+            return Err(BingrepError::InvalidPattern(format!(
+                "Jump '{}' exceeds the maximum of {} bytes",
+                token, MAX_HEX_JUMP
+            )));
+        }
+
+        Ok(if min == max {
+            format!("[\\x00-\\xff]{{{}}}", min)
+        } else {
+            format!("[\\x00-\\xff]{{{},{}}}", min, max)
+        })
+    }
+
+    /// Validate a `{n}`, `{n,}` or `{n,m}` quantifier and pass it through to the regex
+    fn hex_quantifier_token(token: &str) -> Result<String> {
+        let inner = &token[1..token.len() - 1];
+        let valid = match inner.split_once(',') {
+            None => inner.parse::<usize>().is_ok(),
+            Some((low, "")) => low.parse::<usize>().is_ok(),
+            Some((low, high)) => matches!(
+                (low.parse::<usize>(), high.parse::<usize>()),
+                (Ok(low), Ok(high)) if low <= high
+            ),
+        };
+
+        if valid {
+            Ok(token.to_string())
+        } else {
+            Err(BingrepError::InvalidPattern(format!(
+                "Malformed quantifier '{}': expected {{n}}, {{n,}} or {{n,m}}",
+                token
+            )))
+        }
+    }
+
+    /// Convert a single two-character hex token into a byte or byte-class regex
+    fn hex_wildcard_token(token: &str, high: char, low: char) -> Result<String> {
+        let malformed = || {
+            BingrepError::InvalidPattern(format!(
+                "Malformed hex token '{}': expected two hex digits, '??', 'H?' or '?H'",
+                token
+            ))
+        };
+        let nibble = |c: char| match c {
+            '?' => Ok(None),
+            _ => c.to_digit(16).map(|d| Some(d as u8)).ok_or_else(malformed),
+        };
+
+        Ok(match (nibble(high)?, nibble(low)?) {
+            (Some(h), Some(l)) => format!("\\x{:02x}", h << 4 | l),
+            (None, None) => "[\\x00-\\xff]".to_string(),
+            (Some(h), None) => format!("[\\x{:02x}-\\x{:02x}]", h << 4, h << 4 | 0x0f),
+            (None, Some(l)) => {
+                let bytes: String = (0..16u8).map(|h| format!("\\x{:02x}", h << 4 | l)).collect();
+                format!("[{}]", bytes)
+            }
+        })
+    }
+
+    /// Convert a `VALUE:MASK` pair into a regex matching bytes equal to the value under the mask
+    ///
+    /// Both sides are hex strings of the same length (optional `0x` prefix and spaces).
+    /// Each byte `b` at a position matches when `b & mask == value & mask`, so only the bits
+    /// set in the mask are compared. Each position becomes a byte class written as ranges,
+    /// e.g. value `80` with mask `F0` matches `0x80`-`0x8F`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hxgrep::RegexProcessor;
+    /// let pattern = RegexProcessor::mask_pattern("80:f0").unwrap();
+    /// assert_eq!(pattern, "(?-u)[\\x80-\\x8f]");
+    /// ```
+    pub fn mask_pattern(spec: &str) -> Result<String> {
+        let (value, mask) = spec.split_once(':').ok_or_else(|| {
+            BingrepError::InvalidPattern(format!("Mask '{}' must be in VALUE:MASK form", spec))
+        })?;
+        let value = Self::parse_hex_bytes(value)?;
+        let mask = Self::parse_hex_bytes(mask)?;
+
+        if value.is_empty() || value.len() != mask.len() {
+            return Err(BingrepError::InvalidPattern(format!(
+                "Mask '{}' needs a non-empty value and mask of the same length",
+                spec
+            )));
+        }
+
+        let classes: String = value
+            .iter()
+            .zip(&mask)
+            .map(|(&value, &mask)| Self::masked_byte_class(value, mask))
+            .collect();
+        Ok(format!("(?-u){}", classes))
+    }
+
+    /// Build the regex for a single byte position of a mask pattern
+    fn masked_byte_class(value: u8, mask: u8) -> String {
+        let target = value & mask;
+        if mask == 0xFF {
+            return format!("\\x{:02x}", target);
+        }
+
+        // Collapse the matching bytes into contiguous ranges
+        let mut ranges: Vec<(u8, u8)> = Vec::new();
+        for byte in (0..=0xFFu8).filter(|&b| b & mask == target) {
+            match ranges.last_mut() {
+                Some((_, end)) if *end as u16 + 1 == byte as u16 => *end = byte,
+                _ => ranges.push((byte, byte)),
+            }
+        }
+
+        let class: String = ranges
+            .iter()
+            .map(|&(start, end)| {
+                if start == end {
+                    format!("\\x{:02x}", start)
+                } else {
+                    format!("\\x{:02x}-\\x{:02x}", start, end)
+                }
+            })
+            .collect();
+        format!("[{}]", class)
+    }
+
+    /// Parse a hex byte string such as `0x8000` or `80 00`
+    fn parse_hex_bytes(text: &str) -> Result<Vec<u8>> {
+        let digits: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+        let digits = digits
+            .strip_prefix("0x")
+            .or_else(|| digits.strip_prefix("0X"))
+            .unwrap_or(&digits);
+
+        if digits.len() % 2 != 0 {
+            return Err(BingrepError::InvalidPattern(format!(
+                "Hex value '{}' must have an even number of digits",
+                text.trim()
+            )));
+        }
+
+        (0..digits.len())
+            .step_by(2)
+            .map(|i| {
+                digits
+                    .get(i..i + 2)
+                    .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                    .ok_or_else(|| {
+                        BingrepError::InvalidPattern(format!("Invalid hex value '{}'", text.trim()))
+                    })
+            })
+            .collect()
+    }
+
     /// Escape bytes for regex use
     ///
     /// Converts a byte array into a regex-compatible string that disables Unicode mode
@@ -182,6 +768,158 @@ impl RegexProcessor {
     }
 }
 
+/// Individually compiled `-e` patterns, used to tell which one produced a match
+///
+/// Each pattern is anchored at the start of the haystack and compiled with the same flags
+/// as its branch in [`RegexProcessor::combine_patterns`]. Since alternation prefers the
+/// earliest branch, the first pattern matching at a match's start is the one that matched.
+#[derive(Debug, Clone)]
+pub struct PatternSet {
+    anchored: Vec<Regex>,
+}
+
+impl PatternSet {
+    /// Compile each expression anchored at the start of the haystack
+    pub fn new(expressions: &[String]) -> Result<Self> {
+        let (prefix, branches) = RegexProcessor::alternation_branches(expressions)?;
+        let anchored = branches
+            .iter()
+            .map(|branch| build_regex(&format!("^{}(?:{})", prefix, branch)))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { anchored })
+    }
+
+    /// Number of patterns in the set
+    pub fn len(&self) -> usize {
+        self.anchored.len()
+    }
+
+    /// Whether the set has no patterns
+    pub fn is_empty(&self) -> bool {
+        self.anchored.is_empty()
+    }
+
+    /// Zero-based index of the pattern matching at the start of `haystack`
+    ///
+    /// `haystack` should begin at the match and extend as far as the searched data does.
+    pub fn index_at(&self, haystack: &[u8]) -> Option<usize> {
+        self.anchored.iter().position(|regex| regex.is_match(haystack))
+    }
+}
+
+/// Integer types accepted by the value search options
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntegerType {
+    U16,
+    U32,
+    U64,
+    I32,
+}
+
+impl IntegerType {
+    /// Option-style name of the type, used in error messages
+    pub fn name(&self) -> &'static str {
+        match self {
+            IntegerType::U16 => "u16",
+            IntegerType::U32 => "u32",
+            IntegerType::U64 => "u64",
+            IntegerType::I32 => "i32",
+        }
+    }
+}
+
+/// Constraint that only accepts matches starting at `offset % boundary == remainder`
+///
+/// Offsets are absolute file offsets, independent of the seek position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MatchAlignment {
+    /// Alignment boundary in bytes (e.g. 512 or 4096)
+    pub boundary: u64,
+    /// Required remainder of the match offset modulo the boundary
+    pub remainder: u64,
+}
+
+impl MatchAlignment {
+    /// Create a new alignment constraint
+    pub fn new(boundary: u64, remainder: u64) -> Self {
+        Self { boundary, remainder }
+    }
+
+    /// Check whether a match at the given absolute offset satisfies the constraint
+    pub fn accepts(&self, offset: u64) -> bool {
+        offset % self.boundary == self.remainder
+    }
+
+    /// Smallest offset at or after `offset` that satisfies the constraint
+    pub fn next_aligned(&self, offset: u64) -> u64 {
+        let base = offset - offset % self.boundary;
+        let candidate = base.saturating_add(self.remainder);
+        if candidate >= offset {
+            candidate
+        } else {
+            candidate.saturating_add(self.boundary)
+        }
+    }
+
+    /// Iterate over the regex matches in `haystack` that start at aligned offsets
+    ///
+    /// Unlike filtering `find_iter`, the search restarts at every aligned offset, so an
+    /// aligned match is not hidden by an overlapping unaligned one.
+    ///
+    /// # Arguments
+    ///
+    /// * `regex` - Compiled regex pattern to search for
+    /// * `haystack` - Data to search in
+    /// * `base_offset` - Absolute file offset of the first byte of `haystack`
+    pub fn find_iter<'r, 'h>(
+        &self,
+        regex: &'r Regex,
+        haystack: &'h [u8],
+        base_offset: u64,
+    ) -> AlignedMatches<'r, 'h> {
+        AlignedMatches {
+            alignment: *self,
+            regex,
+            haystack,
+            base_offset,
+            pos: 0,
+        }
+    }
+}
+
+/// Iterator over matches starting at aligned offsets, created by [`MatchAlignment::find_iter`]
+pub struct AlignedMatches<'r, 'h> {
+    alignment: MatchAlignment,
+    regex: &'r Regex,
+    haystack: &'h [u8],
+    base_offset: u64,
+    pos: usize,
+}
+
+impl<'r, 'h> Iterator for AlignedMatches<'r, 'h> {
+    type Item = Match<'h>;
+
+    fn next(&mut self) -> Option<Match<'h>> {
+        loop {
+            let aligned = self
+                .alignment
+                .next_aligned(self.base_offset + self.pos as u64)
+                - self.base_offset;
+            if aligned > self.haystack.len() as u64 {
+                return None;
+            }
+            self.pos = aligned as usize;
+
+            let mat = self.regex.find_at(self.haystack, self.pos)?;
+            if self.alignment.accepts(self.base_offset + mat.start() as u64) {
+                self.pos = mat.end().max(mat.start() + 1);
+                return Some(mat);
+            }
+            self.pos = mat.start() + 1;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -289,4 +1027,371 @@ mod tests {
 
         // Files are automatically deleted when NamedTempFile goes out of scope
     }
+
+    #[test]
+    fn test_compile_alternation_hex_patterns() {
+        let regex = RegexProcessor::compile_alternation(&["\\x4d\\x5a", "\\x50\\x4b\\x03\\x04"]).unwrap();
+        assert_eq!(regex.as_str(), "(?-u)(?:(?:\\x4d\\x5a)|(?:\\x50\\x4b\\x03\\x04))");
+
+        let data = b"\x00MZ\x00\x00PK\x03\x04";
+        let starts: Vec<usize> = regex.find_iter(data).map(|m| m.start()).collect();
+        assert_eq!(starts, vec![1, 5]);
+    }
+
+    #[test]
+    fn test_compile_alternation_hex_and_quantifier() {
+        let regex = RegexProcessor::compile_alternation(&["\\xCA\\xFE", "\\x00{3,}\\xFF"]).unwrap();
+
+        // 비 UTF-8 바이트도 바이트 단위로 매치
+        let data = b"\x01\x00\x00\x00\x00\xFF\x02\xCA\xFE";
+        let found: Vec<(usize, usize)> = regex.find_iter(data).map(|m| (m.start(), m.len())).collect();
+        assert_eq!(found, vec![(1, 5), (7, 2)]);
+        assert!(!regex.is_match(b"\x00\x00\xFF"));
+    }
+
+    #[test]
+    fn test_compile_alternation_matches_combined_patterns() {
+        // 각 패턴은 자기 그룹 안에서 변환되므로 괄호가 맞지 않는 패턴은 거부됨
+        assert!(RegexProcessor::compile_alternation(&["\\x41(", ")\\x42"]).is_err());
+        assert!(RegexProcessor::compile_alternation(&[]).is_err());
+
+        // CLI의 -e 결합과 같은 정규식
+        let expressions = ["\\x4d\\x5a", "P."];
+        let owned: Vec<String> = expressions.iter().map(|expression| expression.to_string()).collect();
+        let combined = RegexProcessor::combine_patterns(&owned).unwrap().unwrap();
+        assert_eq!(
+            RegexProcessor::compile_alternation(&expressions).unwrap().as_str(),
+            RegexProcessor::compile_pattern(&combined).unwrap().as_str()
+        );
+    }
+
+    #[test]
+    fn test_can_match_zero_byte() {
+        let zero_patterns = ["\\x00\\x00", "\\x00{5000}", "MZ\\x00+PE", "(?:\\x00|\\x01)A", "[\\x00-\\x1f]", ".", "\\x00?MZ"];
+        for pattern in zero_patterns {
+            let regex = RegexProcessor::compile_pattern(pattern).unwrap();
+            assert!(RegexProcessor::can_match_zero_byte(&regex), "{}", pattern);
+        }
+
+        let other_patterns = ["MZ", "\\xFF\\xD8\\xFF", "[\\x01-\\xff]+", "\\x00{0}MZ", "^PK$"];
+        for pattern in other_patterns {
+            let regex = RegexProcessor::compile_pattern(pattern).unwrap();
+            assert!(!RegexProcessor::can_match_zero_byte(&regex), "{}", pattern);
+        }
+    }
+
+    #[test]
+    fn test_size_limit_is_reported_as_pattern_too_complex() {
+        // 중첩된 큰 반복은 기본 크기 제한을 넘음
+        match RegexProcessor::compile_pattern("(?:\\x00{1,100000}){1,100}") {
+            Err(BingrepError::PatternTooComplex(limit)) => assert_eq!(limit, DEFAULT_REGEX_SIZE_LIMIT),
+            other => panic!("expected PatternTooComplex, got {:?}", other),
+        }
+
+        // 다른 컴파일 오류는 그대로 유지
+        assert!(matches!(
+            RegexProcessor::compile_pattern("\\x41("),
+            Err(BingrepError::RegexCompilation(_))
+        ));
+    }
+
+    #[test]
+    fn test_read_pattern_file() {
+        use std::io::Write;
+        use tempfile::NamedTempFile;
+
+        let mut file = NamedTempFile::new().unwrap();
+        write!(file, "# PE/ZIP 시그니처\n\\x4d\\x5a\n\n  PK\\x03\\x04  \r\n#\\xFF\n").unwrap();
+        let expressions = RegexProcessor::read_pattern_file(file.path()).unwrap();
+        assert_eq!(expressions, vec!["\\x4d\\x5a".to_string(), "PK\\x03\\x04".to_string()]);
+
+        // 잘못된 패턴은 줄 번호와 함께 오류
+        let mut file = NamedTempFile::new().unwrap();
+        write!(file, "\\x4d\\x5a\n# 주석\n(unclosed\n").unwrap();
+        let err = RegexProcessor::read_pattern_file(file.path()).unwrap_err().to_string();
+        assert!(err.contains("line 3"), "{}", err);
+
+        // 패턴이 없는 파일은 오류
+        let mut file = NamedTempFile::new().unwrap();
+        write!(file, "# 비어 있음\n\n").unwrap();
+        assert!(RegexProcessor::read_pattern_file(file.path()).is_err());
+    }
+
+    #[test]
+    fn test_literal_metacharacters_are_escaped() {
+        let regex = RegexProcessor::compile_literal("a.b*(c)", StringEncoding::Utf8).unwrap();
+        assert!(regex.is_match(b"--a.b*(c)--"));
+        assert!(!regex.is_match(b"--axbbc--"));
+    }
+
+    #[test]
+    fn test_literal_empty_string_errors() {
+        assert!(RegexProcessor::compile_literal("", StringEncoding::Utf8).is_err());
+    }
+
+    #[test]
+    fn test_literal_encodings() {
+        assert_eq!(
+            RegexProcessor::encode_literal("é", StringEncoding::Utf8),
+            vec![0xC3, 0xA9]
+        );
+        assert_eq!(
+            RegexProcessor::encode_literal("MZ", StringEncoding::Utf16le),
+            vec![0x4D, 0x00, 0x5A, 0x00]
+        );
+        assert_eq!(
+            RegexProcessor::encode_literal("MZ", StringEncoding::Utf16be),
+            vec![0x00, 0x4D, 0x00, 0x5A]
+        );
+
+        let regex = RegexProcessor::compile_literal("é", StringEncoding::Utf8).unwrap();
+        assert!(regex.is_match(b"caf\xC3\xA9"));
+    }
+
+    #[test]
+    fn test_literal_pattern_roundtrips_through_compile_pattern() {
+        // The backslash sequence is matched literally, not as a hex escape
+        let pattern = RegexProcessor::literal_pattern(r"PK\x03", StringEncoding::Utf8).unwrap();
+        let regex = RegexProcessor::compile_pattern(&pattern).unwrap();
+        assert!(regex.is_match(br"PK\x03"));
+        assert!(!regex.is_match(b"PK\x03"));
+    }
+
+    #[test]
+    fn test_hex_wildcard_pattern() {
+        let regex = Regex::new(&RegexProcessor::hex_wildcard_pattern("4D 5A ?? ?? 50 45").unwrap()).unwrap();
+        assert!(regex.is_match(b"\x4d\x5a\x00\xff\x50\x45"));
+        assert!(!regex.is_match(b"\x4d\x5a\x00\x50\x45"));
+
+        // Spaces between tokens are optional
+        assert_eq!(
+            RegexProcessor::hex_wildcard_pattern("4d5a").unwrap(),
+            RegexProcessor::hex_wildcard_pattern("4D 5A").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_hex_wildcard_nibbles() {
+        let high = Regex::new(&RegexProcessor::hex_wildcard_pattern("A?").unwrap()).unwrap();
+        assert!(high.is_match(&[0xa0]));
+        assert!(high.is_match(&[0xaf]));
+        assert!(!high.is_match(&[0xb0]));
+
+        let low = Regex::new(&RegexProcessor::hex_wildcard_pattern("?D").unwrap()).unwrap();
+        assert!(low.is_match(&[0x0d]));
+        assert!(low.is_match(&[0xfd]));
+        assert!(!low.is_match(&[0xdc]));
+    }
+
+    #[test]
+    fn test_hex_wildcard_errors_name_bad_token() {
+        let err = RegexProcessor::hex_wildcard_pattern("4D 5G").unwrap_err().to_string();
+        assert!(err.contains("'5G'"), "{}", err);
+
+        let err = RegexProcessor::hex_wildcard_pattern("4D 5").unwrap_err().to_string();
+        assert!(err.contains("'5'"), "{}", err);
+
+        assert!(RegexProcessor::hex_wildcard_pattern("  ").is_err());
+    }
+
+    #[test]
+    fn test_combine_patterns_or_search() {
+        let expressions = vec!["\\x00\\x00\\x01\\x67".to_string(), "\\x00\\x00\\x01\\x68".to_string()];
+        let combined = RegexProcessor::combine_patterns(&expressions).unwrap().unwrap();
+        let regex = RegexProcessor::compile_pattern(&combined).unwrap();
+
+        let data = b"\x00\x00\x01\x68\xff\x00\x00\x01\x67";
+        let offsets: Vec<usize> = regex.find_iter(data).map(|m| m.start()).collect();
+        assert_eq!(offsets, vec![0, 5]);
+
+        // A single pattern is used as is, and none means no expression
+        assert_eq!(
+            RegexProcessor::combine_patterns(&expressions[..1]).unwrap(),
+            Some(expressions[0].clone())
+        );
+        assert_eq!(RegexProcessor::combine_patterns(&[]).unwrap(), None);
+        assert!(RegexProcessor::combine_patterns(&["\\x4".to_string(), "MZ".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_pattern_set_reports_first_matching_pattern() {
+        // Both patterns match at offset 0; the earlier one wins, as in the alternation
+        let expressions = vec!["MZ".to_string(), "M.".to_string(), "PK".to_string()];
+        let combined = RegexProcessor::combine_patterns(&expressions).unwrap().unwrap();
+        let regex = RegexProcessor::compile_pattern(&combined).unwrap();
+        let set = PatternSet::new(&expressions).unwrap();
+        assert_eq!(set.len(), 3);
+
+        let data = b"MZ..MQ..PK";
+        let found: Vec<(usize, Option<usize>)> = regex
+            .find_iter(data)
+            .map(|m| (m.start(), set.index_at(&data[m.start()..])))
+            .collect();
+        assert_eq!(found, vec![(0, Some(0)), (4, Some(1)), (8, Some(2))]);
+    }
+
+    #[test]
+    fn test_pattern_set_matches_non_utf8_bytes_like_alternation() {
+        // 결합된 정규식에서 `.`은 0xFF 한 바이트와 매치되므로 PatternSet도 같아야 함
+        let expressions = vec!["MZ".to_string(), "M.".to_string()];
+        let regex = RegexProcessor::compile_alternation(&["MZ", "M."]).unwrap();
+        let set = PatternSet::new(&expressions).unwrap();
+
+        let data = b"\x00M\xFF\x00MZ";
+        let found: Vec<(usize, Option<usize>)> = regex
+            .find_iter(data)
+            .map(|m| (m.start(), set.index_at(&data[m.start()..])))
+            .collect();
+        assert_eq!(found, vec![(1, Some(1)), (4, Some(0))]);
+    }
+
+    #[test]
+    fn test_hex_pattern_jumps_over_nal_payload() {
+        // SPS NAL unit, 4 to 6 payload bytes, then a PPS start code
+        let regex = Regex::new(
+            &RegexProcessor::hex_wildcard_pattern("00 00 00 01 67 [4-6] 00 00 00 01 68").unwrap(),
+        )
+        .unwrap();
+        assert!(regex.is_match(b"\x00\x00\x00\x01\x67\x42\x00\x1f\xe9\x00\x00\x00\x01\x68"));
+        assert!(regex.is_match(b"\x00\x00\x00\x01\x67\x42\x00\x1f\xe9\x8d\x40\x00\x00\x00\x01\x68"));
+        // Payloads shorter or longer than the jump do not match
+        assert!(!regex.is_match(b"\x00\x00\x00\x01\x67\x42\x00\x00\x00\x00\x01\x68"));
+        assert!(!regex.is_match(b"\x00\x00\x00\x01\x67\x42\x00\x1f\xe9\x8d\x40\x01\x00\x00\x00\x01\x68"));
+
+        // Exact and open-ended jumps
+        assert_eq!(
+            RegexProcessor::hex_wildcard_pattern("67[2]68").unwrap(),
+            "(?-u)\\x67[\\x00-\\xff]{2}\\x68"
+        );
+        assert_eq!(
+            RegexProcessor::hex_wildcard_pattern("67 [8-] 68").unwrap(),
+            format!("(?-u)\\x67[\\x00-\\xff]{{8,{}}}\\x68", MAX_HEX_JUMP)
+        );
+    }
+
+    #[test]
+    fn test_hex_pattern_quantifiers_with_jumps() {
+        // Three-byte start code written with a quantifier, then a variable gap
+        let regex =
+            Regex::new(&RegexProcessor::hex_wildcard_pattern("00{2,3} 01 65 [1-] 00{2} 01 41").unwrap()).unwrap();
+        assert!(regex.is_match(b"\x00\x00\x01\x65\x88\x84\x00\x00\x01\x41"));
+        assert!(regex.is_match(b"\x00\x00\x00\x01\x65\x88\x00\x00\x01\x41"));
+        assert!(!regex.is_match(b"\x00\x00\x01\x65\x00\x00\x01\x41"));
+    }
+
+    #[test]
+    fn test_hex_pattern_jump_errors() {
+        for pattern in ["67 [6-4] 68", "67 [x] 68", "67 [4", "67 [0-99999] 68", "67 [5000-]"] {
+            assert!(RegexProcessor::hex_wildcard_pattern(pattern).is_err(), "{}", pattern);
+        }
+        // Quantifiers need a byte token right before them
+        for pattern in ["{2} 67", "67 [2] {3}", "67{2}{3}", "67{a}"] {
+            assert!(RegexProcessor::hex_wildcard_pattern(pattern).is_err(), "{}", pattern);
+        }
+        let err = RegexProcessor::hex_wildcard_pattern("67 [6-4]").unwrap_err().to_string();
+        assert!(err.contains("[6-4]"), "{}", err);
+    }
+
+    #[test]
+    fn test_mask_pattern_matches_masked_bits() {
+        let regex = Regex::new(&RegexProcessor::mask_pattern("80:f0").unwrap()).unwrap();
+        for byte in 0x80..=0x8Fu8 {
+            assert!(regex.is_match(&[byte]), "{:02x}", byte);
+        }
+        assert!(!regex.is_match(&[0x70]));
+        assert!(!regex.is_match(&[0x90]));
+    }
+
+    #[test]
+    fn test_mask_pattern_classes() {
+        // Full mask is an exact byte, empty mask any byte
+        assert_eq!(RegexProcessor::mask_pattern("0x4D5A:0xFF00").unwrap(), "(?-u)\\x4d[\\x00-\\xff]");
+
+        // Low bit only: every odd byte, which cannot be merged into ranges
+        let regex = Regex::new(&RegexProcessor::mask_pattern("01:01").unwrap()).unwrap();
+        assert!(regex.is_match(&[0x03]));
+        assert!(!regex.is_match(&[0x02]));
+    }
+
+    #[test]
+    fn test_mask_pattern_errors() {
+        assert!(RegexProcessor::mask_pattern("80").is_err());
+        assert!(RegexProcessor::mask_pattern("80:f").is_err());
+        assert!(RegexProcessor::mask_pattern("8000:f0").is_err());
+        assert!(RegexProcessor::mask_pattern("zz:ff").is_err());
+        assert!(RegexProcessor::mask_pattern(":").is_err());
+    }
+
+    #[test]
+    fn test_match_alignment_next_aligned() {
+        let alignment = MatchAlignment::new(512, 510);
+        assert_eq!(alignment.next_aligned(0), 510);
+        assert_eq!(alignment.next_aligned(510), 510);
+        assert_eq!(alignment.next_aligned(511), 1022);
+        assert!(alignment.accepts(1022));
+        assert!(!alignment.accepts(512));
+    }
+
+    #[test]
+    fn test_aligned_matches_not_hidden_by_overlap() {
+        // An unaligned match at 3 overlaps the aligned candidate at 4
+        let regex = RegexProcessor::compile_pattern("\\x00\\x00").unwrap();
+        let data = [0xFF, 0xFF, 0xFF, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0x00, 0x00];
+        let alignment = MatchAlignment::new(4, 0);
+
+        let starts: Vec<usize> = alignment.find_iter(&regex, &data, 0).map(|m| m.start()).collect();
+        assert_eq!(starts, vec![4, 8]);
+
+        // Alignment is relative to the absolute offset, not the start of the haystack
+        let starts: Vec<usize> = alignment.find_iter(&regex, &data[2..], 2).map(|m| m.start()).collect();
+        assert_eq!(starts, vec![2, 6]);
+    }
+
+    #[test]
+    fn test_encode_integer() {
+        assert_eq!(
+            RegexProcessor::encode_integer("0x41424344", IntegerType::U32, Endian::Le).unwrap(),
+            vec![0x44, 0x43, 0x42, 0x41]
+        );
+        assert_eq!(
+            RegexProcessor::encode_integer("0x41424344", IntegerType::U32, Endian::Be).unwrap(),
+            vec![0x41, 0x42, 0x43, 0x44]
+        );
+        assert_eq!(
+            RegexProcessor::encode_integer("4096", IntegerType::U16, Endian::Le).unwrap(),
+            vec![0x00, 0x10]
+        );
+        assert_eq!(
+            RegexProcessor::encode_integer("-1", IntegerType::I32, Endian::Le).unwrap(),
+            vec![0xFF, 0xFF, 0xFF, 0xFF]
+        );
+        assert_eq!(
+            RegexProcessor::encode_integer("0x0000000000401000", IntegerType::U64, Endian::Le).unwrap(),
+            vec![0x00, 0x10, 0x40, 0x00, 0x00, 0x00, 0x00, 0x00]
+        );
+    }
+
+    #[test]
+    fn test_encode_integer_errors() {
+        assert!(RegexProcessor::encode_integer("65536", IntegerType::U16, Endian::Le).is_err());
+        assert!(RegexProcessor::encode_integer("-1", IntegerType::U32, Endian::Le).is_err());
+        assert!(RegexProcessor::encode_integer("0xZZ", IntegerType::U32, Endian::Le).is_err());
+        assert!(RegexProcessor::encode_integer("", IntegerType::U32, Endian::Le).is_err());
+    }
+
+    #[test]
+    fn test_compile_uint() {
+        let data = b"\x00\xDE\xAD\xBE\xEF\x11\xEF\xBE\xAD\xDE\x22";
+
+        let le = RegexProcessor::compile_uint(0xDEADBEEF, 4, Endian::Le).unwrap();
+        let starts: Vec<usize> = le.find_iter(data).map(|m| m.start()).collect();
+        assert_eq!(starts, vec![6]);
+
+        let be = RegexProcessor::compile_uint(0xDEADBEEF, 4, Endian::Be).unwrap();
+        let starts: Vec<usize> = be.find_iter(data).map(|m| m.start()).collect();
+        assert_eq!(starts, vec![1]);
+
+        assert!(RegexProcessor::compile_uint(0x10000, 2, Endian::Le).is_err());
+        assert!(RegexProcessor::compile_uint(1, 3, Endian::Le).is_err());
+    }
 }
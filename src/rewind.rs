@@ -0,0 +1,188 @@
+//! Seekable view of a non-seekable stream
+//!
+//! The buffered scanners step back by the buffer padding between reads, re-read a match
+//! that was cut off at the end of a buffer and look ahead for the rest of a long match.
+//! `RewindReader` makes that work on pipes such as stdin by keeping the most recently
+//! read bytes in memory: seeking within them is free, seeking forward reads and discards
+//! the skipped bytes, and only seeking back past the retained bytes fails.
+
+use std::io::{self, Read, Seek, SeekFrom};
+
+/// Reader that supports seeking within a window of recently read bytes
+pub struct RewindReader<R> {
+    inner: R,
+    /// Bytes read from `inner`, starting at absolute offset `window_start`
+    window: Vec<u8>,
+    window_start: u64,
+    pos: u64,
+    /// Number of bytes behind the current position that stay seekable
+    retain: usize,
+}
+
+impl<R: Read> RewindReader<R> {
+    /// Wrap a reader, keeping at least `retain` bytes behind the current position seekable
+    pub fn new(inner: R, retain: usize) -> Self {
+        Self {
+            inner,
+            window: Vec::new(),
+            window_start: 0,
+            pos: 0,
+            retain,
+        }
+    }
+
+    /// Check whether no bytes are left at the current position
+    pub fn is_exhausted(&mut self) -> io::Result<bool> {
+        if self.pos > self.window_end() {
+            return Ok(true);
+        }
+        self.fill(1)?;
+        Ok(self.pos == self.window_end())
+    }
+
+    fn window_end(&self) -> u64 {
+        self.window_start + self.window.len() as u64
+    }
+
+    /// Read from the inner reader until `len` bytes are available at the current position or it ends
+    ///
+    /// Pipes return short reads, so this keeps reading to make the stream behave like a file.
+    fn fill(&mut self, len: usize) -> io::Result<()> {
+        let wanted = (self.pos - self.window_start) as usize + len;
+        if self.window.len() < wanted {
+            let missing = (wanted - self.window.len()) as u64;
+            self.inner.by_ref().take(missing).read_to_end(&mut self.window)?;
+        }
+        Ok(())
+    }
+
+    /// Drop bytes that are no longer seekable, amortized so the window is not shifted on every read
+    fn trim(&mut self) {
+        let behind = (self.pos - self.window_start) as usize;
+        if behind > self.retain.saturating_mul(2) {
+            let dropped = behind - self.retain;
+            self.window.drain(..dropped);
+            self.window_start += dropped as u64;
+        }
+    }
+}
+
+impl<R: Read> Read for RewindReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        // A forward seek past the end of the input leaves the position beyond the window
+        if self.pos > self.window_end() {
+            return Ok(0);
+        }
+
+        self.fill(buf.len())?;
+        let start = (self.pos - self.window_start) as usize;
+        let bytes_read = buf.len().min(self.window.len() - start);
+        buf[..bytes_read].copy_from_slice(&self.window[start..start + bytes_read]);
+        self.pos += bytes_read as u64;
+        self.trim();
+        Ok(bytes_read)
+    }
+}
+
+impl<R: Read> Seek for RewindReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let target = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::Current(delta) => self.pos.checked_add_signed(delta),
+            SeekFrom::End(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "cannot seek relative to the end of a stream",
+                ));
+            }
+        }
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "invalid seek to a negative or overflowing position"))?;
+
+        if target < self.window_start {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "cannot seek back to offset {} in a stream, only offsets from {} are kept",
+                    target, self.window_start
+                ),
+            ));
+        }
+
+        // Skip ahead without keeping the bytes in between
+        let window_end = self.window_end();
+        if target > window_end {
+            let skipped = io::copy(&mut self.inner.by_ref().take(target - window_end), &mut io::sink())?;
+            self.window.clear();
+            self.window_start = window_end + skipped;
+        }
+
+        self.pos = target;
+        Ok(target)
+    }
+
+    fn stream_position(&mut self) -> io::Result<u64> {
+        Ok(self.pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reader returning at most 3 bytes per call, like a pipe delivering data in pieces
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let len = buf.len().min(self.0.len()).min(3);
+            buf[..len].copy_from_slice(&self.0[..len]);
+            self.0 = &self.0[len..];
+            Ok(len)
+        }
+    }
+
+    #[test]
+    fn test_reads_are_filled_and_rewindable() {
+        let data: Vec<u8> = (0..100).collect();
+        let mut reader = RewindReader::new(Trickle(&data), 16);
+
+        let mut buf = [0u8; 10];
+        assert_eq!(reader.read(&mut buf).unwrap(), 10);
+        assert_eq!(buf, data[..10]);
+
+        reader.seek(SeekFrom::Start(4)).unwrap();
+        assert_eq!(reader.read(&mut buf).unwrap(), 10);
+        assert_eq!(buf, data[4..14]);
+        assert_eq!(reader.stream_position().unwrap(), 14);
+    }
+
+    #[test]
+    fn test_forward_seek_discards_and_old_offsets_fail() {
+        let data: Vec<u8> = (0..100).collect();
+        let mut reader = RewindReader::new(Trickle(&data), 4);
+
+        reader.seek(SeekFrom::Start(50)).unwrap();
+        let mut buf = [0u8; 20];
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, data[50..70]);
+
+        // Everything before the skipped bytes was never kept
+        assert!(reader.seek(SeekFrom::Start(10)).is_err());
+        assert!(reader.seek(SeekFrom::End(0)).is_err());
+        assert!(reader.seek(SeekFrom::Current(-4)).is_ok());
+    }
+
+    #[test]
+    fn test_exhausted() {
+        let data = [1u8, 2, 3];
+        let mut reader = RewindReader::new(Trickle(&data), 4);
+        assert!(!reader.is_exhausted().unwrap());
+
+        reader.seek(SeekFrom::Start(3)).unwrap();
+        assert!(reader.is_exhausted().unwrap());
+
+        reader.seek(SeekFrom::Start(10)).unwrap();
+        assert!(reader.is_exhausted().unwrap());
+        assert_eq!(reader.read(&mut [0u8; 4]).unwrap(), 0);
+    }
+}
@@ -0,0 +1,161 @@
+//! Concurrency metrics for parallel scans
+//!
+//! Counters are plain atomics so worker threads can record into a shared `ScanStats`
+//! without locking. The summary is meant for tuning `--chunk-size` and thread counts:
+//! a speedup close to 1.0 or a large idle time means the workers were mostly waiting.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Timing and throughput counters collected during a parallel scan
+#[derive(Debug, Default)]
+pub struct ScanStats {
+    chunks_processed: AtomicU64,
+    bytes_scanned: AtomicU64,
+    scan_nanos: AtomicU64,
+    reader_wait_nanos: AtomicU64,
+    wall_nanos: AtomicU64,
+    workers: AtomicU64,
+}
+
+impl ScanStats {
+    /// Create empty statistics for a scan using the given number of worker threads
+    pub fn new(workers: usize) -> Self {
+        let stats = Self::default();
+        stats.workers.store(workers.max(1) as u64, Ordering::Relaxed);
+        stats
+    }
+
+    /// Record one scanned chunk and the time a worker spent scanning it
+    pub fn record_chunk(&self, bytes: usize, scan_time: Duration) {
+        self.chunks_processed.fetch_add(1, Ordering::Relaxed);
+        self.bytes_scanned.fetch_add(bytes as u64, Ordering::Relaxed);
+        self.scan_nanos.fetch_add(duration_nanos(scan_time), Ordering::Relaxed);
+    }
+
+    /// Record time a worker spent waiting for the reader to fill a chunk
+    pub fn record_reader_wait(&self, wait_time: Duration) {
+        self.reader_wait_nanos.fetch_add(duration_nanos(wait_time), Ordering::Relaxed);
+    }
+
+    /// Record the total elapsed time of the scan
+    pub fn record_wall_time(&self, wall_time: Duration) {
+        self.wall_nanos.store(duration_nanos(wall_time), Ordering::Relaxed);
+    }
+
+    /// Number of chunks scanned
+    pub fn chunks_processed(&self) -> u64 {
+        self.chunks_processed.load(Ordering::Relaxed)
+    }
+
+    /// Number of bytes scanned, not counting overlap re-reads
+    pub fn bytes_scanned(&self) -> u64 {
+        self.bytes_scanned.load(Ordering::Relaxed)
+    }
+
+    /// Number of worker threads available to the scan
+    pub fn workers(&self) -> u64 {
+        self.workers.load(Ordering::Relaxed).max(1)
+    }
+
+    /// Total time workers spent scanning, summed over all threads
+    pub fn total_scan_time(&self) -> Duration {
+        Duration::from_nanos(self.scan_nanos.load(Ordering::Relaxed))
+    }
+
+    /// Average time spent scanning a single chunk
+    pub fn average_chunk_scan_time(&self) -> Duration {
+        match self.chunks_processed() {
+            0 => Duration::ZERO,
+            chunks => self.total_scan_time() / chunks as u32,
+        }
+    }
+
+    /// Time spent waiting for chunk reads, summed over all threads
+    pub fn reader_wait_time(&self) -> Duration {
+        Duration::from_nanos(self.reader_wait_nanos.load(Ordering::Relaxed))
+    }
+
+    /// Total elapsed time of the scan
+    pub fn wall_time(&self) -> Duration {
+        Duration::from_nanos(self.wall_nanos.load(Ordering::Relaxed))
+    }
+
+    /// Worker capacity (wall time × workers) that was spent neither reading nor scanning
+    pub fn worker_idle_time(&self) -> Duration {
+        let capacity = self.wall_time().saturating_mul(self.workers() as u32);
+        capacity.saturating_sub(self.busy_time())
+    }
+
+    /// Estimated speedup: worker time spent reading and scanning per unit of wall time
+    ///
+    /// A sequential scan of the same chunks would take about the busy time, so this is
+    /// roughly the average number of workers kept busy.
+    pub fn speedup_estimate(&self) -> f64 {
+        let wall = self.wall_time();
+        if wall.is_zero() {
+            return 0.0;
+        }
+        self.busy_time().as_secs_f64() / wall.as_secs_f64()
+    }
+
+    fn busy_time(&self) -> Duration {
+        self.total_scan_time() + self.reader_wait_time()
+    }
+
+    /// Human-readable summary printed by `--stats`
+    pub fn summary(&self) -> String {
+        format!(
+            "Scan stats: {} chunk(s), {} bytes scanned, {} worker(s)\n  \
+             avg chunk scan: {:.3} ms, reader wait: {:.3} ms, worker idle: {:.3} ms, \
+             wall: {:.3} ms, speedup: {:.2}x",
+            self.chunks_processed(),
+            self.bytes_scanned(),
+            self.workers(),
+            millis(self.average_chunk_scan_time()),
+            millis(self.reader_wait_time()),
+            millis(self.worker_idle_time()),
+            millis(self.wall_time()),
+            self.speedup_estimate(),
+        )
+    }
+}
+
+fn duration_nanos(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counters_and_averages() {
+        let stats = ScanStats::new(4);
+        stats.record_chunk(1000, Duration::from_millis(10));
+        stats.record_chunk(500, Duration::from_millis(30));
+        stats.record_reader_wait(Duration::from_millis(5));
+        stats.record_wall_time(Duration::from_millis(25));
+
+        assert_eq!(stats.chunks_processed(), 2);
+        assert_eq!(stats.bytes_scanned(), 1500);
+        assert_eq!(stats.average_chunk_scan_time(), Duration::from_millis(20));
+        // 25ms × 4 workers - (40ms scanning + 5ms reading)
+        assert_eq!(stats.worker_idle_time(), Duration::from_millis(55));
+        // 45ms of worker time in 25ms of wall time
+        assert!((stats.speedup_estimate() - 1.8).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_empty_stats() {
+        let stats = ScanStats::new(0);
+        assert_eq!(stats.workers(), 1);
+        assert_eq!(stats.average_chunk_scan_time(), Duration::ZERO);
+        assert_eq!(stats.speedup_estimate(), 0.0);
+        assert!(stats.summary().contains("0 chunk(s)"));
+    }
+}
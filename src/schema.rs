@@ -0,0 +1,179 @@
+//! JSON Schema documents for the structured output
+//!
+//! The schemas are written by hand next to the serde types in `structured_output` and
+//! the tests validate real output against them, so a record change that is not mirrored
+//! here is caught. `SCHEMA_VERSION` is raised whenever a record changes incompatibly.
+
+use crate::cli::SchemaKind;
+use serde_json::{json, Value};
+
+/// Version stamped into every schema document
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// JSON Schema (draft-07) of the records selected by `kind`
+///
+/// * `matches` - one match, an element of the `-f json` array or one `-f ndjson` line
+/// * `hexdump` - one hex dump line, in the same two forms
+/// * `summary` - the `{"skipped": ...}` and `{"summary": ...}` NDJSON records of a
+///   multi-file search, the `{"coverage": ...}` record of a limited scan, or the report
+///   document holding the matches of a `-f json` multi-file search or limited scan
+pub fn schema(kind: SchemaKind) -> Value {
+    let (name, title, body) = match kind {
+        SchemaKind::Matches => ("matches", "hxgrep match", match_schema()),
+        SchemaKind::Hexdump => ("hexdump", "hxgrep hex dump line", hex_dump_line_schema()),
+        SchemaKind::Summary => ("summary", "hxgrep multi-file report", summary_schema()),
+    };
+
+    let mut document = json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "$id": format!("urn:hxgrep:schema:{}:{}", name, SCHEMA_VERSION),
+        "title": title,
+        "x-hxgrep-schema-version": SCHEMA_VERSION,
+    });
+    if let (Some(document), Value::Object(body)) = (document.as_object_mut(), body) {
+        document.extend(body);
+    }
+    document
+}
+
+fn match_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "file_path": { "type": "string" },
+            "offset": { "type": "integer", "minimum": 0 },
+            "hex_data": { "type": "string" },
+            "length": { "type": "integer", "minimum": 0 },
+            "ascii_data": { "type": ["string", "null"] },
+            "string_value": { "type": "string" },
+            "window_len_trimmed": { "type": "integer", "minimum": 0 },
+            "compressed_offset": {
+                "type": "array",
+                "items": { "type": "integer", "minimum": 0 },
+                "minItems": 2,
+                "maxItems": 2
+            }
+        },
+        "required": ["file_path", "offset", "hex_data", "length", "ascii_data"],
+        "additionalProperties": false
+    })
+}
+
+fn hex_dump_line_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "file_path": { "type": "string" },
+            "offset": { "type": "integer", "minimum": 0 },
+            "hex_data": { "type": "string" },
+            "ascii_data": { "type": ["string", "null"] },
+            "byte_count": { "type": "integer", "minimum": 0 }
+        },
+        "required": ["file_path", "offset", "hex_data", "ascii_data", "byte_count"],
+        "additionalProperties": false
+    })
+}
+
+fn summary_schema() -> Value {
+    json!({
+        "definitions": {
+            "match": match_schema(),
+            "skipped_file": {
+                "type": "object",
+                "properties": {
+                    "path": { "type": "string" },
+                    "reason": { "enum": ["permission-denied", "too-large", "not-regular", "excluded"] }
+                },
+                "required": ["path", "reason"],
+                "additionalProperties": false
+            },
+            "summary": {
+                "type": "object",
+                "properties": {
+                    "files_processed": { "type": "integer", "minimum": 0 },
+                    "files_skipped": { "type": "integer", "minimum": 0 }
+                },
+                "required": ["files_processed", "files_skipped"],
+                "additionalProperties": false
+            },
+            "coverage": {
+                "type": "object",
+                "properties": {
+                    "requested": { "$ref": "#/definitions/range" },
+                    "ranges": { "type": "array", "items": { "$ref": "#/definitions/range" } },
+                    "percent": { "type": "number", "minimum": 0, "maximum": 100 }
+                },
+                "required": ["requested", "ranges", "percent"],
+                "additionalProperties": false
+            },
+            "range": {
+                "type": "array",
+                "items": { "type": "integer", "minimum": 0 },
+                "minItems": 2,
+                "maxItems": 2
+            }
+        },
+        "oneOf": [
+            {
+                "type": "object",
+                "properties": { "skipped": { "$ref": "#/definitions/skipped_file" } },
+                "required": ["skipped"],
+                "additionalProperties": false
+            },
+            {
+                "type": "object",
+                "properties": { "summary": { "$ref": "#/definitions/summary" } },
+                "required": ["summary"],
+                "additionalProperties": false
+            },
+            {
+                "type": "object",
+                "properties": { "coverage": { "$ref": "#/definitions/coverage" } },
+                "required": ["coverage"],
+                "additionalProperties": false
+            },
+            {
+                "type": "object",
+                "properties": {
+                    "matches": { "type": "array", "items": { "$ref": "#/definitions/match" } },
+                    "skipped": { "type": "array", "items": { "$ref": "#/definitions/skipped_file" } },
+                    "summary": { "$ref": "#/definitions/summary" }
+                },
+                "required": ["matches", "skipped", "summary"],
+                "additionalProperties": false
+            },
+            {
+                "type": "object",
+                "properties": {
+                    "matches": { "type": "array", "items": { "$ref": "#/definitions/match" } },
+                    "coverage": { "$ref": "#/definitions/coverage" }
+                },
+                "required": ["matches", "coverage"],
+                "additionalProperties": false
+            }
+        ]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structured_output::SkipReason;
+
+    #[test]
+    fn test_schema_is_version_stamped() {
+        let document = schema(SchemaKind::Hexdump);
+        assert_eq!(document["$id"], "urn:hxgrep:schema:hexdump:2");
+        assert_eq!(document["x-hxgrep-schema-version"], SCHEMA_VERSION);
+        assert_eq!(document["required"][4], "byte_count");
+    }
+
+    #[test]
+    fn test_skip_reasons_are_listed() {
+        let document = schema(SchemaKind::Summary);
+        let reasons = &document["definitions"]["skipped_file"]["properties"]["reason"]["enum"];
+        for reason in [SkipReason::PermissionDenied, SkipReason::TooLarge, SkipReason::NotRegular, SkipReason::Excluded] {
+            assert!(reasons.as_array().unwrap().contains(&json!(reason.as_str())), "{}", reason.as_str());
+        }
+    }
+}
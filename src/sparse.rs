@@ -0,0 +1,182 @@
+//! Sparse file support
+//!
+//! Uses `SEEK_DATA`/`SEEK_HOLE` to find the regions of a file that are actually
+//! allocated, so that hex dumps and regex scans can jump over holes instead of
+//! reading zeros the filesystem never stored. On platforms or filesystems without
+//! hole reporting, the whole file is treated as data.
+
+use std::fs::File;
+
+/// Tracks the data regions of a sparse file and the holes skipped while reading it
+pub struct HoleSkipper {
+    data_ranges: Vec<(u64, u64)>,
+    next_range: usize,
+    file_size: u64,
+    skipped: Vec<(u64, u64)>,
+    bytes_read: u64,
+}
+
+impl HoleSkipper {
+    /// Create a hole skipper for the given file
+    ///
+    /// Returns `None` if the platform or filesystem does not report holes.
+    pub fn for_file(file: &File) -> Option<Self> {
+        let file_size = file.metadata().ok()?.len();
+        let data_ranges = data_ranges(file, file_size)?;
+        Some(Self::from_ranges(data_ranges, file_size))
+    }
+
+    /// Create a hole skipper from known data ranges (start inclusive, end exclusive)
+    pub fn from_ranges(data_ranges: Vec<(u64, u64)>, file_size: u64) -> Self {
+        Self {
+            data_ranges,
+            next_range: 0,
+            file_size,
+            skipped: Vec::new(),
+            bytes_read: 0,
+        }
+    }
+
+    /// Get the position to continue reading from
+    ///
+    /// Returns `pos` itself when it lies in a data region, the start of the next data
+    /// region when it lies in a hole, or `None` when only holes remain.
+    pub fn next_data_position(&mut self, pos: u64) -> Option<u64> {
+        while self.next_range < self.data_ranges.len() && self.data_ranges[self.next_range].1 <= pos {
+            self.next_range += 1;
+        }
+
+        match self.data_ranges.get(self.next_range) {
+            Some(&(start, _)) if start > pos => {
+                self.skipped.push((pos, start));
+                Some(start)
+            }
+            Some(_) => Some(pos),
+            None => {
+                if pos < self.file_size {
+                    self.skipped.push((pos, self.file_size));
+                }
+                None
+            }
+        }
+    }
+
+    /// Record bytes actually read from the file
+    ///
+    /// The ranges read are tracked by [`crate::coverage::ScanCoverage`].
+    pub fn record_read(&mut self, bytes: u64) {
+        self.bytes_read = self.bytes_read.saturating_add(bytes);
+    }
+
+    /// Holes skipped so far (start inclusive, end exclusive)
+    pub fn skipped_ranges(&self) -> &[(u64, u64)] {
+        &self.skipped
+    }
+
+    /// Total number of bytes skipped
+    pub fn skipped_bytes(&self) -> u64 {
+        self.skipped.iter().map(|(start, end)| end - start).sum()
+    }
+
+    /// Total number of bytes read
+    pub fn bytes_read(&self) -> u64 {
+        self.bytes_read
+    }
+
+    /// Summary of skipped holes for the end-of-run report
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "Skipped {} hole(s) totalling {} bytes, read {} bytes",
+            self.skipped.len(),
+            self.skipped_bytes(),
+            self.bytes_read
+        );
+        for (start, end) in &self.skipped {
+            summary.push_str(&format!("\n  hole: {:X}h-{:X}h", start, end));
+        }
+        summary
+    }
+}
+
+/// Collect the data regions of a file using SEEK_DATA/SEEK_HOLE
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "macos"
+))]
+fn data_ranges(file: &File, file_size: u64) -> Option<Vec<(u64, u64)>> {
+    use std::os::unix::io::AsRawFd;
+
+    let fd = file.as_raw_fd();
+    let original = unsafe { libc::lseek(fd, 0, libc::SEEK_CUR) };
+    let mut ranges = Vec::new();
+    let mut pos: u64 = 0;
+    let mut supported = true;
+
+    while pos < file_size {
+        let data_start = unsafe { libc::lseek(fd, pos as libc::off_t, libc::SEEK_DATA) };
+        if data_start < 0 {
+            // ENXIO means only a trailing hole remains; anything else means no hole support
+            supported = std::io::Error::last_os_error().raw_os_error() == Some(libc::ENXIO);
+            break;
+        }
+
+        let hole_start = unsafe { libc::lseek(fd, data_start, libc::SEEK_HOLE) };
+        if hole_start < 0 {
+            supported = false;
+            break;
+        }
+
+        ranges.push((data_start as u64, (hole_start as u64).min(file_size)));
+        pos = hole_start as u64;
+    }
+
+    unsafe { libc::lseek(fd, original, libc::SEEK_SET) };
+
+    if supported {
+        Some(ranges)
+    } else {
+        None
+    }
+}
+
+/// Hole detection is not available on this platform
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "macos"
+)))]
+fn data_ranges(_file: &File, _file_size: u64) -> Option<Vec<(u64, u64)>> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_data_position() {
+        let mut skipper = HoleSkipper::from_ranges(vec![(0, 4096), (8192, 12288)], 16384);
+
+        assert_eq!(skipper.next_data_position(0), Some(0));
+        assert_eq!(skipper.next_data_position(4000), Some(4000));
+        assert_eq!(skipper.next_data_position(4096), Some(8192));
+        assert_eq!(skipper.next_data_position(12288), None);
+
+        assert_eq!(skipper.skipped_ranges(), &[(4096, 8192), (12288, 16384)]);
+        assert_eq!(skipper.skipped_bytes(), 8192);
+    }
+
+    #[test]
+    fn test_summary() {
+        let mut skipper = HoleSkipper::from_ranges(vec![(0, 16)], 32);
+        skipper.record_read(16);
+        assert_eq!(skipper.next_data_position(16), None);
+        assert_eq!(
+            skipper.summary(),
+            "Skipped 1 hole(s) totalling 16 bytes, read 16 bytes\n  hole: 10h-20h"
+        );
+    }
+}
@@ -1,18 +1,119 @@
 use crate::buffer_manager::BufferManager;
+use crate::collapse::MatchCollapser;
+use crate::compressed::CompressedOffsets;
 use crate::config::Config;
-use crate::error::Result;
+use crate::coverage::ScanCoverage;
+use crate::diagnostics;
+use crate::entropy::{shannon_entropy, EntropyFilter};
+use crate::extract::Extractor;
+use crate::error::{BingrepError, Result};
 use crate::forensic_image::{ForensicImageReader, is_forensic_image};
-use crate::output::OutputFormatter;
+use crate::options::{DumpOptions, SearchOptions};
+use crate::output::{OutputFormatter, ZERO_RUN_MARKER};
 use crate::progress::ProgressIndicator;
+use crate::regex_processor::{MatchAlignment, PatternSet};
+use crate::sparse::HoleSkipper;
+use crate::strings::StringScanner;
+use crate::structured_output::{BinaryMatch, HexDumpLine, OutputFormat, StructuredFormatter};
+use memmap2::Mmap;
 use regex::bytes::Regex;
+use std::collections::VecDeque;
 use std::fs::File;
-use std::io::{Read, Seek, SeekFrom};
-use std::path::Path;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Bytes read for a display, extraction or dump window
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WindowBytes {
+    /// Bytes from the requested offset, at most the requested length
+    pub bytes: Vec<u8>,
+    /// Whether EOF was reached before the requested length was read
+    pub truncated: bool,
+}
+
+/// A regex match found by [`FileProcessor::find_matches`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Match {
+    /// Absolute offset where the match starts
+    pub offset: u64,
+    /// Length of the match, extended past buffer boundaries like the printed output
+    pub len: usize,
+    /// Bytes displayed for the match: `width` bytes from the offset, or the matched bytes for width 0
+    pub bytes: Vec<u8>,
+    /// Index of the pattern set expression matching at the offset, if a pattern set is configured
+    pub pattern_index: Option<usize>,
+}
+
+/// State of a buffered regex scan over a reader, kept between buffers
+struct ReaderScan {
+    width: usize,
+    limit: usize,
+    trim_trailing: Option<u8>,
+    line: usize,
+    last_hit_pos: i64,
+    last_match_end: u64,
+    done: bool,
+}
+
+/// Regex matches scanned lazily from a reader, one buffer at a time
+///
+/// Matches are yielded in offset order; an error ends the iteration.
+struct RegexMatches<'a, R> {
+    processor: &'a mut FileProcessor,
+    reader: &'a mut R,
+    regex: &'a Regex,
+    progress: Option<&'a mut ProgressIndicator>,
+    scan: ReaderScan,
+    pending: VecDeque<Match>,
+}
+
+impl<R: Read + Seek> Iterator for RegexMatches<'_, R> {
+    type Item = Result<Match>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pending.is_empty() && !self.scan.done {
+            let scanned = self.processor.scan_next_buffer(
+                self.reader,
+                self.regex,
+                &mut self.scan,
+                self.progress.as_deref_mut(),
+                &mut self.pending,
+            );
+            if let Err(err) = scanned {
+                self.scan.done = true;
+                return Some(Err(err));
+            }
+        }
+        self.pending.pop_front().map(Ok)
+    }
+}
 
 /// File processor for handling binary file searching and hex dump operations
 pub struct FileProcessor {
     config: Config,
     buffer_manager: BufferManager,
+    /// Absolute file range currently held in the main buffer
+    buffered: Range<u64>,
+    output_format: OutputFormat,
+    source_name: String,
+    hole_skipper: Option<HoleSkipper>,
+    coverage: Option<ScanCoverage>,
+    collected_matches: Vec<BinaryMatch>,
+    /// Number of matches written to the open JSON array, if one is open
+    json_matches: Option<usize>,
+    /// Whether JSON matches are written as bare elements of an array the caller opened
+    json_elements_only: bool,
+    start_offset: u64,
+    end_offset: Option<u64>,
+    extractor: Option<Extractor>,
+    match_alignment: Option<MatchAlignment>,
+    entropy_filter: Option<EntropyFilter>,
+    pattern_set: Option<PatternSet>,
+    collapser: Option<MatchCollapser>,
+    compressed_offsets: Option<CompressedOffsets>,
+    lookahead_limit: Option<usize>,
 }
 
 impl FileProcessor {
@@ -29,7 +130,147 @@ impl FileProcessor {
         Self {
             config,
             buffer_manager,
+            buffered: 0..0,
+            output_format: OutputFormat::Hex,
+            source_name: String::new(),
+            hole_skipper: None,
+            coverage: None,
+            collected_matches: Vec::new(),
+            json_matches: None,
+            json_elements_only: false,
+            start_offset: 0,
+            end_offset: None,
+            extractor: None,
+            match_alignment: None,
+            entropy_filter: None,
+            pattern_set: None,
+            collapser: None,
+            compressed_offsets: None,
+            lookahead_limit: None,
+        }
+    }
+
+    /// Set the output format used for matches and hex dump lines
+    ///
+    /// `Hex` and `Ndjson` are streamed by the processor and `Msgpack` matches are written
+    /// as one array once the search finishes; other formats fall back to hex.
+    pub fn set_output_format(&mut self, format: OutputFormat) {
+        self.output_format = format;
+    }
+
+    /// Write JSON matches as elements of an array opened and closed by the caller
+    ///
+    /// A multi-file search puts the matches of all files into one report document, so
+    /// each file's search writes neither `[` nor `]`. Its first match has no leading
+    /// comma; the caller separates it from the matches of earlier files.
+    pub fn set_json_elements_only(&mut self, elements_only: bool) {
+        self.json_elements_only = elements_only;
+    }
+
+    /// Set the source file name reported in structured output records
+    pub fn set_source_name(&mut self, name: &str) {
+        self.source_name = name.to_string();
+    }
+
+    /// Set the hole skipper used to jump over holes in sparse files
+    pub fn set_hole_skipper(&mut self, skipper: Option<HoleSkipper>) {
+        self.hole_skipper = skipper;
+    }
+
+    /// Set the offset that forensic images opened by the `_path` methods are read from
+    ///
+    /// Readers passed in directly are read from their current position instead.
+    pub fn set_start_offset(&mut self, start_offset: u64) {
+        self.start_offset = start_offset;
+    }
+
+    /// Set the absolute offset at which reading stops (exclusive)
+    ///
+    /// Matches beginning at or after the bound are never reported.
+    pub fn set_end_offset(&mut self, end_offset: Option<u64>) {
+        self.end_offset = end_offset;
+    }
+
+    /// Set the directory that each full regex match is extracted to
+    ///
+    /// Carved files are recorded in the directory's manifest, see [`crate::extract`].
+    pub fn set_extract_dir(&mut self, extract_dir: Option<PathBuf>) {
+        self.extractor = extract_dir.map(Extractor::new);
+    }
+
+    /// Only report matches whose absolute offset satisfies the alignment constraint
+    pub fn set_match_alignment(&mut self, alignment: Option<MatchAlignment>) {
+        self.match_alignment = alignment;
+    }
+
+    /// Append the Shannon entropy to each line, showing only lines within the filter's range
+    pub fn set_entropy_filter(&mut self, filter: Option<EntropyFilter>) {
+        self.entropy_filter = filter;
+    }
+
+    /// Append the number of the `-e` pattern that produced each regex match to its line
+    pub fn set_pattern_set(&mut self, pattern_set: Option<PatternSet>) {
+        self.pattern_set = pattern_set;
+    }
+
+    /// Collapse dense runs of regex match lines in hex output into a single line
+    pub fn set_match_collapser(&mut self, collapser: Option<MatchCollapser>) {
+        self.collapser = collapser;
+    }
+
+    /// Report each regex match with the compressed range its decompressed bytes came from
+    ///
+    /// Set when the reader is a [`crate::compressed::GzipReader`], with its offsets handle.
+    pub fn set_compressed_offsets(&mut self, offsets: Option<CompressedOffsets>) {
+        self.compressed_offsets = offsets;
+    }
+
+    /// Cap the bytes read ahead to find the end of a match cut off at the end of a buffer
+    ///
+    /// Needed for readers such as [`crate::rewind::RewindReader`] that can only seek back a
+    /// limited distance; longer matches are reported up to the limit. Unset, the lookahead
+    /// is bounded by `max_memory_usage`.
+    pub fn set_lookahead_limit(&mut self, limit: Option<usize>) {
+        self.lookahead_limit = limit;
+    }
+
+    /// Take the hole skipper back, e.g. to report the skipped ranges
+    pub fn take_hole_skipper(&mut self) -> Option<HoleSkipper> {
+        self.hole_skipper.take()
+    }
+
+    /// Set the coverage tracker recording the byte ranges the scan reads
+    pub fn set_coverage(&mut self, coverage: Option<ScanCoverage>) {
+        self.coverage = coverage;
+    }
+
+    /// Take the coverage tracker back to report the scanned ranges
+    pub fn take_coverage(&mut self) -> Option<ScanCoverage> {
+        self.coverage.take()
+    }
+
+    /// Record `bytes` read at `pos` with the hole skipper and the coverage tracker
+    fn record_read(&mut self, pos: u64, bytes: usize) {
+        if let Some(skipper) = self.hole_skipper.as_mut() {
+            skipper.record_read(bytes as u64);
         }
+        if let Some(coverage) = self.coverage.as_mut() {
+            coverage.record_read(pos, bytes as u64);
+        }
+    }
+
+    /// Fill the main buffer from `reader`, positioned at `start_offset`, and record the read
+    ///
+    /// Never reads past the end bound, so matches cannot begin at or after it.
+    fn read_main<R: Read>(&mut self, reader: &mut R, start_offset: u64) -> Result<usize> {
+        let bytes_read = match self.end_offset {
+            Some(end) => self
+                .buffer_manager
+                .read_into_main(&mut reader.by_ref().take(end.saturating_sub(start_offset)))?,
+            None => self.buffer_manager.read_into_main(reader)?,
+        };
+        self.record_read(start_offset, bytes_read);
+        Ok(bytes_read)
     }
 
     /// Process file without regex - simple hex dump
@@ -40,32 +281,29 @@ impl FileProcessor {
     /// # Arguments
     ///
     /// * `file_path` - Path to the file to read from
-    /// * `width` - Number of bytes to display per line
-    /// * `limit` - Maximum number of lines to output (0 for unlimited)
-    /// * `separator` - String to separate hex bytes
-    /// * `show_offset` - Whether to display offset values
+    /// * `out` - Writer receiving the output
+    /// * `options` - Line width, line limit, separator and offset display
     /// * `progress` - Progress indicator to update during processing
-    pub fn process_file_stream_from_path<P: AsRef<Path>>(
+    pub fn dump_path<P: AsRef<Path>>(
         &mut self,
         file_path: P,
-        width: usize,
-        limit: usize,
-        separator: &str,
-        show_offset: bool,
+        out: &mut dyn Write,
+        options: &DumpOptions,
         progress: &mut ProgressIndicator,
     ) -> Result<()> {
         let file_path = file_path.as_ref();
 
         if is_forensic_image(&file_path) {
             // Process forensic image file (E01, VMDK)
-            let mut forensic_reader = ForensicImageReader::new(&file_path)?;
+            let mut forensic_reader = self.open_forensic_image(file_path)?;
             let file_size = forensic_reader.size();
-            self.process_reader_stream(&mut forensic_reader, width, limit, separator, show_offset, file_size, progress)
+            progress.set_total(file_size);
+            self.dump_reader(&mut forensic_reader, out, options, file_size, progress)
         } else {
             // Process regular file
             let mut file = File::open(&file_path)?;
             let file_size = file.metadata()?.len();
-            self.process_reader_stream(&mut file, width, limit, separator, show_offset, file_size, progress)
+            self.dump_reader(&mut file, out, options, file_size, progress)
         }
     }
 
@@ -76,53 +314,114 @@ impl FileProcessor {
     /// # Arguments
     ///
     /// * `file` - File to read from
-    /// * `width` - Number of bytes to display per line
-    /// * `limit` - Maximum number of lines to output (0 for unlimited)
-    /// * `separator` - String to separate hex bytes
-    /// * `show_offset` - Whether to display offset values
+    /// * `out` - Writer receiving the output
+    /// * `options` - Line width, line limit, separator and offset display
     /// * `file_size` - Total size of the file for offset formatting
     /// * `progress` - Progress indicator to update during processing
-    pub fn process_file_stream(
+    pub fn dump_file(
         &mut self,
         file: &mut File,
-        width: usize,
-        limit: usize,
-        separator: &str,
-        show_offset: bool,
+        out: &mut dyn Write,
+        options: &DumpOptions,
         file_size: u64,
         progress: &mut ProgressIndicator,
     ) -> Result<()> {
-        self.process_reader_stream(file, width, limit, separator, show_offset, file_size, progress)
+        self.dump_reader(file, out, options, file_size, progress)
     }
 
     /// Generic stream processing function that works with any Read + Seek reader
-    fn process_reader_stream<R: Read + Seek>(
+    ///
+    /// Dumps from the current reader position. `file_size` is the total size of the input
+    /// and only determines the offset padding, so a stream of unknown length may pass an estimate.
+    pub fn dump_reader<R: Read + Seek>(
         &mut self,
         reader: &mut R,
-        width: usize,
-        limit: usize,
-        separator: &str,
-        show_offset: bool,
+        out: &mut dyn Write,
+        options: &DumpOptions,
         file_size: u64,
         progress: &mut ProgressIndicator,
     ) -> Result<()> {
+        let DumpOptions { width, limit, ref separator, group_size, hex_case, show_offset, skip_zeros, .. } = *options;
         let mut pos = reader.stream_position()?;
         let mut line = 0;
+        let mut skipped_zero_lines = 0;
         let hex_offset_length = OutputFormatter::calculate_hex_offset_length(file_size);
+        let output_format = self.output_format;
+        let source_name = self.source_name.clone();
 
         // Get a reusable buffer of the right size
         let buffer = self.buffer_manager.get_extra_buffer(width);
 
         loop {
-            let bytes_read = reader.read(&mut buffer[..width])?;
+            // Jump over holes in sparse files
+            if let Some(skipper) = self.hole_skipper.as_mut() {
+                match skipper.next_data_position(pos) {
+                    Some(data_pos) if data_pos != pos => {
+                        reader.seek(SeekFrom::Start(data_pos))?;
+                        pos = data_pos;
+                    }
+                    Some(_) => {}
+                    None => break,
+                }
+            }
+
+            // Stop at the end bound
+            let line_width = match self.end_offset {
+                Some(end) => width.min(usize::try_from(end.saturating_sub(pos)).unwrap_or(usize::MAX)),
+                None => width,
+            };
+
+            let bytes_read = reader.read(&mut buffer[..line_width])?;
             if bytes_read == 0 {
                 break;
             }
+            self.record_read(pos, bytes_read);
+
+            // All-zero lines are left out and do not count towards the limit
+            if skip_zeros && buffer[..bytes_read].iter().all(|&b| b == 0) {
+                skipped_zero_lines += 1;
+                pos += bytes_read as u64;
+                progress.update(bytes_read as u64);
+                continue;
+            }
+            if skipped_zero_lines > 0 {
+                self.write_zero_run_marker(out, progress.is_silent())?;
+                skipped_zero_lines = 0;
+            }
+
+            let mut hex_string = OutputFormatter::format_bytes_grouped(&buffer[..bytes_read], separator, group_size, hex_case);
+
+            // Skip lines outside the entropy range; they do not count towards the limit
+            if let Some(filter) = self.entropy_filter {
+                let entropy = shannon_entropy(&buffer[..bytes_read]);
+                if !filter.accepts(entropy) {
+                    pos += bytes_read as u64;
+                    progress.update(bytes_read as u64);
+                    continue;
+                }
+                hex_string = OutputFormatter::append_entropy(&hex_string, entropy);
+            }
 
             line += 1;
 
-            let hex_string = OutputFormatter::format_bytes_as_hex(&buffer[..bytes_read], separator);
-            OutputFormatter::print_line_with_silent(pos, &hex_string, show_offset, hex_offset_length, progress.is_silent());
+            if matches!(output_format, OutputFormat::Ndjson) {
+                if !progress.is_silent() {
+                    let record = HexDumpLine::new(source_name.clone(), pos, hex_string, bytes_read);
+                    Self::emit_ndjson(out, |formatter, writer| formatter.output_hex_dump_line_ndjson(&record, writer))?;
+                }
+            } else if matches!(output_format, OutputFormat::Xxd) {
+                if !progress.is_silent() {
+                    writeln!(out, "{}", OutputFormatter::format_line_xxd(pos, &buffer[..bytes_read]))?;
+                }
+            } else if let OutputFormat::Od(radix) = output_format {
+                if !progress.is_silent() {
+                    let offset = show_offset.then_some(pos);
+                    writeln!(out, "{}", OutputFormatter::format_line_od(offset, radix, &buffer[..bytes_read]))?;
+                }
+            } else {
+                let hex_string = OutputFormatter::append_charset_column(&hex_string, &buffer[..bytes_read]);
+                OutputFormatter::write_line_with_silent(out, pos, &hex_string, show_offset, hex_offset_length, progress.is_silent())?;
+            }
 
             pos += bytes_read as u64;
 
@@ -135,10 +434,32 @@ impl FileProcessor {
             }
         }
 
+        // Zero lines up to the end are marked as well, so the omission is visible
+        if skipped_zero_lines > 0 {
+            self.write_zero_run_marker(out, progress.is_silent())?;
+        }
+
+        // od ends a dump with the offset just past the last byte
+        if let OutputFormat::Od(radix) = output_format {
+            if show_offset && !progress.is_silent() {
+                writeln!(out, "{}", OutputFormatter::format_od_offset(pos, radix))?;
+            }
+        }
+
         progress.finish();
         Ok(())
     }
 
+    /// Write the `*` line standing for omitted all-zero lines
+    ///
+    /// Structured records have no such marker, so the lines are only left out there.
+    fn write_zero_run_marker(&self, out: &mut dyn Write, silent: bool) -> Result<()> {
+        if !silent && !matches!(self.output_format, OutputFormat::Ndjson | OutputFormat::Json | OutputFormat::Msgpack) {
+            writeln!(out, "{}", ZERO_RUN_MARKER)?;
+        }
+        Ok(())
+    }
+
     /// Process file with regex pattern matching from file path
     ///
     /// Searches a file for regex pattern matches and outputs matching regions.
@@ -147,273 +468,1496 @@ impl FileProcessor {
     /// # Arguments
     ///
     /// * `file_path` - Path to the file to search in
+    /// * `out` - Writer receiving the output
     /// * `regex` - Compiled regex pattern to search for
-    /// * `width` - Number of bytes to display per match
-    /// * `limit` - Maximum number of matches to output (0 for unlimited)
-    /// * `separator` - String to separate hex bytes
-    /// * `show_offset` - Whether to display offset values
+    /// * `options` - Match width, match limit, separator, offset display and alignment
     /// * `progress` - Progress indicator to update during processing
-    pub fn process_stream_by_regex_from_path<P: AsRef<Path>>(
+    pub fn grep_path<P: AsRef<Path>>(
         &mut self,
         file_path: P,
+        out: &mut dyn Write,
         regex: &Regex,
-        width: usize,
-        limit: usize,
-        separator: &str,
-        show_offset: bool,
+        options: &SearchOptions,
         progress: &mut ProgressIndicator,
     ) -> Result<()> {
         let file_path = file_path.as_ref();
 
         if is_forensic_image(&file_path) {
             // Process forensic image file (E01, VMDK)
-            let mut forensic_reader = ForensicImageReader::new(&file_path)?;
-            self.process_reader_by_regex(&mut forensic_reader, regex, width, limit, separator, show_offset, progress)
+            let mut forensic_reader = self.open_forensic_image(file_path)?;
+            let file_size = forensic_reader.size();
+            progress.set_total(file_size);
+            self.grep_reader(&mut forensic_reader, out, regex, options, file_size, progress)
         } else {
             // Process regular file
             let mut file = File::open(&file_path)?;
-            self.process_reader_by_regex(&mut file, regex, width, limit, separator, show_offset, progress)
+            self.grep_file(&mut file, out, regex, options, progress)
         }
     }
 
     /// Process file with regex pattern matching
     ///
     /// Searches a file for regex pattern matches and outputs matching regions.
+    /// Files selected by `Config::should_mmap` are searched through a memory mapping instead.
     ///
     /// # Arguments
     ///
     /// * `file` - File to search in
+    /// * `out` - Writer receiving the output
     /// * `regex` - Compiled regex pattern to search for
-    /// * `width` - Number of bytes to display per match
-    /// * `limit` - Maximum number of matches to output (0 for unlimited)
-    /// * `separator` - String to separate hex bytes
-    /// * `show_offset` - Whether to display offset values
-    pub fn process_stream_by_regex(
+    /// * `options` - Match width, match limit, separator, offset display and alignment
+    /// * `progress` - Progress indicator to update during processing
+    pub fn grep_file(
         &mut self,
         file: &mut File,
+        out: &mut dyn Write,
         regex: &Regex,
-        width: usize,
-        limit: usize,
-        separator: &str,
-        show_offset: bool,
+        options: &SearchOptions,
         progress: &mut ProgressIndicator,
     ) -> Result<()> {
-        self.process_reader_by_regex(file, regex, width, limit, separator, show_offset, progress)
+        let file_size = file.metadata()?.len();
+        if self.config.should_mmap(file_size) {
+            return self.grep_mmap(file, out, regex, options, file_size, progress);
+        }
+        self.grep_reader(file, out, regex, options, file_size, progress)
     }
 
-    /// Generic regex processing function that works with any Read + Seek reader
-    fn process_reader_by_regex<R: Read + Seek>(
+    /// Follow a growing file and report regex matches in newly appended bytes
+    ///
+    /// The file is searched from its current position, then polled every `poll_interval`
+    /// until Ctrl-C or the match limit, and a summary is printed to stderr. Each poll
+    /// rescans one buffer padding before the previous end, so a match written across two
+    /// polls is found, but never before the end of the last reported match, so nothing is
+    /// reported twice. A file shrinking below the scanned size was truncated and is
+    /// followed again from offset 0. New bytes go through the buffered regex scanner with
+    /// the end bound at the polled size, so memory stays at one buffer however much was
+    /// appended.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - File to follow, positioned at the first offset to search
+    /// * `out` - Writer receiving the output
+    /// * `regex` - Compiled regex pattern to search for
+    /// * `options` - Match width, match limit, separator, offset display and alignment
+    /// * `poll_interval` - Time between checks for new data
+    pub fn watch_file(
         &mut self,
-        reader: &mut R,
+        file: &mut File,
+        out: &mut dyn Write,
         regex: &Regex,
-        width: usize,
-        limit: usize,
-        separator: &str,
-        show_offset: bool,
-        progress: &mut ProgressIndicator,
+        options: &SearchOptions,
+        poll_interval: Duration,
     ) -> Result<()> {
-        let buffer_size = self.config.get_buffer_size(width);
-        let buffer_padding = self.config.buffer_padding;
+        let SearchOptions { limit, .. } = *options;
+        crate::interrupt::install_handler();
 
+        let mut scanned_to = file.stream_position()?;
+        // A new match may not start before the end of the last reported one
+        let mut resume_from = scanned_to;
         let mut line = 0;
-        let mut last_hit_pos: i64 = -1;
+        let mut bytes_scanned = 0;
 
-        // For EWF files, we need to get size differently
-        // For now, we'll use a large default for generic readers
-        const FORENSIC_IMAGE_DEFAULT_SIZE: u64 = 1024 * 1024 * 1024 * 1024; // 1TB default
-        let file_size = FORENSIC_IMAGE_DEFAULT_SIZE;
-        let hex_offset_length = OutputFormatter::calculate_hex_offset_length(file_size);
+        // The file keeps growing, so pad offsets once for the session, with at least xxd's 8 digits
+        let initial_size = file.metadata()?.len();
+        let hex_offset_length = OutputFormatter::calculate_hex_offset_length(initial_size.max(u64::from(u32::MAX)));
 
         loop {
-            let start_offset = reader.stream_position()?;
-            let bytes_read = self.buffer_manager.read_into_main(reader)?;
-
-            if bytes_read == 0 {
-                break;
-            }
-
-            // Update progress
-            progress.update(bytes_read as u64);
-
-            // Process regex matches directly without collecting into vector
-            let buffer_slice = self.buffer_manager.get_main_slice(0, bytes_read);
-            let mut matches_to_process = Vec::new();
-
-            // Only collect match positions that we actually need to process
-            for mat in regex.find_iter(buffer_slice) {
-                let match_start = mat.start();
-                let new_hit_pos = start_offset + match_start as u64;
-
-                // Skip duplicates early
-                if new_hit_pos as i64 > last_hit_pos {
-                    matches_to_process.push(match_start);
-                    // Limit collection for memory efficiency
-                    if limit > 0 && matches_to_process.len() >= limit - line {
-                        break;
-                    }
-                }
+            let size = file.metadata()?.len();
+            if size < scanned_to {
+                diagnostics::warning(format!("{} was truncated, watching again from offset 0", self.source_name));
+                scanned_to = 0;
+                resume_from = 0;
             }
 
-            for match_start in matches_to_process {
-                let new_hit_pos = start_offset + match_start as u64;
+            if size > scanned_to {
+                let window_start = scanned_to
+                    .saturating_sub(self.config.buffer_padding as u64)
+                    .max(resume_from);
+                file.seek(SeekFrom::Start(window_start))?;
 
-                // Prevent duplicates
-                if new_hit_pos as i64 <= last_hit_pos {
-                    continue;
-                }
-
-                // Handle buffer boundary cases safely
-                // Check if the match extends beyond the current buffer and we're at buffer capacity
-                if let Some(overflow_pos) = match_start.checked_add(width) {
-                    if overflow_pos > bytes_read && bytes_read == self.buffer_manager.get_buffer_size() {
-                        // Pattern extends beyond buffer - need to seek to match position for complete read
-                        reader.seek(SeekFrom::Start(new_hit_pos))?;
-                        last_hit_pos = new_hit_pos as i64;
-                        break;
-                    }
-                } else {
-                    // Integer overflow would occur - skip this match
-                    continue;
+                // --watch excludes --end/--length, so the end bound is free to stop at the polled size
+                self.end_offset = Some(size);
+                let poll_options = options.clone().limit(if limit > 0 { limit - line } else { 0 });
+                let mut matches = self.regex_matches(&mut *file, regex, &poll_options, None);
+                while let Some(found) = matches.next() {
+                    let found = found?;
+                    matches.processor.report_regex_match(
+                        out,
+                        found.offset,
+                        found.len,
+                        &found.bytes,
+                        found.pattern_index,
+                        options,
+                        hex_offset_length,
+                        false,
+                    )?;
+                    resume_from = found.offset + found.len.max(1) as u64;
+                    line += 1;
                 }
 
-                line += 1;
-
-                // Read width bytes from match position
-                let (hex_string, match_info) = self.read_match_data_with_highlight(
-                    reader,
-                    match_start,
-                    width,
-                    bytes_read,
-                    start_offset,
-                    separator,
-                    &regex,
-                )?;
-
-                // Calculate match position within the displayed hex string
-                let match_byte_pos = if match_start < width { Some(0) } else { None };
-                let match_byte_len = if match_byte_pos.is_some() {
-                    match_info.map(|len| std::cmp::min(len, width))
-                } else {
-                    None
-                };
-
-                OutputFormatter::print_line_with_match_highlight_silent(
-                    new_hit_pos,
-                    &hex_string,
-                    show_offset,
-                    hex_offset_length,
-                    crate::color_context::get_color_choice(),
-                    match_byte_pos,
-                    match_byte_len,
-                    progress.is_silent(),
-                );
-                last_hit_pos = new_hit_pos as i64;
-
-                // Check line limit
-                if limit > 0 && line >= limit {
-                    return Ok(());
-                }
+                bytes_scanned += size - scanned_to;
+                scanned_to = size;
+                self.write_collected_matches(out, false)?;
+                out.flush()?;
             }
 
-            // Read next buffer with overlap to handle patterns spanning boundaries
-            if bytes_read == buffer_size {
-                let new_pos = reader
-                    .stream_position()?
-                    .saturating_sub(buffer_padding as u64);
-                reader.seek(SeekFrom::Start(new_pos))?;
+            if (limit > 0 && line >= limit) || crate::interrupt::sleep_unless_interrupted(poll_interval) {
+                break;
             }
         }
 
-        progress.finish();
+        eprintln!("=== Watch stopped: {} matches in {} bytes ===", line, bytes_scanned);
         Ok(())
     }
 
-    /// Read match data, handling cases where width extends beyond buffer
-    #[allow(dead_code)]
-    fn read_match_data(
+    /// Search a memory-mapped file with regex pattern matching
+    ///
+    /// The whole file is scanned as one slice, so there are no buffer boundaries to
+    /// stitch together; the output is the same as the streaming path. Falls back to
+    /// streaming when holes are skipped, the file does not fit in the address space
+    /// (32-bit targets) or it cannot be mapped.
+    fn grep_mmap(
         &mut self,
         file: &mut File,
-        match_start: usize,
-        width: usize,
-        bytes_read: usize,
-        start_offset: u64,
-        separator: &str,
-    ) -> Result<String> {
-        self.read_match_data_generic(file, match_start, width, bytes_read, start_offset, separator)
+        out: &mut dyn Write,
+        regex: &Regex,
+        options: &SearchOptions,
+        file_size: u64,
+        progress: &mut ProgressIndicator,
+    ) -> Result<()> {
+        if self.hole_skipper.is_some() || usize::try_from(file_size).is_err() {
+            return self.grep_reader(file, out, regex, options, file_size, progress);
+        }
+
+        // SAFETY: the mapping is only read. As with any mapping, the file being truncated
+        // by another process while it is searched is undefined behavior.
+        let mmap = match unsafe { Mmap::map(&*file) } {
+            Ok(mmap) => mmap,
+            Err(_) => {
+                return self.grep_reader(file, out, regex, options, file_size, progress);
+            }
+        };
+
+        let start = file.stream_position()?.min(mmap.len() as u64) as usize;
+        // The mapping is searched in one pass up to the end bound
+        let end = self.end_offset.map_or(mmap.len() as u64, |end| end.min(mmap.len() as u64));
+        self.record_read(start as u64, end.saturating_sub(start as u64) as usize);
+        self.grep_slice(&mmap, start, out, regex, options, file_size, progress)
+    }
+
+    /// Regex search over an in-memory slice holding the whole input from offset 0
+    fn grep_slice(
+        &mut self,
+        data: &[u8],
+        start: usize,
+        out: &mut dyn Write,
+        regex: &Regex,
+        options: &SearchOptions,
+        file_size: u64,
+        progress: &mut ProgressIndicator,
+    ) -> Result<()> {
+        let hex_offset_length = OutputFormatter::calculate_hex_offset_length(file_size);
+        let silent = progress.is_silent();
+        self.begin_json_array(out, silent)?;
+        // Show the match count from the start, even while it is still 0
+        progress.add_matches(0);
+
+        self.scan_slice_by_regex(data, start, regex, options, progress, |processor, found| {
+            processor.report_regex_match(
+                out,
+                found.offset,
+                found.len,
+                &found.bytes,
+                found.pattern_index,
+                options,
+                hex_offset_length,
+                silent,
+            )
+        })?;
+        self.write_collected_matches(out, silent)
     }
 
-    /// Read match data with highlighting information
-    fn read_match_data_with_highlight<R: Read + Seek>(
+    /// Generic regex processing function that works with any Read + Seek reader
+    ///
+    /// `file_size` is the total size of the input and only determines the offset padding.
+    pub fn grep_reader<R: Read + Seek>(
         &mut self,
         reader: &mut R,
-        match_start: usize,
-        width: usize,
-        bytes_read: usize,
-        start_offset: u64,
-        separator: &str,
+        out: &mut dyn Write,
         regex: &Regex,
-    ) -> Result<(String, Option<usize>)> {
-        let hex_string = self.read_match_data_generic(reader, match_start, width, bytes_read, start_offset, separator)?;
+        options: &SearchOptions,
+        file_size: u64,
+        progress: &mut ProgressIndicator,
+    ) -> Result<()> {
+        let hex_offset_length = OutputFormatter::calculate_hex_offset_length(file_size);
+        let silent = progress.is_silent();
+        self.begin_json_array(out, silent)?;
+        // Show the match count from the start, even while it is still 0
+        progress.add_matches(0);
 
-        // Find the match length by re-running the regex on the data we're about to display
-        let display_start = start_offset + match_start as u64;
-        let current_pos = reader.stream_position()?;
-        reader.seek(SeekFrom::Start(display_start))?;
+        let mut matches = self.regex_matches(reader, regex, options, Some(progress));
+        while let Some(found) = matches.next() {
+            let found = found?;
+            matches.processor.report_regex_match(
+                out,
+                found.offset,
+                found.len,
+                &found.bytes,
+                found.pattern_index,
+                options,
+                hex_offset_length,
+                silent,
+            )?;
+        }
+        self.write_collected_matches(out, silent)
+    }
 
-        let mut display_buffer = vec![0u8; width];
-        let actual_read = reader.read(&mut display_buffer)?;
-        reader.seek(SeekFrom::Start(current_pos))?;
+    /// Find the regex matches in a reader, from its current position
+    ///
+    /// The reader is scanned exactly as for the printed output: in buffers overlapping by
+    /// the buffer padding, honoring the end bound, match alignment, hole skipping, entropy
+    /// filter and extraction directory set on the processor.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - Reader to search, positioned at the first offset to search
+    /// * `regex` - Compiled regex pattern to search for
+    /// * `options` - `width` bytes are returned per match (0 for exactly the matched bytes)
+    ///   and the search stops after `limit` matches (0 for unlimited)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hxgrep::{Config, FileProcessor, RegexProcessor, SearchOptions};
+    /// use std::io::Cursor;
+    ///
+    /// let mut processor = FileProcessor::new(Config::default());
+    /// let regex = RegexProcessor::compile_pattern("MZ").unwrap();
+    /// let mut reader = Cursor::new(b"..MZ\x90\x00..MZ".to_vec());
+    /// let matches = processor.find_matches(&mut reader, &regex, &SearchOptions::new().width(0)).unwrap();
+    /// let offsets: Vec<u64> = matches.iter().map(|m| m.offset).collect();
+    /// assert_eq!(offsets, vec![2, 8]);
+    /// assert_eq!(matches[0].bytes, b"MZ");
+    /// ```
+    pub fn find_matches<R: Read + Seek>(
+        &mut self,
+        reader: &mut R,
+        regex: &Regex,
+        options: &SearchOptions,
+    ) -> Result<Vec<Match>> {
+        self.regex_matches(reader, regex, options, None).collect()
+    }
 
-        let match_len = if let Some(mat) = regex.find(&display_buffer[..actual_read]) {
-            Some(mat.len())
-        } else {
-            None
-        };
+    /// Search a reader for regex matches without printing anything
+    ///
+    /// The reader is scanned lazily from its current position, one buffer at a time, so
+    /// stopping early never reads the rest of the input. Each match is yielded as a
+    /// [`BinaryMatch`] for the source name set with `set_source_name`, with `hex_data`
+    /// holding the `width` bytes from the match. Processor settings apply as for
+    /// [`FileProcessor::find_matches`]. An error ends the iteration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hxgrep::{Config, FileProcessor, RegexProcessor, SearchOptions};
+    /// use std::io::Cursor;
+    ///
+    /// let mut processor = FileProcessor::new(Config::default());
+    /// let regex = RegexProcessor::compile_pattern("PK\\x03\\x04").unwrap();
+    /// let mut reader = Cursor::new(b"....PK\x03\x04....".to_vec());
+    /// let found = processor
+    ///     .search(&mut reader, &regex, &SearchOptions::new().width(0))
+    ///     .next()
+    ///     .unwrap()
+    ///     .unwrap();
+    /// assert_eq!(found.offset, 4);
+    /// assert_eq!(found.hex_data, "50 4B 03 04");
+    /// ```
+    pub fn search<'a, R: Read + Seek>(
+        &'a mut self,
+        reader: &'a mut R,
+        regex: &'a Regex,
+        options: &SearchOptions,
+    ) -> impl Iterator<Item = Result<BinaryMatch>> + 'a {
+        let source_name = self.source_name.clone();
+        let (separator, group_size, hex_case) = (options.separator.clone(), options.group_size, options.hex_case);
+        self.regex_matches(reader, regex, options, None)
+            .map(move |found| {
+                found.map(|found| {
+                    let hex_data = OutputFormatter::format_bytes_grouped(&found.bytes, &separator, group_size, hex_case);
+                    BinaryMatch::new(source_name.clone(), found.offset, hex_data, found.len)
+                })
+            })
+    }
 
-        Ok((hex_string, match_len))
+    /// Start a lazy regex scan of a reader from its current position
+    fn regex_matches<'a, R: Read + Seek>(
+        &'a mut self,
+        reader: &'a mut R,
+        regex: &'a Regex,
+        options: &SearchOptions,
+        progress: Option<&'a mut ProgressIndicator>,
+    ) -> RegexMatches<'a, R> {
+        self.buffered = 0..0;
+        RegexMatches {
+            processor: self,
+            reader,
+            regex,
+            progress,
+            scan: ReaderScan {
+                width: options.width,
+                limit: options.limit,
+                trim_trailing: options.trim_trailing,
+                line: 0,
+                last_hit_pos: -1,
+                last_match_end: 0,
+                done: false,
+            },
+            pending: VecDeque::new(),
+        }
     }
 
-    /// Generic read match data function that works with any Read + Seek reader
-    fn read_match_data_generic<R: Read + Seek>(
+    /// Scan an in-memory slice holding the whole input from offset 0, passing each match to `on_match`
+    ///
+    /// `on_match` returns whether the match was kept; only kept matches count towards
+    /// the limit and are extracted.
+    fn scan_slice_by_regex<F>(
         &mut self,
-        reader: &mut R,
-        match_start: usize,
-        width: usize,
-        bytes_read: usize,
-        start_offset: u64,
-        separator: &str,
-    ) -> Result<String> {
-        let end_pos = std::cmp::min(match_start + width, bytes_read);
-        let actual_width = end_pos - match_start;
+        data: &[u8],
+        start: usize,
+        regex: &Regex,
+        options: &SearchOptions,
+        progress: &mut ProgressIndicator,
+        mut on_match: F,
+    ) -> Result<()>
+    where
+        F: FnMut(&mut Self, Match) -> Result<bool>,
+    {
+        let SearchOptions { width, limit, trim_trailing, .. } = *options;
+        // Matches never begin at or after the end bound
+        let end = match self.end_offset {
+            Some(end) => (end.min(data.len() as u64) as usize).max(start),
+            None => data.len(),
+        };
+        let search = &data[start..end];
+        let mut line = 0;
+        let mut last_match_end = 0;
 
-        if actual_width < width && match_start + width > bytes_read {
-            // Need to read additional data from reader
-            let current_pos = reader.stream_position()?;
-            reader.seek(SeekFrom::Start(start_offset + end_pos as u64))?;
+        let found: Box<dyn Iterator<Item = regex::bytes::Match> + '_> = match self.match_alignment {
+            Some(alignment) => Box::new(alignment.find_iter(regex, search, start as u64)),
+            None => Box::new(regex.find_iter(search)),
+        };
 
-            let extra_needed = width - actual_width;
-            let extra_read = self.buffer_manager.read_into_extra(reader, extra_needed)?;
+        for mat in found {
+            let match_start = start + mat.start();
+            let match_len = mat.len();
+            // The slice is searched in one pass, so progress follows the matches
+            progress.update_range(start as u64..match_start as u64);
 
-            // Combine data using buffer manager
-            let combined_data =
-                self.buffer_manager
-                    .combine_buffers(match_start, end_pos, extra_read);
+            // In match-only mode, skip the tail of a match that was already printed in full
+            if width == 0 && match_start < last_match_end {
+                continue;
+            }
+            let display_width = if width == 0 { match_len } else { width };
+            let display_end = (match_start + display_width).min(end);
+            let pattern_index = self
+                .pattern_set
+                .as_ref()
+                .and_then(|set| set.index_at(&data[match_start..end]));
 
-            reader.seek(SeekFrom::Start(current_pos))?;
+            let kept = on_match(
+                self,
+                Match {
+                    offset: match_start as u64,
+                    len: match_len,
+                    bytes: data[match_start..display_end].to_vec(),
+                    pattern_index,
+                },
+            )?;
+            last_match_end = match_start + match_len;
+            // Matches hidden by the entropy filter do not count towards the limit
+            if !kept {
+                continue;
+            }
+            line += 1;
+            progress.add_matches(1);
 
-            Ok(OutputFormatter::format_bytes_as_hex(
-                combined_data,
-                separator,
-            ))
-        } else {
-            let main_slice = self.buffer_manager.get_main_slice(match_start, end_pos);
-            Ok(OutputFormatter::format_bytes_as_hex(main_slice, separator))
+            // Write the full matched range to its own file
+            if self.extractor.is_some() {
+                self.extract_match(match_start as u64, &data[match_start..match_start + match_len], trim_trailing)?;
+            }
+
+            if limit > 0 && line >= limit {
+                break;
+            }
         }
+
+        progress.update_range(start as u64..end as u64);
+        progress.finish();
+        Ok(())
     }
-}
 
-#[cfg(test)]
-mod tests {
+    /// Scan the next buffer of a lazy regex scan, appending the matches it holds to `found`
+    ///
+    /// Matches hidden by the entropy filter are dropped and do not count towards the limit.
+    /// Kept matches are extracted here, while their bytes are still buffered. Sets
+    /// `scan.done` at the end of the input or once the limit is reached.
+    fn scan_next_buffer<R: Read + Seek>(
+        &mut self,
+        reader: &mut R,
+        regex: &Regex,
+        scan: &mut ReaderScan,
+        mut progress: Option<&mut ProgressIndicator>,
+        found: &mut VecDeque<Match>,
+    ) -> Result<()> {
+        let buffer_size = self.buffer_manager.get_buffer_size();
+        let buffer_padding = self.config.buffer_padding;
+        let (width, limit) = (scan.width, scan.limit);
+
+        // Jump over holes in sparse files
+        if let Some(skipper) = self.hole_skipper.as_mut() {
+            let pos = reader.stream_position()?;
+            match skipper.next_data_position(pos) {
+                Some(data_pos) if data_pos != pos => {
+                    reader.seek(SeekFrom::Start(data_pos))?;
+                }
+                Some(_) => {}
+                None => {
+                    scan.done = true;
+                    if let Some(progress) = progress {
+                        progress.finish();
+                    }
+                    return Ok(());
+                }
+            }
+        }
+
+        let start_offset = reader.stream_position()?;
+        let bytes_read = self.read_main(reader, start_offset)?;
+
+        if bytes_read == 0 {
+            scan.done = true;
+            if let Some(progress) = progress {
+                progress.finish();
+            }
+            return Ok(());
+        }
+        self.buffered = start_offset..start_offset + bytes_read as u64;
+
+        // Update progress, counting the overlap with the previous buffer once
+        if let Some(progress) = progress.as_deref_mut() {
+            progress.update_range(self.buffered.clone());
+        }
+
+        // Process regex matches directly without collecting into vector
+        let buffer_slice = self.buffer_manager.get_main_slice(0, bytes_read);
+        let mut matches_to_process = Vec::new();
+
+        let regex_found: Box<dyn Iterator<Item = regex::bytes::Match> + '_> = match self.match_alignment {
+            Some(alignment) => Box::new(alignment.find_iter(regex, buffer_slice, start_offset)),
+            None => Box::new(regex.find_iter(buffer_slice)),
+        };
+
+        // Only collect match positions that we actually need to process
+        for mat in regex_found {
+            let match_start = mat.start();
+            let new_hit_pos = start_offset + match_start as u64;
+
+            // Skip duplicates early
+            if new_hit_pos as i64 > scan.last_hit_pos {
+                // Keep the match length so highlighting doesn't need a second regex pass
+                let pattern_index = self
+                    .pattern_set
+                    .as_ref()
+                    .and_then(|set| set.index_at(&buffer_slice[match_start..]));
+                matches_to_process.push((match_start, mat.end() - mat.start(), pattern_index));
+                // Limit collection for memory efficiency
+                if limit > 0 && matches_to_process.len() >= limit - scan.line {
+                    break;
+                }
+            }
+        }
+
+        for (match_start, match_len, pattern_index) in matches_to_process {
+            let new_hit_pos = start_offset + match_start as u64;
+
+            // Prevent duplicates
+            if new_hit_pos as i64 <= scan.last_hit_pos {
+                continue;
+            }
+
+            // In match-only mode, skip the tail of a match that was already printed in full
+            if width == 0 && new_hit_pos < scan.last_match_end {
+                continue;
+            }
+
+            // A match reaching the end of a full buffer may continue past it
+            let match_len = if (width == 0 || self.extractor.is_some())
+                && match_start + match_len == bytes_read
+                && bytes_read == self.buffer_manager.get_buffer_size()
+            {
+                self.full_match_length(reader, new_hit_pos, match_len, regex)?
+            } else {
+                match_len
+            };
+            let mut display_width = if width == 0 { match_len } else { width };
+
+            // Keep the displayed bytes (and an extended match) within the end bound
+            let match_len = match self.end_offset {
+                Some(end) => {
+                    let available = usize::try_from(end.saturating_sub(new_hit_pos)).unwrap_or(usize::MAX);
+                    display_width = display_width.min(available);
+                    match_len.min(available)
+                }
+                None => match_len,
+            };
+
+            // Handle buffer boundary cases safely
+            // Check if the match extends beyond the current buffer and we're at buffer capacity
+            if let Some(overflow_pos) = match_start.checked_add(width) {
+                if overflow_pos > bytes_read && bytes_read == self.buffer_manager.get_buffer_size() {
+                    // Pattern extends beyond buffer - need to seek to match position for complete read.
+                    // The match is reported from the next buffer, so it must not count as a duplicate.
+                    reader.seek(SeekFrom::Start(new_hit_pos))?;
+                    scan.last_hit_pos = new_hit_pos as i64 - 1;
+                    break;
+                }
+            } else {
+                // Integer overflow would occur - skip this match
+                continue;
+            }
+
+            // Read width bytes from match position
+            let match_bytes = self.fetch_window(reader, new_hit_pos, display_width)?.bytes;
+            scan.last_hit_pos = new_hit_pos as i64;
+            scan.last_match_end = new_hit_pos + match_len as u64;
+
+            // Matches hidden by the entropy filter do not count towards the limit
+            if !self.passes_entropy_filter(&match_bytes) {
+                continue;
+            }
+            scan.line += 1;
+            if let Some(progress) = progress.as_deref_mut() {
+                progress.add_matches(1);
+            }
+
+            // Write the full matched range to its own file
+            if self.extractor.is_some() {
+                let data = self.fetch_window(reader, new_hit_pos, match_len)?.bytes;
+                self.extract_match(new_hit_pos, &data, scan.trim_trailing)?;
+            }
+
+            found.push_back(Match {
+                offset: new_hit_pos,
+                len: match_len,
+                bytes: match_bytes,
+                pattern_index,
+            });
+
+            // Check line limit
+            if limit > 0 && scan.line >= limit {
+                scan.done = true;
+                return Ok(());
+            }
+        }
+
+        // Read next buffer with overlap to handle patterns spanning boundaries
+        if bytes_read == buffer_size {
+            let new_pos = reader
+                .stream_position()?
+                .saturating_sub(buffer_padding as u64);
+            reader.seek(SeekFrom::Start(new_pos))?;
+        }
+
+        Ok(())
+    }
+
+    /// Write matched bytes to the extraction directory, trimming their trailing run of `trim_trailing`
+    fn extract_match(&mut self, offset: u64, data: &[u8], trim_trailing: Option<u8>) -> Result<()> {
+        if let Some(extractor) = &mut self.extractor {
+            let data = trim_trailing.map_or(data, |byte| OutputFormatter::trim_trailing(data, byte));
+            extractor.write_match(&self.source_name, offset, data)?;
+        }
+        Ok(())
+    }
+
+    /// Print or collect the output for one regex match
+    ///
+    /// `match_bytes` are the displayed bytes starting at the match. Returns `false` when
+    /// the entropy filter hides the match.
+    fn report_regex_match(
+        &mut self,
+        out: &mut dyn Write,
+        offset: u64,
+        match_len: usize,
+        match_bytes: &[u8],
+        pattern_index: Option<usize>,
+        options: &SearchOptions,
+        hex_offset_length: usize,
+        silent: bool,
+    ) -> Result<bool> {
+        let show_offset = options.show_offset;
+
+        // Skip matches whose displayed bytes fall outside the entropy range
+        if !self.passes_entropy_filter(match_bytes) {
+            return Ok(false);
+        }
+
+        // -b prints where each match starts and nothing else
+        if options.offsets_only {
+            if !silent {
+                OutputFormatter::write_match_offset(out, offset, options.null_terminated)?;
+            }
+            return Ok(true);
+        }
+
+        // Trim the trailing padding from the displayed window; the match offset and length stay
+        let window_len = match_bytes.len();
+        let match_bytes = match options.trim_trailing {
+            Some(byte) => OutputFormatter::trim_trailing(match_bytes, byte),
+            None => match_bytes,
+        };
+        let trimmed = window_len - match_bytes.len();
+
+        // The displayed window starts at the match, so the highlight begins at byte 0
+        // (shifted by any alignment padding below) and covers the match up to the window end
+        let match_byte_pos = Some(0);
+        let match_byte_len = Some(std::cmp::min(match_len, match_bytes.len()));
+
+        // Snap the displayed start offset to the alignment boundary if requested
+        let (display_offset, mut hex_string, match_byte_pos) = match options.align {
+            Some(align) => {
+                let (aligned_offset, padded, lead) = OutputFormatter::align_bytes_as_hex(
+                    offset,
+                    match_bytes,
+                    &options.separator,
+                    options.group_size,
+                    options.hex_case,
+                    align,
+                );
+                (aligned_offset, padded, match_byte_pos.map(|pos| pos + lead))
+            }
+            None => (
+                offset,
+                OutputFormatter::format_bytes_grouped(match_bytes, &options.separator, options.group_size, options.hex_case),
+                match_byte_pos,
+            ),
+        };
+        if self.entropy_filter.is_some() {
+            hex_string = OutputFormatter::append_entropy(&hex_string, shannon_entropy(match_bytes));
+        }
+        let compressed_range = self.compressed_offsets.as_ref().and_then(|offsets| offsets.range_of(offset));
+        let record = || BinaryMatch {
+            window_len_trimmed: options.trim_trailing.map(|_| match_bytes.len()),
+            compressed_offset: compressed_range.as_ref().map(|range| [range.start, range.end]),
+            ..BinaryMatch::new(self.source_name.clone(), offset, hex_string.clone(), match_len)
+        };
+
+        if matches!(self.output_format, OutputFormat::Ndjson) {
+            // Stream each match as its own JSON line so consumers see it immediately
+            if !silent {
+                let record = record();
+                Self::emit_ndjson(out, |formatter, writer| formatter.output_match_ndjson(&record, writer))?;
+            }
+        } else if matches!(self.output_format, OutputFormat::Json) {
+            // Matches go straight into the open array, so output starts before the search ends
+            if !silent {
+                let record = record();
+                self.write_json_match(out, &record)?;
+            }
+        } else if matches!(self.output_format, OutputFormat::Msgpack) {
+            // MessagePack is written as a single array once the search finishes
+            let record = record();
+            self.collected_matches.push(record);
+        } else if matches!(self.output_format, OutputFormat::Xxd) {
+            if !silent {
+                writeln!(out, "{}", OutputFormatter::format_line_xxd(offset, match_bytes))?;
+            }
+        } else if let OutputFormat::Od(radix) = self.output_format {
+            if !silent {
+                let offset = show_offset.then_some(offset);
+                writeln!(out, "{}", OutputFormatter::format_line_od(offset, radix, match_bytes))?;
+            }
+        } else {
+            let mut hex_string = OutputFormatter::append_charset_column(&hex_string, match_bytes);
+            if let Some(byte) = options.trim_trailing.filter(|_| trimmed > 0) {
+                hex_string = OutputFormatter::append_trimmed(&hex_string, trimmed, byte, options.hex_case);
+            }
+            if let Some(index) = pattern_index {
+                hex_string = OutputFormatter::append_pattern_index(&hex_string, index);
+            }
+            if let Some(range) = &compressed_range {
+                hex_string = OutputFormatter::append_compressed_range(&hex_string, range);
+            }
+            if let Some(collapser) = self.collapser.as_mut() {
+                let formatted = OutputFormatter::format_line_with_match_highlight(
+                    display_offset,
+                    &hex_string,
+                    show_offset,
+                    hex_offset_length,
+                    crate::color_context::use_color(crate::color_context::ColorStream::Stdout),
+                    match_byte_pos,
+                    match_byte_len,
+                );
+                for ready in collapser.push(offset, formatted) {
+                    if !silent {
+                        writeln!(out, "{}", ready)?;
+                    }
+                }
+            } else {
+                OutputFormatter::write_line_with_match_highlight_silent(
+                    out,
+                    display_offset,
+                    &hex_string,
+                    show_offset,
+                    hex_offset_length,
+                    crate::color_context::get_color_choice(),
+                    match_byte_pos,
+                    match_byte_len,
+                    silent,
+                )?;
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Hex dump the regions that do not match the regex pattern, from a file path (inverse match)
+    ///
+    /// Automatically detects forensic image files (E01, VMDK) like the other `_from_path` methods.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_path` - Path to the file to read from
+    /// * `out` - Writer receiving the output
+    /// * `regex` - Compiled regex pattern whose matches are excluded
+    /// * `options` - Line width, line limit, separator and offset display
+    /// * `progress` - Progress indicator to update during processing
+    pub fn dump_inverted_path<P: AsRef<Path>>(
+        &mut self,
+        file_path: P,
+        out: &mut dyn Write,
+        regex: &Regex,
+        options: &DumpOptions,
+        progress: &mut ProgressIndicator,
+    ) -> Result<()> {
+        let file_path = file_path.as_ref();
+
+        if is_forensic_image(&file_path) {
+            // Process forensic image file (E01, VMDK)
+            let mut forensic_reader = self.open_forensic_image(file_path)?;
+            let file_size = forensic_reader.size();
+            progress.set_total(file_size);
+            self.dump_inverted(&mut forensic_reader, out, regex, options, file_size, progress)
+        } else {
+            // Process regular file
+            let mut file = File::open(&file_path)?;
+            let file_size = file.metadata()?.len();
+            self.dump_inverted(&mut file, out, regex, options, file_size, progress)
+        }
+    }
+
+    /// Hex dump the regions that do not match the regex pattern (inverse match)
+    ///
+    /// Computes the complement of the matched ranges from the current reader position and
+    /// dumps it line by line. A gap spanning buffer boundaries is dumped as one continuous
+    /// region, so its lines stay aligned to the start of the gap.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - Reader to search in
+    /// * `out` - Writer receiving the output
+    /// * `regex` - Compiled regex pattern whose matches are excluded
+    /// * `options` - Line width, line limit, separator and offset display
+    /// * `file_size` - Total size of the file for offset formatting
+    /// * `progress` - Progress indicator to update during processing
+    pub fn dump_inverted<R: Read + Seek>(
+        &mut self,
+        reader: &mut R,
+        out: &mut dyn Write,
+        regex: &Regex,
+        options: &DumpOptions,
+        file_size: u64,
+        progress: &mut ProgressIndicator,
+    ) -> Result<()> {
+        let width = options.width;
+        let hex_offset_length = OutputFormatter::calculate_hex_offset_length(file_size);
+        let buffer_padding = self.config.buffer_padding as u64;
+        let mut gap_start = reader.stream_position()?;
+        let mut lines = 0;
+        self.buffered = 0..0;
+
+        loop {
+            let start_offset = reader.stream_position()?;
+            let bytes_read = self.read_main(reader, start_offset)?;
+
+            if bytes_read == 0 {
+                break;
+            }
+            self.buffered = start_offset..start_offset + bytes_read as u64;
+            progress.update_range(self.buffered.clone());
+
+            let buffer_end = start_offset + bytes_read as u64;
+            let full_buffer = bytes_read == self.buffer_manager.get_buffer_size();
+
+            // Matches starting in the trailing padding are found again by the next buffer
+            let safe_end = if full_buffer {
+                buffer_end.saturating_sub(buffer_padding).max(start_offset + 1)
+            } else {
+                buffer_end
+            };
+
+            let buffer_slice = self.buffer_manager.get_main_slice(0, bytes_read);
+            let matches: Vec<(usize, usize)> = regex
+                .find_iter(buffer_slice)
+                .map(|mat| (mat.start(), mat.len()))
+                .take_while(|&(match_start, _)| start_offset + (match_start as u64) < safe_end)
+                .collect();
+
+            for (match_start, match_len) in matches {
+                let match_offset = start_offset + match_start as u64;
+
+                // A match reaching the end of a full buffer may continue past it
+                let match_len = if full_buffer && match_start + match_len == bytes_read {
+                    self.full_match_length(reader, match_offset, match_len, regex)?
+                } else {
+                    match_len
+                };
+                let match_end = match_offset + match_len as u64;
+
+                if match_end <= gap_start {
+                    continue;
+                }
+
+                if match_offset > gap_start
+                    && self.dump_region(
+                        reader, out, gap_start, match_offset, options,
+                        hex_offset_length, &mut lines, progress.is_silent(),
+                    )?
+                {
+                    progress.finish();
+                    return Ok(());
+                }
+                gap_start = gap_start.max(match_end);
+            }
+
+            // Dump the whole lines of the pending gap; the remainder continues into the next buffer
+            if gap_start < safe_end {
+                let gap_len = safe_end - gap_start;
+                let dump_len = if full_buffer {
+                    gap_len - gap_len % width as u64
+                } else {
+                    gap_len
+                };
+
+                if dump_len > 0 {
+                    if self.dump_region(
+                        reader, out, gap_start, gap_start + dump_len, options,
+                        hex_offset_length, &mut lines, progress.is_silent(),
+                    )? {
+                        progress.finish();
+                        return Ok(());
+                    }
+                    gap_start += dump_len;
+                }
+            }
+
+            if full_buffer {
+                reader.seek(SeekFrom::Start(safe_end))?;
+            }
+        }
+
+        progress.finish();
+        Ok(())
+    }
+
+    /// Dump `[region_start, region_end)` line by line, returning whether the line limit was reached
+    ///
+    /// Bytes inside the main buffer are taken from it directly; anything else is read from the reader.
+    fn dump_region<R: Read + Seek>(
+        &mut self,
+        reader: &mut R,
+        out: &mut dyn Write,
+        region_start: u64,
+        region_end: u64,
+        options: &DumpOptions,
+        hex_offset_length: usize,
+        lines: &mut usize,
+        silent: bool,
+    ) -> Result<bool> {
+        let DumpOptions { width, limit, ref separator, group_size, hex_case, show_offset, .. } = *options;
+        let mut pos = region_start;
+
+        while pos < region_end {
+            let line_end = (pos + width as u64).min(region_end);
+            let line_bytes = self.fetch_window(reader, pos, (line_end - pos) as usize)?.bytes;
+
+            let hex_string = OutputFormatter::format_bytes_grouped(&line_bytes, separator, group_size, hex_case);
+            if matches!(self.output_format, OutputFormat::Ndjson) {
+                if !silent {
+                    let record = HexDumpLine::new(self.source_name.clone(), pos, hex_string, line_bytes.len());
+                    Self::emit_ndjson(out, |formatter, writer| formatter.output_hex_dump_line_ndjson(&record, writer))?;
+                }
+            } else if matches!(self.output_format, OutputFormat::Xxd) {
+                if !silent {
+                    writeln!(out, "{}", OutputFormatter::format_line_xxd(pos, &line_bytes))?;
+                }
+            } else if let OutputFormat::Od(radix) = self.output_format {
+                if !silent {
+                    writeln!(out, "{}", OutputFormatter::format_line_od(show_offset.then_some(pos), radix, &line_bytes))?;
+                }
+            } else {
+                let hex_string = OutputFormatter::append_charset_column(&hex_string, &line_bytes);
+                OutputFormatter::write_line_with_silent(out, pos, &hex_string, show_offset, hex_offset_length, silent)?;
+            }
+
+            *lines += 1;
+            if limit > 0 && *lines >= limit {
+                return Ok(true);
+            }
+            pos = line_end;
+        }
+
+        Ok(false)
+    }
+
+    /// Extract printable ASCII strings from a file path, like the Unix `strings` utility
+    ///
+    /// Automatically detects forensic image files (E01, VMDK) like the other `_from_path` methods.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_path` - Path to the file to read from
+    /// * `out` - Writer receiving the output
+    /// * `min_len` - Minimum length of reported strings
+    /// * `limit` - Maximum number of strings to output (0 for unlimited)
+    /// * `show_offset` - Whether to display offset values
+    /// * `progress` - Progress indicator to update during processing
+    pub fn find_strings_from_path<P: AsRef<Path>>(
+        &mut self,
+        file_path: P,
+        out: &mut dyn Write,
+        min_len: usize,
+        limit: usize,
+        show_offset: bool,
+        progress: &mut ProgressIndicator,
+    ) -> Result<()> {
+        let file_path = file_path.as_ref();
+
+        if is_forensic_image(&file_path) {
+            // Process forensic image file (E01, VMDK)
+            let mut forensic_reader = self.open_forensic_image(file_path)?;
+            let file_size = forensic_reader.size();
+            progress.set_total(file_size);
+            self.find_strings(&mut forensic_reader, out, min_len, limit, show_offset, file_size, progress)
+        } else {
+            // Process regular file
+            let mut file = File::open(&file_path)?;
+            let file_size = file.metadata()?.len();
+            self.find_strings(&mut file, out, min_len, limit, show_offset, file_size, progress)
+        }
+    }
+
+    /// Extract printable ASCII strings (`0x20`-`0x7E`) of at least `min_len` bytes
+    ///
+    /// Each string is printed with its offset, or alone when `show_offset` is false, which
+    /// matches the output of `strings`. Structured formats report a `BinaryMatch` per string
+    /// with its text in `string_value`.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - Reader to extract strings from, starting at its current position
+    /// * `out` - Writer receiving the output
+    /// * `min_len` - Minimum length of reported strings
+    /// * `limit` - Maximum number of strings to output (0 for unlimited)
+    /// * `show_offset` - Whether to display offset values
+    /// * `file_size` - Total size of the file for offset formatting
+    /// * `progress` - Progress indicator to update during processing
+    pub fn find_strings<R: Read + Seek>(
+        &mut self,
+        reader: &mut R,
+        out: &mut dyn Write,
+        min_len: usize,
+        limit: usize,
+        show_offset: bool,
+        file_size: u64,
+        progress: &mut ProgressIndicator,
+    ) -> Result<()> {
+        let hex_offset_length = OutputFormatter::calculate_hex_offset_length(file_size);
+        let mut scanner = StringScanner::new(min_len);
+        let mut records = Vec::new();
+        let mut count = 0;
+
+        loop {
+            let start_offset = reader.stream_position()?;
+            let bytes_read = self.read_main(reader, start_offset)?;
+            progress.update(bytes_read as u64);
+
+            // The last string may run up to the end of the input
+            let found = if bytes_read == 0 {
+                scanner.finish().into_iter().collect()
+            } else {
+                scanner.scan(self.buffer_manager.get_main_slice(0, bytes_read), start_offset)
+            };
+
+            for (offset, value) in found {
+                self.output_string(out, offset, &value, show_offset, hex_offset_length, progress.is_silent(), &mut records)?;
+
+                count += 1;
+                if limit > 0 && count >= limit {
+                    progress.finish();
+                    return self.write_string_records(out, &records, progress.is_silent());
+                }
+            }
+
+            if bytes_read == 0 {
+                break;
+            }
+        }
+
+        progress.finish();
+        self.write_string_records(out, &records, progress.is_silent())
+    }
+
+    /// Output a single string found by `find_strings`
+    ///
+    /// NDJSON is streamed; JSON and MessagePack records are collected into `records`.
+    fn output_string(
+        &self,
+        out: &mut dyn Write,
+        offset: u64,
+        value: &str,
+        show_offset: bool,
+        hex_offset_length: usize,
+        silent: bool,
+        records: &mut Vec<BinaryMatch>,
+    ) -> Result<()> {
+        if silent {
+            return Ok(());
+        }
+
+        match self.output_format {
+            OutputFormat::Ndjson => {
+                let record = BinaryMatch::from_string(self.source_name.clone(), offset, value);
+                Self::emit_ndjson(out, |formatter, writer| formatter.output_match_ndjson(&record, writer))?;
+            }
+            OutputFormat::Json | OutputFormat::Msgpack => {
+                records.push(BinaryMatch::from_string(self.source_name.clone(), offset, value));
+            }
+            _ if show_offset => {
+                writeln!(out, "{}", OutputFormatter::format_line_with_offset(offset, value, hex_offset_length))?;
+            }
+            _ => writeln!(out, "{}", value)?,
+        }
+        Ok(())
+    }
+
+    /// Write strings collected for JSON or MessagePack output
+    fn write_string_records(&self, out: &mut dyn Write, records: &[BinaryMatch], silent: bool) -> Result<()> {
+        if !matches!(self.output_format, OutputFormat::Json | OutputFormat::Msgpack) || silent {
+            return Ok(());
+        }
+
+        let formatter = StructuredFormatter::new(self.output_format);
+        formatter
+            .output_matches(records, out)
+            .map_err(BingrepError::from)?;
+        out.flush()?;
+        Ok(())
+    }
+
+    /// Check whether the regex pattern occurs anywhere in the reader
+    ///
+    /// Scans from the current reader position and stops at the first match without
+    /// producing any output. Used by quiet mode where only the presence of a match matters.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - Reader to search in
+    /// * `regex` - Compiled regex pattern to search for
+    pub fn contains_match<R: Read + Seek>(&mut self, reader: &mut R, regex: &Regex) -> Result<bool> {
+        let buffer_size = self.buffer_manager.get_buffer_size();
+        let buffer_padding = self.config.buffer_padding;
+
+        loop {
+            let bytes_read = match self.end_offset {
+                Some(end) => {
+                    let remaining = end.saturating_sub(reader.stream_position()?);
+                    self.buffer_manager.read_into_main(&mut reader.by_ref().take(remaining))?
+                }
+                None => self.buffer_manager.read_into_main(reader)?,
+            };
+            if bytes_read == 0 {
+                return Ok(false);
+            }
+
+            let buffer_slice = self.buffer_manager.get_main_slice(0, bytes_read);
+            let found = match self.match_alignment {
+                Some(alignment) => {
+                    let start_offset = reader.stream_position()? - bytes_read as u64;
+                    alignment.find_iter(regex, buffer_slice, start_offset).next().is_some()
+                }
+                None => regex.is_match(buffer_slice),
+            };
+            if found {
+                return Ok(true);
+            }
+
+            // Read next buffer with overlap to handle patterns spanning boundaries
+            if bytes_read == buffer_size {
+                let new_pos = reader
+                    .stream_position()?
+                    .saturating_sub(buffer_padding as u64);
+                reader.seek(SeekFrom::Start(new_pos))?;
+            }
+        }
+    }
+
+    /// Check whether the regex pattern occurs anywhere in the file at the given path
+    ///
+    /// Automatically detects forensic image files (E01, VMDK) like the other `_from_path` methods.
+    pub fn contains_match_from_path<P: AsRef<Path>>(&mut self, file_path: P, regex: &Regex) -> Result<bool> {
+        let file_path = file_path.as_ref();
+
+        if is_forensic_image(&file_path) {
+            let mut forensic_reader = self.open_forensic_image(file_path)?;
+            self.contains_match(&mut forensic_reader, regex)
+        } else {
+            let mut file = File::open(&file_path)?;
+            self.contains_match(&mut file, regex)
+        }
+    }
+
+    /// Open a forensic image positioned at the start offset
+    fn open_forensic_image(&self, file_path: &Path) -> Result<ForensicImageReader> {
+        let mut forensic_reader = ForensicImageReader::new(file_path)?;
+        forensic_reader.seek(SeekFrom::Start(self.start_offset))?;
+        Ok(forensic_reader)
+    }
+
+    /// Determine the full length of a match that was cut off at the end of the read buffer
+    ///
+    /// Re-reads a growing window starting at the match offset until the match ends inside
+    /// the window, EOF is reached, or the window hits the configured memory limit or the
+    /// lookahead limit.
+    fn full_match_length<R: Read + Seek>(
+        &self,
+        reader: &mut R,
+        match_offset: u64,
+        truncated_len: usize,
+        regex: &Regex,
+    ) -> Result<usize> {
+        let current_pos = reader.stream_position()?;
+        let max_window = self
+            .lookahead_limit
+            .map_or(self.config.max_memory_usage, |limit| limit.min(self.config.max_memory_usage));
+        let mut window = truncated_len.saturating_mul(2).max(4096).min(max_window);
+        let mut match_len = truncated_len;
+
+        loop {
+            reader.seek(SeekFrom::Start(match_offset))?;
+            let mut data = Vec::with_capacity(window);
+            reader.by_ref().take(window as u64).read_to_end(&mut data)?;
+
+            match regex.find(&data) {
+                Some(mat) if mat.start() == 0 => match_len = mat.end(),
+                _ => break,
+            }
+
+            if match_len < data.len() || data.len() < window || window >= max_window {
+                break;
+            }
+            window = window.saturating_mul(2).min(max_window);
+        }
+
+        reader.seek(SeekFrom::Start(current_pos))?;
+        Ok(match_len)
+    }
+
+    /// Read `len` bytes at absolute offset `abs_offset`, restoring the reader position afterwards
+    ///
+    /// Bytes held in the main buffer are taken from it and the rest is read from the reader,
+    /// so a window may start in the buffer and continue past its end. A window cut short by
+    /// EOF is returned with the bytes that exist and `truncated` set.
+    fn fetch_window<R: Read + Seek>(&mut self, reader: &mut R, abs_offset: u64, len: usize) -> Result<WindowBytes> {
+        let window_end = abs_offset.saturating_add(len as u64);
+        let mut bytes = Vec::with_capacity(len);
+        let mut pos = abs_offset;
+
+        if self.buffered.contains(&abs_offset) {
+            let in_buffer_end = window_end.min(self.buffered.end);
+            bytes.extend_from_slice(self.buffer_manager.get_main_slice(
+                (abs_offset - self.buffered.start) as usize,
+                (in_buffer_end - self.buffered.start) as usize,
+            ));
+            pos = in_buffer_end;
+        }
+
+        if pos < window_end {
+            let current_pos = reader.stream_position()?;
+            reader.seek(SeekFrom::Start(pos))?;
+            reader.by_ref().take(window_end - pos).read_to_end(&mut bytes)?;
+            reader.seek(SeekFrom::Start(current_pos))?;
+        }
+
+        let truncated = bytes.len() < len;
+        Ok(WindowBytes { bytes, truncated })
+    }
+
+    /// Write a single NDJSON record and flush it immediately
+    fn emit_ndjson<F>(out: &mut dyn Write, write_record: F) -> Result<()>
+    where
+        F: FnOnce(&StructuredFormatter, &mut dyn Write) -> std::result::Result<(), Box<dyn std::error::Error>>,
+    {
+        let formatter = StructuredFormatter::new(OutputFormat::Ndjson);
+        write_record(&formatter, &mut *out)
+            .map_err(BingrepError::from)?;
+        out.flush()?;
+        Ok(())
+    }
+
+    /// Whether the displayed bytes of a match fall inside the entropy range, if one is set
+    fn passes_entropy_filter(&self, bytes: &[u8]) -> bool {
+        self.entropy_filter
+            .is_none_or(|filter| filter.accepts(shannon_entropy(bytes)))
+    }
+
+    /// Open the JSON array for a regex search, if matches are written as JSON
+    fn begin_json_array(&mut self, out: &mut dyn Write, silent: bool) -> Result<()> {
+        if !matches!(self.output_format, OutputFormat::Json) || silent || self.json_matches.is_some() {
+            return Ok(());
+        }
+
+        if !self.json_elements_only {
+            StructuredFormatter::new(OutputFormat::Json)
+                .begin_json_array(out)
+                .map_err(BingrepError::from)?;
+        }
+        self.json_matches = Some(0);
+        Ok(())
+    }
+
+    /// Write one match into the JSON array, opening it first if needed, and flush it
+    fn write_json_match(&mut self, out: &mut dyn Write, record: &BinaryMatch) -> Result<()> {
+        self.begin_json_array(out, false)?;
+        let written = self.json_matches.unwrap_or(0);
+
+        StructuredFormatter::new(OutputFormat::Json)
+            .write_json_match(record, written == 0, out)
+            .map_err(BingrepError::from)?;
+        out.flush()?;
+        self.json_matches = Some(written + 1);
+        Ok(())
+    }
+
+    /// Write matches held back until the end of the search
+    ///
+    /// Lines still pending in the match collapser are written, an open JSON array is
+    /// closed, and matches collected for MessagePack output are written as a single array.
+    fn write_collected_matches(&mut self, out: &mut dyn Write, silent: bool) -> Result<()> {
+        if let Some(collapser) = self.collapser.as_mut() {
+            for ready in collapser.finish() {
+                if !silent {
+                    writeln!(out, "{}", ready)?;
+                }
+            }
+        }
+
+        if self.json_matches.take().is_some() && !self.json_elements_only {
+            StructuredFormatter::new(OutputFormat::Json)
+                .end_json_array(out)
+                .map_err(BingrepError::from)?;
+            out.flush()?;
+        }
+
+        if !matches!(self.output_format, OutputFormat::Msgpack) || silent {
+            self.collected_matches.clear();
+            return Ok(());
+        }
+
+        let matches = std::mem::take(&mut self.collected_matches);
+        let formatter = StructuredFormatter::new(OutputFormat::Msgpack);
+        formatter
+            .output_matches_msgpack(&matches, out)
+            .map_err(BingrepError::from)?;
+        out.flush()?;
+        Ok(())
+    }
+}
+
+/// Positional-argument forms of the dump and search methods, kept for existing callers
+impl FileProcessor {
+    /// Hex dump a file path; see [`FileProcessor::dump_path`]
+    #[deprecated(note = "use `FileProcessor::dump_path` with `DumpOptions`")]
+    pub fn process_file_stream_from_path<P: AsRef<Path>>(
+        &mut self,
+        file_path: P,
+        out: &mut dyn Write,
+        width: usize,
+        limit: usize,
+        separator: &str,
+        show_offset: bool,
+        progress: &mut ProgressIndicator,
+    ) -> Result<()> {
+        let options = DumpOptions::new().width(width).limit(limit).separator(separator).show_offset(show_offset);
+        self.dump_path(file_path, out, &options, progress)
+    }
+
+    /// Hex dump a file; see [`FileProcessor::dump_file`]
+    #[deprecated(note = "use `FileProcessor::dump_file` with `DumpOptions`")]
+    pub fn process_file_stream(
+        &mut self,
+        file: &mut File,
+        out: &mut dyn Write,
+        width: usize,
+        limit: usize,
+        separator: &str,
+        show_offset: bool,
+        file_size: u64,
+        progress: &mut ProgressIndicator,
+    ) -> Result<()> {
+        let options = DumpOptions::new().width(width).limit(limit).separator(separator).show_offset(show_offset);
+        self.dump_file(file, out, &options, file_size, progress)
+    }
+
+    /// Hex dump a reader; see [`FileProcessor::dump_reader`]
+    #[deprecated(note = "use `FileProcessor::dump_reader` with `DumpOptions`")]
+    pub fn process_reader_stream<R: Read + Seek>(
+        &mut self,
+        reader: &mut R,
+        out: &mut dyn Write,
+        width: usize,
+        limit: usize,
+        separator: &str,
+        show_offset: bool,
+        file_size: u64,
+        progress: &mut ProgressIndicator,
+    ) -> Result<()> {
+        let options = DumpOptions::new().width(width).limit(limit).separator(separator).show_offset(show_offset);
+        self.dump_reader(reader, out, &options, file_size, progress)
+    }
+
+    /// Search a file path; see [`FileProcessor::grep_path`]
+    #[deprecated(note = "use `FileProcessor::grep_path` with `SearchOptions`")]
+    pub fn process_stream_by_regex_from_path<P: AsRef<Path>>(
+        &mut self,
+        file_path: P,
+        out: &mut dyn Write,
+        regex: &Regex,
+        width: usize,
+        limit: usize,
+        separator: &str,
+        show_offset: bool,
+        align: Option<usize>,
+        progress: &mut ProgressIndicator,
+    ) -> Result<()> {
+        let options = search_options(width, limit, separator, show_offset, align);
+        self.grep_path(file_path, out, regex, &options, progress)
+    }
+
+    /// Search a file; see [`FileProcessor::grep_file`]
+    #[deprecated(note = "use `FileProcessor::grep_file` with `SearchOptions`")]
+    pub fn process_stream_by_regex(
+        &mut self,
+        file: &mut File,
+        out: &mut dyn Write,
+        regex: &Regex,
+        width: usize,
+        limit: usize,
+        separator: &str,
+        show_offset: bool,
+        align: Option<usize>,
+        progress: &mut ProgressIndicator,
+    ) -> Result<()> {
+        let options = search_options(width, limit, separator, show_offset, align);
+        self.grep_file(file, out, regex, &options, progress)
+    }
+
+    /// Search a reader; see [`FileProcessor::grep_reader`]
+    #[deprecated(note = "use `FileProcessor::grep_reader` with `SearchOptions`")]
+    pub fn process_reader_by_regex<R: Read + Seek>(
+        &mut self,
+        reader: &mut R,
+        out: &mut dyn Write,
+        regex: &Regex,
+        width: usize,
+        limit: usize,
+        separator: &str,
+        show_offset: bool,
+        align: Option<usize>,
+        file_size: u64,
+        progress: &mut ProgressIndicator,
+    ) -> Result<()> {
+        let options = search_options(width, limit, separator, show_offset, align);
+        self.grep_reader(reader, out, regex, &options, file_size, progress)
+    }
+
+    /// Follow a growing file; see [`FileProcessor::watch_file`]
+    #[deprecated(note = "use `FileProcessor::watch_file` with `SearchOptions`")]
+    pub fn process_stream_watch(
+        &mut self,
+        file: &mut File,
+        out: &mut dyn Write,
+        regex: &Regex,
+        width: usize,
+        limit: usize,
+        separator: &str,
+        show_offset: bool,
+        align: Option<usize>,
+        poll_interval: Duration,
+    ) -> Result<()> {
+        let options = search_options(width, limit, separator, show_offset, align);
+        self.watch_file(file, out, regex, &options, poll_interval)
+    }
+
+    /// Inverse match from a file path; see [`FileProcessor::dump_inverted_path`]
+    #[deprecated(note = "use `FileProcessor::dump_inverted_path` with `DumpOptions`")]
+    pub fn process_stream_inverted_from_path<P: AsRef<Path>>(
+        &mut self,
+        file_path: P,
+        out: &mut dyn Write,
+        regex: &Regex,
+        width: usize,
+        limit: usize,
+        separator: &str,
+        show_offset: bool,
+        progress: &mut ProgressIndicator,
+    ) -> Result<()> {
+        let options = DumpOptions::new().width(width).limit(limit).separator(separator).show_offset(show_offset);
+        self.dump_inverted_path(file_path, out, regex, &options, progress)
+    }
+
+    /// Inverse match from a reader; see [`FileProcessor::dump_inverted`]
+    #[deprecated(note = "use `FileProcessor::dump_inverted` with `DumpOptions`")]
+    pub fn process_stream_inverted<R: Read + Seek>(
+        &mut self,
+        reader: &mut R,
+        out: &mut dyn Write,
+        regex: &Regex,
+        width: usize,
+        limit: usize,
+        separator: &str,
+        show_offset: bool,
+        file_size: u64,
+        progress: &mut ProgressIndicator,
+    ) -> Result<()> {
+        let options = DumpOptions::new().width(width).limit(limit).separator(separator).show_offset(show_offset);
+        self.dump_inverted(reader, out, regex, &options, file_size, progress)
+    }
+}
+
+/// Search options from the positional arguments of the deprecated methods
+fn search_options(width: usize, limit: usize, separator: &str, show_offset: bool, align: Option<usize>) -> SearchOptions {
+    SearchOptions::new()
+        .width(width)
+        .limit(limit)
+        .separator(separator)
+        .show_offset(show_offset)
+        .align(align)
+}
+
+#[cfg(test)]
+mod tests {
     use super::*;
     use std::io::Write;
     use tempfile::NamedTempFile;
@@ -426,7 +1970,7 @@ mod tests {
     }
 
     #[test]
-    fn test_process_file_stream() -> Result<()> {
+    fn test_dump_file() -> Result<()> {
         let config = Config::default();
         let mut processor = FileProcessor::new(config);
 
@@ -438,10 +1982,258 @@ mod tests {
         let mut file = temp_file.reopen().unwrap();
         let file_size = file.metadata()?.len();
 
-        // This would normally print, but in tests we just verify it doesn't error
         let mut progress = ProgressIndicator::disabled();
-        let result = processor.process_file_stream(&mut file, 16, 1, " ", false, file_size, &mut progress);
-        assert!(result.is_ok());
+        let mut out = Vec::new();
+        let options = DumpOptions::new().limit(1).show_offset(false);
+        processor.dump_file(&mut file, &mut out, &options, file_size, &mut progress)?;
+        assert_eq!(String::from_utf8(out).unwrap(), "48 65 6C 6C 6F 20 57 6F 72 6C 64 21\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_dump_skips_zero_lines() -> Result<()> {
+        crate::color_context::set_color_choice(crate::cli::ColorChoice::Never);
+        let mut processor = FileProcessor::new(Config::default());
+        let mut data = vec![0u8; 20];
+        data[4] = 0x41;
+        data[16] = 0x42;
+
+        let mut progress = ProgressIndicator::disabled();
+        let mut out = Vec::new();
+        let options = DumpOptions::new().width(4).skip_zeros(true);
+        processor.dump_reader(&mut std::io::Cursor::new(data), &mut out, &options, 20, &mut progress)?;
+        assert_eq!(String::from_utf8(out).unwrap(), "*\n04h : 41 00 00 00\n*\n10h : 42 00 00 00\n");
+
+        // Skipped lines do not count towards the limit, and trailing zeros are marked
+        let mut out = Vec::new();
+        let options = options.limit(1);
+        processor.dump_reader(&mut std::io::Cursor::new(vec![0u8; 8]), &mut out, &options, 8, &mut progress)?;
+        assert_eq!(String::from_utf8(out).unwrap(), "*\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_grep_file_writes_to_writer() -> Result<()> {
+        // Highlighting would add escape codes when the tests run in a terminal
+        crate::color_context::set_color_choice(crate::cli::ColorChoice::Never);
+        let config = Config::default();
+        let mut processor = FileProcessor::new(config);
+        let regex = crate::RegexProcessor::compile_pattern("\\x4D\\x5A")?;
+
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(b"\x00\x00MZ\x90\x00\x00\x00MZ\x01").unwrap();
+        let mut file = temp_file.reopen().unwrap();
+
+        let mut progress = ProgressIndicator::disabled();
+        let mut out = Vec::new();
+        let options = SearchOptions::new().width(3).show_offset(false);
+        processor.grep_file(&mut file, &mut out, &regex, &options, &mut progress)?;
+        assert_eq!(String::from_utf8(out).unwrap(), "4D 5A 90\n4D 5A 01\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_matches_agrees_with_printed_output() -> Result<()> {
+        let config = Config {
+            buffer_size: 64,
+            buffer_padding: 8,
+            ..Config::default()
+        };
+        let regex = crate::RegexProcessor::compile_pattern("MZ")?;
+
+        // Matches inside buffers and straddling the 64-byte boundaries
+        let mut data = vec![0u8; 200];
+        for offset in [3, 63, 127, 150, 198] {
+            data[offset..offset + 2].copy_from_slice(b"MZ");
+        }
+
+        let mut processor = FileProcessor::new(config.clone());
+        let found = processor.find_matches(&mut std::io::Cursor::new(data.clone()), &regex, &SearchOptions::new().width(0))?;
+        let offsets: Vec<u64> = found.iter().map(|m| m.offset).collect();
+        assert_eq!(offsets, vec![3, 63, 127, 150, 198]);
+        assert!(found.iter().all(|m| m.len == 2 && m.bytes == b"MZ"));
+
+        let mut processor = FileProcessor::new(config);
+        processor.set_output_format(OutputFormat::Ndjson);
+        let mut progress = ProgressIndicator::disabled();
+        let mut out = Vec::new();
+        let options = SearchOptions::new().width(0);
+        processor.grep_reader(&mut std::io::Cursor::new(data), &mut out, &regex, &options, 200, &mut progress)?;
+        let printed: Vec<u64> = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<BinaryMatch>(line).unwrap().offset)
+            .collect();
+        assert_eq!(printed, offsets);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_yields_binary_matches_lazily() -> Result<()> {
+        let config = Config {
+            buffer_size: 64,
+            buffer_padding: 8,
+            ..Config::default()
+        };
+        let regex = crate::RegexProcessor::compile_pattern("MZ")?;
+        let mut data = vec![0u8; 200];
+        for offset in [3, 63, 150] {
+            data[offset..offset + 2].copy_from_slice(b"MZ");
+        }
+
+        let mut processor = FileProcessor::new(config);
+        processor.set_source_name("sample.bin");
+        let mut reader = std::io::Cursor::new(data);
+
+        // Taking the first match reads only the first buffer
+        let options = SearchOptions::new().width(4);
+        let first = processor.search(&mut reader, &regex, &options).next().unwrap()?;
+        assert_eq!(first.offset, 3);
+        assert_eq!(first.length, 2);
+        assert_eq!(first.hex_data, "4D 5A 00 00");
+        assert_eq!(first.file_path, "sample.bin");
+        assert!(reader.position() < 200);
+
+        reader.set_position(0);
+        let offsets: Vec<u64> = processor
+            .search(&mut reader, &regex, &options)
+            .map(|found| found.map(|found| found.offset))
+            .collect::<Result<_>>()?;
+        assert_eq!(offsets, vec![3, 63, 150]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_json_array_closed_when_limit_stops_search() -> Result<()> {
+        let mut processor = FileProcessor::new(Config::default());
+        processor.set_output_format(OutputFormat::Json);
+        let regex = crate::RegexProcessor::compile_pattern("MZ")?;
+
+        // Five matches, but the limit stops the search after two
+        let mut reader = std::io::Cursor::new(b"MZ..MZ..MZ..MZ..MZ".to_vec());
+        let mut progress = ProgressIndicator::disabled();
+        let mut out = Vec::new();
+        let options = SearchOptions::new().width(2).limit(2);
+        processor.grep_reader(&mut reader, &mut out, &regex, &options, 18, &mut progress)?;
+
+        let parsed: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        let offsets: Vec<u64> = parsed
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| m["offset"].as_u64().unwrap())
+            .collect();
+        assert_eq!(offsets, vec![0, 4]);
+
+        // Without matches the array is still written
+        let mut reader = std::io::Cursor::new(b"....".to_vec());
+        let mut out = Vec::new();
+        let options = SearchOptions::new().width(2);
+        processor.grep_reader(&mut reader, &mut out, &regex, &options, 4, &mut progress)?;
+        assert_eq!(serde_json::from_slice::<serde_json::Value>(&out).unwrap(), serde_json::json!([]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_full_match_length_extends_past_buffer() -> Result<()> {
+        let config = Config {
+            buffer_size: 64,
+            ..Config::default()
+        };
+        let processor = FileProcessor::new(config);
+        let regex = crate::RegexProcessor::compile_pattern("\\x00+\\x01")?;
+
+        let mut data = vec![0u8; 300];
+        data.push(0x01);
+        let mut reader = std::io::Cursor::new(data);
+
+        // The buffer only held the first 64 bytes of the match
+        let full_len = processor.full_match_length(&mut reader, 0, 64, &regex)?;
+        assert_eq!(full_len, 301);
+        assert_eq!(reader.stream_position()?, 0);
+
+        Ok(())
+    }
+
+    /// Processor whose main buffer holds `data[8..24]`, as after a read at offset 8
+    fn processor_with_buffered_window(data: &[u8]) -> Result<(FileProcessor, std::io::Cursor<Vec<u8>>)> {
+        let config = Config {
+            buffer_size: 16,
+            ..Config::default()
+        };
+        let mut processor = FileProcessor::new(config);
+        let mut reader = std::io::Cursor::new(data.to_vec());
+        reader.seek(SeekFrom::Start(8))?;
+        let bytes_read = processor.buffer_manager.read_into_main(&mut reader)?;
+        processor.buffered = 8..8 + bytes_read as u64;
+        Ok((processor, reader))
+    }
+
+    #[test]
+    fn test_fetch_window_in_buffer() -> Result<()> {
+        let data: Vec<u8> = (0..40).collect();
+        let (mut processor, mut reader) = processor_with_buffered_window(&data)?;
+
+        let window = processor.fetch_window(&mut reader, 10, 4)?;
+        assert_eq!(window.bytes, vec![10, 11, 12, 13]);
+        assert!(!window.truncated);
+        assert_eq!(reader.stream_position()?, 24);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fetch_window_spans_past_buffer() -> Result<()> {
+        let data: Vec<u8> = (0..40).collect();
+        let (mut processor, mut reader) = processor_with_buffered_window(&data)?;
+
+        // Starts in the buffer and continues from the reader
+        let window = processor.fetch_window(&mut reader, 20, 8)?;
+        assert_eq!(window.bytes, (20..28).collect::<Vec<u8>>());
+        assert!(!window.truncated);
+
+        // Entirely before the buffer
+        let window = processor.fetch_window(&mut reader, 0, 4)?;
+        assert_eq!(window.bytes, vec![0, 1, 2, 3]);
+        assert_eq!(reader.stream_position()?, 24);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fetch_window_truncated_at_eof() -> Result<()> {
+        let data: Vec<u8> = (0..30).collect();
+        let (mut processor, mut reader) = processor_with_buffered_window(&data)?;
+
+        let window = processor.fetch_window(&mut reader, 22, 16)?;
+        assert_eq!(window.bytes, (22..30).collect::<Vec<u8>>());
+        assert!(window.truncated);
+
+        let window = processor.fetch_window(&mut reader, 30, 4)?;
+        assert!(window.bytes.is_empty());
+        assert!(window.truncated);
+        assert_eq!(reader.stream_position()?, 24);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_contains_match() -> Result<()> {
+        let config = Config::default();
+        let mut processor = FileProcessor::new(config);
+        let regex = crate::RegexProcessor::compile_pattern("\\x4D\\x5A")?;
+
+        let mut found = std::io::Cursor::new(b"\x00\x00MZ\x90\x00".to_vec());
+        assert!(processor.contains_match(&mut found, &regex)?);
+
+        let mut missing = std::io::Cursor::new(b"\x00\x00M\x00Z\x00".to_vec());
+        assert!(!processor.contains_match(&mut missing, &regex)?);
 
         Ok(())
     }
@@ -0,0 +1,157 @@
+//! Extraction of printable ASCII runs, like the Unix `strings` utility
+//!
+//! A string is a maximal run of bytes in `0x20..=0x7E` of at least the minimum length.
+//! Input arrives in blocks, so a run touching the end of a block is held back until the
+//! next block shows whether it continues.
+
+use std::ops::Range;
+
+/// Check whether a byte is printable ASCII as understood by `strings`
+pub fn is_printable(byte: u8) -> bool {
+    (0x20..=0x7E).contains(&byte)
+}
+
+/// Find the runs of printable bytes in a block
+///
+/// Runs shorter than `min_len` are dropped unless they touch either end of the block,
+/// since those may be part of a longer run continuing in a neighbouring block.
+pub fn printable_runs(data: &[u8], min_len: usize) -> Vec<Range<usize>> {
+    let mut runs = Vec::new();
+    let mut run_start = None;
+
+    for (i, &byte) in data.iter().enumerate() {
+        match (is_printable(byte), run_start) {
+            (true, None) => run_start = Some(i),
+            (false, Some(start)) => {
+                if i - start >= min_len || start == 0 {
+                    runs.push(start..i);
+                }
+                run_start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(start) = run_start {
+        runs.push(start..data.len());
+    }
+
+    runs
+}
+
+/// Stateful scanner that joins printable runs across consecutive blocks
+pub struct StringScanner {
+    min_len: usize,
+    pending: Option<(u64, Vec<u8>)>,
+}
+
+impl StringScanner {
+    /// Create a scanner reporting strings of at least `min_len` bytes
+    pub fn new(min_len: usize) -> Self {
+        Self {
+            min_len: min_len.max(1),
+            pending: None,
+        }
+    }
+
+    /// Scan the next block, which must directly follow the previous one
+    ///
+    /// Returns the offset and text of each string completed within the block.
+    pub fn scan(&mut self, data: &[u8], offset: u64) -> Vec<(u64, String)> {
+        let runs = printable_runs(data, self.min_len);
+        self.scan_runs(data, offset, &runs)
+    }
+
+    /// Scan the next block using runs already found by [`printable_runs`]
+    ///
+    /// This lets the run detection happen on worker threads while strings are still
+    /// joined across block boundaries in order.
+    pub fn scan_runs(&mut self, data: &[u8], offset: u64, runs: &[Range<usize>]) -> Vec<(u64, String)> {
+        let mut found = Vec::new();
+        if data.is_empty() {
+            return found;
+        }
+
+        let mut runs = runs;
+        if let Some((start, mut bytes)) = self.pending.take() {
+            match runs.first() {
+                Some(run) if run.start == 0 => {
+                    bytes.extend_from_slice(&data[run.clone()]);
+                    runs = &runs[1..];
+                    if run.end == data.len() {
+                        self.pending = Some((start, bytes));
+                        return found;
+                    }
+                    self.push_string(start, &bytes, &mut found);
+                }
+                _ => self.push_string(start, &bytes, &mut found),
+            }
+        }
+
+        for run in runs {
+            if run.end == data.len() {
+                self.pending = Some((offset + run.start as u64, data[run.clone()].to_vec()));
+            } else {
+                self.push_string(offset + run.start as u64, &data[run.clone()], &mut found);
+            }
+        }
+
+        found
+    }
+
+    /// Finish scanning, returning a string that ran up to the end of the input
+    pub fn finish(&mut self) -> Option<(u64, String)> {
+        let mut found = Vec::new();
+        if let Some((start, bytes)) = self.pending.take() {
+            self.push_string(start, &bytes, &mut found);
+        }
+        found.pop()
+    }
+
+    fn push_string(&self, offset: u64, bytes: &[u8], found: &mut Vec<(u64, String)>) {
+        if bytes.len() >= self.min_len {
+            found.push((offset, String::from_utf8_lossy(bytes).into_owned()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan_all(blocks: &[&[u8]], min_len: usize) -> Vec<(u64, String)> {
+        let mut scanner = StringScanner::new(min_len);
+        let mut offset = 0;
+        let mut found = Vec::new();
+        for block in blocks {
+            found.extend(scanner.scan(block, offset));
+            offset += block.len() as u64;
+        }
+        found.extend(scanner.finish());
+        found
+    }
+
+    #[test]
+    fn test_strings_in_single_block() {
+        let found = scan_all(&[b"\x00hello\x01ab\x02world!\xff"], 4);
+        assert_eq!(
+            found,
+            vec![(1, "hello".to_string()), (10, "world!".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_strings_joined_across_blocks() {
+        // "abcdef" split over three blocks, one of them entirely printable
+        let found = scan_all(&[b"\x00ab", b"cd", b"ef\x00xy"], 4);
+        assert_eq!(found, vec![(1, "abcdef".to_string())]);
+
+        // Short pieces on both sides of a boundary are not joined with unrelated runs
+        let found = scan_all(&[b"ab\x00", b"cd\x00"], 3);
+        assert!(found.is_empty());
+    }
+
+    #[test]
+    fn test_printable_runs_keep_edge_runs() {
+        assert_eq!(printable_runs(b"ab\x00abcd\x00x\x00cd", 4), vec![0..2, 3..7, 10..12]);
+    }
+}
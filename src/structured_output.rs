@@ -1,3 +1,5 @@
+use crate::cli::OdRadix;
+use crate::output::OutputFormatter;
 use serde::{Deserialize, Serialize};
 use std::io::Write;
 
@@ -12,6 +14,14 @@ pub enum OutputFormat {
     Csv,
     /// Plain text format (similar to hex but without formatting)
     Plain,
+    /// Newline-delimited JSON, one object per line (streamable)
+    Ndjson,
+    /// MessagePack binary format (not human-readable; redirect to a file)
+    Msgpack,
+    /// Exactly `xxd`'s default format (16 bytes per line with an ASCII column)
+    Xxd,
+    /// Exactly `od -tx1 -v` (16 bytes per line, offsets in the given radix)
+    Od(OdRadix),
 }
 
 impl OutputFormat {
@@ -22,13 +32,17 @@ impl OutputFormat {
             "json" => Some(Self::Json),
             "csv" => Some(Self::Csv),
             "plain" => Some(Self::Plain),
+            "ndjson" | "jsonl" => Some(Self::Ndjson),
+            "msgpack" => Some(Self::Msgpack),
+            "xxd" => Some(Self::Xxd),
+            "od" => Some(Self::Od(OdRadix::O)),
             _ => None,
         }
     }
 }
 
 /// Represents a match found in the binary data
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct BinaryMatch {
     /// File path where the match was found
     pub file_path: String,
@@ -40,10 +54,20 @@ pub struct BinaryMatch {
     pub length: usize,
     /// ASCII representation of the data (if printable)
     pub ascii_data: Option<String>,
+    /// Extracted text in `--strings` mode
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub string_value: Option<String>,
+    /// Number of bytes left in `hex_data` after `--trim-trailing`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window_len_trimmed: Option<usize>,
+    /// Approximate `[start, end)` range of the compressed input the match was decompressed
+    /// from with `-z`, one buffer wide
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compressed_offset: Option<[u64; 2]>,
 }
 
 /// Represents a line of hex dump output
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct HexDumpLine {
     /// File path of the source file
     pub file_path: String,
@@ -57,6 +81,61 @@ pub struct HexDumpLine {
     pub byte_count: usize,
 }
 
+/// Why a file was left out of a multi-file search
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum SkipReason {
+    /// The file could not be opened for reading
+    PermissionDenied,
+    /// The file is larger than the maximum file size
+    TooLarge,
+    /// The path is not a regular file (device, FIFO, socket, ...)
+    NotRegular,
+    /// The path matches an `--exclude` pattern
+    Excluded,
+}
+
+impl SkipReason {
+    /// Stable name of the reason, the same string used in structured output
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::PermissionDenied => "permission-denied",
+            Self::TooLarge => "too-large",
+            Self::NotRegular => "not-regular",
+            Self::Excluded => "excluded",
+        }
+    }
+}
+
+/// A file skipped during a multi-file search
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SkippedFile {
+    /// Path of the skipped file
+    pub path: String,
+    /// Why the file was skipped
+    pub reason: SkipReason,
+}
+
+/// Totals of a multi-file search
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct MultiFileSummary {
+    /// Number of files searched
+    pub files_processed: usize,
+    /// Number of files skipped
+    pub files_skipped: usize,
+}
+
+/// Byte ranges read by a scan limited with `--skip-holes`, `-s`/`--end`/`--length` or `--partition`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CoverageRecord {
+    /// Requested range, start inclusive and end exclusive
+    pub requested: [u64; 2],
+    /// Merged ranges actually read, start inclusive and end exclusive
+    pub ranges: Vec<[u64; 2]>,
+    /// Share of the requested range that was read, in percent
+    pub percent: f64,
+}
+
 /// Structured output formatter
 pub struct StructuredFormatter {
     format: OutputFormat,
@@ -69,7 +148,7 @@ impl StructuredFormatter {
     }
 
     /// Output matches in the specified format
-    pub fn output_matches<W: Write>(
+    pub fn output_matches<W: Write + ?Sized>(
         &self,
         matches: &[BinaryMatch],
         writer: &mut W,
@@ -79,11 +158,30 @@ impl StructuredFormatter {
             OutputFormat::Json => self.output_matches_json(matches, writer),
             OutputFormat::Csv => self.output_matches_csv(matches, writer),
             OutputFormat::Plain => self.output_matches_plain(matches, writer),
+            OutputFormat::Ndjson => {
+                for m in matches {
+                    self.output_match_ndjson(m, writer)?;
+                }
+                Ok(())
+            }
+            OutputFormat::Msgpack => self.output_matches_msgpack(matches, writer),
+            OutputFormat::Xxd => {
+                for m in matches {
+                    self.output_xxd_line(m.offset, &m.hex_data, writer)?;
+                }
+                Ok(())
+            }
+            OutputFormat::Od(radix) => {
+                for m in matches {
+                    self.output_od_line(m.offset, radix, &m.hex_data, writer)?;
+                }
+                Ok(())
+            }
         }
     }
 
     /// Output hex dump lines in the specified format
-    pub fn output_hex_dump<W: Write>(
+    pub fn output_hex_dump<W: Write + ?Sized>(
         &self,
         lines: &[HexDumpLine],
         writer: &mut W,
@@ -93,11 +191,211 @@ impl StructuredFormatter {
             OutputFormat::Json => self.output_hex_dump_json(lines, writer),
             OutputFormat::Csv => self.output_hex_dump_csv(lines, writer),
             OutputFormat::Plain => self.output_hex_dump_plain(lines, writer),
+            OutputFormat::Ndjson => {
+                for line in lines {
+                    self.output_hex_dump_line_ndjson(line, writer)?;
+                }
+                Ok(())
+            }
+            OutputFormat::Msgpack => self.output_hex_dump_msgpack(lines, writer),
+            OutputFormat::Xxd => {
+                for line in lines {
+                    self.output_xxd_line(line.offset, &line.hex_data, writer)?;
+                }
+                Ok(())
+            }
+            OutputFormat::Od(radix) => {
+                for line in lines {
+                    self.output_od_line(line.offset, radix, &line.hex_data, writer)?;
+                }
+                Ok(())
+            }
         }
     }
 
+    /// Output a single match as one line of NDJSON
+    ///
+    /// Each match is serialized independently so it can be written as soon as it is found.
+    pub fn output_match_ndjson<W: Write + ?Sized>(
+        &self,
+        m: &BinaryMatch,
+        writer: &mut W,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        serde_json::to_writer(&mut *writer, m)?;
+        writeln!(writer)?;
+        Ok(())
+    }
+
+    /// Output a single hex dump line as one line of NDJSON
+    pub fn output_hex_dump_line_ndjson<W: Write + ?Sized>(
+        &self,
+        line: &HexDumpLine,
+        writer: &mut W,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        serde_json::to_writer(&mut *writer, line)?;
+        writeln!(writer)?;
+        Ok(())
+    }
+
+    /// Output a skipped file as one line of NDJSON, wrapped as `{"skipped": {...}}`
+    pub fn output_skipped_ndjson<W: Write + ?Sized>(
+        &self,
+        skipped: &SkippedFile,
+        writer: &mut W,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        serde_json::to_writer(&mut *writer, &serde_json::json!({ "skipped": skipped }))?;
+        writeln!(writer)?;
+        Ok(())
+    }
+
+    /// Output the multi-file totals as one line of NDJSON, wrapped as `{"summary": {...}}`
+    pub fn output_summary_ndjson<W: Write + ?Sized>(
+        &self,
+        summary: &MultiFileSummary,
+        writer: &mut W,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        serde_json::to_writer(&mut *writer, &serde_json::json!({ "summary": summary }))?;
+        writeln!(writer)?;
+        Ok(())
+    }
+
+    /// Output the coverage of a limited scan as one line of NDJSON, wrapped as `{"coverage": {...}}`
+    pub fn output_coverage_ndjson<W: Write + ?Sized>(
+        &self,
+        coverage: &CoverageRecord,
+        writer: &mut W,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        serde_json::to_writer(&mut *writer, &serde_json::json!({ "coverage": coverage }))?;
+        writeln!(writer)?;
+        Ok(())
+    }
+
+    /// Open a JSON array whose matches are written one at a time
+    ///
+    /// Follow with [`write_json_match`](Self::write_json_match) for each match and
+    /// [`end_json_array`](Self::end_json_array), so output starts before the search ends.
+    pub fn begin_json_array<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Box<dyn std::error::Error>> {
+        write!(writer, "[")?;
+        Ok(())
+    }
+
+    /// Write one match into an array opened with `begin_json_array`
+    ///
+    /// `first` tells whether this is the array's first element, which has no leading comma.
+    pub fn write_json_match<W: Write + ?Sized>(
+        &self,
+        m: &BinaryMatch,
+        first: bool,
+        writer: &mut W,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if !first {
+            write!(writer, ",")?;
+        }
+        write!(writer, "\n  ")?;
+        serde_json::to_writer(&mut *writer, m)?;
+        Ok(())
+    }
+
+    /// Close an array opened with `begin_json_array`
+    pub fn end_json_array<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Box<dyn std::error::Error>> {
+        writeln!(writer, "\n]")?;
+        Ok(())
+    }
+
+    /// Open a JSON report document, up to its `matches` array
+    ///
+    /// The matches are written into the array with `write_json_match`, and
+    /// `end_multi_file_report_json` or `end_coverage_report_json` closes the document.
+    pub fn begin_report_json<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Box<dyn std::error::Error>> {
+        write!(writer, "{{\"matches\": [")?;
+        Ok(())
+    }
+
+    /// Close the `matches` array and end the document with the skipped files and totals
+    pub fn end_multi_file_report_json<W: Write + ?Sized>(
+        &self,
+        skipped: &[SkippedFile],
+        summary: &MultiFileSummary,
+        writer: &mut W,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        write!(writer, "\n],\n\"skipped\": ")?;
+        serde_json::to_writer(&mut *writer, skipped)?;
+        write!(writer, ",\n\"summary\": ")?;
+        serde_json::to_writer(&mut *writer, summary)?;
+        writeln!(writer, "}}")?;
+        Ok(())
+    }
+
+    /// Close the `matches` array and end the document with the coverage of a limited scan
+    pub fn end_coverage_report_json<W: Write + ?Sized>(
+        &self,
+        coverage: &CoverageRecord,
+        writer: &mut W,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        write!(writer, "\n],\n\"coverage\": ")?;
+        serde_json::to_writer(&mut *writer, coverage)?;
+        writeln!(writer, "}}")?;
+        Ok(())
+    }
+
+    /// Output one record as an `xxd` line, decoding its space-separated hex data
+    fn output_xxd_line<W: Write + ?Sized>(
+        &self,
+        offset: u64,
+        hex_data: &str,
+        writer: &mut W,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let bytes = Self::decode_hex_data(hex_data)?;
+        writeln!(writer, "{}", OutputFormatter::format_line_xxd(offset, &bytes))?;
+        Ok(())
+    }
+
+    /// Output one record as an `od -tx1` line, decoding its space-separated hex data
+    fn output_od_line<W: Write + ?Sized>(
+        &self,
+        offset: u64,
+        radix: OdRadix,
+        hex_data: &str,
+        writer: &mut W,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let bytes = Self::decode_hex_data(hex_data)?;
+        writeln!(writer, "{}", OutputFormatter::format_line_od(Some(offset), radix, &bytes))?;
+        Ok(())
+    }
+
+    fn decode_hex_data(hex_data: &str) -> Result<Vec<u8>, std::num::ParseIntError> {
+        hex_data
+            .split_whitespace()
+            .map(|hex_byte| u8::from_str_radix(hex_byte, 16))
+            .collect()
+    }
+
+    /// Output matches in MessagePack format
+    ///
+    /// The whole match list is serialized as a single MessagePack array.
+    pub fn output_matches_msgpack<W: Write + ?Sized>(
+        &self,
+        matches: &[BinaryMatch],
+        writer: &mut W,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let bytes = rmp_serde::to_vec(matches)?;
+        writer.write_all(&bytes)?;
+        Ok(())
+    }
+
+    /// Output hex dump lines in MessagePack format
+    fn output_hex_dump_msgpack<W: Write + ?Sized>(
+        &self,
+        lines: &[HexDumpLine],
+        writer: &mut W,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let bytes = rmp_serde::to_vec(lines)?;
+        writer.write_all(&bytes)?;
+        Ok(())
+    }
+
     /// Output matches in hex format (default)
-    fn output_matches_hex<W: Write>(
+    fn output_matches_hex<W: Write + ?Sized>(
         &self,
         matches: &[BinaryMatch],
         writer: &mut W,
@@ -116,7 +414,7 @@ impl StructuredFormatter {
     }
 
     /// Output matches in JSON format
-    fn output_matches_json<W: Write>(
+    fn output_matches_json<W: Write + ?Sized>(
         &self,
         matches: &[BinaryMatch],
         writer: &mut W,
@@ -127,7 +425,7 @@ impl StructuredFormatter {
     }
 
     /// Output matches in CSV format
-    fn output_matches_csv<W: Write>(
+    fn output_matches_csv<W: Write + ?Sized>(
         &self,
         matches: &[BinaryMatch],
         writer: &mut W,
@@ -153,7 +451,7 @@ impl StructuredFormatter {
     }
 
     /// Output matches in plain format
-    fn output_matches_plain<W: Write>(
+    fn output_matches_plain<W: Write + ?Sized>(
         &self,
         matches: &[BinaryMatch],
         writer: &mut W,
@@ -165,7 +463,7 @@ impl StructuredFormatter {
     }
 
     /// Output hex dump in hex format (default)
-    fn output_hex_dump_hex<W: Write>(
+    fn output_hex_dump_hex<W: Write + ?Sized>(
         &self,
         lines: &[HexDumpLine],
         writer: &mut W,
@@ -184,7 +482,7 @@ impl StructuredFormatter {
     }
 
     /// Output hex dump in JSON format
-    fn output_hex_dump_json<W: Write>(
+    fn output_hex_dump_json<W: Write + ?Sized>(
         &self,
         lines: &[HexDumpLine],
         writer: &mut W,
@@ -195,7 +493,7 @@ impl StructuredFormatter {
     }
 
     /// Output hex dump in CSV format
-    fn output_hex_dump_csv<W: Write>(
+    fn output_hex_dump_csv<W: Write + ?Sized>(
         &self,
         lines: &[HexDumpLine],
         writer: &mut W,
@@ -227,7 +525,7 @@ impl StructuredFormatter {
     }
 
     /// Output hex dump in plain format
-    fn output_hex_dump_plain<W: Write>(
+    fn output_hex_dump_plain<W: Write + ?Sized>(
         &self,
         lines: &[HexDumpLine],
         writer: &mut W,
@@ -254,18 +552,29 @@ impl BinaryMatch {
             hex_data,
             length,
             ascii_data,
+            string_value: None,
+            window_len_trimmed: None,
+            compressed_offset: None,
+        }
+    }
+
+    /// Create a BinaryMatch for a string found in `--strings` mode
+    pub fn from_string(file_path: String, offset: u64, value: &str) -> Self {
+        let hex_data = OutputFormatter::format_bytes_as_hex(value.as_bytes(), " ");
+        Self {
+            string_value: Some(value.to_string()),
+            ..Self::new(file_path, offset, hex_data, value.len())
         }
     }
 
     /// Convert hex string to ASCII if all bytes are printable
+    ///
+    /// Bytes grouped with `--group-size` are decoded as well. With `--charset`, the bytes
+    /// are always decoded with that table instead.
     fn bytes_to_ascii_if_printable(hex_data: &str) -> Option<String> {
-        let hex_bytes: Result<Vec<u8>, _> = hex_data
-            .split_whitespace()
-            .map(|hex_byte| u8::from_str_radix(hex_byte, 16))
-            .collect();
-
-        match hex_bytes {
-            Ok(bytes) => {
+        match (OutputFormatter::parse_hex_bytes(hex_data), crate::charset::get_charset()) {
+            (Some(bytes), Some(charset)) => Some(charset.decode(&bytes)),
+            (Some(bytes), None) => {
                 if bytes
                     .iter()
                     .all(|&b| b.is_ascii() && (b.is_ascii_graphic() || b == b' '))
@@ -275,7 +584,7 @@ impl BinaryMatch {
                     None
                 }
             }
-            Err(_) => None,
+            (None, _) => None,
         }
     }
 }
@@ -316,9 +625,58 @@ mod tests {
             OutputFormat::from_str("plain"),
             Some(OutputFormat::Plain)
         ));
+        assert!(matches!(
+            OutputFormat::from_str("ndjson"),
+            Some(OutputFormat::Ndjson)
+        ));
+        assert!(matches!(
+            OutputFormat::from_str("jsonl"),
+            Some(OutputFormat::Ndjson)
+        ));
+        assert!(matches!(
+            OutputFormat::from_str("msgpack"),
+            Some(OutputFormat::Msgpack)
+        ));
+        assert!(matches!(
+            OutputFormat::from_str("xxd"),
+            Some(OutputFormat::Xxd)
+        ));
+        assert!(matches!(
+            OutputFormat::from_str("od"),
+            Some(OutputFormat::Od(OdRadix::O))
+        ));
         assert!(matches!(OutputFormat::from_str("invalid"), None));
     }
 
+    #[test]
+    fn test_skip_reason_strings_match_serialization() {
+        for reason in [
+            SkipReason::PermissionDenied,
+            SkipReason::TooLarge,
+            SkipReason::NotRegular,
+            SkipReason::Excluded,
+        ] {
+            let json = serde_json::to_string(&reason).unwrap();
+            assert_eq!(json, format!("\"{}\"", reason.as_str()));
+        }
+    }
+
+    #[test]
+    fn test_skipped_ndjson_record() {
+        let skipped = SkippedFile {
+            path: "a.bin".to_string(),
+            reason: SkipReason::TooLarge,
+        };
+        let formatter = StructuredFormatter::new(OutputFormat::Ndjson);
+        let mut output = Vec::new();
+        formatter.output_skipped_ndjson(&skipped, &mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "{\"skipped\":{\"path\":\"a.bin\",\"reason\":\"too-large\"}}\n"
+        );
+    }
+
     #[test]
     fn test_binary_match_creation() {
         let m = BinaryMatch::new(
@@ -351,4 +709,74 @@ mod tests {
         assert!(output_str.contains("test.bin"));
         assert!(output_str.contains("48 65 6C 6C 6F"));
     }
+
+    #[test]
+    fn test_csv_output_with_grouped_hex() {
+        let matches = vec![BinaryMatch::new("test.bin".to_string(), 0, "48656C6C 6F".to_string(), 5)];
+        assert_eq!(matches[0].ascii_data, Some("Hello".to_string()));
+
+        let formatter = StructuredFormatter::new(OutputFormat::Csv);
+        let mut output = Vec::new();
+        formatter.output_matches(&matches, &mut output).unwrap();
+
+        let output_str = String::from_utf8(output).unwrap();
+        assert!(output_str.contains("test.bin,0,48656C6C 6F,5,Hello"));
+    }
+
+    #[test]
+    fn test_ndjson_output() {
+        let matches = vec![
+            BinaryMatch::new("test.bin".to_string(), 0, "48 65 6C 6C 6F".to_string(), 5),
+            BinaryMatch::new("test.bin".to_string(), 16, "57 6F 72 6C 64".to_string(), 5),
+        ];
+
+        let formatter = StructuredFormatter::new(OutputFormat::Ndjson);
+        let mut output = Vec::new();
+        formatter.output_matches(&matches, &mut output).unwrap();
+
+        let output_str = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = output_str.lines().collect();
+        assert_eq!(lines.len(), 2);
+
+        let second: BinaryMatch = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(second.offset, 16);
+        assert_eq!(second.ascii_data, Some("World".to_string()));
+    }
+
+    #[test]
+    fn test_msgpack_round_trip() {
+        let matches = vec![
+            BinaryMatch::new("test.bin".to_string(), 0, "4D 5A 90 00".to_string(), 4),
+            BinaryMatch::new("test.bin".to_string(), 0x200, "48 65 6C 6C 6F".to_string(), 5),
+        ];
+
+        let formatter = StructuredFormatter::new(OutputFormat::Msgpack);
+        let mut output = Vec::new();
+        formatter.output_matches(&matches, &mut output).unwrap();
+
+        let decoded: Vec<BinaryMatch> = rmp_serde::from_slice(&output).unwrap();
+        assert_eq!(decoded, matches);
+    }
+
+    #[test]
+    fn test_string_value_field() {
+        let string_match = BinaryMatch::from_string("test.bin".to_string(), 8, "Hi!");
+        assert_eq!(string_match.hex_data, "48 69 21");
+        assert_eq!(string_match.length, 3);
+
+        // Only matches from --strings mode carry the field
+        let json = serde_json::to_string(&string_match).unwrap();
+        assert!(json.contains(r#""string_value":"Hi!""#));
+        let plain = BinaryMatch::new("test.bin".to_string(), 0, "00".to_string(), 1);
+        assert!(!serde_json::to_string(&plain).unwrap().contains("string_value"));
+
+        // Records with and without the field decode in the same MessagePack array
+        let matches = vec![plain, string_match];
+        let formatter = StructuredFormatter::new(OutputFormat::Msgpack);
+        let mut output = Vec::new();
+        formatter.output_matches(&matches, &mut output).unwrap();
+
+        let decoded: Vec<BinaryMatch> = rmp_serde::from_slice(&output).unwrap();
+        assert_eq!(decoded, matches);
+    }
 }
@@ -0,0 +1,255 @@
+//! Shared helpers for modes that write whole output files
+//!
+//! [`AtomicOutput`] writes to a temporary file and only replaces the destination on
+//! [`AtomicOutput::commit`], so an error or Ctrl-C halfway never leaves a truncated
+//! destination behind. The temporary file is created next to the destination, on the
+//! same filesystem, so the final rename is atomic. Only output meant for stdout is staged
+//! in the system temporary directory (`TMPDIR`). Temporary names end in a random suffix
+//! and are created exclusively, so they can be neither predicted nor pre-created.
+
+use crate::error::Result;
+use crate::interrupt;
+use std::collections::hash_map::RandomState;
+use std::ffi::OsString;
+use std::fs::{self, File, OpenOptions};
+use std::hash::{BuildHasher, Hasher};
+use std::io::{self, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Attempts at finding an unused temporary name before giving up
+const MAX_NAME_ATTEMPTS: usize = 16;
+
+/// Output staged in a temporary file until it is committed
+///
+/// Dropping it without calling [`Self::commit`] removes the temporary file, like
+/// [`Self::abort`]. Writes fail once Ctrl-C has been pressed (see [`crate::interrupt`]),
+/// so a mode writing through `?` stops and cleans up instead of committing.
+///
+/// # Examples
+///
+/// ```
+/// use hxgrep::util::AtomicOutput;
+/// use std::io::Write;
+///
+/// let dir = std::env::temp_dir().join(format!("hxgrep_doc_{}", std::process::id()));
+/// std::fs::create_dir_all(&dir).unwrap();
+/// let destination = dir.join("out.bin");
+///
+/// let mut output = AtomicOutput::create(&destination).unwrap();
+/// output.write_all(b"MZ").unwrap();
+/// assert!(!destination.exists());
+/// output.commit().unwrap();
+/// assert_eq!(std::fs::read(&destination).unwrap(), b"MZ");
+/// # std::fs::remove_dir_all(dir).ok();
+/// ```
+#[derive(Debug)]
+pub struct AtomicOutput {
+    file: File,
+    temp_path: PathBuf,
+    /// File replaced on commit, or `None` to copy the output to stdout
+    destination: Option<PathBuf>,
+    finished: bool,
+}
+
+impl AtomicOutput {
+    /// Stage output for `destination`, in a temporary file in the same directory
+    ///
+    /// Fails without creating anything if the directory does not exist.
+    pub fn create<P: AsRef<Path>>(destination: P) -> Result<Self> {
+        let destination = destination.as_ref();
+        let file_name = destination.file_name().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{} does not name a file", destination.display()),
+            )
+        })?;
+        let dir = match destination.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        Self::stage(dir, file_name.to_os_string(), Some(destination.to_path_buf()))
+    }
+
+    /// Stage output for stdout, in the system temporary directory
+    pub fn stdout() -> Result<Self> {
+        Self::stage(&std::env::temp_dir(), OsString::from("hxgrep-stdout"), None)
+    }
+
+    /// Create `.<name>.<random>.tmp` in `dir`, retrying with a new suffix if the name is taken
+    fn stage(dir: &Path, name: OsString, destination: Option<PathBuf>) -> Result<Self> {
+        for _ in 0..MAX_NAME_ATTEMPTS {
+            let mut temp_name = OsString::from(".");
+            temp_name.push(&name);
+            temp_name.push(format!(".{:016x}.tmp", random_suffix()));
+            let temp_path = dir.join(temp_name);
+
+            match OpenOptions::new().read(true).write(true).create_new(true).open(&temp_path) {
+                Ok(file) => {
+                    return Ok(Self {
+                        file,
+                        temp_path,
+                        destination,
+                        finished: false,
+                    })
+                }
+                Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("no unused temporary file name in {}", dir.display()),
+        )
+        .into())
+    }
+
+    /// Path of the temporary file holding the output so far
+    pub fn temp_path(&self) -> &Path {
+        &self.temp_path
+    }
+
+    /// Make the output visible: rename it over the destination, or copy it to stdout
+    pub fn commit(mut self) -> Result<()> {
+        self.file.flush()?;
+        match self.destination.take() {
+            Some(destination) => {
+                self.file.sync_all()?;
+                fs::rename(&self.temp_path, &destination)?;
+            }
+            None => {
+                self.file.seek(SeekFrom::Start(0))?;
+                let stdout = io::stdout();
+                let mut stdout = stdout.lock();
+                io::copy(&mut self.file, &mut stdout)?;
+                stdout.flush()?;
+                fs::remove_file(&self.temp_path)?;
+            }
+        }
+        self.finished = true;
+        Ok(())
+    }
+
+    /// Discard the output, leaving the destination as it was
+    pub fn abort(mut self) -> Result<()> {
+        self.finished = true;
+        fs::remove_file(&self.temp_path)?;
+        Ok(())
+    }
+}
+
+impl Write for AtomicOutput {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // Not `ErrorKind::Interrupted`, which `write_all` would retry
+        if interrupt::is_interrupted() {
+            return Err(io::Error::other("interrupted by Ctrl-C"));
+        }
+        self.file.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+impl Drop for AtomicOutput {
+    fn drop(&mut self) {
+        if !self.finished {
+            fs::remove_file(&self.temp_path).ok();
+        }
+    }
+}
+
+/// Random value for temporary names
+///
+/// `RandomState` keys are seeded from the OS; the process id, time and a counter keep
+/// values distinct within a process as well.
+fn random_suffix() -> u64 {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u32(std::process::id());
+    hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+    if let Ok(elapsed) = SystemTime::now().duration_since(UNIX_EPOCH) {
+        hasher.write_u128(elapsed.as_nanos());
+    }
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_commit_replaces_destination() {
+        let dir = tempfile::tempdir().unwrap();
+        let destination = dir.path().join("out.bin");
+        fs::write(&destination, b"old").unwrap();
+
+        let mut output = AtomicOutput::create(&destination).unwrap();
+        assert_eq!(output.temp_path().parent(), Some(dir.path()));
+        output.write_all(b"new contents").unwrap();
+        // The destination keeps its old contents until the commit
+        assert_eq!(fs::read(&destination).unwrap(), b"old");
+
+        output.commit().unwrap();
+        assert_eq!(fs::read(&destination).unwrap(), b"new contents");
+        assert_eq!(entries(dir.path()), vec!["out.bin"]);
+    }
+
+    #[test]
+    fn test_abort_and_drop_leave_no_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let destination = dir.path().join("out.bin");
+        fs::write(&destination, b"old").unwrap();
+
+        let mut output = AtomicOutput::create(&destination).unwrap();
+        output.write_all(b"partial").unwrap();
+        output.abort().unwrap();
+
+        // An error returned through `?` drops the output
+        let failed = || -> Result<()> {
+            let mut output = AtomicOutput::create(&destination)?;
+            output.write_all(b"partial")?;
+            Err(io::Error::other("failed halfway").into())
+        };
+        assert!(failed().is_err());
+
+        assert_eq!(fs::read(&destination).unwrap(), b"old");
+        assert_eq!(entries(dir.path()), vec!["out.bin"]);
+    }
+
+    #[test]
+    fn test_missing_directory_fails_cleanly() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(AtomicOutput::create(dir.path().join("missing").join("out.bin")).is_err());
+        assert!(AtomicOutput::create(dir.path().join("..")).is_err());
+        assert!(entries(dir.path()).is_empty());
+    }
+
+    #[test]
+    fn test_temporary_names_are_unpredictable() {
+        let dir = tempfile::tempdir().unwrap();
+        let destination = dir.path().join("out.bin");
+
+        let first = AtomicOutput::create(&destination).unwrap();
+        let second = AtomicOutput::create(&destination).unwrap();
+        assert_ne!(first.temp_path(), second.temp_path());
+        let name = first.temp_path().file_name().unwrap().to_string_lossy().into_owned();
+        assert!(name.starts_with(".out.bin.") && name.ends_with(".tmp"), "{}", name);
+
+        drop(first);
+        drop(second);
+        assert!(entries(dir.path()).is_empty());
+    }
+}
@@ -298,9 +298,54 @@ fn test_extremely_large_position() {
         .expect("Failed to execute command");
 
     let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    // 파일 끝을 넘어선 위치에서 시작하면 명확한 오류로 종료
+    assert!(stdout.trim().is_empty());
+    assert!(!output.status.success());
+    assert!(stderr.contains("exceeds file size"), "stderr: {}", stderr);
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
 
-    // 파일 끝을 넘어선 위치에서 시작하면 출력이 없거나 적절한 처리가 되어야 함
-    assert!(stdout.trim().is_empty() || !output.status.success());
+#[test]
+fn test_extreme_offset_arguments_fail_cleanly() {
+    let binary_path = get_binary_path();
+    let test_file = create_test_file(b"MZ\x90\x00Small file content");
+
+    let run = |args: &[&str]| {
+        Command::new(&binary_path)
+            .arg(&test_file)
+            .args(args)
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    // 극단적인 값은 패닉 없이 오류로 종료
+    for args in [
+        &["-s", "18446744073709551615"][..],
+        &["-s", "18446744073709551615", "-e", "MZ"],
+        &["-s", "18446744073709551615", "--length", "1"],
+        &["-e", "MZ", "--parallel", "--chunk-size", "0"],
+        &["-e", "MZ", "--parallel", "--chunk-size", "18446744073709551615"],
+    ] {
+        let output = run(args);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(!output.status.success(), "args: {:?}", args);
+        assert!(!stderr.contains("panicked"), "args: {:?}, stderr: {}", args, stderr);
+    }
+
+    // 파일 끝을 넘는 --length는 파일 끝까지로 제한됨
+    for args in [
+        &["-s", "1", "--length", "18446744073709551615"][..],
+        &["-s", "1", "--length", "18446744073709551615", "-e", "\\x90"],
+    ] {
+        let output = run(args);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(output.status.success(), "args: {:?}, stderr: {}", args, stderr);
+        assert!(String::from_utf8_lossy(&output.stdout).contains("90"), "args: {:?}", args);
+    }
 
     // 정리
     fs::remove_file(test_file).ok();
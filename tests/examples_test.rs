@@ -0,0 +1,100 @@
+//! Runs the programs in examples/ against generated fixtures (built by `cargo test`)
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::Command;
+
+fn get_example_path(name: &str) -> PathBuf {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("target");
+    path.push("debug");
+    path.push("examples");
+    path.push(name);
+    path
+}
+
+fn create_test_file(content: &[u8]) -> PathBuf {
+    let temp_dir = std::env::temp_dir();
+    let file_path = temp_dir.join(format!("hxgrep_example_{}.bin", uuid::Uuid::new_v4()));
+    let mut file = File::create(&file_path).unwrap();
+    file.write_all(content).unwrap();
+    file_path
+}
+
+#[test]
+fn test_count_matches_example() {
+    let mut test_data = vec![0u8; 0x100];
+    for offset in [0x10, 0x40, 0xF0] {
+        test_data[offset..offset + 2].copy_from_slice(b"MZ");
+    }
+    let test_file = create_test_file(&test_data);
+
+    let output = Command::new(get_example_path("count_matches"))
+        .arg(&test_file)
+        .arg("\\x4D\\x5A")
+        .output()
+        .expect("Failed to execute example");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains(": 3 matches"), "stdout: {}", stdout);
+    assert!(stdout.contains("  10h\n  40h\n  F0h"), "stdout: {}", stdout);
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_carve_jpegs_example() {
+    // 쓰레기 데이터 사이에 JPEG 두 개
+    let first: &[u8] = b"\xFF\xD8\xFF\xE0\x00\x10JFIF\x00\xFF\xD9";
+    let second: &[u8] = b"\xFF\xD8\xFF\xDB\x00\x43\x01\x02\x03\xFF\xD9";
+    let mut test_data = vec![0x55u8; 0x20];
+    test_data.extend_from_slice(first);
+    test_data.extend_from_slice(&[0xAA; 0x30]);
+    let second_offset = test_data.len();
+    test_data.extend_from_slice(second);
+    test_data.extend_from_slice(&[0x55; 0x10]);
+    let test_file = create_test_file(&test_data);
+    let out_dir = std::env::temp_dir().join(format!("hxgrep_carve_{}", uuid::Uuid::new_v4()));
+
+    let output = Command::new(get_example_path("carve_jpegs"))
+        .arg(&test_file)
+        .arg(&out_dir)
+        .output()
+        .expect("Failed to execute example");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("2 JPEG images carved"), "stdout: {}", stdout);
+
+    // 추출된 파일은 SOI부터 EOI까지
+    let source_name = test_file.file_name().unwrap().to_string_lossy().to_string();
+    let carved_first = fs::read(out_dir.join(format!("{}_offset_20.bin", source_name))).unwrap();
+    let carved_second = fs::read(out_dir.join(format!("{}_offset_{:X}.bin", source_name, second_offset))).unwrap();
+    assert_eq!(carved_first, first);
+    assert_eq!(carved_second, second);
+
+    // 정리
+    fs::remove_file(test_file).ok();
+    fs::remove_dir_all(out_dir).ok();
+}
+
+#[test]
+fn test_hexdump_to_string_example() {
+    let test_file = create_test_file(b"ABCDEFGHIJ");
+
+    let output = Command::new(get_example_path("hexdump_to_string"))
+        .arg(&test_file)
+        .output()
+        .expect("Failed to execute example");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("4142 4344 4546 4748"), "stdout: {}", stdout);
+    assert!(stdout.contains("494A"), "stdout: {}", stdout);
+    assert!(stdout.contains("(2 lines,"), "stdout: {}", stdout);
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
@@ -0,0 +1,70 @@
+/* Scans a known buffer through the C ABI; run by tests/ffi_test.rs */
+#include <stdio.h>
+#include <string.h>
+
+#include "hxgrep.h"
+
+#define MAX_FOUND 8
+
+struct found {
+    uint64_t offset[MAX_FOUND];
+    size_t length[MAX_FOUND];
+    int32_t pattern_index[MAX_FOUND];
+    int count;
+};
+
+static bool record(uint64_t offset, size_t length, int32_t pattern_index, void *user_data) {
+    struct found *found = user_data;
+    if (found->count < MAX_FOUND) {
+        found->offset[found->count] = offset;
+        found->length[found->count] = length;
+        found->pattern_index[found->count] = pattern_index;
+    }
+    found->count++;
+    return true;
+}
+
+static bool stop_after_first(uint64_t offset, size_t length, int32_t pattern_index, void *user_data) {
+    (void)offset;
+    (void)length;
+    (void)pattern_index;
+    (void)user_data;
+    return false;
+}
+
+#define CHECK(condition)                                              \
+    do {                                                              \
+        if (!(condition)) {                                           \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__, #condition); \
+            return 1;                                                 \
+        }                                                             \
+    } while (0)
+
+int main(void) {
+    static const uint8_t data[] = "..MZ..PK\x03\x04..MZ";
+    const size_t len = sizeof(data) - 1;
+
+    HxgrepHandle *handle = hxgrep_compile("\\x4D\\x5A\n\\x50\\x4B\\x03\\x04");
+    CHECK(handle != NULL);
+
+    struct found found;
+    memset(&found, 0, sizeof(found));
+    CHECK(hxgrep_scan_buffer(handle, data, len, 0x1000, record, &found) == 3);
+    CHECK(found.count == 3);
+    CHECK(found.offset[0] == 0x1002 && found.length[0] == 2 && found.pattern_index[0] == 0);
+    CHECK(found.offset[1] == 0x1006 && found.length[1] == 4 && found.pattern_index[1] == 1);
+    CHECK(found.offset[2] == 0x100C && found.length[2] == 2 && found.pattern_index[2] == 0);
+
+    /* The callback can stop the scan early */
+    CHECK(hxgrep_scan_buffer(handle, data, len, 0, stop_after_first, NULL) == 1);
+    CHECK(hxgrep_scan_buffer(handle, NULL, 0, 0, record, &found) == 0);
+    CHECK(hxgrep_scan_buffer(NULL, data, len, 0, record, &found) == -1);
+
+    hxgrep_free(handle);
+
+    CHECK(hxgrep_compile("(") == NULL);
+    CHECK(hxgrep_compile(NULL) == NULL);
+
+    printf("ok\n");
+    return 0;
+}
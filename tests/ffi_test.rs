@@ -0,0 +1,59 @@
+//! Builds tests/ffi/scan_buffer.c against the static library and runs it (`cargo test --features ffi`)
+#![cfg(all(feature = "ffi", target_os = "linux"))]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Build the static library with `cargo rustc --crate-type staticlib` and return its path
+///
+/// The library crate is only an rlib, so regular builds do not link an archive. The
+/// running `cargo test` holds the lock on its target directory, so the archive is built
+/// in a directory of its own under `CARGO_TARGET_TMPDIR`.
+fn build_static_library(manifest_dir: &Path) -> PathBuf {
+    let target_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("ffi");
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let status = Command::new(cargo)
+        .args(["rustc", "--lib", "--features", "ffi", "--crate-type", "staticlib"])
+        .arg("--manifest-path")
+        .arg(manifest_dir.join("Cargo.toml"))
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .status()
+        .expect("Failed to run cargo rustc");
+    assert!(status.success(), "Failed to build the static library");
+    target_dir.join("debug").join("libhxgrep.a")
+}
+
+#[test]
+fn test_c_program_scans_known_buffer() {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let library = build_static_library(&manifest_dir);
+    assert!(library.is_file(), "Static library not found at {}", library.display());
+    let program = std::env::temp_dir().join(format!("hxgrep_ffi_test_{}", uuid::Uuid::new_v4()));
+    let compiler = std::env::var("CC").unwrap_or_else(|_| "cc".to_string());
+
+    // 이 테스트는 --features ffi로만 실행되므로 C 컴파일러가 없으면 통과시키지 않고 실패
+    let status = Command::new(&compiler)
+        .arg("-std=c99")
+        .arg(manifest_dir.join("tests").join("ffi").join("scan_buffer.c"))
+        .arg("-I")
+        .arg(manifest_dir.join("include"))
+        .arg(&library)
+        .args(["-lgcc_s", "-lutil", "-lrt", "-lpthread", "-lm", "-ldl", "-lc"])
+        .arg("-o")
+        .arg(&program)
+        .status()
+        .unwrap_or_else(|err| panic!("C compiler {} is required for the FFI test (set CC): {}", compiler, err));
+    assert!(status.success(), "Failed to compile the C test program");
+
+    let output = Command::new(&program).output().expect("Failed to run the C test program");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    // C 프로그램이 알려진 버퍼의 매치를 모두 확인
+    assert!(output.status.success(), "stderr: {}", stderr);
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "ok");
+
+    // 정리
+    fs::remove_file(program).ok();
+}
@@ -95,40 +95,231 @@ fn test_no_offset() {
     let test_data = b"Test";
     let test_file = create_test_file(test_data);
 
+    // --no-offset과 이전 이름인 --hideoffset 모두 오프셋 숨김
+    for flag in ["--no-offset", "--hideoffset"] {
+        let output = Command::new(&binary_path)
+            .arg(&test_file)
+            .arg(flag)
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(output.status.success(), "{}", flag);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        // 오프셋이 숨겨졌는지 확인
+        assert!(!stdout.contains("h :"), "{}", flag);
+        assert!(stdout.contains("54 65 73 74"), "{}", flag); // "Test"의 hex
+    }
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_custom_separator() {
+    let binary_path = get_binary_path();
+    let test_data = b"ABC";
+    let test_file = create_test_file(test_data);
+
     let output = Command::new(&binary_path)
         .arg(&test_file)
+        .arg("-t")
+        .arg("-")
         .arg("--no-offset")
         .output()
         .expect("Failed to execute command");
 
     let stdout = String::from_utf8_lossy(&output.stdout);
 
-    // 오프셋이 숨겨졌는지 확인
-    assert!(!stdout.contains("h :"));
-    assert!(stdout.contains("54 65 73 74")); // "Test"의 hex
+    // 커스텀 구분자 확인
+    assert!(stdout.contains("41-42-43"));
 
     // 정리
     fs::remove_file(test_file).ok();
 }
 
 #[test]
-fn test_custom_separator() {
+fn test_group_size() {
     let binary_path = get_binary_path();
-    let test_data = b"ABC";
+    let test_data = b"\xDE\xAD\xBE\xEF\xCA\xFE\xBA\xBE\x01\x02\x03\x04";
     let test_file = create_test_file(test_data);
 
     let output = Command::new(&binary_path)
         .arg(&test_file)
-        .arg("-t")
-        .arg("-")
+        .arg("--group-size")
+        .arg("4")
         .arg("--no-offset")
         .output()
         .expect("Failed to execute command");
 
     let stdout = String::from_utf8_lossy(&output.stdout);
 
-    // 커스텀 구분자 확인
-    assert!(stdout.contains("41-42-43"));
+    // 4바이트씩 묶어서 출력
+    assert_eq!(stdout.trim_end(), "DEADBEEF CAFEBABE 01020304");
+
+    // 매치 출력도 정렬 패딩을 포함해 같은 단위로 묶임
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("-e")
+        .arg("\\xFE\\xBA")
+        .arg("-w")
+        .arg("3")
+        .arg("--align")
+        .arg("4")
+        .arg("--group-size")
+        .arg("2")
+        .arg("--color")
+        .arg("never")
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("4h : ..FE BABE"));
+
+    // 0은 거부
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("--group-size")
+        .arg("0")
+        .output()
+        .expect("Failed to execute command");
+    assert!(!output.status.success());
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_skip_zeros() {
+    let binary_path = get_binary_path();
+    let mut test_data = vec![0u8; 64];
+    test_data[0x20] = 0x41;
+    let test_file = create_test_file(&test_data);
+
+    // 0으로만 된 줄은 연속 구간마다 `*` 한 줄로 표시, 병렬 처리에서도 청크 경계와 무관하게 같은 결과
+    let expected = "*\n41 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00\n*\n";
+    for extra in [vec![], vec!["--parallel", "--chunk-size", "16"]] {
+        let output = Command::new(&binary_path)
+            .arg(&test_file)
+            .arg("--skip-zeros")
+            .arg("--no-offset")
+            .args(&extra)
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(output.status.success());
+        assert_eq!(String::from_utf8_lossy(&output.stdout), expected, "args: {:?}", extra);
+    }
+
+    // 생략된 줄은 -n 제한에 포함되지 않음
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("--skip-zeros")
+        .arg("--no-offset")
+        .arg("-n")
+        .arg("1")
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "*\n41 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00\n"
+    );
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_lowercase_hex() {
+    let binary_path = get_binary_path();
+    let test_data = b"\xAB\xCD\xEF\x01";
+    let test_file = create_test_file(test_data);
+
+    // 기본은 대문자
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("--no-offset")
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim_end(), "AB CD EF 01");
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("--no-offset")
+        .arg("--lowercase")
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim_end(), "ab cd ef 01");
+
+    // 매치 출력, 병렬 처리, JSON 출력도 소문자
+    for extra in [vec![], vec!["--parallel", "--chunk-size", "2"], vec!["-f", "json"]] {
+        let output = Command::new(&binary_path)
+            .arg(&test_file)
+            .arg("-e")
+            .arg("\\xCD\\xEF")
+            .arg("-w")
+            .arg("2")
+            .arg("--lowercase")
+            .arg("--color")
+            .arg("never")
+            .args(&extra)
+            .output()
+            .expect("Failed to execute command");
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("cd ef"), "args: {:?}, stdout: {}", extra, stdout);
+        assert!(!stdout.contains("CD EF"), "args: {:?}, stdout: {}", extra, stdout);
+    }
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_regex_offset_padding_matches_dump() {
+    let binary_path = get_binary_path();
+
+    // 4KB 파일이므로 오프셋은 4자리 (0x1000)
+    let mut test_data = vec![0u8; 4096];
+    test_data[0x10..0x12].copy_from_slice(b"\xCA\xFE");
+    test_data[0xF00..0xF02].copy_from_slice(b"\xCA\xFE");
+    let test_file = create_test_file(&test_data);
+
+    let offset_widths = |stdout: &[u8]| -> Vec<usize> {
+        String::from_utf8_lossy(stdout)
+            .lines()
+            .filter_map(|line| line.split_once("h : ").map(|(offset, _)| offset.len()))
+            .collect()
+    };
+
+    let cases: Vec<Vec<&str>> = vec![
+        vec!["-n", "2"],
+        vec!["-e", "\\xCA\\xFE"],
+        vec!["-e", "\\xCA\\xFE", "--mmap"],
+    ];
+
+    for args in cases {
+        let output = Command::new(&binary_path)
+            .arg(&test_file)
+            .args(&args)
+            .arg("--color")
+            .arg("never")
+            .output()
+            .expect("Failed to execute command");
+
+        let widths = offset_widths(&output.stdout);
+        assert_eq!(widths, vec![4, 4], "args: {:?}", args);
+    }
+
+    // 파일에서 리다이렉트한 stdin도 파일 크기 기준으로 패딩
+    let output = Command::new(&binary_path)
+        .arg("-")
+        .arg("-e")
+        .arg("\\xCA\\xFE")
+        .stdin(fs::File::open(&test_file).unwrap())
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(offset_widths(&output.stdout), vec![4, 4]);
 
     // 정리
     fs::remove_file(test_file).ok();
@@ -213,6 +404,44 @@ fn test_regex_search_with_limit() {
     fs::remove_file(test_file).ok();
 }
 
+#[test]
+fn test_regex_size_limit_exit_code() {
+    let binary_path = get_binary_path();
+    let test_file = create_test_file(b"\x00\x00\x00\x01\x02\x03");
+
+    let run = |args: &[&str]| {
+        Command::new(&binary_path)
+            .arg(&test_file)
+            .args(args)
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    // 크기 제한을 넘는 패턴은 제한값과 --regex-size-limit 안내를 출력하고 종료 코드 3
+    let output = run(&["-e", "(?:\\x00{1,100000}){1,100}"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(3), "stderr: {}", stderr);
+    assert!(stderr.contains("size limit of 10485760 bytes"), "stderr: {}", stderr);
+    assert!(stderr.contains("--regex-size-limit"), "stderr: {}", stderr);
+
+    // quiet 모드에서도 같은 종료 코드
+    let output = run(&["-q", "-e", "(?:\\x00{1,100000}){1,100}"]);
+    assert_eq!(output.status.code(), Some(3));
+
+    // 제한을 낮추면 평소에 컴파일되는 패턴도 거부되고, 설정한 제한값이 출력됨
+    let output = run(&["--regex-size-limit", "100", "-e", "\\x00{1,1000}"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(3), "stderr: {}", stderr);
+    assert!(stderr.contains("size limit of 100 bytes"), "stderr: {}", stderr);
+
+    // 다른 컴파일 오류는 기존 종료 코드 유지
+    let output = run(&["-q", "-e", "\\x41("]);
+    assert_eq!(output.status.code(), Some(2));
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
 #[test]
 fn test_start_position() {
     let binary_path = get_binary_path();
@@ -340,3 +569,2382 @@ fn test_version_output() {
     assert!(stdout.contains("hxgrep"));
     assert!(stdout.contains("0.1.0"));
 }
+
+#[test]
+fn test_quiet_mode_match_found() {
+    let binary_path = get_binary_path();
+    let test_data = b"Header\x00\x00\x00\x01\x67Footer";
+    let test_file = create_test_file(test_data);
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("-q")
+        .arg("-e")
+        .arg("\\x00\\x00\\x00\\x01\\x67")
+        .output()
+        .expect("Failed to execute command");
+
+    // 매치가 있으면 종료 코드 0, 표준 출력 없음
+    assert_eq!(output.status.code(), Some(0));
+    assert!(output.stdout.is_empty());
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_quiet_mode_no_match() {
+    let binary_path = get_binary_path();
+    let test_data = b"Header Footer";
+    let test_file = create_test_file(test_data);
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("--quiet")
+        .arg("-e")
+        .arg("\\x00\\x00\\x00\\x01\\x67")
+        .output()
+        .expect("Failed to execute command");
+
+    // 매치가 없으면 종료 코드 1
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_quiet_mode_error_exit_code() {
+    let binary_path = get_binary_path();
+    let test_file = create_test_file(b"data");
+
+    // 잘못된 정규표현식
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("-q")
+        .arg("-e")
+        .arg("\\x00(")
+        .output()
+        .expect("Failed to execute command");
+
+    // 오류 시 종료 코드 2
+    assert_eq!(output.status.code(), Some(2));
+    assert!(output.stdout.is_empty());
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_files_with_matches_single_file() {
+    let binary_path = get_binary_path();
+    let test_file = create_test_file(b"Header\xFF\xD8\xFF\xE0Footer");
+    let path = test_file.to_string_lossy().to_string();
+
+    let run = |flag: &str, pattern: &str| {
+        Command::new(&binary_path)
+            .args([path.as_str(), flag, "-e", pattern])
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    // 매치가 있으면 -l은 경로만 출력하고 헥사 출력은 없음
+    let output = run("-l", "\\xFF\\xD8");
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), format!("{}\n", path));
+
+    // 매치가 없으면 -l은 아무것도 출력하지 않음
+    let output = run("-l", "\\x00\\x01");
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+
+    // -L은 그 반대
+    assert!(run("-L", "\\xFF\\xD8").stdout.is_empty());
+    assert_eq!(String::from_utf8_lossy(&run("-L", "\\x00\\x01").stdout), format!("{}\n", path));
+
+    // stdin은 "(standard input)"으로 표시
+    let output = Command::new(&binary_path)
+        .args(["-", "-l", "-e", "\\xFF\\xD8"])
+        .stdin(fs::File::open(&test_file).unwrap())
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "(standard input)\n");
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_byte_offset_round_trip() {
+    let binary_path = get_binary_path();
+    let mut test_data: Vec<u8> = (0..0x3000u32).map(|i| (i % 200) as u8).collect();
+    for &pos in &[0x10usize, 0xFFE, 0x1800, 0x2FFC] {
+        test_data[pos..pos + 4].copy_from_slice(b"\xDE\xAD\xBE\xEF");
+    }
+    let test_file = create_test_file(&test_data);
+
+    let run = |args: &[&str]| {
+        let output = Command::new(&binary_path)
+            .arg(&test_file)
+            .args(args)
+            .output()
+            .expect("Failed to execute command");
+        assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    // 헥사 출력 없이 10진수 오프셋만 한 줄에 하나씩
+    let stdout = run(&["-b", "-e", "\\xDE\\xAD\\xBE\\xEF"]);
+    assert_eq!(stdout, "16\n4094\n6144\n12284\n");
+
+    // 각 오프셋을 -s로 넘기면 그 위치가 매치의 시작
+    for offset in stdout.lines() {
+        let dump = run(&["-s", offset, "-n", "1", "-w", "4", "--no-offset"]);
+        assert_eq!(dump.trim(), "DE AD BE EF", "offset: {}", offset);
+    }
+
+    // 병렬 처리도 같은 순서, -Z는 NUL로 구분
+    let parallel = run(&["-b", "-e", "\\xDE\\xAD\\xBE\\xEF", "--parallel", "--chunk-size", "1024"]);
+    assert_eq!(parallel, stdout);
+    let null_separated = run(&["-b", "-Z", "-e", "\\xDE\\xAD\\xBE\\xEF"]);
+    assert_eq!(null_separated, "16\x004094\x006144\x0012284\x00");
+
+    // 구조화 출력과는 함께 사용할 수 없음
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .args(["-b", "-f", "json", "-e", "\\xDE"])
+        .output()
+        .expect("Failed to execute command");
+    assert!(!output.status.success());
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_align_match_display() {
+    let binary_path = get_binary_path();
+    let mut test_data = vec![0x41u8; 0x13];
+    test_data.extend_from_slice(b"MZ\x90\x00");
+    let test_file = create_test_file(&test_data);
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("-e")
+        .arg("\\x4D\\x5A")
+        .arg("-w")
+        .arg("2")
+        .arg("--align")
+        .arg("16")
+        .arg("--color")
+        .arg("never")
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    // 매치 오프셋 0x13이 0x10으로 정렬되고 앞의 3바이트는 점으로 채워짐
+    assert!(stdout.contains("10h : .. .. .. 4D 5A"));
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_align_rejects_non_power_of_two() {
+    let binary_path = get_binary_path();
+    let test_file = create_test_file(b"MZ");
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("-e")
+        .arg("\\x4D\\x5A")
+        .arg("--align")
+        .arg("24")
+        .output()
+        .expect("Failed to execute command");
+
+    // 2의 거듭제곱이 아닌 값은 거부
+    assert!(!output.status.success());
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_ndjson_output_format() {
+    let binary_path = get_binary_path();
+    let test_data = b"First\x00\x00\x00\x01\x67Middle\x00\x00\x00\x01\x67Last";
+    let test_file = create_test_file(test_data);
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("-e")
+        .arg("\\x00\\x00\\x00\\x01\\x67")
+        .arg("-w")
+        .arg("5")
+        .arg("-f")
+        .arg("ndjson")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    // 매치마다 한 줄의 JSON 객체가 출력되어야 함
+    assert_eq!(lines.len(), 2);
+    let offsets: Vec<u64> = lines
+        .iter()
+        .map(|line| {
+            let value: serde_json::Value = serde_json::from_str(line).unwrap();
+            assert_eq!(value["hex_data"], "00 00 00 01 67");
+            assert_eq!(value["length"], 5);
+            value["offset"].as_u64().unwrap()
+        })
+        .collect();
+    assert_eq!(offsets, vec![5, 16]);
+
+    // jq가 설치되어 있으면 각 줄을 jq로 파싱
+    let jq_available = Command::new("jq").arg("--version").output().is_ok();
+    if jq_available {
+        for line in &lines {
+            let mut jq = Command::new("jq")
+                .arg("-e")
+                .arg(".offset")
+                .stdin(std::process::Stdio::piped())
+                .stdout(std::process::Stdio::piped())
+                .spawn()
+                .expect("Failed to spawn jq");
+            jq.stdin.take().unwrap().write_all(line.as_bytes()).unwrap();
+            let jq_output = jq.wait_with_output().unwrap();
+            assert!(jq_output.status.success(), "jq rejected line: {}", line);
+        }
+    }
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_match_only_prints_exact_match() {
+    let binary_path = get_binary_path();
+    let mut test_data = b"AB".to_vec();
+    test_data.extend_from_slice(&[0u8; 40]);
+    test_data.extend_from_slice(b"\x01CDEFGHIJ");
+    let test_file = create_test_file(&test_data);
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("-e")
+        .arg("\\x00+\\x01")
+        .arg("--match-only")
+        .arg("--no-offset")
+        .arg("--color")
+        .arg("never")
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    // 40개의 NULL 바이트와 0x01까지 정확히 41바이트만 출력
+    let expected = format!("{} 01", vec!["00"; 40].join(" "));
+    assert_eq!(lines, vec![expected.as_str()]);
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_match_only_ndjson_length() {
+    let binary_path = get_binary_path();
+    let test_data = b"xxMZyyyyyyyyyyyyyyyy";
+    let test_file = create_test_file(test_data);
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("-e")
+        .arg("\\x4D\\x5A")
+        .arg("--match-only")
+        .arg("-f")
+        .arg("ndjson")
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let value: serde_json::Value = serde_json::from_str(stdout.trim()).unwrap();
+
+    // 뒤따르는 바이트 없이 매치된 2바이트만 포함
+    assert_eq!(value["hex_data"], "4D 5A");
+    assert_eq!(value["length"], 2);
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_color_highlight_covers_only_match() {
+    let binary_path = get_binary_path();
+    // 매치가 버퍼 앞부분(width 이내)이 아닌 위치에 있어도 강조되어야 함
+    let mut test_data = vec![0x41u8; 0x53];
+    test_data.extend_from_slice(b"MZ\x90\x00");
+    let test_file = create_test_file(&test_data);
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("-e")
+        .arg("\\x4D\\x5A")
+        .arg("-w")
+        .arg("3")
+        .arg("--align")
+        .arg("16")
+        .arg("--color")
+        .arg("always")
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let red = "\x1b[1;31m";
+
+    // 정렬로 생긴 앞쪽 3바이트와 뒤따르는 0x90은 강조되지 않고 매치된 2바이트만 빨간색
+    assert_eq!(stdout.matches(red).count(), 2, "stdout: {:?}", stdout);
+    assert!(stdout.contains(&format!("{}4D\x1b[0m", red)));
+    assert!(stdout.contains(&format!("{}5A\x1b[0m", red)));
+    assert!(stdout.contains(".. .. .. "));
+    assert!(!stdout.contains(&format!("{}90", red)));
+    assert!(!stdout.contains(&format!("{}..", red)));
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_color_environment_precedence() {
+    let binary_path = get_binary_path();
+    let test_file = create_test_file(b"AAAAMZ\x90\x00");
+
+    let run = |env: &[(&str, &str)], color: Option<&str>| {
+        let mut command = Command::new(&binary_path);
+        command.arg(&test_file).arg("-e").arg("\\x4D\\x5A");
+        command.env_remove("NO_COLOR").env_remove("CLICOLOR_FORCE").envs(env.iter().copied());
+        if let Some(color) = color {
+            command.arg("--color").arg(color);
+        }
+        let output = command.output().expect("Failed to execute command");
+        assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+        String::from_utf8_lossy(&output.stdout).contains("\x1b[")
+    };
+
+    // 리다이렉트된 stdout은 기본적으로 색상 없음, CLICOLOR_FORCE는 색상을 강제
+    assert!(!run(&[], None));
+    assert!(run(&[("CLICOLOR_FORCE", "1")], None));
+    assert!(!run(&[("CLICOLOR_FORCE", "0")], None));
+
+    // NO_COLOR는 CLICOLOR_FORCE보다 우선
+    assert!(!run(&[("NO_COLOR", "1"), ("CLICOLOR_FORCE", "1")], None));
+
+    // 명시한 --color는 환경 변수보다 우선
+    assert!(run(&[("NO_COLOR", "1")], Some("always")));
+    assert!(!run(&[("CLICOLOR_FORCE", "1")], Some("never")));
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_highlight_color_and_byte_categories() {
+    let binary_path = get_binary_path();
+    let test_file = create_test_file(b"\x00AMZ\xFF");
+
+    let run = |args: &[&str]| {
+        Command::new(&binary_path)
+            .arg(&test_file)
+            .args(["-e", "\\x4D\\x5A", "-w", "0", "--color", "always"])
+            .args(args)
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    // 기본 매치 색상은 굵은 빨강
+    let stdout = String::from_utf8_lossy(&run(&[]).stdout).to_string();
+    assert!(stdout.contains("\x1b[1;31m4D\x1b[0m"), "stdout: {:?}", stdout);
+
+    // --highlight-color로 매치 색상 변경
+    let stdout = String::from_utf8_lossy(&run(&["--highlight-color", "bright_blue"]).stdout).to_string();
+    assert!(stdout.contains("\x1b[1;94m4D\x1b[0m"), "stdout: {:?}", stdout);
+
+    // --color-bytes는 매치 밖의 바이트를 종류별로 색칠
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .args(["-e", "\\x4D\\x5A", "-w", "5", "--color", "always", "--color-bytes"])
+        .output()
+        .expect("Failed to execute command");
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    assert!(stdout.contains("\x1b[1;31m4D\x1b[0m"), "stdout: {:?}", stdout);
+    assert!(stdout.contains("\x1b[1;91mFF\x1b[0m"), "stdout: {:?}", stdout);
+
+    // 알 수 없는 색상 이름은 거부
+    assert!(!run(&["--highlight-color", "sparkly"]).status.success());
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+/// 4KB 데이터 섬 두 개를 가진 64MB 희소 파일 생성
+fn create_sparse_test_file() -> (PathBuf, u64) {
+    use std::io::{Seek, SeekFrom};
+
+    let file_size: u64 = 64 * 1024 * 1024;
+    let file_path = create_test_file(b"");
+    let mut file = fs::OpenOptions::new().write(true).open(&file_path).unwrap();
+    file.set_len(file_size).unwrap();
+
+    let mut island = vec![0x41u8; 4096];
+    island[..6].copy_from_slice(b"ISLAND");
+    file.write_all(&island).unwrap();
+    file.seek(SeekFrom::Start(32 * 1024 * 1024)).unwrap();
+    file.write_all(&island).unwrap();
+
+    (file_path, file_size)
+}
+
+#[test]
+fn test_skip_holes_reads_only_data_islands() {
+    let binary_path = get_binary_path();
+    let (test_file, file_size) = create_sparse_test_file();
+
+    // 파일시스템이 hole을 보고하지 않으면 검증할 수 없음
+    if hxgrep::sparse::HoleSkipper::for_file(&File::open(&test_file).unwrap()).is_none() {
+        fs::remove_file(test_file).ok();
+        return;
+    }
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("-e")
+        .arg("ISLAND")
+        .arg("--skip-holes")
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    // 두 데이터 섬의 매치가 모두 출력되어야 함
+    assert!(stdout.contains("0000000h"), "stdout: {}", stdout);
+    assert!(stdout.contains("2000000h"), "stdout: {}", stdout);
+
+    // 요약에서 읽은 바이트 수 추출
+    let summary = stderr
+        .lines()
+        .find(|line| line.starts_with("Skipped "))
+        .expect("hole summary missing");
+    if summary.starts_with("Skipped 0 hole(s)") {
+        // 파일시스템이 전체를 데이터로 보고한 경우
+        fs::remove_file(test_file).ok();
+        return;
+    }
+    let bytes_read: u64 = summary
+        .rsplit("read ")
+        .next()
+        .and_then(|rest| rest.split_whitespace().next())
+        .and_then(|n| n.parse().ok())
+        .expect("bytes read missing");
+
+    // 읽은 양은 파일 크기가 아니라 데이터 섬(과 버퍼 단위 읽기)에 비례해야 함
+    assert!(bytes_read > 0);
+    assert!(bytes_read * 4 < file_size, "bytes read: {}", bytes_read);
+    assert!(stderr.contains("hole: "));
+
+    // 실제로 읽은 범위와 파일 대비 비율이 요약되어야 함
+    assert!(stderr.contains("Scanned "), "stderr: {}", stderr);
+    assert!(stderr.contains("scanned: 0h-"), "stderr: {}", stderr);
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_limited_scan_reports_coverage() {
+    let binary_path = get_binary_path();
+    let test_file = create_test_file(&[0x41u8; 256]);
+
+    // -s/--length로 제한하면 요청 범위 대비 읽은 범위를 stderr에 요약
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .args(["-s", "0x10", "--length", "0x20", "-e", "AAAA"])
+        .output()
+        .expect("Failed to execute command");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {}", stderr);
+    assert!(
+        stderr.contains("Scanned 100.0% of 32.0 B (10h-30h) in 1 range(s)"),
+        "stderr: {}",
+        stderr
+    );
+    assert!(stderr.contains("scanned: 10h-30h"), "stderr: {}", stderr);
+
+    // 덤프에도 적용되고, 범위 제한이 없으면 요약하지 않음
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .args(["-s", "0x80"])
+        .output()
+        .expect("Failed to execute command");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Scanned 100.0% of 128.0 B (80h-100h)"), "stderr: {}", stderr);
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .args(["-e", "AAAA"])
+        .output()
+        .expect("Failed to execute command");
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("Scanned "));
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_skip_holes_disabled_for_zero_pattern() {
+    let binary_path = get_binary_path();
+    let test_file = create_test_file(b"\x00\x00\x00\x00MZ");
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("-e")
+        .arg("\\x00\\x00")
+        .arg("--skip-holes")
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    // 0을 매치할 수 있는 패턴은 경고 후 hole 건너뛰기 비활성화
+    assert!(stderr.contains("--skip-holes disabled"));
+    assert!(stdout.contains("00 00"));
+
+    // 긴 0 구간이 필요한 패턴도 비활성화되고 매치를 찾아야 함
+    let mut long_zeros = vec![0u8; 6000];
+    long_zeros.extend_from_slice(b"MZ");
+    let long_file = create_test_file(&long_zeros);
+    let output = Command::new(&binary_path)
+        .arg(&long_file)
+        .args(["-e", "\\x00{5000}", "--skip-holes", "-b"])
+        .output()
+        .expect("Failed to execute command");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("pattern can match zero bytes"), "stderr: {}", stderr);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "0\n");
+
+    // 정리
+    fs::remove_file(test_file).ok();
+    fs::remove_file(long_file).ok();
+}
+
+#[test]
+fn test_interactive_mode() {
+    use std::process::Stdio;
+
+    let binary_path = get_binary_path();
+    let test_data = b"\x00\x00MZ\x90\x00\x03\x00PE\x00\x00MZ\xFF\xFF";
+    let test_file = create_test_file(test_data);
+
+    let mut child = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("--interactive")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to execute command");
+
+    // 패턴과 명령을 stdin으로 전달
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"PE\n:width 2\n:limit 1\nMZ\n(\n:quit\nMZ\n")
+        .unwrap();
+
+    let output = child.wait_with_output().expect("Failed to wait for command");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    // PE는 기본 폭(16)으로, MZ는 폭 2와 제한 1로 출력되고 :quit 이후 패턴은 무시됨
+    assert_eq!(lines.len(), 2, "stdout: {}", stdout);
+    assert!(lines[0].contains("50 45 00 00 4D 5A FF FF"));
+    assert!(lines[1].ends_with(": 4D 5A"));
+
+    // 프롬프트와 명령 응답, 잘못된 패턴 오류는 stderr로 출력
+    assert!(stderr.contains("hxgrep> "));
+    assert!(stderr.contains("width = 2"));
+    assert!(stderr.contains("limit = 1"));
+    assert!(stderr.contains("Error: "));
+    assert!(output.status.success());
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_fixed_string_search() {
+    let binary_path = get_binary_path();
+    let test_data = b"\x00\x00a.b*\x00\x00aXbb\x00";
+    let test_file = create_test_file(test_data);
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("-F")
+        .arg("a.b*")
+        .arg("-w")
+        .arg("4")
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    // 메타문자가 리터럴로 취급되어 "a.b*"만 매치되어야 함
+    assert_eq!(lines.len(), 1, "stdout: {}", stdout);
+    assert!(lines[0].contains("61 2E 62 2A"));
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_fixed_string_utf16le() {
+    let binary_path = get_binary_path();
+    let test_data = b"MZ\x00\x00M\x00Z\x00\x00\x00";
+    let test_file = create_test_file(test_data);
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("-F")
+        .arg("MZ")
+        .arg("--string-encoding")
+        .arg("utf16le")
+        .arg("-w")
+        .arg("4")
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    // 와이드 문자열만 매치되어야 함
+    assert_eq!(lines.len(), 1, "stdout: {}", stdout);
+    assert!(lines[0].contains("4D 00 5A 00"));
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_fixed_string_empty_errors() {
+    let binary_path = get_binary_path();
+    let test_file = create_test_file(b"MZ");
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("-F")
+        .arg("")
+        .output()
+        .expect("Failed to execute command");
+
+    // 빈 문자열은 오류
+    assert!(!output.status.success());
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_search_range_end() {
+    let binary_path = get_binary_path();
+    let mut test_data = vec![0xFFu8; 64];
+    test_data[8..10].copy_from_slice(b"MZ");
+    test_data[30..32].copy_from_slice(b"MZ");
+    test_data[40..42].copy_from_slice(b"MZ");
+    let test_file = create_test_file(&test_data);
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("-e")
+        .arg("MZ")
+        .arg("-s")
+        .arg("4")
+        .arg("--end")
+        .arg("31")
+        .arg("-w")
+        .arg("4")
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    // 종료 위치를 넘는 매치(30, 40)는 출력되지 않고 오프셋은 절대값 유지
+    assert_eq!(lines.len(), 1, "stdout: {}", stdout);
+    assert!(lines[0].contains("8h : 4D 5A FF FF"));
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_search_range_length_hex_dump() {
+    let binary_path = get_binary_path();
+    let test_data: Vec<u8> = (0u8..64).collect();
+    let test_file = create_test_file(&test_data);
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("-s")
+        .arg("16")
+        .arg("--length")
+        .arg("20")
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    // 16바이트부터 20바이트만 출력 (16 + 4)
+    assert_eq!(lines.len(), 2, "stdout: {}", stdout);
+    assert!(lines[0].contains("10h : 10 11 12"));
+    assert!(lines[1].ends_with("20 21 22 23"));
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_search_range_invalid_end() {
+    let binary_path = get_binary_path();
+    let test_file = create_test_file(b"MZ\x90\x00");
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("-s")
+        .arg("2")
+        .arg("--end")
+        .arg("2")
+        .output()
+        .expect("Failed to execute command");
+
+    // 종료 위치가 시작 위치 이하이면 오류
+    assert!(!output.status.success());
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_search_range_start_and_end() {
+    let binary_path = get_binary_path();
+    let mut test_data = vec![0xFFu8; 300];
+    for offset in [50, 150, 198, 200, 250] {
+        test_data[offset..offset + 2].copy_from_slice(b"MZ");
+    }
+    let test_file = create_test_file(&test_data);
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("-e")
+        .arg("MZ")
+        .arg("-s")
+        .arg("100")
+        .arg("--end")
+        .arg("200")
+        .arg("-w")
+        .arg("2")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let offsets: Vec<u64> = stdout
+        .lines()
+        .map(|line| u64::from_str_radix(line.split('h').next().unwrap().trim(), 16).unwrap())
+        .collect();
+
+    // 시작 위치 이전(50)과 종료 위치 이후(200, 250)의 매치는 출력되지 않음
+    assert_eq!(offsets, vec![150, 198], "stdout: {}", stdout);
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_extract_embedded_jpegs() {
+    let binary_path = get_binary_path();
+
+    // 바이너리 사이에 JPEG 두 개를 삽입
+    let jpeg_a: &[u8] = b"\xFF\xD8\xFF\xE0\x00\x10JFIF\x00\x01\xFF\xD9";
+    let jpeg_b: &[u8] = b"\xFF\xD8\xFF\xE1\x12\x34Exif\x00\x00\xAB\xCD\xFF\xD9";
+    let mut test_data = vec![0x11u8; 100];
+    test_data.extend_from_slice(jpeg_a);
+    test_data.extend_from_slice(&[0x22u8; 50]);
+    let second_offset = test_data.len();
+    test_data.extend_from_slice(jpeg_b);
+    test_data.extend_from_slice(&[0x33u8; 20]);
+    let test_file = create_test_file(&test_data);
+
+    let extract_dir = std::env::temp_dir().join(format!("bingrep_extract_{}", uuid::Uuid::new_v4()));
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("-e")
+        .arg("(?s-u)\\xFF\\xD8\\xFF.*?\\xFF\\xD9")
+        .arg("--extract-dir")
+        .arg(&extract_dir)
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+
+    // 원본 파일명과 16진수 오프셋으로 파일이 생성되어야 함
+    let source_name = test_file.file_name().unwrap().to_string_lossy().to_string();
+    let first = extract_dir.join(format!("{}_offset_64.bin", source_name));
+    let second = extract_dir.join(format!("{}_offset_{:X}.bin", source_name, second_offset));
+
+    let first_data = fs::read(&first).expect("first JPEG not extracted");
+    let second_data = fs::read(&second).expect("second JPEG not extracted");
+
+    // 추출된 파일은 JPEG 시그니처로 시작하고 매치 전체를 포함
+    assert!(first_data.starts_with(b"\xFF\xD8\xFF"));
+    assert!(second_data.starts_with(b"\xFF\xD8\xFF"));
+    assert_eq!(first_data, jpeg_a);
+    assert_eq!(second_data, jpeg_b);
+    // 추출 파일 2개와 manifest.jsonl
+    assert_eq!(fs::read_dir(&extract_dir).unwrap().count(), 3);
+
+    // 정리
+    fs::remove_dir_all(extract_dir).ok();
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_extract_rerun_uses_manifest() {
+    let binary_path = get_binary_path();
+    let mut test_data = vec![0x11u8; 32];
+    test_data[16..20].copy_from_slice(b"\xFF\xD8\xAA\xD9");
+    let test_file = create_test_file(&test_data);
+    let extract_dir = std::env::temp_dir().join(format!("bingrep_extract_{}", uuid::Uuid::new_v4()));
+
+    let run = || {
+        let output = Command::new(&binary_path)
+            .arg(&test_file)
+            .args(["-e", "(?s-u)\\xFF\\xD8.\\xD9", "-x"])
+            .arg(&extract_dir)
+            .output()
+            .expect("Failed to execute command");
+        assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+        output
+    };
+    let manifest = || -> Vec<serde_json::Value> {
+        fs::read_to_string(extract_dir.join("manifest.jsonl"))
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    };
+
+    // 첫 실행: 추출 파일과 매니페스트 항목 하나
+    run();
+    let source_name = test_file.file_name().unwrap().to_string_lossy().to_string();
+    let first = extract_dir.join(format!("{}_offset_10.bin", source_name));
+    assert_eq!(fs::read(&first).unwrap(), b"\xFF\xD8\xAA\xD9");
+    let entries = manifest();
+    assert_eq!(entries.len(), 1);
+    assert!(entries[0]["source"].as_str().unwrap().ends_with(&source_name));
+    assert_eq!(entries[0]["offset"], 16);
+    assert_eq!(entries[0]["length"], 4);
+    assert_eq!(entries[0]["file"], format!("{}_offset_10.bin", source_name));
+    assert_eq!(entries[0]["sha256"].as_str().unwrap().len(), 64);
+
+    // 같은 내용으로 다시 실행하면 건너뜀
+    let output = run();
+    assert!(String::from_utf8_lossy(&output.stderr).contains("already extracted"));
+    assert_eq!(manifest().len(), 1);
+
+    // 원본 바이트가 바뀌면 버전이 붙은 새 파일로 저장하고 기존 파일은 유지
+    test_data[18] = 0xBB;
+    fs::write(&test_file, &test_data).unwrap();
+    run();
+    let second = extract_dir.join(format!("{}_offset_10_v2.bin", source_name));
+    assert_eq!(fs::read(&second).unwrap(), b"\xFF\xD8\xBB\xD9");
+    assert_eq!(fs::read(&first).unwrap(), b"\xFF\xD8\xAA\xD9");
+    let entries = manifest();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[1]["file"], format!("{}_offset_10_v2.bin", source_name));
+    assert_ne!(entries[0]["sha256"], entries[1]["sha256"]);
+
+    // 정리
+    fs::remove_dir_all(extract_dir).ok();
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_aligned_matches_only() {
+    let binary_path = get_binary_path();
+    let mut test_data = vec![0xFFu8; 2048];
+    for &loc in &[100usize, 512, 700, 1024] {
+        test_data[loc..loc + 2].copy_from_slice(b"\x55\xAA");
+    }
+    let test_file = create_test_file(&test_data);
+
+    // -s 300으로 시작해도 정렬 기준은 파일 오프셋 0
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("-e")
+        .arg("\\x55\\xAA")
+        .arg("-s")
+        .arg("300")
+        .arg("--aligned")
+        .arg("512")
+        .arg("-w")
+        .arg("2")
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    assert_eq!(lines.len(), 2, "stdout: {}", stdout);
+    assert!(lines[0].contains("200h : 55 AA"));
+    assert!(lines[1].contains("400h : 55 AA"));
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_aligned_offset_mbr_signature() {
+    let binary_path = get_binary_path();
+    let mut test_data = vec![0x00u8; 1536];
+    test_data[100..102].copy_from_slice(b"\x55\xAA");
+    test_data[510..512].copy_from_slice(b"\x55\xAA");
+    test_data[1534..1536].copy_from_slice(b"\x55\xAA");
+    let test_file = create_test_file(&test_data);
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("-e")
+        .arg("\\x55\\xAA")
+        .arg("--aligned")
+        .arg("512")
+        .arg("--aligned-offset")
+        .arg("510")
+        .arg("-w")
+        .arg("2")
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    // 오프셋 % 512 == 510 인 매치만 출력
+    assert_eq!(lines.len(), 2, "stdout: {}", stdout);
+    assert!(lines[0].contains("1FEh : 55 AA"));
+    assert!(lines[1].contains("5FEh : 55 AA"));
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_integer_value_search_little_endian() {
+    let binary_path = get_binary_path();
+    let test_data = b"\x00\x41\x42\x43\x44\x00\x44\x43\x42\x41\x00\x44\x43\x42\x41";
+    let test_file = create_test_file(test_data);
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("--u32")
+        .arg("0x41424344")
+        .arg("--endian")
+        .arg("le")
+        .arg("-w")
+        .arg("4")
+        .arg("-n")
+        .arg("1")
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    // 리틀 엔디안 바이트 44 43 42 41 중 첫 매치만 출력 (-n 1)
+    assert_eq!(lines.len(), 1, "stdout: {}", stdout);
+    assert!(lines[0].contains("6h : 44 43 42 41"));
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_integer_value_search_big_endian_decimal() {
+    let binary_path = get_binary_path();
+    let test_data = b"\xFF\x10\x00\xFF\x00\x10\xFF";
+    let test_file = create_test_file(test_data);
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("--u16")
+        .arg("4096")
+        .arg("--endian")
+        .arg("be")
+        .arg("-w")
+        .arg("2")
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    // 4096 = 0x1000 빅 엔디안 바이트 10 00
+    assert_eq!(lines.len(), 1, "stdout: {}", stdout);
+    assert!(lines[0].contains("1h : 10 00"));
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_typed_uint32_le_search() {
+    let binary_path = get_binary_path();
+    let test_data = b"\x00\xDE\xAD\xBE\xEF\x00\x00\xEF\xBE\xAD\xDE\x00";
+    let test_file = create_test_file(test_data);
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("--uint32-le")
+        .arg("0xDEADBEEF")
+        .arg("-w")
+        .arg("4")
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    // 리틀 엔디안 바이트 EF BE AD DE 만 매치 (빅 엔디안 표현은 제외)
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(lines.len(), 1, "stdout: {}", stdout);
+    assert!(lines[0].contains("7h : EF BE AD DE"));
+
+    // --endian 과 함께 사용할 수 없음
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("--uint32-le")
+        .arg("0xDEADBEEF")
+        .arg("--endian")
+        .arg("be")
+        .output()
+        .expect("Failed to execute command");
+    assert!(!output.status.success());
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_entropy_column_and_threshold() {
+    let binary_path = get_binary_path();
+
+    // 첫 줄은 모두 0(엔트로피 0), 둘째 줄은 서로 다른 16바이트(엔트로피 4)
+    let mut test_data = vec![0u8; 16];
+    test_data.extend(0u8..16);
+    let test_file = create_test_file(&test_data);
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("--entropy")
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    assert_eq!(lines.len(), 2, "stdout: {}", stdout);
+    assert!(lines[0].ends_with("| H=0.00"));
+    assert!(lines[1].ends_with("| H=4.00"));
+
+    // 최소 임계값으로 낮은 엔트로피 라인 제외
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("--entropy")
+        .arg("--entropy-threshold-min")
+        .arg("1.5")
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    assert_eq!(lines.len(), 1, "stdout: {}", stdout);
+    assert!(lines[0].contains("10h : 00 01 02"));
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_invert_match_dumps_unmatched_regions() {
+    let binary_path = get_binary_path();
+
+    // 'A' 20바이트, 0 8바이트, 'B' 4바이트
+    let mut test_data = vec![b'A'; 20];
+    test_data.extend([0u8; 8]);
+    test_data.extend([b'B'; 4]);
+    let test_file = create_test_file(&test_data);
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("-e")
+        .arg(r"\x00+")
+        .arg("-v")
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    // 매치된 0 영역을 제외한 나머지만 실제 파일 오프셋으로 출력
+    assert_eq!(lines.len(), 3, "stdout: {}", stdout);
+    assert!(lines[0].contains("0h : 41 41 41 41 41 41 41 41 41 41 41 41 41 41 41 41"));
+    assert!(lines[1].ends_with("10h : 41 41 41 41"));
+    assert!(lines[2].ends_with("1Ch : 42 42 42 42"));
+
+    // -n으로 라인 수 제한
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("-e")
+        .arg(r"\x00+")
+        .arg("-v")
+        .arg("-n")
+        .arg("1")
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 1, "stdout: {}", stdout);
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_invert_match_gap_across_buffer_boundary() {
+    let binary_path = get_binary_path();
+
+    // 4MB 버퍼 경계에 걸친 64바이트 'A' 영역
+    let gap_start = 4 * 1024 * 1024 - 30;
+    let mut test_data = vec![0u8; 5 * 1024 * 1024];
+    test_data[gap_start..gap_start + 64].fill(b'A');
+    let test_file = create_test_file(&test_data);
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("-e")
+        .arg(r"\x00+")
+        .arg("-v")
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    // 경계에서 나뉘지 않고 하나의 영역으로 이어서 출력
+    assert_eq!(lines.len(), 4, "stdout: {}", stdout);
+    assert!(lines[0].contains("3FFFE2h : 41"));
+    assert!(lines[1].contains("3FFFF2h : 41"));
+    assert!(lines[2].contains("400002h : 41"));
+    assert!(lines[3].contains("400012h : 41"));
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_hex_pattern_wildcards() {
+    let binary_path = get_binary_path();
+
+    // MZ 헤더 뒤 2바이트 건너뛰고 PE, 이어서 상위 니블이 6인 바이트
+    let mut test_data = vec![0xFFu8; 8];
+    test_data.extend_from_slice(b"MZ\x12\x34PE\x64");
+    test_data.extend_from_slice(b"MZ\x00\x00PE\x74");
+    let test_file = create_test_file(&test_data);
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("--hex-pattern")
+        .arg("4D 5A ?? ?? 50 45 6?")
+        .arg("-w")
+        .arg("7")
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    assert_eq!(lines.len(), 1, "stdout: {}", stdout);
+    assert!(lines[0].contains("8h : 4D 5A 12 34 50 45 64"));
+
+    // 잘못된 토큰은 해당 토큰을 포함한 오류로 거부
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("--hex-pattern")
+        .arg("4D ZZ")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("'ZZ'"));
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_hex_pattern_jump() {
+    let binary_path = get_binary_path();
+
+    // SPS NAL 유닛 뒤 길이가 다른 페이로드, 이어서 PPS 시작 코드
+    let mut test_data = Vec::new();
+    test_data.extend_from_slice(b"\x00\x00\x00\x01\x67\x42\x00\x1f\xe9\x00\x00\x00\x01\x68");
+    test_data.extend_from_slice(b"\x00\x00\x00\x01\x67\x42\x00\x00\x00\x00\x01\x68");
+    let test_file = create_test_file(&test_data);
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("--hex-pattern")
+        .arg("00 00 00 01 67 [4-6] 00 00 00 01 68")
+        .arg("--match-only")
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    // 페이로드가 4바이트 미만인 두 번째 NAL 유닛은 매치되지 않음
+    assert_eq!(lines.len(), 1, "stdout: {}", stdout);
+    assert!(lines[0].ends_with("00 00 00 01 67 42 00 1F E9 00 00 00 01 68"), "stdout: {}", stdout);
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_strings_mode() {
+    let binary_path = get_binary_path();
+
+    let test_data = b"\x00\x01hello world\x00ab\xffMZ\x90\x00This program\x7f";
+    let test_file = create_test_file(test_data);
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("--strings")
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    // 4바이트 미만의 "ab", "MZ"는 제외
+    assert_eq!(lines.len(), 2, "stdout: {}", stdout);
+    assert!(lines[0].ends_with("2h : hello world"));
+    assert!(lines[1].ends_with("15h : This program"));
+
+    // --no-offset은 strings 유틸리티와 같은 출력
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("--strings")
+        .arg("--strings-min")
+        .arg("2")
+        .arg("--no-offset")
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout, "hello world\nab\nMZ\nThis program\n");
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_strings_mode_json() {
+    let binary_path = get_binary_path();
+
+    let test_file = create_test_file(b"\x00\x00GIF89a\x00");
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("--strings")
+        .arg("-f")
+        .arg("json")
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let records: serde_json::Value = serde_json::from_str(&stdout).expect("invalid JSON");
+
+    assert_eq!(records.as_array().unwrap().len(), 1, "stdout: {}", stdout);
+    assert_eq!(records[0]["offset"], 2);
+    assert_eq!(records[0]["string_value"], "GIF89a");
+    assert_eq!(records[0]["hex_data"], "47 49 46 38 39 61");
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_mask_search() {
+    let binary_path = get_binary_path();
+
+    let test_data = [0x70u8, 0x80, 0x8F, 0x90, 0x85];
+    let test_file = create_test_file(&test_data);
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("--mask")
+        .arg("80:f0")
+        .arg("-w")
+        .arg("1")
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    // 상위 니블이 8인 바이트만 매치 (0x70, 0x90 제외)
+    assert_eq!(lines.len(), 3, "stdout: {}", stdout);
+    assert!(lines[0].ends_with("1h : 80"));
+    assert!(lines[1].ends_with("2h : 8F"));
+    assert!(lines[2].ends_with("4h : 85"));
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_ebcdic_charset_column_and_json() {
+    let binary_path = get_binary_path();
+
+    // EBCDIC(cp037)로 인코딩된 "HELLO"
+    let test_file = create_test_file(&[0xC8, 0xC5, 0xD3, 0xD3, 0xD6]);
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("--charset")
+        .arg("ebcdic")
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.trim_end().ends_with("C8 C5 D3 D3 D6 | HELLO"), "stdout: {}", stdout);
+
+    // 구조화 출력의 ascii_data도 같은 문자 집합으로 디코딩
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("--charset")
+        .arg("ebcdic")
+        .arg("-f")
+        .arg("ndjson")
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let value: serde_json::Value = serde_json::from_str(stdout.trim()).unwrap();
+    assert_eq!(value["ascii_data"], "HELLO");
+    assert_eq!(value["hex_data"], "C8 C5 D3 D3 D6");
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_xxd_compat_matches_xxd() {
+    let binary_path = get_binary_path();
+
+    // 16바이트 경계에 걸치지 않는 길이로 마지막 줄 패딩까지 확인
+    let data: Vec<u8> = (0u8..=255).chain(b"hello, xxd!".iter().copied()).collect();
+    let test_file = create_test_file(&data);
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("--xxd-compat")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 17);
+    assert_eq!(
+        lines[0],
+        "00000000: 0001 0203 0405 0607 0809 0a0b 0c0d 0e0f  ................"
+    );
+    assert_eq!(lines[16], "00000100: 6865 6c6c 6f2c 2078 7864 21              hello, xxd!");
+
+    // xxd가 설치되어 있으면 출력이 완전히 같고 xxd -r로 원본이 복원되는지 확인
+    if let Ok(xxd) = Command::new("xxd").arg(&test_file).output() {
+        assert_eq!(String::from_utf8_lossy(&xxd.stdout), stdout);
+
+        let dump_file = create_test_file(stdout.as_bytes());
+        let restored = Command::new("xxd")
+            .arg("-r")
+            .arg(&dump_file)
+            .output()
+            .expect("Failed to execute xxd -r");
+        assert_eq!(restored.stdout, data);
+        fs::remove_file(dump_file).ok();
+    }
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_multiple_patterns_with_pattern_index() {
+    let binary_path = get_binary_path();
+
+    // SPS와 PPS NAL 유닛이 번갈아 나타나는 데이터
+    let mut test_data = vec![0xFFu8; 4];
+    test_data.extend_from_slice(b"\x00\x00\x00\x01\x68\xce");
+    test_data.extend_from_slice(b"\x00\x00\x00\x01\x67\x42");
+    test_data.extend_from_slice(b"\x00\x00\x00\x01\x68\xce");
+    let test_file = create_test_file(&test_data);
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("-e")
+        .arg("\\x00\\x00\\x00\\x01\\x67")
+        .arg("-e")
+        .arg("\\x00\\x00\\x00\\x01\\x68")
+        .arg("-w")
+        .arg("6")
+        .arg("--with-pattern")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    // 오프셋 순서대로, 각 줄에 매치한 패턴 번호 표시
+    assert_eq!(lines.len(), 3, "stdout: {}", stdout);
+    assert!(lines[0].contains("4h : 00 00 00 01 68 CE | #2"), "stdout: {}", stdout);
+    assert!(lines[1].contains("Ah : 00 00 00 01 67 42 | #1"), "stdout: {}", stdout);
+    assert!(lines[2].contains("10h : 00 00 00 01 68 CE | #2"), "stdout: {}", stdout);
+
+    // --with-pattern 없이도 두 패턴 모두 검색
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("-e")
+        .arg("\\x00\\x00\\x00\\x01\\x67")
+        .arg("-e")
+        .arg("\\x00\\x00\\x00\\x01\\x68")
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 3, "stdout: {}", stdout);
+    assert!(!stdout.contains('#'));
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_od_compat_matches_od() {
+    let binary_path = get_binary_path();
+
+    // 16바이트 경계에 걸치지 않는 길이로 마지막 줄과 끝 오프셋까지 확인
+    let data: Vec<u8> = (0u8..40).collect();
+    let test_file = create_test_file(&data);
+
+    for (extra_args, od_args) in [
+        (vec![], vec!["-tx1", "-v"]),
+        (vec!["--no-offset"], vec!["-An", "-tx1", "-v"]),
+        (vec!["--od-radix", "x"], vec!["-Ax", "-tx1", "-v"]),
+        (vec!["--od-radix", "d"], vec!["-Ad", "-tx1", "-v"]),
+    ] {
+        let output = Command::new(&binary_path)
+            .arg(&test_file)
+            .arg("--od-compat")
+            .args(&extra_args)
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(output.status.success());
+        let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+        if extra_args.is_empty() {
+            assert_eq!(
+                stdout,
+                "0000000 00 01 02 03 04 05 06 07 08 09 0a 0b 0c 0d 0e 0f\n\
+                 0000020 10 11 12 13 14 15 16 17 18 19 1a 1b 1c 1d 1e 1f\n\
+                 0000040 20 21 22 23 24 25 26 27\n\
+                 0000050\n"
+            );
+        }
+
+        // od가 설치되어 있으면 출력이 완전히 같은지 확인
+        if let Ok(od) = Command::new("od").args(&od_args).arg(&test_file).output() {
+            assert_eq!(String::from_utf8_lossy(&od.stdout), stdout, "args {:?}", extra_args);
+        }
+    }
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_degenerate_alternation_collapsed() {
+    let binary_path = get_binary_path();
+
+    // 4KB 0 바이트 영역 앞뒤로 일반 매치
+    let mut test_data = b"\x00\x01".to_vec();
+    test_data.extend_from_slice(&[0xFFu8; 30]);
+    test_data.extend_from_slice(&[0u8; 4096]);
+    test_data.extend_from_slice(&[0xFFu8; 30]);
+    test_data.extend_from_slice(b"\x00\x01");
+    let test_file = create_test_file(&test_data);
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("-e")
+        .arg("(\\x00|\\x00\\x00)")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    // 0 영역 전체가 한 줄로 축약되고 주변 매치는 그대로 출력
+    assert!(lines.len() <= 3, "stdout: {}", stdout);
+    assert!(lines[1].ends_with("(+4095 collapsed)"), "stdout: {}", stdout);
+    assert!(lines[1].starts_with("0020h : 00 00"), "stdout: {}", stdout);
+
+    // --no-collapse는 모든 매치를 출력
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("-e")
+        .arg("(\\x00|\\x00\\x00)")
+        .arg("--no-collapse")
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 4098);
+    assert!(!stdout.contains("collapsed"));
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_mmap_matches_streaming_search() {
+    let binary_path = get_binary_path();
+
+    // 4MB 버퍼 경계에 걸친 매치와 경계 전후 매치
+    let buffer_size = 4 * 1024 * 1024;
+    let mut test_data = vec![0x11u8; buffer_size + 64 * 1024];
+    for &pos in &[100usize, buffer_size - 4, buffer_size - 8 * 1024 + 3, buffer_size + 500] {
+        test_data[pos..pos + 8].copy_from_slice(b"\xCA\xFE\xBA\xBE\x01\x02\x03\x04");
+    }
+    let test_file = create_test_file(&test_data);
+
+    let cases: Vec<Vec<&str>> = vec![
+        vec!["-e", "\\xCA\\xFE\\xBA\\xBE"],
+        vec!["-e", "\\xCA\\xFE\\xBA\\xBE", "-n", "2"],
+        vec!["-e", "\\xCA\\xFE\\xBA\\xBE[\\x01-\\x04]+", "-w", "0"],
+        vec!["-e", "\\xCA\\xFE\\xBA\\xBE", "--end", "4194304"],
+    ];
+
+    for args in cases {
+        let streamed = Command::new(&binary_path)
+            .arg(&test_file)
+            .args(&args)
+            .output()
+            .expect("Failed to execute command");
+        let mapped = Command::new(&binary_path)
+            .arg(&test_file)
+            .args(&args)
+            .arg("--mmap")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(mapped.status.success(), "args: {:?}", args);
+        assert!(!streamed.stdout.is_empty(), "args: {:?}", args);
+        assert_eq!(mapped.stdout, streamed.stdout, "args: {:?}", args);
+    }
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_stdin_respects_position_and_range() {
+    let binary_path = get_binary_path();
+
+    // 0x3000 바이트, 시작 위치 앞뒤에 패턴
+    let mut test_data: Vec<u8> = (0..0x3000u32).map(|i| (i * 7 % 251) as u8).collect();
+    for &pos in &[0x100usize, 0x1010, 0x2FF0] {
+        test_data[pos..pos + 4].copy_from_slice(b"\xCA\xFE\xBA\xBE");
+    }
+    let test_file = create_test_file(&test_data);
+
+    let cases: Vec<Vec<&str>> = vec![
+        vec!["-s", "4096", "-n", "4"],
+        vec!["-s", "4096", "-e", "\\xCA\\xFE\\xBA\\xBE"],
+        vec!["-s", "4096", "--length", "1024", "-e", "\\xCA\\xFE\\xBA\\xBE"],
+        vec!["-s", "4100", "--strings", "-n", "3"],
+        vec!["-s", "4096", "--od-compat", "-n", "2"],
+    ];
+
+    for args in cases {
+        let from_file = Command::new(&binary_path)
+            .arg(&test_file)
+            .args(&args)
+            .output()
+            .expect("Failed to execute command");
+        let from_stdin = Command::new(&binary_path)
+            .arg("-")
+            .args(&args)
+            .stdin(fs::File::open(&test_file).unwrap())
+            .output()
+            .expect("Failed to execute command");
+
+        // stdin도 시작 위치를 건너뛰고 절대 오프셋으로 출력
+        assert!(from_stdin.status.success(), "args: {:?}", args);
+        assert!(!from_file.stdout.is_empty(), "args: {:?}", args);
+        assert_eq!(
+            String::from_utf8_lossy(&from_stdin.stdout),
+            String::from_utf8_lossy(&from_file.stdout),
+            "args: {:?}",
+            args
+        );
+    }
+
+    // 파일에서만 동작하는 옵션은 무시하지 않고 오류
+    for flag in ["--parallel", "--progress", "--mmap"] {
+        let output = Command::new(&binary_path)
+            .arg("-")
+            .arg(flag)
+            .stdin(fs::File::open(&test_file).unwrap())
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(!output.status.success(), "flag: {}", flag);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(flag) && stderr.contains("stdin"), "stderr: {}", stderr);
+    }
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_stdin_streams_large_pipe() {
+    use std::process::Stdio;
+
+    let binary_path = get_binary_path();
+
+    // 4MB 버퍼 경계를 여러 번 넘는 10MB 스트림, 경계에 걸친 패턴 포함
+    let mut test_data: Vec<u8> = (0..10 * 1024 * 1024u32).map(|i| (i * 7 % 251) as u8).collect();
+    for &pos in &[0x1000usize, 0x3FFFFE, 0x7FDFFE, 0x9FFFF0] {
+        test_data[pos..pos + 4].copy_from_slice(b"\xCA\xFE\xBA\xBE");
+    }
+    let test_file = create_test_file(&test_data);
+
+    // 파이프는 길이를 알 수 없어 오프셋 자릿수가 다르므로 값으로 비교
+    let normalize = |stdout: &[u8]| -> Vec<String> {
+        String::from_utf8_lossy(stdout)
+            .lines()
+            .map(|line| match line.split_once("h : ") {
+                Some((offset, rest)) => {
+                    let offset = u64::from_str_radix(offset, 16).expect("offset should be hex");
+                    format!("{:X}h : {}", offset, rest)
+                }
+                None => line.to_string(),
+            })
+            .collect()
+    };
+
+    let cases: Vec<Vec<&str>> = vec![
+        vec!["-e", "\\xCA\\xFE\\xBA\\xBE"],
+        vec!["-e", "\\xCA\\xFE\\xBA\\xBE", "-n", "2"],
+        vec!["-e", "\\xCA\\xFE\\xBA\\xBE", "--match-only"],
+        vec!["-e", "\\xCA\\xFE\\xBA\\xBE", "--no-offset", "--color", "always"],
+        vec!["-s", "4194300", "-n", "3"],
+        vec!["--strings", "-s", "8380000", "-n", "5"],
+    ];
+
+    for args in cases {
+        let from_file = Command::new(&binary_path)
+            .arg(&test_file)
+            .args(&args)
+            .output()
+            .expect("Failed to execute command");
+
+        let mut child = Command::new(&binary_path)
+            .arg("-")
+            .args(&args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("Failed to execute command");
+        let mut stdin = child.stdin.take().unwrap();
+        let data = test_data.clone();
+        // -n 으로 일찍 끝나면 쓰기가 실패할 수 있음
+        let writer = std::thread::spawn(move || stdin.write_all(&data).ok());
+        let from_stdin = child.wait_with_output().expect("Failed to execute command");
+        writer.join().unwrap();
+
+        assert!(
+            from_stdin.status.success(),
+            "args: {:?}, stderr: {}",
+            args,
+            String::from_utf8_lossy(&from_stdin.stderr)
+        );
+        assert!(!from_file.stdout.is_empty(), "args: {:?}", args);
+        assert_eq!(normalize(&from_stdin.stdout), normalize(&from_file.stdout), "args: {:?}", args);
+    }
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_stdin_match_longer_than_rewind_window() {
+    use std::process::Stdio;
+
+    let binary_path = get_binary_path();
+    let output_dir = std::env::temp_dir().join(format!("bingrep_extract_{}", uuid::Uuid::new_v4()));
+    let output_dir_arg = output_dir.to_string_lossy().to_string();
+
+    // 되감기 창보다 훨씬 긴 20MB의 0 구간 하나가 전부 매치됨
+    let run = |args: &[&str]| {
+        let mut child = Command::new(&binary_path)
+            .arg("-")
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("Failed to execute command");
+        let mut stdin = child.stdin.take().unwrap();
+        // -n 으로 일찍 끝나면 쓰기가 실패할 수 있음
+        let writer = std::thread::spawn(move || stdin.write_all(&vec![0u8; 20 * 1024 * 1024]).ok());
+        let output = child.wait_with_output().expect("Failed to execute command");
+        writer.join().unwrap();
+        assert!(output.status.success(), "args: {:?}, stderr: {}", args, String::from_utf8_lossy(&output.stderr));
+        output
+    };
+
+    // 되돌아갈 수 없는 위치로 seek하지 않고, 매치는 한 버퍼만큼 더 따라간 길이로 출력
+    let output = run(&["-e", "\\x00+", "--match-only", "-n", "1"]);
+    assert!(output.stdout.len() > 4 * 1024 * 1024);
+
+    let run_extract = run(&["-e", "\\x00+", "-n", "1", "-x", &output_dir_arg]);
+    assert!(!run_extract.stdout.is_empty());
+    let extracted: Vec<_> = fs::read_dir(&output_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "bin"))
+        .collect();
+    assert_eq!(extracted.len(), 1, "{:?}", extracted);
+    assert_eq!(fs::metadata(&extracted[0]).unwrap().len(), 4 * 1024 * 1024);
+
+    // 정리
+    fs::remove_dir_all(output_dir).ok();
+}
+
+#[test]
+fn test_swapped_position_and_limit_hints() {
+    use std::io::{BufRead, BufReader};
+    use std::process::Stdio;
+
+    let binary_path = get_binary_path();
+    let test_file = create_test_file(&[0x41u8; 256]);
+
+    let stderr_of = |args: &[&str]| {
+        let output = Command::new(&binary_path)
+            .arg(&test_file)
+            .args(args)
+            .output()
+            .expect("Failed to execute command");
+        assert!(output.status.success(), "args: {:?}", args);
+        String::from_utf8_lossy(&output.stderr).to_string()
+    };
+
+    // 큰 -n만 지정하면 -s를 의도했을 수 있다는 힌트
+    let stderr = stderr_of(&["-n", "1048576"]);
+    assert!(stderr.contains("Hint: -n 1048576"), "stderr: {}", stderr);
+    assert!(stderr.contains("use -s 1048576"), "stderr: {}", stderr);
+
+    // 일반적인 조합과 --no-hints에서는 힌트 없음
+    for args in [&["-n", "10"][..], &["-s", "16", "-n", "1048576"], &["-n", "1048576", "--no-hints"], &["-s", "5"]] {
+        let stderr = stderr_of(args);
+        assert!(!stderr.contains("Hint:"), "args: {:?}, stderr: {}", args, stderr);
+    }
+
+    // 큰 파일에서 작은 -s로 전체 덤프하면 -n을 의도했을 수 있다는 힌트 (힌트만 확인하고 종료)
+    let large_file = std::env::temp_dir().join(format!("bingrep_test_hint_{}.bin", uuid::Uuid::new_v4()));
+    fs::File::create(&large_file).unwrap().set_len(300 * 1024 * 1024).unwrap();
+
+    let mut child = Command::new(&binary_path)
+        .arg(&large_file)
+        .arg("-s")
+        .arg("5")
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to execute command");
+    let mut first_line = String::new();
+    BufReader::new(child.stderr.take().unwrap()).read_line(&mut first_line).unwrap();
+    child.kill().ok();
+    child.wait().ok();
+    assert!(first_line.starts_with("Hint: -s 5"), "stderr: {}", first_line);
+    assert!(first_line.contains("use -n 5"), "stderr: {}", first_line);
+
+    let output = Command::new(&binary_path)
+        .arg(&large_file)
+        .arg("-s")
+        .arg("5")
+        .arg("-n")
+        .arg("2")
+        .output()
+        .expect("Failed to execute command");
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("Hint:"));
+
+    // 정리
+    fs::remove_file(test_file).ok();
+    fs::remove_file(large_file).ok();
+}
+
+#[test]
+fn test_watch_reports_appended_matches_and_handles_truncation() {
+    use std::io::Read;
+    use std::process::Stdio;
+    use std::thread::sleep;
+    use std::time::Duration;
+
+    let binary_path = get_binary_path();
+    let test_file = create_test_file(b"MARK....");
+
+    let mut child = Command::new(&binary_path)
+        .arg(&test_file)
+        .args(["--watch", "--poll-ms", "20", "-e", "MARK", "-w", "4"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to execute command");
+    sleep(Duration::from_millis(300));
+
+    // 끝에 추가된 데이터만 새로 검색
+    fs::OpenOptions::new().append(true).open(&test_file).unwrap().write_all(b"..MARK").unwrap();
+    sleep(Duration::from_millis(300));
+
+    // 파일이 잘리면 오프셋 0부터 다시 감시
+    fs::write(&test_file, b"MARK").unwrap();
+    sleep(Duration::from_millis(300));
+
+    // SAFETY: 방금 생성한 자식 프로세스에 SIGINT 전송
+    unsafe {
+        libc::kill(child.id() as libc::pid_t, libc::SIGINT);
+    }
+    let status = child.wait().unwrap();
+    let mut stdout = String::new();
+    let mut stderr = String::new();
+    child.stdout.take().unwrap().read_to_string(&mut stdout).unwrap();
+    child.stderr.take().unwrap().read_to_string(&mut stderr).unwrap();
+
+    assert!(status.success(), "stderr: {}", stderr);
+    let offsets: Vec<u64> = stdout
+        .lines()
+        .map(|line| u64::from_str_radix(line.split('h').next().unwrap().trim(), 16).unwrap())
+        .collect();
+    assert_eq!(offsets, vec![0, 10, 0], "stdout: {}", stdout);
+    assert!(stderr.contains("truncated"), "stderr: {}", stderr);
+    assert!(stderr.contains("Watch stopped: 3 matches"), "stderr: {}", stderr);
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_broken_pipe_exits_cleanly() {
+    use std::io::Read;
+    use std::process::Stdio;
+
+    let binary_path = get_binary_path();
+    let test_data: Vec<u8> = (0..1024 * 1024).map(|i| (i % 256) as u8).collect();
+    let test_file = create_test_file(&test_data);
+
+    let mut child = Command::new(&binary_path)
+        .arg(&test_file)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to execute command");
+
+    // head 처럼 앞부분만 읽고 파이프를 닫음
+    let mut stdout = child.stdout.take().unwrap();
+    let mut head = [0u8; 256];
+    stdout.read_exact(&mut head).unwrap();
+    drop(stdout);
+
+    let output = child.wait_with_output().expect("Failed to wait for command");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    // grep 처럼 오류 메시지 없이 종료 코드 0
+    assert!(output.status.success(), "status: {:?}, stderr: {}", output.status, stderr);
+    assert!(!stderr.contains("panicked"), "stderr: {}", stderr);
+    assert!(!stderr.contains("Broken pipe"), "stderr: {}", stderr);
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_color_never_emits_no_escapes() {
+    let binary_path = get_binary_path();
+    let mut test_data = vec![0u8; 4096];
+    test_data[100..102].copy_from_slice(b"MZ");
+    test_data[2000..2002].copy_from_slice(b"MZ");
+    let test_file = create_test_file(&test_data);
+
+    for extra in [vec![], vec!["--parallel", "--chunk-size", "1024", "--stats"]] {
+        let output = Command::new(&binary_path)
+            .arg(&test_file)
+            .arg("-e")
+            .arg("MZ")
+            .arg("--color")
+            .arg("never")
+            .arg("--progress")
+            .args(&extra)
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+        assert!(!output.stdout.is_empty());
+
+        // stdout과 stderr 어디에도 ESC 바이트가 없어야 함
+        assert!(!output.stdout.contains(&0x1B), "stdout: {:?}", output.stdout);
+        assert!(!output.stderr.contains(&0x1B), "stderr: {:?}", output.stderr);
+    }
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_config_file_defaults_and_overrides() {
+    let binary_path = get_binary_path();
+    let test_file = create_test_file(b"ABCDEFGH");
+    let config_file = std::env::temp_dir().join(format!("bingrep_test_{}.toml", uuid::Uuid::new_v4()));
+    fs::write(&config_file, "default_width = 4\ndefault_separator = \"-\"\nunknown_key = true\n").unwrap();
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("--config")
+        .arg(&config_file)
+        .arg("--no-offset")
+        .output()
+        .expect("Failed to execute command");
+
+    // 설정 파일의 기본값 적용, 알 수 없는 키는 경고만 출력
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("41-42-43-44"), "stdout: {}", stdout);
+    assert!(stdout.contains("45-46-47-48"), "stdout: {}", stdout);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unknown_key"));
+
+    // 명령줄 옵션이 설정 파일보다 우선
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("--config")
+        .arg(&config_file)
+        .arg("--no-offset")
+        .arg("-t")
+        .arg(":")
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("41:42:43:44"), "stdout: {}", stdout);
+
+    // 설정 파일이 없으면 오류
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("--config")
+        .arg(config_file.with_extension("missing"))
+        .output()
+        .expect("Failed to execute command");
+    assert!(!output.status.success());
+
+    // 정리
+    fs::remove_file(test_file).ok();
+    fs::remove_file(config_file).ok();
+}
+
+#[test]
+fn test_split_raw_image() {
+    let binary_path = get_binary_path();
+
+    // 두 세그먼트의 경계(0x400)에 걸친 패턴
+    let dir = std::env::temp_dir().join(format!("split_raw_{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    let mut data = vec![0x11u8; 0x800];
+    data[0x3FE..0x402].copy_from_slice(b"\xDE\xAD\xBE\xEF");
+    fs::write(dir.join("disk.001"), &data[..0x400]).unwrap();
+    fs::write(dir.join("disk.002"), &data[0x400..]).unwrap();
+
+    let output = Command::new(&binary_path)
+        .arg(dir.join("disk.001"))
+        .arg("-e")
+        .arg("\\xDE\\xAD\\xBE\\xEF")
+        .arg("--color")
+        .arg("never")
+        .output()
+        .expect("Failed to execute command");
+
+    // 세그먼트를 하나의 이미지로 이어서 검색
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 1, "stdout: {}", stdout);
+    assert!(stdout.contains("3FEh : DE AD BE EF"), "stdout: {}", stdout);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("split raw"));
+
+    // 헥사 덤프도 두 번째 세그먼트까지 이어짐
+    let output = Command::new(&binary_path)
+        .arg(dir.join("disk.001"))
+        .arg("--color")
+        .arg("never")
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(String::from_utf8_lossy(&output.stdout).lines().count(), 0x80);
+
+    // 정리
+    fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn test_trim_trailing() {
+    let binary_path = get_binary_path();
+
+    // 0xFF로 채워진 섹터 끝 패딩
+    let mut data = vec![0x4D, 0x5A, 0x01, 0x02];
+    data.extend([0xFF; 12]);
+    data.extend([0x11; 16]);
+    data.extend([0xAA, 0xBB]);
+    data.extend([0xFF; 30]);
+    let test_file = create_test_file(&data);
+
+    let run = |args: &[&str]| -> String {
+        let output = Command::new(&binary_path)
+            .arg(&test_file)
+            .args(args)
+            .arg("--color")
+            .arg("never")
+            .output()
+            .expect("Failed to execute command");
+        assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+
+    // 끝의 FF 12바이트를 잘라내고 잘린 개수 표시
+    let stdout = run(&["-e", "\\x4D\\x5A", "--trim-trailing", "FF"]);
+    assert!(stdout.contains(" : 4D 5A 01 02 | +12*FF"), "stdout: {}", stdout);
+
+    // 병렬 검색도 같은 출력
+    let stdout = run(&["-e", "\\x4D\\x5A", "--trim-trailing", "FF", "-p", "--chunk-size", "16"]);
+    assert!(stdout.contains(" : 4D 5A 01 02 | +12*FF"), "stdout: {}", stdout);
+
+    // 창 전체가 자를 바이트면 첫 바이트만 남김
+    let stdout = run(&["-e", "\\xFF{16}", "-n", "1", "--trim-trailing", "0xFF"]);
+    assert!(stdout.contains(" : FF | +15*FF"), "stdout: {}", stdout);
+
+    // 구조화 출력: 오프셋과 길이는 그대로, window_len_trimmed에 남은 바이트 수
+    let stdout = run(&["-e", "\\x4D\\x5A", "-f", "ndjson", "--trim-trailing", "FF"]);
+    let record: serde_json::Value = serde_json::from_str(stdout.lines().next().unwrap()).unwrap();
+    assert_eq!(record["offset"], 0);
+    assert_eq!(record["length"], 2);
+    assert_eq!(record["hex_data"], "4D 5A 01 02");
+    assert_eq!(record["window_len_trimmed"], 4);
+
+    // 추출 파일에서도 끝의 패딩 제거
+    let extract_dir = std::env::temp_dir().join(format!("trim_extract_{}", uuid::Uuid::new_v4()));
+    run(&["-e", "\\xAA\\xBB\\xFF+", "-x", extract_dir.to_str().unwrap(), "--trim-trailing", "FF"]);
+    let extracted: Vec<_> = fs::read_dir(&extract_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| !path.ends_with("manifest.jsonl"))
+        .collect();
+    assert_eq!(extracted.len(), 1);
+    assert_eq!(fs::read(&extracted[0]).unwrap(), vec![0xAA, 0xBB]);
+
+    // 잘못된 바이트 값은 거부
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("-e")
+        .arg("\\x4D")
+        .arg("--trim-trailing")
+        .arg("FFF")
+        .output()
+        .expect("Failed to execute command");
+    assert!(!output.status.success());
+
+    // 정리
+    fs::remove_file(test_file).ok();
+    fs::remove_dir_all(extract_dir).ok();
+}
+
+#[test]
+fn test_partition_scan() {
+    let binary_path = get_binary_path();
+
+    // 보호 MBR + GPT 헤더(LBA 1) + 파티션 엔트리(LBA 2), 1번 파티션은 LBA 34~63
+    let mut image = vec![0u8; 64 * 512];
+    image[446 + 4] = 0xEE;
+    image[510..512].copy_from_slice(&[0x55, 0xAA]);
+    image[512..520].copy_from_slice(b"EFI PART");
+    image[512 + 72..512 + 80].copy_from_slice(&2u64.to_le_bytes());
+    image[512 + 80..512 + 84].copy_from_slice(&128u32.to_le_bytes());
+    image[512 + 84..512 + 88].copy_from_slice(&128u32.to_le_bytes());
+    image[1024..1040].copy_from_slice(&[
+        0xA2, 0xA0, 0xD0, 0xEB, 0xE5, 0xB9, 0x33, 0x44, 0x87, 0xC0, 0x68, 0xB6, 0xB7, 0x26, 0x99, 0xC7,
+    ]);
+    image[1024 + 32..1024 + 40].copy_from_slice(&34u64.to_le_bytes());
+    image[1024 + 40..1024 + 48].copy_from_slice(&63u64.to_le_bytes());
+
+    // 파티션 밖(0x3000)과 안(0x5000)에 같은 패턴
+    image[0x3000..0x3004].copy_from_slice(b"\xDE\xAD\xBE\xEF");
+    image[0x5000..0x5004].copy_from_slice(b"\xDE\xAD\xBE\xEF");
+    let test_file = create_test_file(&image);
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("--list-partitions")
+        .output()
+        .expect("Failed to execute command");
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("GPT partition table, 1 partition(s)"), "stdout: {}", stdout);
+    assert!(stdout.contains("Microsoft basic data"), "stdout: {}", stdout);
+    assert!(stdout.contains("0x4400"), "stdout: {}", stdout);
+
+    // 파티션 안의 매치만 이미지 기준 오프셋으로 출력
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("--partition")
+        .arg("1")
+        .arg("-e")
+        .arg("\\xDE\\xAD\\xBE\\xEF")
+        .arg("--color")
+        .arg("never")
+        .output()
+        .expect("Failed to execute command");
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 1, "stdout: {}", stdout);
+    assert!(stdout.contains("5000h : DE AD BE EF"), "stdout: {}", stdout);
+
+    // -s는 파티션 시작 기준
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("--partition")
+        .arg("1")
+        .arg("-s")
+        .arg("3073")
+        .arg("-e")
+        .arg("\\xDE\\xAD\\xBE\\xEF")
+        .arg("--color")
+        .arg("never")
+        .output()
+        .expect("Failed to execute command");
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(String::from_utf8_lossy(&output.stdout).lines().count(), 0);
+
+    // 없는 파티션 번호는 오류
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("--partition")
+        .arg("2")
+        .arg("-e")
+        .arg("\\xDE\\xAD\\xBE\\xEF")
+        .output()
+        .expect("Failed to execute command");
+    assert!(!output.status.success());
+
+    // 파티션 테이블이 없는 파일
+    let plain_file = create_test_file(&[0u8; 1024]);
+    let output = Command::new(&binary_path)
+        .arg(&plain_file)
+        .arg("--list-partitions")
+        .output()
+        .expect("Failed to execute command");
+    assert!(String::from_utf8_lossy(&output.stdout).contains("No partition table found"));
+
+    // 정리
+    fs::remove_file(test_file).ok();
+    fs::remove_file(plain_file).ok();
+}
+
+#[test]
+fn test_progress_always_when_stderr_redirected() {
+    let binary_path = get_binary_path();
+    let mut data = vec![0x11u8; 4096];
+    data[100..104].copy_from_slice(b"\xDE\xAD\xBE\xEF");
+    let test_file = create_test_file(&data);
+
+    let run = |progress: &str| {
+        let output = Command::new(&binary_path)
+            .arg(&test_file)
+            .arg("-e")
+            .arg("\\xDE\\xAD\\xBE\\xEF")
+            .arg(progress)
+            .arg("--color")
+            .arg("never")
+            .output()
+            .expect("Failed to execute command");
+        assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+        assert!(String::from_utf8_lossy(&output.stdout).contains("DE AD BE EF"));
+        String::from_utf8_lossy(&output.stderr).to_string()
+    };
+
+    // 기본값은 stderr가 터미널이 아니면 진행률을 표시하지 않음
+    assert!(!run("--progress").contains("100%"));
+
+    // --progress=always는 리다이렉트된 stderr에도 진행률 표시
+    let stderr = run("--progress=always");
+    assert!(stderr.contains("100%"), "stderr: {}", stderr);
+    assert!(stderr.contains("1 match, ETA: 00:00"), "stderr: {}", stderr);
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_output_file_json() {
+    let binary_path = get_binary_path();
+    let mut data = vec![0x11u8; 64];
+    data[8..10].copy_from_slice(b"MZ");
+    data[40..42].copy_from_slice(b"MZ");
+    let test_file = create_test_file(&data);
+    let output_dir = std::env::temp_dir().join(format!("bingrep_output_{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&output_dir).unwrap();
+    let output_file = output_dir.join("out.json");
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("-e")
+        .arg("MZ")
+        .arg("-o")
+        .arg(&output_file)
+        .arg("-f")
+        .arg("json")
+        .output()
+        .expect("Failed to execute command");
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    // 출력은 모두 파일로 가고 stdout에는 아무것도 쓰지 않음
+    assert!(output.stdout.is_empty());
+
+    let written = fs::read_to_string(&output_file).unwrap();
+    let records: serde_json::Value = serde_json::from_str(&written).expect("invalid JSON");
+    let offsets: Vec<u64> = records.as_array().unwrap().iter().map(|record| record["offset"].as_u64().unwrap()).collect();
+    assert_eq!(offsets, vec![8, 40]);
+
+    // 없는 디렉터리에는 파일을 만들지 않고 오류로 종료
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("-e")
+        .arg("MZ")
+        .arg("-o")
+        .arg(output_dir.join("missing").join("out.json"))
+        .output()
+        .expect("Failed to execute command");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("cannot create output file"));
+
+    // 입력 파일을 출력으로 지정하면 덮어쓰지 않음
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("-o")
+        .arg(&test_file)
+        .output()
+        .expect("Failed to execute command");
+    assert!(!output.status.success());
+    assert_eq!(fs::read(&test_file).unwrap(), data);
+
+    // 정리
+    fs::remove_file(test_file).ok();
+    fs::remove_dir_all(output_dir).ok();
+}
+
+#[test]
+fn test_decompress_reports_compressed_offset() {
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+
+    let binary_path = get_binary_path();
+
+    // 압축되지 않는 데이터 사이에 앞쪽과 뒤쪽 매치를 하나씩 넣음
+    let mut data: Vec<u8> = (0u32..300_000).map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8).collect();
+    data[0x100..0x104].copy_from_slice(b"\xDE\xAD\xBE\xEF");
+    data[0x40000..0x40004].copy_from_slice(b"\xDE\xAD\xBE\xEF");
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&data).unwrap();
+    let compressed = encoder.finish().unwrap();
+    let test_file = create_test_file(&compressed);
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .args(["-z", "-e", "\\xDE\\xAD\\xBE\\xEF", "-f", "ndjson"])
+        .output()
+        .expect("Failed to execute command");
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+
+    // 오프셋은 압축을 푼 데이터 기준, compressed_offset은 압축 파일 안의 범위
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let matches: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .filter(|value: &serde_json::Value| value.get("hex_data").is_some())
+        .collect();
+    assert_eq!(matches.len(), 2, "{}", stdout);
+    assert_eq!(matches[0]["offset"], 0x100);
+    assert_eq!(matches[1]["offset"], 0x40000);
+
+    let range = |value: &serde_json::Value| {
+        let range = value["compressed_offset"].as_array().expect("compressed_offset").clone();
+        (range[0].as_u64().unwrap(), range[1].as_u64().unwrap())
+    };
+    let (early_start, early_end) = range(&matches[0]);
+    let (late_start, late_end) = range(&matches[1]);
+    assert!(early_start < early_end && early_end <= late_start && late_start < late_end);
+    assert!(late_end <= compressed.len() as u64);
+
+    // 헥사 출력에는 범위가 `gz` 열로 붙음
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .args(["-z", "-e", "\\xDE\\xAD\\xBE\\xEF"])
+        .output()
+        .expect("Failed to execute command");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().filter(|line| line.contains("| gz ")).count(), 2, "{}", stdout);
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
@@ -157,31 +157,43 @@ fn test_multi_file_with_limit() {
     let binary_path = get_binary_path();
     let files = create_test_files_with_pattern();
 
-    let temp_dir = std::env::temp_dir();
-    let glob_pattern = temp_dir.join("multifile_test_*.bin");
-
-    let output = Command::new(&binary_path)
-        .arg(glob_pattern.to_string_lossy().as_ref())
-        .arg("-e")
-        .arg("\\x01\\x02\\x03\\x04")
-        .arg("--multi")
-        .arg("--global-limit")
-        .arg("2") // Global limit of 2 matches
-        .output()
-        .expect("Failed to execute multi-file with limit");
-
-    assert!(output.status.success(), "Multi-file with limit failed");
-
-    let stdout = String::from_utf8_lossy(&output.stdout);
-
-    // Should show processing messages for files
+    // 다른 테스트의 파일이 섞이지 않도록 이 테스트에서 만든 파일만 선택
+    let first_name = files[0].file_name().unwrap().to_string_lossy().to_string();
+    let glob_pattern = files[0].with_file_name(first_name.replace("_0.bin", "_*.bin"));
+
+    let run = |pattern: &str, global_limit: &str| -> String {
+        let output = Command::new(&binary_path)
+            .arg(glob_pattern.to_string_lossy().as_ref())
+            .arg("-e")
+            .arg(pattern)
+            .arg("--multi")
+            .arg("--global-limit")
+            .arg(global_limit)
+            .arg("--color")
+            .arg("never")
+            .output()
+            .expect("Failed to execute multi-file with limit");
+        assert!(output.status.success(), "Multi-file with limit failed");
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+    let match_lines = |stdout: &str| stdout.lines().filter(|line| line.contains("h : ")).count();
+
+    // 세 파일에 패턴이 있으므로 두 번째 매치에서 중단되어야 함
+    let stdout = run("\\x01\\x02\\x03\\x04", "2");
+    assert!(stdout.contains("=== Processing:"), "Should show file processing messages");
     assert!(
-        stdout.contains("=== Processing:"),
-        "Should show file processing messages"
+        stdout.contains("=== Global limit of 2 reached ==="),
+        "Should stop at the global limit: {}",
+        stdout
     );
+    assert_eq!(match_lines(&stdout), 2, "stdout: {}", stdout);
+    assert!(stdout.contains("=== Total matches/lines processed: 2 ==="), "stdout: {}", stdout);
+    assert_eq!(stdout.matches("=== Processing:").count(), 2, "stdout: {}", stdout);
 
-    // Note: Global limit functionality needs to be properly implemented in the multifile processor
-    // For now, just verify the command runs successfully
+    // 한 파일에 매치가 더 많아도 전역 제한에서 멈춤
+    let stdout = run("\\xFF{4}", "3");
+    assert_eq!(match_lines(&stdout), 3, "stdout: {}", stdout);
+    assert!(stdout.contains("=== Total matches/lines processed: 3 ==="), "stdout: {}", stdout);
 
     // 정리 (지연 추가)
     std::thread::sleep(std::time::Duration::from_millis(100));
@@ -190,6 +202,59 @@ fn test_multi_file_with_limit() {
     }
 }
 
+#[test]
+fn test_multi_file_per_file_and_global_limit() {
+    let binary_path = get_binary_path();
+
+    // 매치가 10개씩 있는 파일 3개
+    let dir = std::env::temp_dir().join(format!("multifile_global_limit_{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    for i in 0..3 {
+        let mut data = vec![0x11u8; 0x200];
+        for m in 0..10 {
+            data[m * 0x30..m * 0x30 + 4].copy_from_slice(b"\xDE\xAD\xBE\xEF");
+        }
+        fs::write(dir.join(format!("{}.bin", i)), data).unwrap();
+    }
+
+    let glob_pattern = dir.join("*.bin");
+    let run = |extra: &[&str]| -> String {
+        let output = Command::new(&binary_path)
+            .arg(glob_pattern.to_string_lossy().as_ref())
+            .arg("--multi")
+            .arg("-e")
+            .arg("\\xDE\\xAD\\xBE\\xEF")
+            .arg("--color")
+            .arg("never")
+            .args(extra)
+            .output()
+            .expect("Failed to execute multi-file command");
+        assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+    let match_lines = |stdout: &str| stdout.lines().filter(|line| line.contains("h : ")).count();
+
+    // 세 번째 파일 안에서 정확히 전역 제한에 멈춤
+    let stdout = run(&["--global-limit", "25"]);
+    assert_eq!(match_lines(&stdout), 25, "stdout: {}", stdout);
+    assert_eq!(stdout.matches("=== Processing:").count(), 3, "stdout: {}", stdout);
+    assert!(stdout.contains("=== Global limit of 25 reached ==="), "stdout: {}", stdout);
+    assert!(stdout.contains("=== Total matches/lines processed: 25 ==="), "stdout: {}", stdout);
+
+    // -n은 파일마다, --global-limit은 전체에 적용 (4 + 4 + 2)
+    let stdout = run(&["-n", "4", "--global-limit", "10"]);
+    assert_eq!(match_lines(&stdout), 10, "stdout: {}", stdout);
+    let third = stdout.split("=== Processing:").nth(3).unwrap();
+    assert_eq!(match_lines(third), 2, "stdout: {}", stdout);
+
+    // 전역 제한이 없으면 -n만 적용
+    let stdout = run(&["-n", "4"]);
+    assert_eq!(match_lines(&stdout), 12, "stdout: {}", stdout);
+
+    // 정리
+    fs::remove_dir_all(dir).ok();
+}
+
 #[test]
 fn test_multi_file_parallel() {
     let binary_path = get_binary_path();
@@ -346,3 +411,954 @@ fn test_multi_file_nonexistent_pattern() {
         "Should show 0 total processed"
     );
 }
+
+#[test]
+fn test_multi_file_files_with_matches() {
+    let binary_path = get_binary_path();
+    let files = create_test_files_with_pattern();
+
+    let first_file_name = files[0].file_name().unwrap().to_string_lossy();
+    let test_id = first_file_name.split('_').nth(2).unwrap();
+    let glob_pattern = std::env::temp_dir().join(format!("multifile_test_{}_*.bin", test_id));
+
+    let output = Command::new(&binary_path)
+        .arg(glob_pattern.to_string_lossy().as_ref())
+        .arg("--multi")
+        .arg("-l")
+        .arg("-e")
+        .arg("\\x01\\x02\\x03\\x04")
+        .output()
+        .expect("Failed to execute multi-file command");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    // 패턴이 있는 3개 파일의 경로만 출력되어야 함
+    assert_eq!(lines.len(), 3, "stdout: {}", stdout);
+    assert!(!stdout.contains("=== Processing:"));
+    assert!(!stdout.contains("nopattern"));
+    assert!(lines.iter().all(|line| !line.contains("01 02 03 04")));
+
+    for file in files {
+        fs::remove_file(file).ok();
+    }
+}
+
+#[test]
+fn test_multi_file_files_without_match() {
+    let binary_path = get_binary_path();
+    let files = create_test_files_with_pattern();
+
+    let first_file_name = files[0].file_name().unwrap().to_string_lossy();
+    let test_id = first_file_name.split('_').nth(2).unwrap();
+    let glob_pattern = std::env::temp_dir().join(format!("multifile_test_{}_*.bin", test_id));
+
+    let output = Command::new(&binary_path)
+        .arg(glob_pattern.to_string_lossy().as_ref())
+        .arg("--multi")
+        .arg("-L")
+        .arg("-e")
+        .arg("\\x01\\x02\\x03\\x04")
+        .output()
+        .expect("Failed to execute multi-file command");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    // 패턴이 없는 파일 하나만 출력되어야 함
+    assert_eq!(lines.len(), 1, "stdout: {}", stdout);
+    assert!(lines[0].contains("nopattern"));
+
+    for file in files {
+        fs::remove_file(file).ok();
+    }
+}
+
+#[test]
+fn test_multi_file_pattern_file() {
+    let binary_path = get_binary_path();
+    let files = create_test_files_with_pattern();
+
+    let first_file_name = files[0].file_name().unwrap().to_string_lossy();
+    let test_id = first_file_name.split('_').nth(2).unwrap();
+    let glob_pattern = std::env::temp_dir().join(format!("multifile_test_{}_*.bin", test_id));
+
+    // 주석과 빈 줄이 섞인 시그니처 파일
+    let pattern_file = std::env::temp_dir().join(format!("multifile_patterns_{}.txt", test_id));
+    fs::write(&pattern_file, "# 테스트 시그니처\n\\x01\\x02\\x03\\x04\n\n\\xAA{4}\n").unwrap();
+
+    let output = Command::new(&binary_path)
+        .arg(glob_pattern.to_string_lossy().as_ref())
+        .arg("--multi")
+        .arg("-l")
+        .arg("--pattern-file")
+        .arg(&pattern_file)
+        .output()
+        .expect("Failed to execute multi-file command");
+
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    // 두 패턴 중 하나라도 포함된 네 파일 모두 출력되어야 함
+    assert_eq!(stdout.lines().count(), 4, "stdout: {}", stdout);
+
+    // 잘못된 패턴은 줄 번호와 함께 실패
+    fs::write(&pattern_file, "\\x01\\x02\n(unclosed\n").unwrap();
+    let output = Command::new(&binary_path)
+        .arg(glob_pattern.to_string_lossy().as_ref())
+        .arg("--multi")
+        .arg("--pattern-file")
+        .arg(&pattern_file)
+        .output()
+        .expect("Failed to execute multi-file command");
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("line 2"));
+
+    // 정리
+    fs::remove_file(pattern_file).ok();
+    for file in files {
+        fs::remove_file(file).ok();
+    }
+}
+
+#[test]
+fn test_multi_file_include_exclude_banners() {
+    let binary_path = get_binary_path();
+
+    let dir = std::env::temp_dir().join(format!("multifile_banners_{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    let mut data = vec![0xFFu8; 32];
+    data[4..8].copy_from_slice(b"\x01\x02\x03\x04");
+    for name in ["a.bin", "b.bin", "disk.iso", "disk.vmdk.lck"] {
+        fs::write(dir.join(name), &data).unwrap();
+    }
+    fs::write(dir.join("huge.bin"), vec![0x01u8; 4096]).unwrap();
+
+    let glob_pattern = dir.join("*");
+    let run = |extra: &[&str]| {
+        let output = Command::new(&binary_path)
+            .arg(glob_pattern.to_string_lossy().as_ref())
+            .arg("--multi")
+            .arg("-e")
+            .arg("\\x01\\x02\\x03\\x04")
+            .args(extra)
+            .output()
+            .expect("Failed to execute multi-file command");
+        assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let processed: Vec<String> = stdout
+            .lines()
+            .filter_map(|line| line.strip_prefix("=== Processing: "))
+            .map(|line| line.trim_end_matches(" ===").rsplit('/').next().unwrap().to_string())
+            .collect();
+        (processed, String::from_utf8_lossy(&output.stderr).to_string())
+    };
+
+    // 제외 패턴과 크기 제한에 걸린 파일은 배너 없이 stderr에 알림
+    let (processed, stderr) = run(&["--exclude", "*.iso", "--exclude", "*.vmdk.lck", "--max-filesize", "1024"]);
+    assert_eq!(processed, vec!["a.bin", "b.bin"]);
+    assert!(stderr.contains("disk.iso skipped (excluded)"), "stderr: {}", stderr);
+    assert!(stderr.contains("huge.bin skipped (too-large)"), "stderr: {}", stderr);
+
+    // 포함 패턴을 먼저 적용한 뒤 제외 패턴 적용
+    let (processed, _) = run(&["--include", "*.bin", "--exclude", "b.bin"]);
+    assert_eq!(processed, vec!["a.bin", "huge.bin"]);
+
+    // 정리
+    fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn test_files_from() {
+    use std::process::Stdio;
+
+    let binary_path = get_binary_path();
+
+    let dir = std::env::temp_dir().join(format!("multifile_files_from_{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    let mut data = vec![0xFFu8; 64];
+    for offset in [4, 20, 36] {
+        data[offset..offset + 4].copy_from_slice(b"\x01\x02\x03\x04");
+    }
+    for name in ["a.bin", "b.bin", "c.bin"] {
+        fs::write(dir.join(name), &data).unwrap();
+    }
+    let path = |name: &str| dir.join(name).to_string_lossy().to_string();
+
+    let run = |list: &[u8], extra: &[&str]| {
+        let mut child = Command::new(&binary_path)
+            .arg("--files-from")
+            .arg("-")
+            .arg("-e")
+            .arg("\\x01\\x02\\x03\\x04")
+            .args(extra)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("Failed to execute command");
+        child.stdin.take().unwrap().write_all(list).unwrap();
+        let output = child.wait_with_output().expect("Failed to wait for command");
+        assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+        (
+            String::from_utf8_lossy(&output.stdout).to_string(),
+            String::from_utf8_lossy(&output.stderr).to_string(),
+        )
+    };
+
+    // 목록 순서대로 검색하고 없는 경로는 경고 후 계속
+    let list = format!("{}\n{}\r\n\n{}\n", path("c.bin"), path("missing.bin"), path("a.bin"));
+    let (stdout, stderr) = run(list.as_bytes(), &[]);
+    let processed: Vec<&str> = stdout.lines().filter_map(|line| line.strip_prefix("=== Processing: ")).collect();
+    assert_eq!(processed.len(), 2, "stdout: {}", stdout);
+    assert!(processed[0].ends_with("c.bin ==="));
+    assert!(processed[1].ends_with("a.bin ==="));
+    assert!(stderr.contains("missing.bin does not exist"), "stderr: {}", stderr);
+
+    // -0은 NUL로 구분된 목록을 읽고, 전역 제한과 출력 형식은 glob 모드와 같게 적용
+    let list = format!("{}\0{}\0{}\0", path("a.bin"), path("b.bin"), path("c.bin"));
+    let (stdout, _) = run(list.as_bytes(), &["-0", "--global-limit", "4", "-f", "ndjson"]);
+    let records: Vec<serde_json::Value> = stdout.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+    let matches = records.iter().filter(|record| record.get("offset").is_some()).count();
+    assert_eq!(matches, 4, "stdout: {}", stdout);
+    assert_eq!(records.last().unwrap()["summary"]["files_processed"], 2);
+
+    // -l는 매치가 있는 파일 경로만 출력
+    let list = format!("{}\n{}\n", path("b.bin"), path("missing.bin"));
+    let (stdout, _) = run(list.as_bytes(), &["-l"]);
+    assert_eq!(stdout.lines().collect::<Vec<_>>(), vec![path("b.bin")]);
+
+    // 정리
+    fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn test_fail_on_warning() {
+    let binary_path = get_binary_path();
+
+    let dir = std::env::temp_dir().join(format!("multifile_warning_{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("data.bin"), b"\xFF\x01\x02\x03\x04\xFF").unwrap();
+    fs::write(dir.join("notes.log"), b"\x01\x02\x03\x04").unwrap();
+    // 일반 파일이 아닌 경로 (장치 파일을 가리키는 심볼릭 링크)
+    std::os::unix::fs::symlink("/dev/null", dir.join("device.bin")).unwrap();
+
+    let glob_pattern = dir.join("*");
+    let run = |extra: &[&str]| {
+        Command::new(&binary_path)
+            .arg(glob_pattern.to_string_lossy().as_ref())
+            .arg("--multi")
+            .arg("-e")
+            .arg("\\x01\\x02\\x03\\x04")
+            .args(extra)
+            .output()
+            .expect("Failed to execute multi-file command")
+    };
+
+    // 플래그가 없으면 경고가 있어도 성공
+    let output = run(&[]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Warning: "));
+
+    // --fail-on-warning은 검색을 마친 뒤 경고 목록과 함께 종료 코드 2
+    let output = run(&["--fail-on-warning"]);
+    assert_eq!(output.status.code(), Some(2));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("01 02 03 04"), "stdout: {}", stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("1 warning(s) with --fail-on-warning"), "stderr: {}", stderr);
+    assert!(stderr.contains("device.bin skipped (not-regular)"), "stderr: {}", stderr);
+
+    // NDJSON 레코드로만 기록된 건너뛰기도 경고로 셈
+    let output = run(&["--fail-on-warning", "-f", "ndjson"]);
+    assert_eq!(output.status.code(), Some(2));
+
+    // --exclude로 제외된 파일은 경고가 아님
+    let output = run(&["--fail-on-warning", "--exclude", "device.bin", "--exclude", "*.log"]);
+    assert_eq!(output.status.code(), Some(0), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+
+    // 정리
+    fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn test_multi_file_skipped_records() {
+    let binary_path = get_binary_path();
+
+    let dir = std::env::temp_dir().join(format!("multifile_skip_{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("small.bin"), b"\xFF\x01\x02\x03\x04\xFF").unwrap();
+    fs::write(dir.join("large.bin"), vec![0x01u8; 4096]).unwrap();
+    fs::write(dir.join("notes.log"), b"\x01\x02\x03\x04").unwrap();
+    // 일반 파일이 아닌 경로 (장치 파일을 가리키는 심볼릭 링크)
+    std::os::unix::fs::symlink("/dev/null", dir.join("device.bin")).unwrap();
+
+    let glob_pattern = dir.join("*");
+    let output = Command::new(&binary_path)
+        .arg(glob_pattern.to_string_lossy().as_ref())
+        .arg("--multi")
+        .arg("-f")
+        .arg("ndjson")
+        .arg("-e")
+        .arg("\\x01\\x02\\x03\\x04")
+        .arg("--exclude")
+        .arg("*.log")
+        .arg("--max-filesize")
+        .arg("1024")
+        .output()
+        .expect("Failed to execute multi-file command");
+
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let records: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("모든 줄은 JSON이어야 함"))
+        .collect();
+
+    // 건너뛴 파일마다 이유가 기록되어야 함
+    let reason_of = |name: &str| {
+        records
+            .iter()
+            .filter_map(|record| record.get("skipped"))
+            .find(|skipped| skipped["path"].as_str().unwrap().ends_with(name))
+            .map(|skipped| skipped["reason"].as_str().unwrap().to_string())
+    };
+    assert_eq!(reason_of("large.bin").as_deref(), Some("too-large"));
+    assert_eq!(reason_of("notes.log").as_deref(), Some("excluded"));
+    assert_eq!(reason_of("device.bin").as_deref(), Some("not-regular"));
+    assert_eq!(reason_of("small.bin"), None);
+
+    // 검색한 파일의 매치와 마지막 요약 레코드
+    assert!(records.iter().any(|record| record["file_path"].as_str().is_some_and(|path| path.ends_with("small.bin"))));
+    let summary = &records.last().unwrap()["summary"];
+    assert_eq!(summary["files_processed"], 1);
+    assert_eq!(summary["files_skipped"], 3);
+
+    // JSON 문서 형식은 skipped 배열과 summary를 담음
+    let output = Command::new(&binary_path)
+        .arg(glob_pattern.to_string_lossy().as_ref())
+        .arg("--multi")
+        .arg("-f")
+        .arg("json")
+        .arg("--exclude")
+        .arg("*.log")
+        .arg("--max-filesize")
+        .arg("1024")
+        .arg("-e")
+        .arg("\\xEE\\xEE")
+        .output()
+        .expect("Failed to execute multi-file command");
+
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["skipped"].as_array().unwrap().len(), 3);
+    assert_eq!(report["summary"]["files_skipped"], 3);
+    assert_eq!(report["matches"], serde_json::json!([]));
+
+    // 정리
+    fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn test_multi_file_json_single_document() {
+    let binary_path = get_binary_path();
+
+    let dir = std::env::temp_dir().join(format!("multifile_json_{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("a.bin"), b"\xDE\xAD..\xDE\xAD").unwrap();
+    fs::write(dir.join("b.bin"), b"\x00\x00\x00\x00").unwrap();
+    fs::write(dir.join("c.bin"), b"..\xDE\xAD").unwrap();
+
+    let glob_pattern = dir.join("*");
+    let run = |extra: &[&str]| {
+        let output = Command::new(&binary_path)
+            .arg(glob_pattern.to_string_lossy().as_ref())
+            .arg("--multi")
+            .arg("-f")
+            .arg("json")
+            .arg("-e")
+            .arg("\\xDE\\xAD")
+            .args(extra)
+            .output()
+            .expect("Failed to execute multi-file command");
+        assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+        let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("stdout은 하나의 JSON 문서여야 함");
+        report["matches"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|record| {
+                let path = record["file_path"].as_str().unwrap();
+                (path[path.len() - 5..].to_string(), record["offset"].as_u64().unwrap())
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // 매치가 없는 파일은 아무것도 쓰지 않고, 모든 매치가 한 배열에 들어감
+    let expected = vec![("a.bin".to_string(), 0), ("a.bin".to_string(), 4), ("c.bin".to_string(), 2)];
+    assert_eq!(run(&[]), expected);
+    assert_eq!(run(&["-j", "2"]), expected);
+
+    // 전역 제한은 매치 수만 셈
+    assert_eq!(run(&["--global-limit", "3"]), expected);
+    assert_eq!(run(&["--global-limit", "2"]), expected[..2].to_vec());
+
+    // 정리
+    fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn test_multi_file_output_independent_of_locale() {
+    let binary_path = get_binary_path();
+
+    // 로캘 정렬 규칙에 따라 순서가 달라지는 파일 이름 (대문자, 밑줄, 비 ASCII)
+    let dir = std::env::temp_dir().join(format!("multifile_locale_{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    for (name, offset) in [("a.bin", 0x1C), ("ä.bin", 0x08), ("B.bin", 0x04), ("_x.bin", 0x10)] {
+        let mut data = vec![0x11u8; 0x20];
+        data[offset..offset + 4].copy_from_slice(b"\xDE\xAD\xBE\xEF");
+        fs::write(dir.join(name), data).unwrap();
+    }
+
+    let golden_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/golden/multi_file_locale.txt");
+    let golden = fs::read_to_string(golden_path).unwrap();
+
+    for locale in ["C", "POSIX", "en_US.UTF-8", "de_DE.UTF-8", "tr_TR.UTF-8", "ar_SA.UTF-8", "xx_XX.invalid"] {
+        let output = Command::new(&binary_path)
+            .current_dir(&dir)
+            .env("LC_ALL", locale)
+            .env("LANG", locale)
+            .arg("*.bin")
+            .arg("--multi")
+            .arg("-e")
+            .arg("\\xDE\\xAD\\xBE\\xEF")
+            .arg("-w")
+            .arg("8")
+            .output()
+            .expect("Failed to execute multi-file command");
+
+        assert!(output.status.success(), "LC_ALL={}", locale);
+        // 경로는 바이트 순서로 정렬되고 숫자 형식은 로캘과 무관해야 함
+        assert_eq!(String::from_utf8_lossy(&output.stdout), golden, "LC_ALL={}", locale);
+    }
+
+    // 정리
+    fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn test_recursive_directory_search() {
+    let binary_path = get_binary_path();
+
+    // 중첩된 하위 디렉터리와 상위 디렉터리를 가리키는 심볼릭 링크 (루프)
+    let dir = std::env::temp_dir().join(format!("multifile_recursive_{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(dir.join("sub/deeper")).unwrap();
+    fs::create_dir_all(dir.join("empty")).unwrap();
+    let pattern = b"\x01\x02\x03\x04";
+    for name in ["top.bin", "sub/mid.bin", "sub/deeper/leaf.bin"] {
+        let mut data = vec![0xFFu8; 64];
+        data[8..12].copy_from_slice(pattern);
+        fs::write(dir.join(name), data).unwrap();
+    }
+    fs::write(dir.join("sub/nomatch.bin"), vec![0xAAu8; 64]).unwrap();
+    std::os::unix::fs::symlink(&dir, dir.join("sub/deeper/loop")).unwrap();
+
+    for parallel in [false, true] {
+        let mut command = Command::new(&binary_path);
+        command.arg(&dir).arg("-r").arg("-e").arg("\\x01\\x02\\x03\\x04");
+        if parallel {
+            command.arg("--parallel").arg("--chunk-size").arg("16");
+        }
+        let output = command.output().expect("Failed to execute recursive command");
+
+        assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        // 모든 일반 파일이 바이트 순서로 한 번씩 처리되고 링크 루프는 따라가지 않음
+        let processed: Vec<&str> = stdout
+            .lines()
+            .filter_map(|line| line.strip_prefix("=== Processing: "))
+            .collect();
+        assert_eq!(processed.len(), 4, "stdout: {}", stdout);
+        assert!(processed[0].ends_with("sub/deeper/leaf.bin ==="), "stdout: {}", stdout);
+        assert!(processed[1].ends_with("sub/mid.bin ==="), "stdout: {}", stdout);
+        assert!(processed[2].ends_with("sub/nomatch.bin ==="), "stdout: {}", stdout);
+        assert!(processed[3].ends_with("top.bin ==="), "stdout: {}", stdout);
+
+        let matches = stdout.lines().filter(|line| line.contains("01 02 03 04")).count();
+        assert_eq!(matches, 3, "stdout: {}", stdout);
+    }
+
+    // 정리
+    fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn test_recursive_follow_symlinks() {
+    let binary_path = get_binary_path();
+
+    // 트리 밖 디렉터리를 가리키는 링크와 트리 루트를 가리키는 링크 (루프)
+    let base = std::env::temp_dir().join(format!("multifile_follow_{}", uuid::Uuid::new_v4()));
+    let dir = base.join("tree");
+    let outside = base.join("outside");
+    fs::create_dir_all(dir.join("sub")).unwrap();
+    fs::create_dir_all(&outside).unwrap();
+    let mut data = vec![0xFFu8; 32];
+    data[4..8].copy_from_slice(b"\x01\x02\x03\x04");
+    fs::write(dir.join("sub/inside.bin"), &data).unwrap();
+    fs::write(outside.join("linked.bin"), &data).unwrap();
+    std::os::unix::fs::symlink(&outside, dir.join("link")).unwrap();
+    std::os::unix::fs::symlink(&dir, dir.join("sub/loop")).unwrap();
+
+    for extra in [&[][..], &["--respect-gitignore"][..]] {
+        let run = |follow: bool| {
+            let mut command = Command::new(&binary_path);
+            command.arg(&dir).arg("-r").arg("-e").arg("\\x01\\x02\\x03\\x04").args(extra);
+            if follow {
+                command.arg("--follow");
+            }
+            let output = command.output().expect("Failed to execute recursive command");
+            assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+            let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+            let processed: Vec<String> = stdout
+                .lines()
+                .filter_map(|line| line.strip_prefix("=== Processing: "))
+                .map(|line| line.trim_end_matches(" ===").strip_prefix(dir.to_str().unwrap()).unwrap().to_string())
+                .collect();
+            (processed, String::from_utf8_lossy(&output.stderr).to_string())
+        };
+
+        // 기본값은 디렉터리 링크를 따라가지 않음
+        let (processed, _) = run(false);
+        assert_eq!(processed, vec!["/sub/inside.bin"], "{:?}", extra);
+
+        // --follow는 링크된 디렉터리를 검색하고 루프는 경고 후 건너뜀
+        let (processed, stderr) = run(true);
+        assert_eq!(processed, vec!["/link/linked.bin", "/sub/inside.bin"], "{:?}", extra);
+        assert!(stderr.contains("loop"), "stderr: {}", stderr);
+    }
+
+    // 정리
+    fs::remove_dir_all(base).ok();
+}
+
+#[test]
+fn test_recursive_include_exclude_and_gitignore() {
+    let binary_path = get_binary_path();
+
+    let dir = std::env::temp_dir().join(format!("multifile_filters_{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(dir.join("build")).unwrap();
+    fs::create_dir_all(dir.join("src")).unwrap();
+    let mut data = vec![0xFFu8; 32];
+    data[4..8].copy_from_slice(b"\x01\x02\x03\x04");
+    for name in ["src/main.bin", "src/debug.log", "build/out.bin", "notes.txt"] {
+        fs::write(dir.join(name), &data).unwrap();
+    }
+    fs::write(dir.join(".gitignore"), "build/\n").unwrap();
+
+    let run = |extra: &[&str]| {
+        let output = Command::new(&binary_path)
+            .arg(&dir)
+            .arg("-r")
+            .arg("-e")
+            .arg("\\x01\\x02\\x03\\x04")
+            .args(extra)
+            .output()
+            .expect("Failed to execute recursive command");
+        assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let mut processed: Vec<String> = stdout
+            .lines()
+            .filter_map(|line| line.strip_prefix("=== Processing: "))
+            .map(|line| line.trim_end_matches(" ===").strip_prefix(dir.to_str().unwrap()).unwrap().to_string())
+            .collect();
+        processed.sort();
+        (processed, String::from_utf8_lossy(&output.stderr).to_string())
+    };
+
+    // --exclude "*.log"와 일치하는 파일만 건너뛰고 나머지는 검색
+    let (processed, stderr) = run(&["--exclude", "*.log"]);
+    assert_eq!(processed, vec!["/.gitignore", "/build/out.bin", "/notes.txt", "/src/main.bin"]);
+    assert!(stderr.contains("debug.log skipped (excluded)"), "stderr: {}", stderr);
+
+    // --include는 일치하는 파일만 검색하고 --exclude가 우선
+    let (processed, _) = run(&["--include", "*.bin", "--include", "*.log", "--exclude", "*.log"]);
+    assert_eq!(processed, vec!["/build/out.bin", "/src/main.bin"]);
+
+    // --respect-gitignore는 .gitignore로 무시된 디렉터리를 건너뜀
+    let (processed, _) = run(&["--respect-gitignore", "--include", "*.bin"]);
+    assert_eq!(processed, vec!["/src/main.bin"]);
+
+    // 정리
+    fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn test_recursive_max_depth() {
+    let binary_path = get_binary_path();
+
+    let dir = std::env::temp_dir().join(format!("multifile_depth_{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(dir.join("a/b")).unwrap();
+    let mut data = vec![0xFFu8; 32];
+    data[4..8].copy_from_slice(b"\x01\x02\x03\x04");
+    for name in ["top.bin", "a/mid.bin", "a/b/deep.bin"] {
+        fs::write(dir.join(name), &data).unwrap();
+    }
+
+    for extra in [&[][..], &["--respect-gitignore"][..]] {
+        let run = |max_depth: &str| {
+            let output = Command::new(&binary_path)
+                .arg(&dir)
+                .arg("-r")
+                .arg("--max-depth")
+                .arg(max_depth)
+                .arg("-e")
+                .arg("\\x01\\x02\\x03\\x04")
+                .args(extra)
+                .output()
+                .expect("Failed to execute recursive command");
+            assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+            let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+            stdout
+                .lines()
+                .filter_map(|line| line.strip_prefix("=== Processing: "))
+                .map(|line| line.trim_end_matches(" ===").strip_prefix(dir.to_str().unwrap()).unwrap().to_string())
+                .collect::<Vec<String>>()
+        };
+
+        // 깊이 1은 디렉터리 바로 아래 파일만, 깊이가 늘면 하위 디렉터리까지 검색
+        assert_eq!(run("1"), vec!["/top.bin"], "{:?}", extra);
+        assert_eq!(run("2"), vec!["/a/mid.bin", "/top.bin"], "{:?}", extra);
+        assert_eq!(run("3"), vec!["/a/b/deep.bin", "/a/mid.bin", "/top.bin"], "{:?}", extra);
+        assert!(run("0").is_empty(), "{:?}", extra);
+    }
+
+    // --max-depth는 -r 없이 사용할 수 없음
+    let output = Command::new(&binary_path)
+        .arg(dir.join("top.bin"))
+        .arg("--max-depth")
+        .arg("1")
+        .output()
+        .expect("Failed to execute command");
+    assert!(!output.status.success());
+
+    // 정리
+    fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn test_recursive_files_with_and_without_match() {
+    let binary_path = get_binary_path();
+
+    let dir = std::env::temp_dir().join(format!("multifile_list_{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(dir.join("sub")).unwrap();
+    let mut data = vec![0xFFu8; 64];
+    data[8..12].copy_from_slice(b"\x01\x02\x03\x04");
+    for name in ["a.bin", "sub/b.bin", "sub/c.log"] {
+        fs::write(dir.join(name), &data).unwrap();
+    }
+    fs::write(dir.join("sub/none.bin"), vec![0xAAu8; 64]).unwrap();
+
+    let run = |extra: &[&str]| {
+        let output = Command::new(&binary_path)
+            .arg(&dir)
+            .arg("-r")
+            .arg("-e")
+            .arg("\\x01\\x02\\x03\\x04")
+            .args(extra)
+            .output()
+            .expect("Failed to execute recursive command");
+        assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        stdout
+            .lines()
+            .map(|line| line.strip_prefix(dir.to_str().unwrap()).unwrap().to_string())
+            .collect::<Vec<_>>()
+    };
+
+    // 경로만 바이트 순서로 출력되고 헥사 출력이나 배너는 없음
+    assert_eq!(run(&["-l"]), vec!["/a.bin", "/sub/b.bin", "/sub/c.log"]);
+    assert_eq!(run(&["-L"]), vec!["/sub/none.bin"]);
+
+    // 제외된 파일은 어느 쪽에도 출력되지 않음
+    assert_eq!(run(&["-l", "--exclude", "*.log"]), vec!["/a.bin", "/sub/b.bin"]);
+    assert_eq!(run(&["-L", "--include", "*.log"]), Vec::<String>::new());
+
+    // 정리
+    fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn test_multi_file_jobs_match_sequential_output() {
+    let binary_path = get_binary_path();
+
+    // 크기와 매치 수가 다른 8개 파일과 제외될 파일 하나
+    let dir = std::env::temp_dir().join(format!("multifile_jobs_{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    for i in 0..8 {
+        let mut data = vec![0xFFu8; 4096 * (8 - i)];
+        for offset in (100..data.len() - 4).step_by(700 + i * 100) {
+            data[offset..offset + 4].copy_from_slice(b"\x01\x02\x03\x04");
+        }
+        fs::write(dir.join(format!("file_{}.bin", i)), &data).unwrap();
+    }
+    fs::write(dir.join("notes.log"), b"\x01\x02\x03\x04").unwrap();
+    let glob_pattern = dir.join("*").to_string_lossy().to_string();
+
+    let run = |args: &[&str]| {
+        let output = Command::new(&binary_path)
+            .args(args)
+            .args(["--exclude", "*.log", "--color", "never"])
+            .output()
+            .expect("Failed to execute multi-file command");
+        assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+        output.stdout
+    };
+
+    // 검색, 헥사 덤프, NDJSON, 재귀 모드 모두 순차 실행과 바이트 단위로 같아야 함
+    for args in [
+        vec![glob_pattern.as_str(), "--multi", "-e", "\\x01\\x02\\x03\\x04"],
+        vec![glob_pattern.as_str(), "--multi", "-n", "3"],
+        vec![glob_pattern.as_str(), "--multi", "-f", "ndjson", "-e", "\\x01\\x02\\x03\\x04"],
+        vec![dir.to_str().unwrap(), "-r", "-e", "\\x01\\x02\\x03\\x04"],
+    ] {
+        let sequential = run(&args);
+        let concurrent = run(&[&args[..], &["--jobs", "4"]].concat());
+        assert!(!sequential.is_empty());
+        assert_eq!(
+            String::from_utf8_lossy(&concurrent),
+            String::from_utf8_lossy(&sequential),
+            "{:?}",
+            args
+        );
+    }
+
+    // 정리
+    fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn test_multi_file_jobs_large_file_before_small_ones() {
+    let binary_path = get_binary_path();
+
+    // 느린 큰 파일 하나 뒤에 작은 파일 여러 개
+    let dir = std::env::temp_dir().join(format!("multifile_jobs_large_{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    let mut large = vec![0xFFu8; 32 * 1024 * 1024];
+    for &offset in &[0x10usize, 0x1000000, 0x1FFFFF0] {
+        large[offset..offset + 4].copy_from_slice(b"\x01\x02\x03\x04");
+    }
+    fs::write(dir.join("a_large.bin"), &large).unwrap();
+    for i in 0..40 {
+        let mut data = vec![0xFFu8; 256];
+        data[i..i + 4].copy_from_slice(b"\x01\x02\x03\x04");
+        fs::write(dir.join(format!("b_small_{:02}.bin", i)), &data).unwrap();
+    }
+    let glob_pattern = dir.join("*").to_string_lossy().to_string();
+
+    let run = |extra: &[&str]| {
+        let output = Command::new(&binary_path)
+            .args([glob_pattern.as_str(), "--multi", "-e", "\\x01\\x02\\x03\\x04", "--color", "never"])
+            .args(extra)
+            .output()
+            .expect("Failed to execute multi-file command");
+        assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    // 작은 파일들은 큰 파일을 앞질러 검색되더라도 경로 순서대로 출력됨
+    let sequential = run(&[]);
+    assert_eq!(sequential.matches("=== Processing: ").count(), 41);
+    for jobs in ["2", "4", "8"] {
+        assert_eq!(run(&["-j", jobs]), sequential, "-j {}", jobs);
+    }
+
+    // 정리
+    fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn test_multi_file_sort_files() {
+    let binary_path = get_binary_path();
+
+    // 이름 순서와 크기 순서가 다른 파일들 (이름 순서와 다르게 생성)
+    let dir = std::env::temp_dir().join(format!("multifile_sort_{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    for (name, size) in [("charlie.bin", 300), ("alpha.bin", 200), ("bravo.bin", 100)] {
+        let mut data = vec![0xFFu8; size];
+        data[10..14].copy_from_slice(b"\x01\x02\x03\x04");
+        fs::write(dir.join(name), &data).unwrap();
+    }
+    let glob_pattern = dir.join("*.bin").to_string_lossy().to_string();
+
+    let processed = |extra: &[&str]| {
+        let output = Command::new(&binary_path)
+            .arg(&glob_pattern)
+            .arg("--multi")
+            .arg("-e")
+            .arg("\\x01\\x02\\x03\\x04")
+            .args(extra)
+            .output()
+            .expect("Failed to execute multi-file command");
+        assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| line.strip_prefix("=== Processing: "))
+            .map(|line| line.trim_end_matches(" ===").rsplit('/').next().unwrap().to_string())
+            .collect::<Vec<String>>()
+    };
+
+    // 기본값과 name은 이름 순서
+    assert_eq!(processed(&[]), vec!["alpha.bin", "bravo.bin", "charlie.bin"]);
+    assert_eq!(processed(&["--sort-files", "name"]), vec!["alpha.bin", "bravo.bin", "charlie.bin"]);
+    assert_eq!(
+        processed(&["--sort-files", "name", "--sort-descending"]),
+        vec!["charlie.bin", "bravo.bin", "alpha.bin"]
+    );
+
+    // size는 작은 파일부터, --sort-descending은 큰 파일부터
+    assert_eq!(processed(&["--sort-files", "size"]), vec!["bravo.bin", "alpha.bin", "charlie.bin"]);
+    assert_eq!(
+        processed(&["--sort-files", "size", "--sort-descending"]),
+        vec!["charlie.bin", "alpha.bin", "bravo.bin"]
+    );
+
+    // 정리
+    fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn test_multi_file_continues_after_file_errors() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let binary_path = get_binary_path();
+
+    let dir = std::env::temp_dir().join(format!("multifile_errors_{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    let mut data = vec![0xFFu8; 64];
+    data[8..12].copy_from_slice(b"\x01\x02\x03\x04");
+    for name in ["a.bin", "b.bin", "c.bin"] {
+        fs::write(dir.join(name), &data).unwrap();
+    }
+
+    let run = |args: &[&str]| {
+        Command::new(&binary_path)
+            .args(args)
+            .args(["-e", "\\x01\\x02\\x03\\x04", "--color", "never"])
+            .output()
+            .expect("Failed to execute multi-file command")
+    };
+    let processed = |stdout: &[u8]| {
+        String::from_utf8_lossy(stdout)
+            .lines()
+            .filter_map(|line| line.strip_prefix("=== Processing: "))
+            .map(|line| line.trim_end_matches(" ===").rsplit('/').next().unwrap().to_string())
+            .collect::<Vec<String>>()
+    };
+
+    // 읽을 수 없는 파일은 건너뛰고 나머지 파일은 계속 검색 (root는 권한과 무관하게 읽을 수 있음)
+    let unreadable = dir.join("b.bin");
+    fs::set_permissions(&unreadable, fs::Permissions::from_mode(0o000)).unwrap();
+    if File::open(&unreadable).is_err() {
+        let glob_pattern = dir.join("*.bin").to_string_lossy().to_string();
+        let output = run(&[&glob_pattern, "--multi"]);
+        assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+        assert_eq!(processed(&output.stdout), vec!["a.bin", "c.bin"]);
+        assert!(String::from_utf8_lossy(&output.stderr).contains("b.bin skipped (permission-denied)"));
+    }
+    fs::set_permissions(&unreadable, fs::Permissions::from_mode(0o644)).unwrap();
+
+    // 열리지만 읽는 중 실패하는 파일 (/proc/self/mem은 0번지를 읽으면 오류)
+    if std::path::Path::new("/proc/self/mem").exists() {
+        let list = dir.join("list.txt");
+        let good = [dir.join("a.bin"), dir.join("c.bin")].map(|path| path.to_string_lossy().to_string());
+        fs::write(&list, format!("{}\n/proc/self/mem\n{}\n", good[0], good[1])).unwrap();
+        let list = list.to_string_lossy().to_string();
+
+        // 오류는 경고로 알리고 다음 파일을 계속 검색한 뒤 요약 출력
+        let output = run(&["--files-from", &list]);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(output.status.success(), "stderr: {}", stderr);
+        assert_eq!(processed(&output.stdout), vec!["a.bin", "mem", "c.bin"]);
+        assert!(stderr.contains("Warning: /proc/self/mem: "), "stderr: {}", stderr);
+        assert!(stderr.contains("1 file(s) failed, 2 processed"), "stderr: {}", stderr);
+
+        // 여러 작업으로 검색해도 같은 출력
+        let concurrent = run(&["--files-from", &list, "--jobs", "3"]);
+        assert!(concurrent.status.success());
+        assert_eq!(concurrent.stdout, output.stdout);
+
+        // --strict는 하나라도 실패하면 실패로 종료
+        let output = run(&["--files-from", &list, "--strict"]);
+        assert_eq!(output.status.code(), Some(2));
+
+        // 모든 파일이 실패하면 --strict 없이도 실패로 종료
+        let output = Command::new(&binary_path)
+            .args(["--files-from", "-", "-e", "\\x01\\x02\\x03\\x04"])
+            .stdin(std::process::Stdio::piped())
+            .spawn()
+            .and_then(|mut child| {
+                child.stdin.take().unwrap().write_all(b"/proc/self/mem\n")?;
+                child.wait_with_output()
+            })
+            .expect("Failed to execute multi-file command");
+        assert_eq!(output.status.code(), Some(2));
+        assert!(String::from_utf8_lossy(&output.stderr).contains("0 processed"));
+    }
+
+    // 정리
+    fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn test_glob_without_multi_is_reported() {
+    let binary_path = get_binary_path();
+
+    let dir = std::env::temp_dir().join(format!("multifile_glob_hint_{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("a.bin"), vec![0xFFu8; 32]).unwrap();
+    let mut data = vec![0xFFu8; 32];
+    data[4..8].copy_from_slice(b"\x01\x02\x03\x04");
+    fs::write(dir.join("b.bin"), &data).unwrap();
+    let [a, b] = ["a.bin", "b.bin"].map(|name| dir.join(name).to_string_lossy().to_string());
+
+    let run = |args: &[&str]| {
+        Command::new(&binary_path)
+            .args(args)
+            .args(["-e", "\\x01\\x02\\x03\\x04"])
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    // 따옴표로 감싼 glob을 --multi 없이 넘기면 --multi를 안내
+    let glob_pattern = dir.join("*.bin").to_string_lossy().to_string();
+    let output = run(&[&glob_pattern]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(stderr.contains("looks like a glob pattern"), "stderr: {}", stderr);
+    assert!(stderr.contains("--multi"), "stderr: {}", stderr);
+
+    // 셸이 펼친 여러 경로를 --multi 없이 넘겨도 --multi를 안내
+    let output = run(&[&a, &b]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(stderr.contains("2 input paths"), "stderr: {}", stderr);
+    assert!(stderr.contains("--multi"), "stderr: {}", stderr);
+
+    // --multi와 함께면 주어진 순서대로 모두 검색
+    let output = run(&[&b, &a, "--multi"]);
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let banners: Vec<&str> = stdout.lines().filter(|line| line.starts_with("=== Processing: ")).collect();
+    assert_eq!(banners, vec![format!("=== Processing: {} ===", b), format!("=== Processing: {} ===", a)]);
+
+    // quiet 모드도 모든 경로를 검사
+    assert_eq!(run(&[&a, &b, "--multi", "-q"]).status.code(), Some(0));
+    assert_eq!(run(&[&a, "--multi", "-q"]).status.code(), Some(1));
+
+    // 정리
+    fs::remove_dir_all(dir).ok();
+}
@@ -335,3 +335,311 @@ fn test_parallel_performance_benchmark() {
     // 정리
     fs::remove_file(test_file).ok();
 }
+
+#[test]
+fn test_parallel_search_range_end() {
+    let binary_path = get_binary_path();
+
+    // 256KB 파일의 여러 위치에 패턴 삽입
+    let mut test_data = vec![0xFFu8; 256 * 1024];
+    let pattern = b"\x00\x01\x02\x03";
+    let pattern_locations = [1000, 70000, 131070, 131072, 200000];
+    for &loc in &pattern_locations {
+        test_data[loc..loc + pattern.len()].copy_from_slice(pattern);
+    }
+    let test_file = create_test_file(&test_data, "range");
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("-e")
+        .arg("\\x00\\x01\\x02\\x03")
+        .arg("--end")
+        .arg("131072")
+        .arg("--parallel")
+        .arg("--chunk-size")
+        .arg("65536")
+        .output()
+        .expect("Failed to execute parallel command");
+
+    assert!(output.status.success(), "Parallel processing failed");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    // 종료 위치 이전에 완전히 들어가는 매치만 출력 (1000, 70000)
+    // 131070의 매치는 종료 위치를 넘어가고 131072 이후는 범위 밖
+    assert_eq!(stdout.lines().count(), 2, "stdout: {}", stdout);
+    assert!(!stdout.contains("20000h"));
+    assert!(!stdout.contains("30D40h"));
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_parallel_aligned_matches() {
+    let binary_path = get_binary_path();
+
+    // 청크 경계를 넘나드는 위치에 정렬/비정렬 패턴 삽입
+    let mut test_data = vec![0xFFu8; 256 * 1024];
+    let pattern_locations = [4096, 5000, 65536, 70001, 131072, 200000];
+    for &loc in &pattern_locations {
+        test_data[loc..loc + 4].copy_from_slice(b"\x00\x01\x02\x03");
+    }
+    let test_file = create_test_file(&test_data, "aligned");
+
+    let run = |parallel: bool| {
+        let mut command = Command::new(&binary_path);
+        command
+            .arg(&test_file)
+            .arg("-e")
+            .arg("\\x00\\x01\\x02\\x03")
+            .arg("--aligned")
+            .arg("4096");
+        if parallel {
+            command.arg("--parallel").arg("--chunk-size").arg("65536");
+        }
+        command.output().expect("Failed to execute command")
+    };
+
+    let output_seq = run(false);
+    let output_par = run(true);
+    assert!(output_seq.status.success());
+    assert!(output_par.status.success());
+
+    let stdout_seq = String::from_utf8_lossy(&output_seq.stdout);
+    let stdout_par = String::from_utf8_lossy(&output_par.stdout);
+
+    // 4096의 배수 위치(4096, 65536, 131072)만 출력
+    assert_eq!(stdout_seq.lines().count(), 3, "sequential: {}", stdout_seq);
+    assert_eq!(stdout_par.lines().count(), 3, "parallel: {}", stdout_par);
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_parallel_scan_stats() {
+    let binary_path = get_binary_path();
+
+    // 청크 크기 64KB로 여러 청크에 걸친 256KB 파일
+    let chunk_size = 64 * 1024;
+    let mut test_data = vec![0xFFu8; 256 * 1024];
+    test_data[100_000..100_004].copy_from_slice(b"\x00\x01\x02\x03");
+    let test_file = create_test_file(&test_data, "stats");
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("-e")
+        .arg("\\x00\\x01\\x02\\x03")
+        .arg("--parallel")
+        .arg("--chunk-size")
+        .arg(chunk_size.to_string())
+        .arg("--stats")
+        .output()
+        .expect("Failed to execute parallel command");
+
+    assert!(output.status.success(), "Parallel processing failed");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(stdout.lines().count(), 1, "stdout: {}", stdout);
+
+    // 통계는 stderr에만 출력: "Scan stats: N chunk(s), M bytes scanned, ..."
+    let stats_line = stderr
+        .lines()
+        .find(|line| line.starts_with("Scan stats:"))
+        .unwrap_or_else(|| panic!("stats missing, stderr: {}", stderr));
+    let fields: Vec<&str> = stats_line.split_whitespace().collect();
+    let chunks: u64 = fields[2].parse().unwrap();
+    let bytes_scanned: u64 = fields[4].parse().unwrap();
+
+    assert!(chunks >= 4, "stderr: {}", stderr);
+    // 겹침 영역은 중복 집계하지 않음
+    assert_eq!(bytes_scanned, test_data.len() as u64, "stderr: {}", stderr);
+    assert!(chunks * chunk_size as u64 >= bytes_scanned, "stderr: {}", stderr);
+    assert!(stderr.contains("speedup:"));
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_parallel_output_identical_to_sequential() {
+    let binary_path = get_binary_path();
+
+    // 64KB 청크 16개 분량, 청크 경계 근처와 경계에 걸친 패턴 및 연속된 'A'
+    let chunk_size = 64 * 1024;
+    let mut test_data = vec![0xFFu8; 16 * chunk_size];
+    for &loc in &[10, chunk_size - 2, 3 * chunk_size, 7 * chunk_size - 1, 16 * chunk_size - 4] {
+        test_data[loc..loc + 4].copy_from_slice(b"\x00\x01\x02\x03");
+    }
+    // 경계를 넘는 10바이트 'A': A{4}는 겹치지 않게 두 번만 매치
+    test_data[5 * chunk_size - 6..5 * chunk_size + 4].fill(b'A');
+    let test_file = create_test_file(&test_data, "identical");
+
+    for (pattern, extra_args) in [
+        ("\\x00\\x01\\x02\\x03", vec![]),
+        ("\\x00\\x01\\x02\\x03", vec!["-w", "8", "-n", "3"]),
+        ("\\x00\\x01\\x02\\x03", vec!["--color", "always"]),
+        ("A{4}", vec![]),
+    ] {
+        let output_seq = Command::new(&binary_path)
+            .arg(&test_file)
+            .arg("-e")
+            .arg(pattern)
+            .args(&extra_args)
+            .output()
+            .expect("Failed to execute sequential command");
+
+        let output_par = Command::new(&binary_path)
+            .arg(&test_file)
+            .arg("-e")
+            .arg(pattern)
+            .args(&extra_args)
+            .arg("--parallel")
+            .arg("--chunk-size")
+            .arg(chunk_size.to_string())
+            .output()
+            .expect("Failed to execute parallel command");
+
+        assert!(output_seq.status.success(), "Sequential processing failed");
+        assert!(output_par.status.success(), "Parallel processing failed");
+        assert!(!output_seq.stdout.is_empty());
+        // 병렬 처리 결과는 순차 처리와 바이트 단위로 동일해야 함
+        assert_eq!(
+            String::from_utf8_lossy(&output_par.stdout),
+            String::from_utf8_lossy(&output_seq.stdout),
+            "pattern {} args {:?}",
+            pattern,
+            extra_args
+        );
+    }
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_parallel_chunk_boundary_offsets() {
+    let binary_path = get_binary_path();
+
+    // 청크 경계 바로 앞(chunk_size - 1), 경계(chunk_size), 경계에 걸친 패턴
+    let chunk_size = 64 * 1024;
+    let mut test_data = vec![0xFFu8; 8 * chunk_size];
+    test_data[chunk_size - 1..chunk_size + 3].copy_from_slice(b"\x00\x01\x02\x03");
+    test_data[2 * chunk_size..2 * chunk_size + 4].copy_from_slice(b"\x00\x01\x02\x03");
+    test_data[3 * chunk_size - 2..3 * chunk_size + 2].copy_from_slice(b"\x00\x01\x02\x03");
+    // 1KB보다 긴 매치가 경계에 걸친 경우
+    let long_start = 5 * chunk_size - 100;
+    test_data[long_start] = 0x01;
+    test_data[long_start + 1..long_start + 2001].fill(0xEE);
+    test_data[long_start + 2001] = 0x02;
+    let test_file = create_test_file(&test_data, "boundary");
+
+    for pattern in ["\\x00\\x01\\x02\\x03", "(?-u)\\x01\\xEE{2000}\\x02"] {
+        let offsets = |parallel: bool| {
+            let mut command = Command::new(&binary_path);
+            command.arg(&test_file).arg("-e").arg(pattern).arg("-w").arg("4");
+            if parallel {
+                command.arg("--parallel").arg("--chunk-size").arg(chunk_size.to_string());
+            }
+            let output = command.output().expect("Failed to execute command");
+            assert!(output.status.success());
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .map(|line| line.split(" : ").next().unwrap_or_default().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        let sequential = offsets(false);
+        let parallel = offsets(true);
+        assert_eq!(parallel, sequential, "pattern {}", pattern);
+        if pattern.contains("{2000}") {
+            assert_eq!(sequential, vec![format!("{:05X}h", long_start)]);
+        } else {
+            assert_eq!(sequential, vec!["0FFFFh", "20000h", "2FFFEh"]);
+        }
+    }
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_parallel_limit_stops_scanning_early() {
+    let binary_path = get_binary_path();
+
+    // 모든 바이트가 매치되는 64KB 청크 16개 (줄 수를 세기 위해 축약 비활성화)
+    let chunk_size = 64 * 1024;
+    let test_data = vec![0u8; 16 * chunk_size];
+    let test_file = create_test_file(&test_data, "limit");
+
+    let output = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("-e")
+        .arg("\\x00")
+        .arg("-n")
+        .arg("10")
+        .arg("--parallel")
+        .arg("--chunk-size")
+        .arg(chunk_size.to_string())
+        .arg("--stats")
+        .arg("--no-collapse")
+        .env("RAYON_NUM_THREADS", "2")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 10);
+
+    // -n에 도달하면 첫 배치(스레드 2개 = 청크 2개) 이후로는 읽지 않음
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Scan stats: 2 chunk(s)"), "stderr: {}", stderr);
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
+
+#[test]
+fn test_parallel_strings_identical_to_sequential() {
+    let binary_path = get_binary_path();
+
+    // 청크 경계에 걸친 문자열과 청크 전체를 채우는 긴 문자열
+    let chunk_size = 64 * 1024;
+    let mut test_data = vec![0u8; 8 * chunk_size];
+    test_data[100..110].fill(b'a');
+    test_data[chunk_size - 3..chunk_size + 3].copy_from_slice(b"SPLIT!");
+    test_data[2 * chunk_size - 10..4 * chunk_size + 10].fill(b'z');
+    test_data[6 * chunk_size - 2..6 * chunk_size + 1].copy_from_slice(b"abc");
+    let test_file = create_test_file(&test_data, "strings");
+
+    let output_seq = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("--strings")
+        .output()
+        .expect("Failed to execute sequential command");
+
+    let output_par = Command::new(&binary_path)
+        .arg(&test_file)
+        .arg("--strings")
+        .arg("--parallel")
+        .arg("--chunk-size")
+        .arg(chunk_size.to_string())
+        .output()
+        .expect("Failed to execute parallel command");
+
+    assert!(output_seq.status.success(), "Sequential processing failed");
+    assert!(output_par.status.success(), "Parallel processing failed");
+
+    let stdout_seq = String::from_utf8_lossy(&output_seq.stdout);
+    let stdout_par = String::from_utf8_lossy(&output_par.stdout);
+
+    // "abc"는 최소 길이 미만이므로 3개의 문자열만 출력
+    assert_eq!(stdout_seq.lines().count(), 3, "stdout: {}", stdout_seq);
+    assert!(stdout_seq.contains("FFFDh : SPLIT!"));
+    assert_eq!(stdout_par, stdout_seq);
+
+    // 정리
+    fs::remove_file(test_file).ok();
+}
@@ -0,0 +1,120 @@
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+fn get_binary_path() -> PathBuf {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("target");
+    path.push("debug");
+    path.push("hxgrep");
+    path
+}
+
+fn run(args: &[&str]) -> String {
+    let output = Command::new(get_binary_path())
+        .args(args)
+        .output()
+        .expect("Failed to execute hxgrep");
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout).unwrap()
+}
+
+fn schema(kind: &str) -> Value {
+    serde_json::from_str(&run(&["--schema", kind])).expect("스키마는 JSON이어야 함")
+}
+
+fn assert_valid(schema: &Value, instance: &Value) {
+    assert!(jsonschema::is_valid(schema, instance), "스키마와 맞지 않는 레코드: {}", instance);
+}
+
+#[test]
+fn test_schema_documents() {
+    for kind in ["matches", "hexdump", "summary"] {
+        let document = schema(kind);
+        assert_eq!(document["$schema"], "http://json-schema.org/draft-07/schema#");
+        assert_eq!(document["$id"], format!("urn:hxgrep:schema:{}:2", kind));
+        assert_eq!(document["x-hxgrep-schema-version"], 2);
+    }
+
+    // 알 수 없는 종류는 거부
+    let output = Command::new(get_binary_path()).args(["--schema", "unknown"]).output().unwrap();
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_output_matches_schema() {
+    let dir = std::env::temp_dir().join(format!("schema_test_{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    let file = dir.join("data.bin");
+    let mut data = vec![0x11u8; 64];
+    data[8..12].copy_from_slice(b"\xDE\xAD\xBE\xEF");
+    data[40..44].copy_from_slice(b"\xDE\xAD\xBE\xEF");
+    fs::write(&file, &data).unwrap();
+    let file = file.to_string_lossy().to_string();
+
+    // 매치: NDJSON 각 줄과 JSON 배열의 각 원소
+    let matches = schema("matches");
+    let stdout = run(&[&file, "-f", "ndjson", "-e", "\\xDE\\xAD\\xBE\\xEF"]);
+    assert_eq!(stdout.lines().count(), 2);
+    for line in stdout.lines() {
+        assert_valid(&matches, &serde_json::from_str(line).unwrap());
+    }
+    let array: Value = serde_json::from_str(&run(&[&file, "-f", "json", "-e", "\\xDE\\xAD\\xBE\\xEF"])).unwrap();
+    for record in array.as_array().unwrap() {
+        assert_valid(&matches, record);
+    }
+
+    // offset이 빠진 레코드는 스키마를 만족하지 않아야 함
+    let mut broken: Value = serde_json::from_str(stdout.lines().next().unwrap()).unwrap();
+    broken.as_object_mut().unwrap().remove("offset");
+    assert!(!jsonschema::is_valid(&matches, &broken));
+
+    // 헥사 덤프 줄
+    let hexdump = schema("hexdump");
+    let stdout = run(&[&file, "-f", "ndjson"]);
+    assert_eq!(stdout.lines().count(), 4);
+    for line in stdout.lines() {
+        assert_valid(&hexdump, &serde_json::from_str(line).unwrap());
+    }
+
+    // 멀티파일 검색의 skipped/summary 레코드와 JSON 보고서
+    fs::write(dir.join("notes.log"), b"\xDE\xAD\xBE\xEF").unwrap();
+    let glob_pattern = dir.join("*").to_string_lossy().to_string();
+    let summary = schema("summary");
+    let stdout = run(&[&glob_pattern, "--multi", "-f", "ndjson", "--exclude", "*.log", "-e", "\\xDE\\xAD\\xBE\\xEF"]);
+    let records: Vec<Value> = stdout.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+    let mut checked = 0;
+    for record in &records {
+        if record.get("skipped").is_some() || record.get("summary").is_some() {
+            assert_valid(&summary, record);
+            checked += 1;
+        } else {
+            assert_valid(&matches, record);
+        }
+    }
+    assert_eq!(checked, 2);
+
+    // JSON 보고서는 모든 파일의 매치를 담은 하나의 문서
+    let stdout = run(&[&glob_pattern, "--multi", "-f", "json", "--exclude", "*.log", "-e", "\\xDE\\xAD\\xBE\\xEF"]);
+    let report: Value = serde_json::from_str(&stdout).expect("JSON 보고서는 하나의 문서여야 함");
+    assert_valid(&summary, &report);
+    assert_eq!(report["matches"].as_array().unwrap().len(), 2);
+    for record in report["matches"].as_array().unwrap() {
+        assert_valid(&matches, record);
+    }
+
+    // -s/--length로 제한한 검색의 coverage 레코드와 JSON 보고서
+    let stdout = run(&[&file, "-f", "ndjson", "-s", "4", "--length", "32", "-e", "\\xDE\\xAD\\xBE\\xEF"]);
+    let last: Value = serde_json::from_str(stdout.lines().last().unwrap()).unwrap();
+    assert_valid(&summary, &last);
+    assert_eq!(last["coverage"]["requested"], serde_json::json!([4, 36]));
+    let stdout = run(&[&file, "-f", "json", "-s", "4", "--length", "32", "-e", "\\xDE\\xAD\\xBE\\xEF"]);
+    let report: Value = serde_json::from_str(&stdout).expect("JSON 보고서는 하나의 문서여야 함");
+    assert_valid(&summary, &report);
+    assert_eq!(report["matches"].as_array().unwrap().len(), 1);
+    assert_eq!(report["coverage"]["percent"], 100.0);
+
+    // 정리
+    fs::remove_dir_all(dir).ok();
+}
@@ -86,6 +86,31 @@ fn test_json_output_matches() {
     assert!(output_str.contains("Hello"));
 }
 
+#[test]
+fn test_streamed_json_array() {
+    let formatter = StructuredFormatter::new(OutputFormat::Json);
+    let mut output = Vec::new();
+
+    formatter.begin_json_array(&mut output).unwrap();
+    for (i, offset) in [0x10u64, 0x20].iter().enumerate() {
+        let m = BinaryMatch::new("test.bin".to_string(), *offset, "4D 5A".to_string(), 2);
+        formatter.write_json_match(&m, i == 0, &mut output).unwrap();
+    }
+    formatter.end_json_array(&mut output).unwrap();
+
+    // Matches written one at a time still form a single JSON array
+    let parsed: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let parsed: Vec<BinaryMatch> = serde_json::from_value(parsed).unwrap();
+    assert_eq!(parsed.len(), 2);
+    assert_eq!(parsed[1].offset, 0x20);
+
+    // An empty stream is an empty array
+    let mut output = Vec::new();
+    formatter.begin_json_array(&mut output).unwrap();
+    formatter.end_json_array(&mut output).unwrap();
+    assert_eq!(String::from_utf8(output).unwrap(), "[\n]\n");
+}
+
 #[test]
 fn test_csv_output_matches() {
     let matches = vec![